@@ -0,0 +1,76 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use todos::core::{Priority, Status, Task};
+use todos::ui::component::AsyncList;
+use todos::ui::component::list::search::match_score;
+use todos::ui::component::list::testing::{ListHarness, MockStorage};
+use todos::ui::component::list::{ListFilter, ListSorter};
+use todos::ui::task_list::{StatusFilter, TaskSorter};
+
+const COUNT: usize = 10_000;
+
+fn tasks() -> Vec<Task> {
+    let statuses = [Status::ToDo, Status::InProgress, Status::Done];
+    let priorities = [Priority::Low, Priority::Medium, Priority::High];
+    (0..COUNT)
+        .map(|n| Task {
+            id: n as i64 + 1,
+            description: format!("Task number {n}").into(),
+            status: statuses[n % 3],
+            priority: priorities[n / 3 % 3],
+            // Not in the order of the ids.
+            position: (n * 7919 % COUNT) as i64,
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn sort(c: &mut Criterion) {
+    let tasks = tasks();
+    for (name, sorter) in [
+        ("status first", TaskSorter::StatusFirst),
+        ("priority first", TaskSorter::PriorityFirst),
+        ("manual", TaskSorter::Manual),
+    ] {
+        c.bench_function(&format!("sort 10k tasks, {name}"), |b| {
+            b.iter_batched(
+                || tasks.clone(),
+                |mut tasks| tasks.sort_by(|a, b| sorter.sort(a, b, 0., 0.)),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+fn filter(c: &mut Criterion) {
+    let tasks = tasks();
+    c.bench_function("filter 10k tasks by status", |b| {
+        b.iter(|| {
+            tasks
+                .iter()
+                .filter(|task| StatusFilter::Active.filter(task).0)
+                .count()
+        })
+    });
+    c.bench_function("search 10k tasks", |b| {
+        b.iter(|| {
+            tasks
+                .iter()
+                .filter_map(|task| match_score(black_box("number 12"), &task.description))
+                .count()
+        })
+    });
+}
+
+fn view(c: &mut Criterion) {
+    MockStorage::<Task>::set_items(tasks());
+    let mut harness = ListHarness::<Task>::new(AsyncList::new(false, false, false));
+    harness.run_all();
+    c.bench_function("build the view of 10k tasks", |b| {
+        b.iter(|| black_box(harness.list_mut().view()))
+    });
+}
+
+criterion_group!(benches, sort, filter, view);
+criterion_main!(benches);
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use sqlx::sqlite::SqlitePoolOptions;
+use todos::database::{
+    TaskOrder, TaskQuery, connect_options, create_tasks, get_tasks, migrate, set_pool,
+    set_simulated_latency,
+};
+use tokio::runtime::Runtime;
+
+const COUNT: usize = 10_000;
+const BATCH_SIZE: usize = 100;
+
+fn descriptions(count: usize) -> Vec<String> {
+    (0..count).map(|n| format!("Task number {n}")).collect()
+}
+
+/// Runtime with an empty in-memory database, migrated like the one of the app.
+fn runtime() -> Runtime {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        // Each connection to `sqlite::memory:` has its own database.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(connect_options("sqlite::memory:").unwrap())
+            .await
+            .unwrap();
+        set_pool(pool);
+        set_simulated_latency(Duration::ZERO);
+        migrate().await.unwrap();
+    });
+    runtime
+}
+
+fn insert(c: &mut Criterion) {
+    let runtime = runtime();
+    c.bench_function("insert a batch of 100 tasks", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(create_tasks(descriptions(BATCH_SIZE)))
+                .unwrap()
+        })
+    });
+}
+
+fn fetch(c: &mut Criterion) {
+    let runtime = runtime();
+    runtime.block_on(async {
+        for _ in 0..COUNT / BATCH_SIZE {
+            create_tasks(descriptions(BATCH_SIZE)).await.unwrap();
+        }
+    });
+    c.bench_function("fetch 10k tasks", |b| {
+        b.iter(|| runtime.block_on(get_tasks(TaskQuery::default())).unwrap())
+    });
+    c.bench_function("fetch 10k tasks sorted", |b| {
+        let query = TaskQuery {
+            order: Some(TaskOrder::StatusFirst),
+            ..Default::default()
+        };
+        b.iter(|| runtime.block_on(get_tasks(query.clone())).unwrap())
+    });
+    c.bench_function("search 10k tasks", |b| {
+        let query = TaskQuery {
+            search: Some("number 12".to_string()),
+            ..Default::default()
+        };
+        b.iter(|| runtime.block_on(get_tasks(query.clone())).unwrap())
+    });
+}
+
+criterion_group!(benches, insert, fetch);
+criterion_main!(benches);
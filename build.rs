@@ -0,0 +1,6 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // The gRPC service is generated from the published proto, which needs `protoc`.
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/todos/v1/todos.proto").expect("can't compile the proto");
+}
@@ -0,0 +1,259 @@
+//! Types shared by the app and its frontends: the tasks with their status and priority, the
+//! custom fields, and the errors of the storage.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, FromRow, Type};
+use thiserror::Error;
+use tokio::time::error::Elapsed;
+
+#[derive(Default, Type, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum Status {
+    #[default]
+    ToDo,
+    InProgress,
+    Done,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::ToDo => write!(f, "To Do"),
+            Status::InProgress => write!(f, "In Progress"),
+            Status::Done => write!(f, "Done"),
+        }
+    }
+}
+
+impl Status {
+    pub fn next(&self) -> Self {
+        match self {
+            Status::ToDo => Status::InProgress,
+            Status::InProgress => Status::Done,
+            Status::Done => Status::ToDo,
+        }
+    }
+}
+
+#[derive(Default, Type, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum Priority {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Priority::Low => write!(f, "Low"),
+            Priority::Medium => write!(f, "Medium"),
+            Priority::High => write!(f, "High"),
+        }
+    }
+}
+
+impl Priority {
+    pub fn next(&self) -> Self {
+        match self {
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High => Priority::Low,
+        }
+    }
+}
+
+#[derive(Default, FromRow, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Task {
+    pub id: i64,
+    /// Shared, so that rebuilding the views doesn't copy every description.
+    #[sqlx(try_from = "String")]
+    pub description: Arc<str>,
+    pub status: Status,
+    pub priority: Priority,
+    pub updated_at: String,
+    /// Position in the order chosen by the user, unique among the tasks.
+    pub position: i64,
+}
+
+/// Kind of the values of a `CustomField`.
+#[derive(Default, Type, PartialEq, Eq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum FieldKind {
+    #[default]
+    Text,
+    Number,
+    /// A day like `2026-10-15`.
+    Date,
+    /// One of the choices of the field.
+    Choice,
+}
+
+impl fmt::Display for FieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldKind::Text => write!(f, "Text"),
+            FieldKind::Number => write!(f, "Number"),
+            FieldKind::Date => write!(f, "Date"),
+            FieldKind::Choice => write!(f, "Choice"),
+        }
+    }
+}
+
+impl FromStr for FieldKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_ascii_lowercase().as_str() {
+            "text" => Ok(FieldKind::Text),
+            "number" => Ok(FieldKind::Number),
+            "date" => Ok(FieldKind::Date),
+            "choice" | "enum" => Ok(FieldKind::Choice),
+            _ => Err(format!("unknown field kind: {kind}")),
+        }
+    }
+}
+
+impl FieldKind {
+    pub fn next(&self) -> Self {
+        match self {
+            FieldKind::Text => FieldKind::Number,
+            FieldKind::Number => FieldKind::Date,
+            FieldKind::Date => FieldKind::Choice,
+            FieldKind::Choice => FieldKind::Text,
+        }
+    }
+}
+
+/// A field defined by the user, which the tasks may have a value of.
+#[derive(Default, FromRow, PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CustomField {
+    pub id: i64,
+    pub name: String,
+    pub kind: FieldKind,
+    /// Choices of a `FieldKind::Choice` field, one per line.
+    pub options: String,
+}
+
+impl CustomField {
+    /// The choices of the field, without the blank lines.
+    pub fn choices(&self) -> impl Iterator<Item = &str> {
+        self.options
+            .lines()
+            .map(str::trim)
+            .filter(|choice| !choice.is_empty())
+    }
+
+    /// Checks `value` against the kind of the field, returning it as stored: trimmed, and the
+    /// numbers without a leading `+`.
+    pub fn parse_value(&self, value: &str) -> Result<String, FieldError> {
+        let value = value.trim();
+        let valid = match self.kind {
+            FieldKind::Text => true,
+            FieldKind::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            FieldKind::Date => is_date(value),
+            FieldKind::Choice => self.choices().any(|choice| choice == value),
+        };
+        if !valid {
+            return Err(FieldError {
+                field: self.name.clone(),
+                kind: self.kind,
+                value: value.to_string(),
+            });
+        }
+        Ok(value.strip_prefix('+').unwrap_or(value).to_string())
+    }
+}
+
+/// Whether `value` is an existing day like `2026-10-15`.
+fn is_date(value: &str) -> bool {
+    let parts = value.split('-').collect::<Vec<_>>();
+    let [year, month, day] = parts[..] else {
+        return false;
+    };
+    let is_number = |part: &str, len| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(year, 4) || !is_number(month, 2) || !is_number(day, 2) {
+        return false;
+    }
+    let [year, month, day] = [year, month, day].map(|part| part.parse::<u32>().unwrap_or(0));
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => 0,
+    };
+    (1..=days).contains(&day)
+}
+
+/// A value not matching the kind of its field.
+#[derive(Debug, Error, PartialEq)]
+#[error("{value:?} isn't a valid {} value of {field}", kind.to_string().to_lowercase())]
+pub struct FieldError {
+    pub field: String,
+    pub kind: FieldKind,
+    pub value: String,
+}
+
+#[derive(Default, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct StatusCounts {
+    pub to_do: i64,
+    pub in_progress: i64,
+    pub done: i64,
+}
+
+impl StatusCounts {
+    pub fn active(&self) -> i64 {
+        self.to_do + self.in_progress
+    }
+
+    pub fn total(&self) -> i64 {
+        self.active() + self.done
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ServerError {
+    #[error("received a database error: {0}")]
+    Database(SqlxError),
+    #[error("the database took too long to respond")]
+    Timeout,
+    #[error("lost the connection to the database: {0}")]
+    ConnectionLost(SqlxError),
+    #[error("the tasks are read-only")]
+    ReadOnly,
+}
+
+impl From<SqlxError> for ServerError {
+    fn from(value: SqlxError) -> Self {
+        match value {
+            SqlxError::PoolTimedOut => Self::Timeout,
+            SqlxError::Io(_) | SqlxError::PoolClosed | SqlxError::WorkerCrashed => {
+                Self::ConnectionLost(value)
+            }
+            value => Self::Database(value),
+        }
+    }
+}
+
+impl From<Elapsed> for ServerError {
+    fn from(_: Elapsed) -> Self {
+        Self::Timeout
+    }
+}
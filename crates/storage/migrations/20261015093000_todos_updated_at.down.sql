@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS todos_delete;
+DROP TABLE IF EXISTS deleted_todos;
+
+DROP TRIGGER IF EXISTS todos_update_updated_at;
+DROP TRIGGER IF EXISTS todos_insert_updated_at;
+
+ALTER TABLE todos DROP COLUMN updated_at;
@@ -0,0 +1,27 @@
+-- todo last modification time
+ALTER TABLE todos ADD COLUMN updated_at TIMESTAMP;
+
+UPDATE todos
+SET updated_at = COALESCE(created_at, CURRENT_TIMESTAMP);
+
+CREATE TRIGGER IF NOT EXISTS todos_insert_updated_at AFTER INSERT ON todos
+BEGIN
+    UPDATE todos SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS todos_update_updated_at
+AFTER UPDATE OF description, status, priority ON todos
+BEGIN
+    UPDATE todos SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
+
+-- deleted todos, so that deletions can be picked up by incremental refreshes
+CREATE TABLE IF NOT EXISTS deleted_todos (
+    id         INTEGER PRIMARY KEY,
+    deleted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER IF NOT EXISTS todos_delete AFTER DELETE ON todos
+BEGIN
+    INSERT OR REPLACE INTO deleted_todos (id) VALUES (OLD.id);
+END;
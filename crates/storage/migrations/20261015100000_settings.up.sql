@@ -0,0 +1,5 @@
+-- key-value store for the application settings
+CREATE TABLE IF NOT EXISTS settings (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
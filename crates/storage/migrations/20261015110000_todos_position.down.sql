@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS todos_update_position_updated_at;
+
+ALTER TABLE todos DROP COLUMN position;
@@ -0,0 +1,11 @@
+-- todo position in the order chosen by the user
+ALTER TABLE todos ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+UPDATE todos
+SET position = id;
+
+CREATE TRIGGER IF NOT EXISTS todos_update_position_updated_at
+AFTER UPDATE OF position ON todos
+BEGIN
+    UPDATE todos SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
@@ -0,0 +1,8 @@
+-- link between todos and the VTODOs of a CalDAV collection
+CREATE TABLE IF NOT EXISTS caldav_sync (
+    task_id           INTEGER PRIMARY KEY,
+    href              TEXT NOT NULL,
+    uid               TEXT NOT NULL,
+    etag              TEXT NOT NULL,
+    synced_updated_at TEXT NOT NULL
+);
@@ -0,0 +1,8 @@
+-- link between todos and the tasks of Google Tasks lists
+CREATE TABLE IF NOT EXISTS google_tasks_sync (
+    task_id           INTEGER PRIMARY KEY,
+    list_id           TEXT NOT NULL,
+    remote_id         TEXT NOT NULL,
+    etag              TEXT NOT NULL,
+    synced_updated_at TEXT NOT NULL
+);
@@ -0,0 +1,6 @@
+-- link between todos and the GitHub issues they were imported from
+CREATE TABLE IF NOT EXISTS github_issues (
+    task_id INTEGER PRIMARY KEY,
+    repo    TEXT NOT NULL,
+    number  INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS todo_events_no_delete;
+DROP TRIGGER IF EXISTS todo_events_no_update;
+DROP TRIGGER IF EXISTS todo_events_deleted;
+DROP TRIGGER IF EXISTS todo_events_updated;
+DROP TRIGGER IF EXISTS todo_events_created;
+DROP INDEX IF EXISTS todo_events_task_id;
+DROP TABLE IF EXISTS todo_events;
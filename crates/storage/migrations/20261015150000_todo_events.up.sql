@@ -0,0 +1,55 @@
+-- append-only log of the changes to the todos, the todos table being its projection
+CREATE TABLE IF NOT EXISTS todo_events (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id     INTEGER NOT NULL,
+    -- 'created', 'updated' or 'deleted'
+    kind        TEXT NOT NULL,
+    -- values set by the change, NULL when an update leaves them unchanged
+    description TEXT,
+    status      INTEGER,
+    priority    INTEGER,
+    position    INTEGER,
+    occurred_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS todo_events_task_id ON todo_events (task_id);
+
+-- the existing todos start their history as if they were created as they are
+INSERT INTO todo_events (task_id, kind, description, status, priority, position, occurred_at)
+SELECT id, 'created', description, status, priority, position, COALESCE(created_at, updated_at, CURRENT_TIMESTAMP)
+FROM todos
+ORDER BY id;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_created AFTER INSERT ON todo_events
+WHEN NEW.kind = 'created'
+BEGIN
+    INSERT INTO todos (id, description, status, priority, position, updated_at)
+    VALUES (NEW.task_id, NEW.description, COALESCE(NEW.status, 0), COALESCE(NEW.priority, 0), NEW.position, NEW.occurred_at);
+END;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_updated AFTER INSERT ON todo_events
+WHEN NEW.kind = 'updated'
+BEGIN
+    UPDATE todos
+    SET description = COALESCE(NEW.description, description),
+        status = COALESCE(NEW.status, status),
+        priority = COALESCE(NEW.priority, priority),
+        position = COALESCE(NEW.position, position)
+    WHERE id = NEW.task_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_deleted AFTER INSERT ON todo_events
+WHEN NEW.kind = 'deleted'
+BEGIN
+    DELETE FROM todos WHERE id = NEW.task_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_no_update BEFORE UPDATE ON todo_events
+BEGIN
+    SELECT RAISE(ABORT, 'todo events are append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_no_delete BEFORE DELETE ON todo_events
+BEGIN
+    SELECT RAISE(ABORT, 'todo events are append-only');
+END;
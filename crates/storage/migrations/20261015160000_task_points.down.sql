@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS achievements;
+DROP TRIGGER IF EXISTS todos_award_points;
+DROP TABLE IF EXISTS task_points;
@@ -0,0 +1,21 @@
+-- points awarded once per task, the first time it is done, scaled by its priority
+CREATE TABLE IF NOT EXISTS task_points (
+    task_id    INTEGER PRIMARY KEY,
+    priority   INTEGER NOT NULL,
+    points     INTEGER NOT NULL,
+    awarded_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER IF NOT EXISTS todos_award_points
+AFTER UPDATE OF status ON todos
+WHEN NEW.status = 2 AND OLD.status != 2
+BEGIN
+    INSERT OR IGNORE INTO task_points (task_id, priority, points)
+    VALUES (NEW.id, NEW.priority, 10 * (NEW.priority + 1));
+END;
+
+-- achievements unlocked, by key
+CREATE TABLE IF NOT EXISTS achievements (
+    key         TEXT PRIMARY KEY,
+    unlocked_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS todos_delete_habit;
+DROP TABLE IF EXISTS habit_completions;
+DROP TABLE IF EXISTS habits;
@@ -0,0 +1,18 @@
+-- tasks in habit mode, done once a day
+CREATE TABLE IF NOT EXISTS habits (
+    task_id INTEGER PRIMARY KEY
+);
+
+-- days a habit was done, in the local time zone, like `2026-10-15`
+CREATE TABLE IF NOT EXISTS habit_completions (
+    task_id INTEGER NOT NULL,
+    day     TEXT NOT NULL,
+    PRIMARY KEY (task_id, day)
+);
+
+CREATE TRIGGER IF NOT EXISTS todos_delete_habit
+AFTER DELETE ON todos
+BEGIN
+    DELETE FROM habit_completions WHERE task_id = OLD.id;
+    DELETE FROM habits WHERE task_id = OLD.id;
+END;
@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS custom_fields_delete_values;
+DROP TRIGGER IF EXISTS todos_delete_field_values;
+DROP INDEX IF EXISTS custom_field_values_field;
+DROP TABLE IF EXISTS custom_field_values;
+DROP TABLE IF EXISTS custom_fields;
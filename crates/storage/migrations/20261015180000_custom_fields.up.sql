@@ -0,0 +1,30 @@
+-- fields defined by the user, their kind being the discriminant of `FieldKind`
+CREATE TABLE IF NOT EXISTS custom_fields (
+    id      INTEGER PRIMARY KEY AUTOINCREMENT,
+    name    TEXT NOT NULL UNIQUE,
+    kind    INTEGER NOT NULL,
+    -- choices of an enum field, one per line
+    options TEXT NOT NULL DEFAULT ''
+);
+
+-- values of the fields, normalized by `CustomField::parse_value`
+CREATE TABLE IF NOT EXISTS custom_field_values (
+    task_id  INTEGER NOT NULL,
+    field_id INTEGER NOT NULL,
+    value    TEXT NOT NULL,
+    PRIMARY KEY (task_id, field_id)
+);
+
+CREATE INDEX IF NOT EXISTS custom_field_values_field ON custom_field_values (field_id, value);
+
+CREATE TRIGGER IF NOT EXISTS todos_delete_field_values
+AFTER DELETE ON todos
+BEGIN
+    DELETE FROM custom_field_values WHERE task_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS custom_fields_delete_values
+AFTER DELETE ON custom_fields
+BEGIN
+    DELETE FROM custom_field_values WHERE field_id = OLD.id;
+END;
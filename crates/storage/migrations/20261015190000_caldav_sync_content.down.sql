@@ -0,0 +1 @@
+ALTER TABLE caldav_sync DROP COLUMN synced_content;
@@ -0,0 +1,2 @@
+-- synchronized fields of the tasks, telling apart the changes made in the second of a sync
+ALTER TABLE caldav_sync ADD COLUMN synced_content TEXT NOT NULL DEFAULT '';
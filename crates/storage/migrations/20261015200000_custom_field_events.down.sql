@@ -0,0 +1,12 @@
+DROP TRIGGER IF EXISTS todo_events_field;
+
+-- the events are append-only, except for dropping the kind unknown before this migration
+DROP TRIGGER IF EXISTS todo_events_no_delete;
+DELETE FROM todo_events WHERE kind = 'field';
+CREATE TRIGGER IF NOT EXISTS todo_events_no_delete BEFORE DELETE ON todo_events
+BEGIN
+    SELECT RAISE(ABORT, 'todo events are append-only');
+END;
+
+ALTER TABLE todo_events DROP COLUMN field_value;
+ALTER TABLE todo_events DROP COLUMN field_id;
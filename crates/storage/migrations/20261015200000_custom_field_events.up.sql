@@ -0,0 +1,22 @@
+-- changes of the values of the custom fields, with the 'field' kind, NULL clearing the value
+ALTER TABLE todo_events ADD COLUMN field_id INTEGER;
+ALTER TABLE todo_events ADD COLUMN field_value TEXT;
+
+-- the existing values start their history as if they were set when their task was created
+INSERT INTO todo_events (task_id, kind, field_id, field_value, occurred_at)
+SELECT custom_field_values.task_id, 'field', custom_field_values.field_id, custom_field_values.value, COALESCE(todos.created_at, todos.updated_at, CURRENT_TIMESTAMP)
+FROM custom_field_values
+JOIN todos ON todos.id = custom_field_values.task_id
+ORDER BY custom_field_values.task_id, custom_field_values.field_id;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_field AFTER INSERT ON todo_events
+WHEN NEW.kind = 'field'
+BEGIN
+    DELETE FROM custom_field_values
+    WHERE NEW.field_value IS NULL AND task_id = NEW.task_id AND field_id = NEW.field_id;
+    INSERT INTO custom_field_values (task_id, field_id, value)
+    SELECT NEW.task_id, NEW.field_id, NEW.field_value
+    WHERE NEW.field_value IS NOT NULL
+    ON CONFLICT (task_id, field_id) DO UPDATE SET value = excluded.value;
+    UPDATE todos SET updated_at = NEW.occurred_at WHERE id = NEW.task_id;
+END;
@@ -0,0 +1,704 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{LazyLock, OnceLock, RwLock};
+use std::time::Duration;
+
+use my_todos_core::{CustomField, FieldKind, Priority, ServerError, Status, StatusCounts, Task};
+use std::str::FromStr;
+
+use regex::Regex;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{FromRow, SqlitePool, Type};
+use tracing::instrument;
+
+mod memory;
+pub mod metrics;
+mod sqlite;
+
+pub static DB: LazyLock<RwLock<SqlitePool>> = LazyLock::new(|| RwLock::new(connect()));
+
+/// Where the tasks are stored, chosen at startup with the `TODOS_BACKEND` environment variable.
+/// The functions of this module forward to the `TaskStore` of the backend, see `STORE`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Backend {
+    Sqlite,
+    /// Demo backend keeping the tasks in memory.
+    Memory,
+}
+
+pub static BACKEND: LazyLock<Backend> =
+    LazyLock::new(|| match env::var("TODOS_BACKEND").as_deref() {
+        Ok("memory") => Backend::Memory,
+        _ => Backend::Sqlite,
+    });
+
+/// The future of the operations of `TaskStore`.
+type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ServerError>> + Send + 'a>>;
+
+/// Operations of a backend, documented by the functions of this module forwarding to them.
+trait TaskStore: Send + Sync {
+    fn database_file(&self) -> Option<PathBuf>;
+    fn migrate(&self) -> StoreFuture<'static, ()>;
+    fn ping(&self) -> StoreFuture<'static, ()>;
+    fn backup<'a>(&self, path: &'a Path) -> StoreFuture<'a, ()>;
+    fn vacuum(&self) -> StoreFuture<'static, ()>;
+    fn get_tasks(&self, query: TaskQuery) -> StoreFuture<'static, Vec<Task>>;
+    fn get_tasks_page(
+        &self,
+        query: TaskQuery,
+        offset: i64,
+        limit: i64,
+    ) -> StoreFuture<'static, (Vec<Task>, i64)>;
+    fn get_task(&self, id: i64) -> StoreFuture<'static, Task>;
+    fn get_tasks_since(&self, since: String) -> StoreFuture<'static, (Vec<Task>, Vec<i64>)>;
+    fn get_stale_tasks(&self, days: u32) -> StoreFuture<'static, Vec<Task>>;
+    fn local_date(&self) -> StoreFuture<'static, String>;
+    fn count_by_status(&self) -> StoreFuture<'static, StatusCounts>;
+    fn create_task(&self, desc: String, priority: Priority) -> StoreFuture<'static, Task>;
+    fn create_tasks(&self, new_tasks: Vec<(String, Priority)>) -> StoreFuture<'static, Vec<Task>>;
+    fn update_task(
+        &self,
+        id: i64,
+        desc: String,
+        status: Status,
+        priority: Priority,
+    ) -> StoreFuture<'static, Task>;
+    fn update_tasks(
+        &self,
+        updates: Vec<(i64, String, Status, Priority)>,
+    ) -> StoreFuture<'static, Vec<Task>>;
+    fn set_tasks_status(&self, ids: Vec<i64>, status: Status) -> StoreFuture<'static, Vec<Task>>;
+    fn reorder_tasks(&self, ids: Vec<i64>) -> StoreFuture<'static, Vec<Task>>;
+    fn delete_task(&self, id: i64) -> StoreFuture<'static, i64>;
+    fn delete_tasks(&self, ids: Vec<i64>) -> StoreFuture<'static, Vec<i64>>;
+    fn get_task_history(&self, id: i64) -> StoreFuture<'static, Vec<TaskEvent>>;
+    fn get_tasks_at(&self, at: String) -> StoreFuture<'static, Vec<Task>>;
+    fn get_points(&self) -> StoreFuture<'static, PointsSummary>;
+    fn get_achievements(&self) -> StoreFuture<'static, Vec<String>>;
+    fn unlock_achievement<'a>(&self, key: &'a str) -> StoreFuture<'a, bool>;
+    fn get_habit(&self, task_id: i64) -> StoreFuture<'static, Option<Vec<String>>>;
+    fn set_habit(&self, task_id: i64, habit: bool) -> StoreFuture<'static, ()>;
+    fn set_habit_done<'a>(&self, task_id: i64, day: &'a str, done: bool) -> StoreFuture<'a, ()>;
+    fn get_custom_fields(&self) -> StoreFuture<'static, Vec<CustomField>>;
+    fn create_custom_field<'a>(
+        &self,
+        name: &'a str,
+        kind: FieldKind,
+        options: &'a str,
+    ) -> StoreFuture<'a, CustomField>;
+    fn delete_custom_field(&self, id: i64) -> StoreFuture<'static, ()>;
+    fn get_field_values(&self, task_id: Option<i64>) -> StoreFuture<'static, Vec<FieldValue>>;
+    fn set_field_value<'a>(
+        &self,
+        task_id: i64,
+        field_id: i64,
+        value: Option<&'a str>,
+    ) -> StoreFuture<'a, Task>;
+    fn get_setting<'a>(&self, key: &'a str) -> StoreFuture<'a, Option<String>>;
+    fn set_setting<'a>(&self, key: &'a str, value: String) -> StoreFuture<'a, ()>;
+    fn get_sync_entries(&self) -> StoreFuture<'static, Vec<SyncEntry>>;
+    fn set_sync_entry(&self, entry: SyncEntry) -> StoreFuture<'static, ()>;
+    fn delete_sync_entry(&self, task_id: i64) -> StoreFuture<'static, ()>;
+    fn get_google_tasks_entries(&self) -> StoreFuture<'static, Vec<GoogleTasksEntry>>;
+    fn set_google_tasks_entry(&self, entry: GoogleTasksEntry) -> StoreFuture<'static, ()>;
+    fn delete_google_tasks_entry(&self, task_id: i64) -> StoreFuture<'static, ()>;
+    fn get_github_issues(&self) -> StoreFuture<'static, Vec<GitHubIssue>>;
+    fn set_github_issue(&self, issue: GitHubIssue) -> StoreFuture<'static, ()>;
+    fn delete_github_issue(&self, task_id: i64) -> StoreFuture<'static, ()>;
+}
+
+/// The backend of `BACKEND`, chosen once.
+static STORE: LazyLock<Box<dyn TaskStore>> = LazyLock::new(|| match *BACKEND {
+    Backend::Sqlite => Box::new(sqlite::SqliteStore),
+    Backend::Memory => Box::new(memory::MemoryStore),
+});
+
+/// Directory of the database when `DATABASE_URL` isn't set, see `set_data_dir`.
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Keeps the database in `dir` instead of `db/`, created if missing, for platforms like Android
+/// where the working directory isn't writable. Must be called before the first query.
+pub fn set_data_dir(dir: PathBuf) {
+    let _ = DATA_DIR.set(dir);
+}
+
+/// Directory of the files of the app, `db/` unless changed by `set_data_dir`.
+pub fn data_dir() -> PathBuf {
+    DATA_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("db"))
+}
+
+/// Database file opened instead of the default one, see `set_database_path`.
+static DATABASE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Opens the database at `path`, created if missing, instead of the one of `DATABASE_URL` or of
+/// the data directory. Must be called before the first query.
+pub fn set_database_path(path: PathBuf) {
+    let _ = DATABASE_PATH.set(path);
+}
+
+fn connection_string() -> String {
+    match DATABASE_PATH.get() {
+        Some(path) => format!("sqlite://{}?mode=rwc", path.display()),
+        None => env::var("DATABASE_URL").unwrap_or_else(|_| match DATA_DIR.get() {
+            Some(dir) => format!("sqlite://{}?mode=rwc", dir.join("Todos.db").display()),
+            None => "sqlite://db/Todos.db".to_string(),
+        }),
+    }
+}
+
+/// File of the SQLite database, `None` if the tasks aren't kept in a file, like with the
+/// in-memory backend or a `sqlite::memory:` URL.
+pub fn database_file() -> Option<PathBuf> {
+    STORE.database_file()
+}
+
+fn connect() -> SqlitePool {
+    let db_connection_str = connection_string();
+    let options = connect_options(&db_connection_str).expect("invalid database URL");
+
+    SqlitePoolOptions::new()
+        .max_connections(20)
+        .acquire_timeout(Duration::from_secs(3))
+        .connect_lazy_with(options)
+}
+
+/// Options of the connections to the database at `url`, with the REGEXP function running the
+/// searches, see `search_pattern`. The pools given to `set_pool` must be opened with them.
+pub fn connect_options(url: &str) -> Result<SqliteConnectOptions, ServerError> {
+    Ok(SqliteConnectOptions::from_str(url)?.with_regexp())
+}
+
+/// Pattern of the descriptions matching `search`, ignoring the case of any script. Both backends
+/// search with it, SQLite through REGEXP, so that they fold the case the same way.
+fn search_pattern(search: &str) -> String {
+    format!("(?i){}", regex::escape(search))
+}
+
+pub(crate) fn search_regex(search: &str) -> Regex {
+    Regex::new(&search_pattern(search)).expect("escaped patterns are valid")
+}
+
+/// Latency added to every SQLite query, to see the loading states while developing. Defaults to
+/// 500 ms in debug builds and none in release builds, `TODOS_SIMULATED_LATENCY_MS` overrides it.
+static SIMULATED_LATENCY: LazyLock<RwLock<Duration>> = LazyLock::new(|| {
+    let default = if cfg!(debug_assertions) { 500 } else { 0 };
+    let millis = env::var("TODOS_SIMULATED_LATENCY_MS")
+        .ok()
+        .and_then(|millis| millis.parse().ok())
+        .unwrap_or(default);
+    RwLock::new(Duration::from_millis(millis))
+});
+
+/// Changes the simulated latency, `Duration::ZERO` disabling it.
+pub fn set_simulated_latency(latency: Duration) {
+    *SIMULATED_LATENCY.write().unwrap() = latency;
+}
+
+async fn simulate_latency() {
+    let latency = *SIMULATED_LATENCY.read().unwrap();
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+}
+
+/// Returns the current pool. Pools are reference counted so this is cheap.
+pub fn pool() -> SqlitePool {
+    DB.read().unwrap().clone()
+}
+
+/// Applies the migrations not applied yet, for the databases created by the app itself.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn migrate() -> Result<(), ServerError> {
+    STORE.migrate().await
+}
+
+/// Replaces the current pool with a new one. Operations already running keep using the old pool.
+pub fn reconnect() {
+    *DB.write().unwrap() = connect();
+}
+
+/// Replaces the current pool with `pool`, like an in-memory database for the tests. Its
+/// connections are opened with `connect_options`.
+pub fn set_pool(pool: SqlitePool) {
+    *DB.write().unwrap() = pool;
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn ping() -> Result<(), ServerError> {
+    STORE.ping().await
+}
+
+/// Writes a copy of the database to `path`, which must not exist yet. The copy is consistent even
+/// while the tasks change.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn backup(path: &Path) -> Result<(), ServerError> {
+    STORE.backup(path).await
+}
+
+/// Rebuilds the database file to reclaim the space of the deleted rows.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn vacuum() -> Result<(), ServerError> {
+    STORE.vacuum().await
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum TaskOrder {
+    StatusFirst,
+    PriorityFirst,
+    UserOrder,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct TaskQuery {
+    pub statuses: Option<Vec<Status>>,
+    pub search: Option<String>,
+    pub order: Option<TaskOrder>,
+    /// Only the tasks with this value of a custom field, by field id.
+    pub field: Option<(i64, String)>,
+    /// Id of a custom field ordering the tasks before `order`, by number for the number fields,
+    /// the tasks without a value last.
+    pub field_order: Option<i64>,
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_tasks(query: TaskQuery) -> Result<Vec<Task>, ServerError> {
+    STORE.get_tasks(query).await
+}
+
+/// Returns a page of the tasks matching the query and the total number of matching tasks.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_tasks_page(
+    query: TaskQuery,
+    offset: i64,
+    limit: i64,
+) -> Result<(Vec<Task>, i64), ServerError> {
+    STORE.get_tasks_page(query, offset, limit).await
+}
+
+#[instrument(level = "debug", skip_all, fields(id = id), err)]
+pub async fn get_task(id: i64) -> Result<Task, ServerError> {
+    STORE.get_task(id).await
+}
+
+/// Returns the tasks updated and the ids of the tasks deleted since the `since` timestamp.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_tasks_since(since: String) -> Result<(Vec<Task>, Vec<i64>), ServerError> {
+    STORE.get_tasks_since(since).await
+}
+
+/// Returns the tasks not done and untouched for `days` days, the oldest first. The timestamps of
+/// the in-memory backend aren't times, so none of its tasks are stale.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_stale_tasks(days: u32) -> Result<Vec<Task>, ServerError> {
+    STORE.get_stale_tasks(days).await
+}
+
+/// Today in the local time zone, like `2026-10-15`, changing at midnight. The in-memory backend
+/// returns the date in UTC instead.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn local_date() -> Result<String, ServerError> {
+    STORE.local_date().await
+}
+
+/// Follows the changes made to the tasks by anyone, with `get_tasks_since`.
+pub struct TaskWatcher {
+    /// `updated_at` of the known tasks, since `get_tasks_since` also returns the tasks changed at
+    /// `since`.
+    known: HashMap<i64, String>,
+    since: String,
+}
+
+impl TaskWatcher {
+    /// Starts from the current tasks, which aren't reported as changed.
+    #[instrument(level = "debug", skip_all, err)]
+    pub async fn new() -> Result<Self, ServerError> {
+        let known = get_tasks(TaskQuery::default())
+            .await?
+            .into_iter()
+            .map(|task| (task.id, task.updated_at))
+            .collect::<HashMap<_, _>>();
+        let since = known.values().max().cloned().unwrap_or_default();
+        Ok(Self { known, since })
+    }
+
+    /// Returns the tasks changed and the ids of the tasks deleted since the last call.
+    #[instrument(level = "debug", skip_all, err)]
+    pub async fn changes(&mut self) -> Result<(Vec<Task>, Vec<i64>), ServerError> {
+        let (tasks, deleted) = get_tasks_since(self.since.clone()).await?;
+        let changed = tasks
+            .into_iter()
+            .filter(|task| self.known.get(&task.id) != Some(&task.updated_at))
+            .collect::<Vec<_>>();
+        for task in &changed {
+            self.since = self.since.clone().max(task.updated_at.clone());
+            self.known.insert(task.id, task.updated_at.clone());
+        }
+        let deleted = deleted
+            .into_iter()
+            .filter(|id| self.known.remove(id).is_some())
+            .collect();
+        Ok((changed, deleted))
+    }
+
+    /// Ids of the known tasks.
+    pub fn ids(&self) -> impl Iterator<Item = i64> + '_ {
+        self.known.keys().copied()
+    }
+
+    /// Doesn't report the task as changed, until it changes again.
+    pub fn mark_known(&mut self, task: &Task) {
+        self.known.insert(task.id, task.updated_at.clone());
+    }
+
+    /// Doesn't report the task as deleted.
+    pub fn forget(&mut self, id: i64) {
+        self.known.remove(&id);
+    }
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn count_by_status() -> Result<StatusCounts, ServerError> {
+    STORE.count_by_status().await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn create_task(desc: String) -> Result<Task, ServerError> {
+    STORE.create_task(desc, Priority::default()).await
+}
+
+/// Creates the task with its priority in a single insert, so that it is never stored with
+/// another one.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn create_task_with_priority(
+    desc: String,
+    priority: Priority,
+) -> Result<Task, ServerError> {
+    STORE.create_task(desc, priority).await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn create_tasks(descs: Vec<String>) -> Result<Vec<Task>, ServerError> {
+    let new_tasks = descs
+        .into_iter()
+        .map(|desc| (desc, Priority::default()))
+        .collect();
+    STORE.create_tasks(new_tasks).await
+}
+
+/// Creates the tasks with their priorities, like `create_task_with_priority`, in a single
+/// transaction.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn create_tasks_with_priorities(
+    new_tasks: Vec<(String, Priority)>,
+) -> Result<Vec<Task>, ServerError> {
+    STORE.create_tasks(new_tasks).await
+}
+
+#[instrument(level = "debug", skip_all, fields(id = id), err)]
+pub async fn update_task(
+    id: i64,
+    desc: String,
+    status: Status,
+    priority: Priority,
+) -> Result<Task, ServerError> {
+    STORE.update_task(id, desc, status, priority).await
+}
+
+/// Applies all the updates in a single transaction.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn update_tasks(
+    updates: Vec<(i64, String, Status, Priority)>,
+) -> Result<Vec<Task>, ServerError> {
+    STORE.update_tasks(updates).await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn set_tasks_status(ids: Vec<i64>, status: Status) -> Result<Vec<Task>, ServerError> {
+    STORE.set_tasks_status(ids, status).await
+}
+
+/// Puts the tasks in the order of `ids` by swapping their positions, so that they keep their
+/// place relative to the other tasks. Returns the tasks that moved.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn reorder_tasks(ids: Vec<i64>) -> Result<Vec<Task>, ServerError> {
+    STORE.reorder_tasks(ids).await
+}
+
+#[instrument(level = "debug", skip_all, fields(id = id), err)]
+pub async fn delete_task(id: i64) -> Result<i64, ServerError> {
+    STORE.delete_task(id).await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn delete_tasks(ids: Vec<i64>) -> Result<Vec<i64>, ServerError> {
+    STORE.delete_tasks(ids).await
+}
+
+#[derive(Type, PartialEq, Clone, Copy, Debug)]
+#[sqlx(rename_all = "lowercase")]
+pub enum TaskEventKind {
+    Created,
+    Updated,
+    Deleted,
+    /// Change of the value of a custom field.
+    Field,
+}
+
+/// A change in the append-only log the tasks are projected from.
+#[derive(FromRow, Clone, Debug)]
+pub struct TaskEvent {
+    pub id: i64,
+    pub task_id: i64,
+    pub kind: TaskEventKind,
+    /// Values set by the change, `None` when an update leaves them unchanged.
+    pub description: Option<String>,
+    pub status: Option<Status>,
+    pub priority: Option<Priority>,
+    pub position: Option<i64>,
+    /// Custom field of a `TaskEventKind::Field` change, with its new value, `None` when cleared.
+    pub field_id: Option<i64>,
+    pub field_value: Option<String>,
+    pub occurred_at: String,
+}
+
+impl TaskEvent {
+    /// Applies the change to the tasks, by id, like the triggers of `todo_events` do to `todos`.
+    pub fn apply(&self, tasks: &mut BTreeMap<i64, Task>) {
+        match self.kind {
+            TaskEventKind::Created => {
+                let task = Task {
+                    id: self.task_id,
+                    description: self.description.as_deref().unwrap_or_default().into(),
+                    status: self.status.unwrap_or_default(),
+                    priority: self.priority.unwrap_or_default(),
+                    updated_at: self.occurred_at.clone(),
+                    position: self.position.unwrap_or_default(),
+                };
+                tasks.insert(self.task_id, task);
+            }
+            TaskEventKind::Updated => {
+                let Some(task) = tasks.get_mut(&self.task_id) else {
+                    return;
+                };
+                if let Some(description) = &self.description {
+                    task.description = description.as_str().into();
+                }
+                task.status = self.status.unwrap_or(task.status);
+                task.priority = self.priority.unwrap_or(task.priority);
+                task.position = self.position.unwrap_or(task.position);
+                task.updated_at = self.occurred_at.clone();
+            }
+            TaskEventKind::Deleted => {
+                tasks.remove(&self.task_id);
+            }
+            TaskEventKind::Field => {
+                if let Some(task) = tasks.get_mut(&self.task_id) {
+                    task.updated_at = self.occurred_at.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the changes of the task, the oldest first.
+#[instrument(level = "debug", skip_all, fields(id = id), err)]
+pub async fn get_task_history(id: i64) -> Result<Vec<TaskEvent>, ServerError> {
+    STORE.get_task_history(id).await
+}
+
+/// Returns the tasks as they were at the `at` timestamp, like `2026-10-15 09:30:00` in UTC, by
+/// replaying their changes. The tasks are in the user order.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_tasks_at(at: String) -> Result<Vec<Task>, ServerError> {
+    STORE.get_tasks_at(at).await
+}
+
+/// Points awarded for the completed tasks, each task counting once.
+#[derive(FromRow, Default, Clone, Copy, Debug)]
+pub struct PointsSummary {
+    pub points: i64,
+    pub completed: i64,
+    pub high_priority: i64,
+}
+
+/// Sums the points awarded when the tasks were done for the first time. The demo awards none.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_points() -> Result<PointsSummary, ServerError> {
+    STORE.get_points().await
+}
+
+/// Keys of the unlocked achievements.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_achievements() -> Result<Vec<String>, ServerError> {
+    STORE.get_achievements().await
+}
+
+/// Unlocks the achievement `key`, returning whether it was locked.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn unlock_achievement(key: &str) -> Result<bool, ServerError> {
+    STORE.unlock_achievement(key).await
+}
+
+/// Days the task was done as a habit, the most recent first, or `None` if it isn't a habit.
+#[instrument(level = "debug", skip_all, fields(task_id = task_id), err)]
+pub async fn get_habit(task_id: i64) -> Result<Option<Vec<String>>, ServerError> {
+    STORE.get_habit(task_id).await
+}
+
+/// Switches the habit mode of the task, the days it was done being kept when switched off.
+#[instrument(level = "debug", skip_all, fields(task_id = task_id), err)]
+pub async fn set_habit(task_id: i64, habit: bool) -> Result<(), ServerError> {
+    STORE.set_habit(task_id, habit).await
+}
+
+/// Records whether the habit was done on `day`, like `2026-10-15`, see `local_date`.
+#[instrument(level = "debug", skip_all, fields(task_id = task_id), err)]
+pub async fn set_habit_done(task_id: i64, day: &str, done: bool) -> Result<(), ServerError> {
+    STORE.set_habit_done(task_id, day, done).await
+}
+
+/// The custom fields, in the order they were defined.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_custom_fields() -> Result<Vec<CustomField>, ServerError> {
+    STORE.get_custom_fields().await
+}
+
+/// Defines a custom field, failing if one already has the name. `options` are the choices of a
+/// `FieldKind::Choice` field, one per line.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn create_custom_field(
+    name: &str,
+    kind: FieldKind,
+    options: &str,
+) -> Result<CustomField, ServerError> {
+    STORE.create_custom_field(name, kind, options).await
+}
+
+/// Deletes a custom field with the values of the tasks.
+#[instrument(level = "debug", skip_all, fields(id = id), err)]
+pub async fn delete_custom_field(id: i64) -> Result<(), ServerError> {
+    STORE.delete_custom_field(id).await
+}
+
+/// Value of a custom field for a task.
+#[derive(FromRow, Clone, Debug, PartialEq)]
+pub struct FieldValue {
+    pub task_id: i64,
+    pub field_id: i64,
+    pub value: String,
+}
+
+/// Values of the custom fields of the task, or of all the tasks when `None`, like for the exports.
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_field_values(task_id: Option<i64>) -> Result<Vec<FieldValue>, ServerError> {
+    STORE.get_field_values(task_id).await
+}
+
+/// Sets the value of a custom field of the task, removing it when `None`, and returns the task.
+/// The value is stored as given, see `CustomField::parse_value`. A change is logged in the history
+/// of the task and updates it, like the other changes.
+#[instrument(level = "debug", skip_all, fields(task_id = task_id, field_id = field_id), err)]
+pub async fn set_field_value(
+    task_id: i64,
+    field_id: i64,
+    value: Option<&str>,
+) -> Result<Task, ServerError> {
+    STORE.set_field_value(task_id, field_id, value).await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_setting(key: &str) -> Result<Option<String>, ServerError> {
+    STORE.get_setting(key).await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn set_setting(key: &str, value: String) -> Result<(), ServerError> {
+    STORE.set_setting(key, value).await
+}
+
+/// Link between a task and the VTODO it is synchronized with, see `sync::caldav`.
+#[derive(FromRow, Clone, Debug)]
+pub struct SyncEntry {
+    pub task_id: i64,
+    /// Path of the VTODO on the CalDAV server.
+    pub href: String,
+    pub uid: String,
+    /// ETag of the VTODO when it was last synchronized.
+    pub etag: String,
+    /// Synchronized fields of the task when it was last synchronized, empty for the entries saved
+    /// before they were.
+    pub synced_content: String,
+    /// `updated_at` of the task when it was last synchronized.
+    pub synced_updated_at: String,
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_sync_entries() -> Result<Vec<SyncEntry>, ServerError> {
+    STORE.get_sync_entries().await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn set_sync_entry(entry: SyncEntry) -> Result<(), ServerError> {
+    STORE.set_sync_entry(entry).await
+}
+
+#[instrument(level = "debug", skip_all, fields(task_id = task_id), err)]
+pub async fn delete_sync_entry(task_id: i64) -> Result<(), ServerError> {
+    STORE.delete_sync_entry(task_id).await
+}
+
+/// Link between a task and the Google task it is synchronized with, see `sync::google_tasks`.
+#[derive(FromRow, Clone, Debug)]
+pub struct GoogleTasksEntry {
+    pub task_id: i64,
+    pub list_id: String,
+    pub remote_id: String,
+    /// ETag of the Google task when it was last synchronized.
+    pub etag: String,
+    /// `updated_at` of the task when it was last synchronized.
+    pub synced_updated_at: String,
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_google_tasks_entries() -> Result<Vec<GoogleTasksEntry>, ServerError> {
+    STORE.get_google_tasks_entries().await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn set_google_tasks_entry(entry: GoogleTasksEntry) -> Result<(), ServerError> {
+    STORE.set_google_tasks_entry(entry).await
+}
+
+#[instrument(level = "debug", skip_all, fields(task_id = task_id), err)]
+pub async fn delete_google_tasks_entry(task_id: i64) -> Result<(), ServerError> {
+    STORE.delete_google_tasks_entry(task_id).await
+}
+
+/// Link between a task and the GitHub issue it was imported from, see `import::github`.
+#[derive(FromRow, Clone, Debug)]
+pub struct GitHubIssue {
+    pub task_id: i64,
+    /// Repository of the issue, like `owner/name`.
+    pub repo: String,
+    pub number: i64,
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn get_github_issues() -> Result<Vec<GitHubIssue>, ServerError> {
+    STORE.get_github_issues().await
+}
+
+#[instrument(level = "debug", skip_all, err)]
+pub async fn set_github_issue(issue: GitHubIssue) -> Result<(), ServerError> {
+    STORE.set_github_issue(issue).await
+}
+
+#[instrument(level = "debug", skip_all, fields(task_id = task_id), err)]
+pub async fn delete_github_issue(task_id: i64) -> Result<(), ServerError> {
+    STORE.delete_github_issue(task_id).await
+}
@@ -0,0 +1,710 @@
+//! In-memory backend used as a demo, with the same functions as the SQLite backend. Nothing is
+//! persisted once the application is closed.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::future::ready;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use my_todos_core::{CustomField, FieldKind, Priority, ServerError, Status, StatusCounts, Task};
+use regex::Regex;
+use sqlx::Error as SqlxError;
+use sqlx::{Connection, SqliteConnection};
+
+use crate::database::{
+    FieldValue, GitHubIssue, GoogleTasksEntry, PointsSummary, StoreFuture, SyncEntry, TaskEvent,
+    TaskOrder, TaskQuery, TaskStore, search_regex,
+};
+
+static STORE: LazyLock<Mutex<Store>> = LazyLock::new(|| Mutex::new(Store::demo()));
+
+#[derive(Default)]
+struct Store {
+    tasks: Vec<Task>,
+    deleted: Vec<(i64, String)>,
+    settings: HashMap<String, String>,
+    sync_entries: Vec<SyncEntry>,
+    google_tasks_entries: Vec<GoogleTasksEntry>,
+    github_issues: Vec<GitHubIssue>,
+    /// Days each habit was done, by task id.
+    habits: HashMap<i64, BTreeSet<String>>,
+    custom_fields: Vec<CustomField>,
+    /// Values of the custom fields, by task id and field id.
+    field_values: BTreeMap<(i64, i64), String>,
+    next_id: i64,
+    next_field_id: i64,
+    /// Incremented on every change, used as the `updated_at` timestamp.
+    clock: u64,
+}
+
+impl Store {
+    fn demo() -> Self {
+        let mut store = Store::default();
+        for (description, status, priority) in [
+            ("Try the in-memory demo", Status::Done, Priority::Low),
+            ("Add a few tasks", Status::InProgress, Priority::Medium),
+            ("Switch to the SQLite backend", Status::ToDo, Priority::High),
+        ] {
+            let id = store.insert(description.to_string(), priority).id;
+            store.get_mut(id).unwrap().status = status;
+        }
+        store
+    }
+
+    fn now(&mut self) -> String {
+        self.clock += 1;
+        format!("{:020}", self.clock)
+    }
+
+    fn insert(&mut self, description: String, priority: Priority) -> Task {
+        self.next_id += 1;
+        let position = self
+            .tasks
+            .iter()
+            .map(|task| task.position)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let task = Task {
+            id: self.next_id,
+            description: description.into(),
+            priority,
+            updated_at: self.now(),
+            position,
+            ..Default::default()
+        };
+        self.tasks.push(task.clone());
+        task
+    }
+
+    fn get_mut(&mut self, id: i64) -> Result<&mut Task, ServerError> {
+        self.tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or(ServerError::Database(SqlxError::RowNotFound))
+    }
+
+    fn update(
+        &mut self,
+        id: i64,
+        desc: String,
+        status: Status,
+        priority: Priority,
+    ) -> Result<Task, ServerError> {
+        let updated_at = self.now();
+        let task = self.get_mut(id)?;
+        task.description = desc.into();
+        task.status = status;
+        task.priority = priority;
+        task.updated_at = updated_at;
+        Ok(task.clone())
+    }
+
+    fn delete(&mut self, id: i64) {
+        let deleted_at = self.now();
+        self.tasks.retain(|task| task.id != id);
+        self.habits.remove(&id);
+        self.field_values.retain(|(task_id, _), _| *task_id != id);
+        self.deleted.push((id, deleted_at));
+    }
+
+    fn matches(&self, query: &TaskQuery, search: Option<&Regex>, task: &Task) -> bool {
+        let status = query
+            .statuses
+            .as_ref()
+            .is_none_or(|statuses| statuses.contains(&task.status));
+        let search = search.is_none_or(|search| search.is_match(&task.description));
+        let field = query.field.as_ref().is_none_or(|(field_id, value)| {
+            self.field_values.get(&(task.id, *field_id)) == Some(value)
+        });
+        status && search && field
+    }
+
+    /// Orders by the value of the field, the tasks without one last.
+    fn field_order(&self, field_id: i64, a: &Task, b: &Task) -> Ordering {
+        let numeric = self
+            .custom_fields
+            .iter()
+            .any(|field| field.id == field_id && field.kind == FieldKind::Number);
+        let number = |value: &String| value.parse::<f64>().unwrap_or_default();
+        match (
+            self.field_values.get(&(a.id, field_id)),
+            self.field_values.get(&(b.id, field_id)),
+        ) {
+            (Some(a), Some(b)) if numeric => number(a).total_cmp(&number(b)),
+            (a, b) => a.is_none().cmp(&b.is_none()).then(a.cmp(&b)),
+        }
+    }
+
+    fn query(&self, query: &TaskQuery) -> Vec<Task> {
+        let search = query.search.as_deref().map(search_regex);
+        let mut tasks = self
+            .tasks
+            .iter()
+            .filter(|task| self.matches(query, search.as_ref(), task))
+            .cloned()
+            .collect::<Vec<_>>();
+        if query.order.is_some() || query.field_order.is_some() {
+            tasks.sort_by(|a, b| {
+                let field = query
+                    .field_order
+                    .map_or(Ordering::Equal, |field_id| self.field_order(field_id, a, b));
+                let Some(order) = query.order else {
+                    return field;
+                };
+                let status = a.status.cmp(&b.status);
+                let priority = b.priority.cmp(&a.priority);
+                let ordering = match order {
+                    TaskOrder::StatusFirst => status.then(priority),
+                    TaskOrder::PriorityFirst => priority.then(status),
+                    TaskOrder::UserOrder => a.position.cmp(&b.position),
+                };
+                field.then(ordering).then(b.id.cmp(&a.id))
+            });
+        }
+        tasks
+    }
+}
+
+fn store() -> std::sync::MutexGuard<'static, Store> {
+    STORE.lock().unwrap()
+}
+
+pub fn get_tasks(query: TaskQuery) -> Result<Vec<Task>, ServerError> {
+    Ok(store().query(&query))
+}
+
+pub fn get_tasks_page(
+    query: TaskQuery,
+    offset: i64,
+    limit: i64,
+) -> Result<(Vec<Task>, i64), ServerError> {
+    let query = TaskQuery {
+        order: query.order.or(Some(TaskOrder::StatusFirst)),
+        ..query
+    };
+    let tasks = store().query(&query);
+    let total = tasks.len() as i64;
+    let page = tasks
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    Ok((page, total))
+}
+
+pub fn get_task(id: i64) -> Result<Task, ServerError> {
+    store().get_mut(id).cloned()
+}
+
+pub fn get_tasks_since(since: String) -> Result<(Vec<Task>, Vec<i64>), ServerError> {
+    let store = store();
+    let tasks = store
+        .tasks
+        .iter()
+        .filter(|task| task.updated_at >= since)
+        .cloned()
+        .collect();
+    let deleted = store
+        .deleted
+        .iter()
+        .filter(|(_, deleted_at)| *deleted_at >= since)
+        .map(|(id, _)| *id)
+        .collect();
+    Ok((tasks, deleted))
+}
+
+pub fn count_by_status() -> Result<StatusCounts, ServerError> {
+    let mut counts = StatusCounts::default();
+    for task in &store().tasks {
+        match task.status {
+            Status::ToDo => counts.to_do += 1,
+            Status::InProgress => counts.in_progress += 1,
+            Status::Done => counts.done += 1,
+        }
+    }
+    Ok(counts)
+}
+
+pub fn create_task(desc: String, priority: Priority) -> Result<Task, ServerError> {
+    Ok(store().insert(desc, priority))
+}
+
+pub fn create_tasks(new_tasks: Vec<(String, Priority)>) -> Result<Vec<Task>, ServerError> {
+    let mut store = store();
+    Ok(new_tasks
+        .into_iter()
+        .map(|(desc, priority)| store.insert(desc, priority))
+        .collect())
+}
+
+pub fn update_task(
+    id: i64,
+    desc: String,
+    status: Status,
+    priority: Priority,
+) -> Result<Task, ServerError> {
+    store().update(id, desc, status, priority)
+}
+
+pub fn update_tasks(
+    updates: Vec<(i64, String, Status, Priority)>,
+) -> Result<Vec<Task>, ServerError> {
+    let mut store = store();
+    updates
+        .into_iter()
+        .map(|(id, desc, status, priority)| store.update(id, desc, status, priority))
+        .collect()
+}
+
+pub fn set_tasks_status(ids: Vec<i64>, status: Status) -> Result<Vec<Task>, ServerError> {
+    let mut store = store();
+    let mut tasks = Vec::with_capacity(ids.len());
+    for id in ids {
+        let updated_at = store.now();
+        if let Ok(task) = store.get_mut(id) {
+            task.status = status;
+            task.updated_at = updated_at;
+            tasks.push(task.clone());
+        }
+    }
+    Ok(tasks)
+}
+
+pub fn reorder_tasks(ids: Vec<i64>) -> Result<Vec<Task>, ServerError> {
+    let mut store = store();
+    let ids = ids
+        .into_iter()
+        .filter(|id| store.tasks.iter().any(|task| task.id == *id))
+        .collect::<Vec<_>>();
+    let mut positions = ids
+        .iter()
+        .filter_map(|id| store.get_mut(*id).ok().map(|task| task.position))
+        .collect::<Vec<_>>();
+    positions.sort();
+    let mut tasks = Vec::new();
+    for (id, position) in ids.into_iter().zip(positions) {
+        let updated_at = store.now();
+        let task = store.get_mut(id)?;
+        if task.position != position {
+            task.position = position;
+            task.updated_at = updated_at;
+            tasks.push(task.clone());
+        }
+    }
+    Ok(tasks)
+}
+
+pub fn delete_task(id: i64) -> Result<i64, ServerError> {
+    store().delete(id);
+    Ok(id)
+}
+
+pub fn delete_tasks(ids: Vec<i64>) -> Result<Vec<i64>, ServerError> {
+    let mut store = store();
+    let ids = ids
+        .into_iter()
+        .filter(|id| store.tasks.iter().any(|task| task.id == *id))
+        .collect::<Vec<_>>();
+    for id in &ids {
+        store.delete(*id);
+    }
+    Ok(ids)
+}
+
+/// The demo keeps no history.
+pub fn get_task_history(_id: i64) -> Result<Vec<TaskEvent>, ServerError> {
+    Ok(Vec::new())
+}
+
+/// The demo keeps no history, so these are the current tasks.
+pub fn get_tasks_at(_at: String) -> Result<Vec<Task>, ServerError> {
+    get_tasks(TaskQuery {
+        order: Some(TaskOrder::UserOrder),
+        ..Default::default()
+    })
+}
+
+/// Today in the local time zone, asked to an in-memory SQLite database so that the day changes
+/// at the same time as with the SQLite backend.
+pub async fn local_date() -> Result<String, ServerError> {
+    let mut connection = SqliteConnection::connect("sqlite::memory:").await?;
+    let date = sqlx::query_scalar::<_, String>("SELECT date('now', 'localtime')")
+        .fetch_one(&mut connection)
+        .await?;
+    Ok(date)
+}
+
+pub fn get_habit(task_id: i64) -> Result<Option<Vec<String>>, ServerError> {
+    Ok(store()
+        .habits
+        .get(&task_id)
+        .map(|days| days.iter().rev().cloned().collect()))
+}
+
+pub fn set_habit(task_id: i64, habit: bool) -> Result<(), ServerError> {
+    let mut store = store();
+    if !habit {
+        store.habits.remove(&task_id);
+    } else if store.tasks.iter().any(|task| task.id == task_id) {
+        store.habits.entry(task_id).or_default();
+    }
+    Ok(())
+}
+
+pub fn set_habit_done(task_id: i64, day: &str, done: bool) -> Result<(), ServerError> {
+    if let Some(days) = store().habits.get_mut(&task_id) {
+        if done {
+            days.insert(day.to_string());
+        } else {
+            days.remove(day);
+        }
+    }
+    Ok(())
+}
+
+pub fn get_custom_fields() -> Result<Vec<CustomField>, ServerError> {
+    Ok(store().custom_fields.clone())
+}
+
+pub fn create_custom_field(
+    name: &str,
+    kind: FieldKind,
+    options: &str,
+) -> Result<CustomField, ServerError> {
+    let mut store = store();
+    if store.custom_fields.iter().any(|field| field.name == name) {
+        let message = format!("UNIQUE constraint failed: custom_fields.name ({name})");
+        return Err(ServerError::Database(SqlxError::Protocol(message)));
+    }
+    store.next_field_id += 1;
+    let field = CustomField {
+        id: store.next_field_id,
+        name: name.to_string(),
+        kind,
+        options: options.to_string(),
+    };
+    store.custom_fields.push(field.clone());
+    Ok(field)
+}
+
+pub fn delete_custom_field(id: i64) -> Result<(), ServerError> {
+    let mut store = store();
+    store.custom_fields.retain(|field| field.id != id);
+    store
+        .field_values
+        .retain(|(_, field_id), _| *field_id != id);
+    Ok(())
+}
+
+pub fn get_field_values(task_id: Option<i64>) -> Result<Vec<FieldValue>, ServerError> {
+    Ok(store()
+        .field_values
+        .iter()
+        .filter(|((id, _), _)| task_id.is_none_or(|task_id| task_id == *id))
+        .map(|(&(task_id, field_id), value)| FieldValue {
+            task_id,
+            field_id,
+            value: value.clone(),
+        })
+        .collect())
+}
+
+pub fn set_field_value(
+    task_id: i64,
+    field_id: i64,
+    value: Option<&str>,
+) -> Result<Task, ServerError> {
+    let mut store = store();
+    store.get_mut(task_id)?;
+    let exists = store.custom_fields.iter().any(|field| field.id == field_id);
+    let changed = match value {
+        Some(value) if exists => store
+            .field_values
+            .insert((task_id, field_id), value.to_string())
+            .is_none_or(|previous| previous != value),
+        Some(_) => false,
+        None => store.field_values.remove(&(task_id, field_id)).is_some(),
+    };
+    if changed {
+        let updated_at = store.now();
+        store.get_mut(task_id)?.updated_at = updated_at;
+    }
+    store.get_mut(task_id).cloned()
+}
+
+pub fn get_setting(key: &str) -> Result<Option<String>, ServerError> {
+    Ok(store().settings.get(key).cloned())
+}
+
+pub fn set_setting(key: &str, value: String) -> Result<(), ServerError> {
+    store().settings.insert(key.to_string(), value);
+    Ok(())
+}
+
+pub fn get_sync_entries() -> Result<Vec<SyncEntry>, ServerError> {
+    Ok(store().sync_entries.clone())
+}
+
+pub fn set_sync_entry(entry: SyncEntry) -> Result<(), ServerError> {
+    let mut store = store();
+    store
+        .sync_entries
+        .retain(|stored| stored.task_id != entry.task_id);
+    store.sync_entries.push(entry);
+    Ok(())
+}
+
+pub fn delete_sync_entry(task_id: i64) -> Result<(), ServerError> {
+    store()
+        .sync_entries
+        .retain(|stored| stored.task_id != task_id);
+    Ok(())
+}
+
+pub fn get_google_tasks_entries() -> Result<Vec<GoogleTasksEntry>, ServerError> {
+    Ok(store().google_tasks_entries.clone())
+}
+
+pub fn set_google_tasks_entry(entry: GoogleTasksEntry) -> Result<(), ServerError> {
+    let mut store = store();
+    store
+        .google_tasks_entries
+        .retain(|stored| stored.task_id != entry.task_id);
+    store.google_tasks_entries.push(entry);
+    Ok(())
+}
+
+pub fn delete_google_tasks_entry(task_id: i64) -> Result<(), ServerError> {
+    store()
+        .google_tasks_entries
+        .retain(|stored| stored.task_id != task_id);
+    Ok(())
+}
+
+pub fn get_github_issues() -> Result<Vec<GitHubIssue>, ServerError> {
+    Ok(store().github_issues.clone())
+}
+
+pub fn set_github_issue(issue: GitHubIssue) -> Result<(), ServerError> {
+    let mut store = store();
+    store
+        .github_issues
+        .retain(|stored| stored.task_id != issue.task_id);
+    store.github_issues.push(issue);
+    Ok(())
+}
+
+pub fn delete_github_issue(task_id: i64) -> Result<(), ServerError> {
+    store()
+        .github_issues
+        .retain(|stored| stored.task_id != task_id);
+    Ok(())
+}
+
+/// Keeps the tasks in memory, see `Store::demo`.
+pub(super) struct MemoryStore;
+
+impl TaskStore for MemoryStore {
+    fn database_file(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn migrate(&self) -> StoreFuture<'static, ()> {
+        Box::pin(ready(Ok(())))
+    }
+
+    fn ping(&self) -> StoreFuture<'static, ()> {
+        Box::pin(ready(Ok(())))
+    }
+
+    fn backup<'a>(&self, _path: &'a Path) -> StoreFuture<'a, ()> {
+        let message = "the in-memory backend can't be backed up";
+        Box::pin(ready(Err(SqlxError::Configuration(message.into()).into())))
+    }
+
+    fn vacuum(&self) -> StoreFuture<'static, ()> {
+        Box::pin(ready(Ok(())))
+    }
+
+    fn get_tasks(&self, query: TaskQuery) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(get_tasks(query)))
+    }
+
+    fn get_tasks_page(
+        &self,
+        query: TaskQuery,
+        offset: i64,
+        limit: i64,
+    ) -> StoreFuture<'static, (Vec<Task>, i64)> {
+        Box::pin(ready(get_tasks_page(query, offset, limit)))
+    }
+
+    fn get_task(&self, id: i64) -> StoreFuture<'static, Task> {
+        Box::pin(ready(get_task(id)))
+    }
+
+    fn get_tasks_since(&self, since: String) -> StoreFuture<'static, (Vec<Task>, Vec<i64>)> {
+        Box::pin(ready(get_tasks_since(since)))
+    }
+
+    fn get_stale_tasks(&self, _days: u32) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(Ok(Vec::new())))
+    }
+
+    fn local_date(&self) -> StoreFuture<'static, String> {
+        Box::pin(local_date())
+    }
+
+    fn count_by_status(&self) -> StoreFuture<'static, StatusCounts> {
+        Box::pin(ready(count_by_status()))
+    }
+
+    fn create_task(&self, desc: String, priority: Priority) -> StoreFuture<'static, Task> {
+        Box::pin(ready(create_task(desc, priority)))
+    }
+
+    fn create_tasks(&self, new_tasks: Vec<(String, Priority)>) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(create_tasks(new_tasks)))
+    }
+
+    fn update_task(
+        &self,
+        id: i64,
+        desc: String,
+        status: Status,
+        priority: Priority,
+    ) -> StoreFuture<'static, Task> {
+        Box::pin(ready(update_task(id, desc, status, priority)))
+    }
+
+    fn update_tasks(
+        &self,
+        updates: Vec<(i64, String, Status, Priority)>,
+    ) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(update_tasks(updates)))
+    }
+
+    fn set_tasks_status(&self, ids: Vec<i64>, status: Status) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(set_tasks_status(ids, status)))
+    }
+
+    fn reorder_tasks(&self, ids: Vec<i64>) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(reorder_tasks(ids)))
+    }
+
+    fn delete_task(&self, id: i64) -> StoreFuture<'static, i64> {
+        Box::pin(ready(delete_task(id)))
+    }
+
+    fn delete_tasks(&self, ids: Vec<i64>) -> StoreFuture<'static, Vec<i64>> {
+        Box::pin(ready(delete_tasks(ids)))
+    }
+
+    fn get_task_history(&self, id: i64) -> StoreFuture<'static, Vec<TaskEvent>> {
+        Box::pin(ready(get_task_history(id)))
+    }
+
+    fn get_tasks_at(&self, at: String) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(ready(get_tasks_at(at)))
+    }
+
+    fn get_points(&self) -> StoreFuture<'static, PointsSummary> {
+        Box::pin(ready(Ok(PointsSummary::default())))
+    }
+
+    fn get_achievements(&self) -> StoreFuture<'static, Vec<String>> {
+        Box::pin(ready(Ok(Vec::new())))
+    }
+
+    fn unlock_achievement<'a>(&self, _key: &'a str) -> StoreFuture<'a, bool> {
+        Box::pin(ready(Ok(false)))
+    }
+
+    fn get_habit(&self, task_id: i64) -> StoreFuture<'static, Option<Vec<String>>> {
+        Box::pin(ready(get_habit(task_id)))
+    }
+
+    fn set_habit(&self, task_id: i64, habit: bool) -> StoreFuture<'static, ()> {
+        Box::pin(ready(set_habit(task_id, habit)))
+    }
+
+    fn set_habit_done<'a>(&self, task_id: i64, day: &'a str, done: bool) -> StoreFuture<'a, ()> {
+        Box::pin(ready(set_habit_done(task_id, day, done)))
+    }
+
+    fn get_custom_fields(&self) -> StoreFuture<'static, Vec<CustomField>> {
+        Box::pin(ready(get_custom_fields()))
+    }
+
+    fn create_custom_field<'a>(
+        &self,
+        name: &'a str,
+        kind: FieldKind,
+        options: &'a str,
+    ) -> StoreFuture<'a, CustomField> {
+        Box::pin(ready(create_custom_field(name, kind, options)))
+    }
+
+    fn delete_custom_field(&self, id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(ready(delete_custom_field(id)))
+    }
+
+    fn get_field_values(&self, task_id: Option<i64>) -> StoreFuture<'static, Vec<FieldValue>> {
+        Box::pin(ready(get_field_values(task_id)))
+    }
+
+    fn set_field_value<'a>(
+        &self,
+        task_id: i64,
+        field_id: i64,
+        value: Option<&'a str>,
+    ) -> StoreFuture<'a, Task> {
+        Box::pin(ready(set_field_value(task_id, field_id, value)))
+    }
+
+    fn get_setting<'a>(&self, key: &'a str) -> StoreFuture<'a, Option<String>> {
+        Box::pin(ready(get_setting(key)))
+    }
+
+    fn set_setting<'a>(&self, key: &'a str, value: String) -> StoreFuture<'a, ()> {
+        Box::pin(ready(set_setting(key, value)))
+    }
+
+    fn get_sync_entries(&self) -> StoreFuture<'static, Vec<SyncEntry>> {
+        Box::pin(ready(get_sync_entries()))
+    }
+
+    fn set_sync_entry(&self, entry: SyncEntry) -> StoreFuture<'static, ()> {
+        Box::pin(ready(set_sync_entry(entry)))
+    }
+
+    fn delete_sync_entry(&self, task_id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(ready(delete_sync_entry(task_id)))
+    }
+
+    fn get_google_tasks_entries(&self) -> StoreFuture<'static, Vec<GoogleTasksEntry>> {
+        Box::pin(ready(get_google_tasks_entries()))
+    }
+
+    fn set_google_tasks_entry(&self, entry: GoogleTasksEntry) -> StoreFuture<'static, ()> {
+        Box::pin(ready(set_google_tasks_entry(entry)))
+    }
+
+    fn delete_google_tasks_entry(&self, task_id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(ready(delete_google_tasks_entry(task_id)))
+    }
+
+    fn get_github_issues(&self) -> StoreFuture<'static, Vec<GitHubIssue>> {
+        Box::pin(ready(get_github_issues()))
+    }
+
+    fn set_github_issue(&self, issue: GitHubIssue) -> StoreFuture<'static, ()> {
+        Box::pin(ready(set_github_issue(issue)))
+    }
+
+    fn delete_github_issue(&self, task_id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(ready(delete_github_issue(task_id)))
+    }
+}
@@ -0,0 +1,122 @@
+//! Latency and errors of the database operations, recorded from the spans of the `database`
+//! functions once `logging::init` installed `MetricsLayer`.
+
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Upper bounds of the latency buckets, slower operations going in a last bucket.
+pub const BUCKETS: [Duration; 10] = [
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(20),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+    Duration::from_millis(500),
+    Duration::from_millis(1000),
+];
+
+static METRICS: LazyLock<Mutex<BTreeMap<&'static str, OperationMetrics>>> =
+    LazyLock::new(Mutex::default);
+
+#[derive(Default, Clone, Debug)]
+pub struct OperationMetrics {
+    pub calls: u64,
+    pub errors: u64,
+    pub total: Duration,
+    pub max: Duration,
+    /// Number of calls in each bucket of `BUCKETS`, then of the slower calls.
+    pub histogram: [u64; BUCKETS.len() + 1],
+}
+
+impl OperationMetrics {
+    fn record(&mut self, latency: Duration, failed: bool) {
+        self.calls += 1;
+        self.errors += u64::from(failed);
+        self.total += latency;
+        self.max = self.max.max(latency);
+        let bucket = BUCKETS
+            .iter()
+            .position(|bound| latency <= *bound)
+            .unwrap_or(BUCKETS.len());
+        self.histogram[bucket] += 1;
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.total.div_f64(self.calls.max(1) as f64)
+    }
+
+    /// Bound under which the given fraction of the calls finished, `None` if it is past the last
+    /// bucket.
+    pub fn percentile(&self, fraction: f64) -> Option<Duration> {
+        let target = ((self.calls as f64 * fraction).ceil() as u64).max(1);
+        let mut count = 0;
+        for (bucket, calls) in self.histogram.iter().enumerate() {
+            count += calls;
+            if count >= target {
+                return BUCKETS.get(bucket).copied();
+            }
+        }
+        None
+    }
+}
+
+/// Returns the metrics of every operation called since the start, by name.
+pub fn snapshot() -> Vec<(&'static str, OperationMetrics)> {
+    let metrics = METRICS.lock().unwrap();
+    metrics
+        .iter()
+        .map(|(name, metrics)| (*name, metrics.clone()))
+        .collect()
+}
+
+/// Times the spans it receives, recording them by name. Failed spans are the ones with an error
+/// event, like those logged by `#[instrument(err)]`.
+pub struct MetricsLayer;
+
+struct Started(Instant);
+
+struct Failed;
+
+impl<S> Layer<S> for MetricsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attributes: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Started(Instant::now()));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() == Level::ERROR
+            && let Some(span) = ctx.event_span(event)
+        {
+            span.extensions_mut().insert(Failed);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        if let Some(Started(started)) = extensions.get::<Started>() {
+            let failed = extensions.get::<Failed>().is_some();
+            let mut metrics = METRICS.lock().unwrap();
+            metrics
+                .entry(span.name())
+                .or_default()
+                .record(started.elapsed(), failed);
+        }
+    }
+}
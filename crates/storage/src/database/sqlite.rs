@@ -0,0 +1,1013 @@
+//! SQLite backend, the default one. The tasks are kept in the database of `connection_string`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use my_todos_core::{CustomField, FieldKind, Priority, ServerError, Status, StatusCounts, Task};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+
+use crate::database::{
+    FieldValue, GitHubIssue, GoogleTasksEntry, PointsSummary, StoreFuture, SyncEntry, TaskEvent,
+    TaskOrder, TaskQuery, TaskStore, connection_string, pool, search_pattern, simulate_latency,
+};
+
+/// Keeps the tasks in the SQLite database of the pool, see `pool`.
+pub(super) struct SqliteStore;
+
+impl TaskOrder {
+    fn order_by(&self) -> &'static str {
+        match self {
+            TaskOrder::StatusFirst => " ORDER BY status ASC, priority DESC, id DESC",
+            TaskOrder::PriorityFirst => " ORDER BY priority DESC, status ASC, id DESC",
+            TaskOrder::UserOrder => " ORDER BY position ASC",
+        }
+    }
+}
+
+impl TaskQuery {
+    fn push_conditions(&self, builder: &mut QueryBuilder<'_, Sqlite>) {
+        if let Some(statuses) = &self.statuses {
+            builder.push(" AND status IN (");
+            let mut separated = builder.separated(", ");
+            for status in statuses {
+                separated.push_bind(*status);
+            }
+            separated.push_unseparated(")");
+        }
+        if let Some(search) = &self.search {
+            builder
+                .push(" AND description REGEXP ")
+                .push_bind(search_pattern(search));
+        }
+        if let Some((field_id, value)) = &self.field {
+            builder
+                .push(" AND id IN (SELECT task_id FROM custom_field_values WHERE field_id = ")
+                .push_bind(*field_id)
+                .push(" AND value = ")
+                .push_bind(value.clone())
+                .push(")");
+        }
+    }
+
+    fn push_order(&self, builder: &mut QueryBuilder<'_, Sqlite>, default: Option<TaskOrder>) {
+        let order = self.order.or(default);
+        let Some(field_id) = self.field_order else {
+            if let Some(order) = order {
+                builder.push(order.order_by());
+            }
+            return;
+        };
+        builder.push(" ORDER BY ");
+        for suffix in [" IS NULL, ", ""] {
+            // The numbers are stored as text, which would put 10 before 9.
+            builder
+                .push("(SELECT CASE custom_fields.kind WHEN ")
+                .push_bind(FieldKind::Number)
+                .push(" THEN CAST(value AS REAL) ELSE value END FROM custom_field_values")
+                .push(" JOIN custom_fields ON custom_fields.id = field_id")
+                .push(" WHERE task_id = todos.id AND field_id = ")
+                .push_bind(field_id)
+                .push(")")
+                .push(suffix);
+        }
+        if let Some(order) = order {
+            builder.push(order.order_by().replacen(" ORDER BY ", ", ", 1));
+        }
+    }
+}
+
+fn database_file() -> Option<PathBuf> {
+    let url = connection_string();
+    let path = url
+        .strip_prefix("sqlite://")
+        .or_else(|| url.strip_prefix("sqlite:"))?;
+    let path = path.split('?').next().unwrap_or_default();
+    (!path.is_empty() && path != ":memory:").then(|| PathBuf::from(path))
+}
+
+async fn migrate() -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::migrate!()
+        .run(pool)
+        .await
+        .map_err(|error| sqlx::Error::Migrate(Box::new(error)))?;
+    Ok(())
+}
+
+async fn ping() -> Result<(), ServerError> {
+    sqlx::query("SELECT 1").execute(&pool()).await?;
+    Ok(())
+}
+
+async fn backup(path: &Path) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(path.to_string_lossy().into_owned())
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn vacuum() -> Result<(), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    sqlx::query("VACUUM").execute(pool).await?;
+    Ok(())
+}
+
+async fn get_tasks(query: TaskQuery) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "SELECT id, description, status, priority, updated_at, position FROM todos WHERE 1 = 1",
+    );
+    query.push_conditions(&mut builder);
+    query.push_order(&mut builder, None);
+    let tasks = builder.build_query_as::<Task>().fetch_all(pool).await?;
+    Ok(tasks)
+}
+
+async fn get_tasks_page(
+    query: TaskQuery,
+    offset: i64,
+    limit: i64,
+) -> Result<(Vec<Task>, i64), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let mut builder = QueryBuilder::<Sqlite>::new(
+        "SELECT id, description, status, priority, updated_at, position FROM todos WHERE 1 = 1",
+    );
+    query.push_conditions(&mut builder);
+    query.push_order(&mut builder, Some(TaskOrder::StatusFirst));
+    builder
+        .push(" LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let tasks = builder.build_query_as::<Task>().fetch_all(pool).await?;
+
+    let mut builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM todos WHERE 1 = 1");
+    query.push_conditions(&mut builder);
+    let total = builder.build_query_scalar::<i64>().fetch_one(pool).await?;
+    Ok((tasks, total))
+}
+
+async fn get_task(id: i64) -> Result<Task, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let task = sqlx::query_as::<_, Task>(
+        "SELECT id, description, status, priority, updated_at, position FROM todos WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+    Ok(task)
+}
+
+async fn get_tasks_since(since: String) -> Result<(Vec<Task>, Vec<i64>), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let tasks = sqlx::query_as::<_, Task>(
+        "SELECT id, description, status, priority, updated_at, position FROM todos WHERE updated_at >= ?",
+    )
+    .bind(&since)
+    .fetch_all(pool)
+    .await?;
+    let deleted =
+        sqlx::query_scalar::<_, i64>("SELECT id FROM deleted_todos WHERE deleted_at >= ?")
+            .bind(since)
+            .fetch_all(pool)
+            .await?;
+    Ok((tasks, deleted))
+}
+
+async fn get_stale_tasks(days: u32) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let tasks = sqlx::query_as::<_, Task>(
+        "SELECT id, description, status, priority, updated_at, position FROM todos WHERE status != ? AND updated_at < datetime('now', ?) ORDER BY updated_at, id",
+    )
+    .bind(Status::Done)
+    .bind(format!("-{days} days"))
+    .fetch_all(pool)
+    .await?;
+    Ok(tasks)
+}
+
+async fn local_date() -> Result<String, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let date = sqlx::query_scalar::<_, String>("SELECT date('now', 'localtime')")
+        .fetch_one(pool)
+        .await?;
+    Ok(date)
+}
+
+async fn count_by_status() -> Result<StatusCounts, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let rows =
+        sqlx::query_as::<_, (Status, i64)>("SELECT status, COUNT(*) FROM todos GROUP BY status")
+            .fetch_all(pool)
+            .await?;
+    let mut counts = StatusCounts::default();
+    for (status, count) in rows {
+        match status {
+            Status::ToDo => counts.to_do = count,
+            Status::InProgress => counts.in_progress = count,
+            Status::Done => counts.done = count,
+        }
+    }
+    Ok(counts)
+}
+
+/// Rows written or read by a single statement, so that their parameters stay under the limit of
+/// SQLite (32766).
+const ROWS_PER_STATEMENT: usize = 1000;
+
+/// Reads the tasks with the given ids, in the user order.
+async fn fetch_tasks(
+    connection: &mut SqliteConnection,
+    ids: &[i64],
+) -> Result<Vec<Task>, ServerError> {
+    let mut tasks = Vec::with_capacity(ids.len());
+    for chunk in ids.chunks(ROWS_PER_STATEMENT) {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT id, description, status, priority, updated_at, position FROM todos WHERE id IN (",
+        );
+        let mut separated = query.separated(", ");
+        for id in chunk {
+            separated.push_bind(*id);
+        }
+        separated.push_unseparated(")");
+        tasks.extend(
+            query
+                .build_query_as::<Task>()
+                .fetch_all(&mut *connection)
+                .await?,
+        );
+    }
+    tasks.sort_by_key(|task| task.position);
+    Ok(tasks)
+}
+
+// The changes are appended to `todo_events`, whose triggers apply them to `todos`, so that the
+// tasks keep their full history.
+
+async fn create_task(desc: String, priority: Priority) -> Result<Task, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    // Ids come from the sequence of `todos`, so that the ids of deleted tasks aren't reused.
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO todo_events (task_id, kind, description, priority, position) VALUES ((SELECT COALESCE(MAX(seq), 0) + 1 FROM sqlite_sequence WHERE name = 'todos'), 'created', ?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM todos)) RETURNING task_id",
+    )
+    .bind(desc)
+    .bind(priority)
+    .fetch_one(pool)
+    .await?;
+    get_task(id).await
+}
+
+async fn create_tasks(new_tasks: Vec<(String, Priority)>) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    if new_tasks.is_empty() {
+        return Ok(Vec::new());
+    }
+    // New tasks are put at the end of the user order, in the order they are given. All of them are
+    // created or none, however many statements it takes.
+    let mut transaction = pool.begin().await?;
+    let (last_id, last_position) = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT (SELECT COALESCE(MAX(seq), 0) FROM sqlite_sequence WHERE name = 'todos'), (SELECT COALESCE(MAX(position), 0) FROM todos)",
+    )
+    .fetch_one(&mut *transaction)
+    .await?;
+    let rows = new_tasks.into_iter().zip(1..).collect::<Vec<_>>();
+    let mut ids = Vec::with_capacity(rows.len());
+    for chunk in rows.chunks(ROWS_PER_STATEMENT) {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "INSERT INTO todo_events (task_id, kind, description, priority, position) ",
+        );
+        query
+            .push_values(chunk, |mut row, ((desc, priority), i)| {
+                row.push_bind(last_id + i)
+                    .push("'created'")
+                    .push_bind(desc.as_str())
+                    .push_bind(*priority)
+                    .push_bind(last_position + i);
+            })
+            .push(" RETURNING task_id");
+        ids.extend(
+            query
+                .build_query_scalar::<i64>()
+                .fetch_all(&mut *transaction)
+                .await?,
+        );
+    }
+    let tasks = fetch_tasks(&mut *transaction, &ids).await?;
+    transaction.commit().await?;
+    Ok(tasks)
+}
+
+async fn update_task(
+    id: i64,
+    desc: String,
+    status: Status,
+    priority: Priority,
+) -> Result<Task, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    sqlx::query(
+        "INSERT INTO todo_events (task_id, kind, description, status, priority) SELECT id, 'updated', ?, ?, ? FROM todos WHERE id = ?",
+    )
+    .bind(desc)
+    .bind(status)
+    .bind(priority)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    get_task(id).await
+}
+
+async fn update_tasks(
+    updates: Vec<(i64, String, Status, Priority)>,
+) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let mut transaction = pool.begin().await?;
+    let mut tasks = Vec::with_capacity(updates.len());
+    for (id, desc, status, priority) in updates {
+        sqlx::query(
+            "INSERT INTO todo_events (task_id, kind, description, status, priority) SELECT id, 'updated', ?, ?, ? FROM todos WHERE id = ?",
+        )
+        .bind(desc)
+        .bind(status)
+        .bind(priority)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await?;
+        let task = sqlx::query_as::<_, Task>(
+            "SELECT id, description, status, priority, updated_at, position FROM todos WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&mut *transaction)
+        .await?;
+        tasks.push(task);
+    }
+    transaction.commit().await?;
+    Ok(tasks)
+}
+
+async fn set_tasks_status(ids: Vec<i64>, status: Status) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut transaction = pool.begin().await?;
+    let mut query = QueryBuilder::<Sqlite>::new(
+        "INSERT INTO todo_events (task_id, kind, status) SELECT id, 'updated', ",
+    );
+    query.push_bind(status).push(" FROM todos WHERE id IN (");
+    let mut separated = query.separated(", ");
+    for id in ids {
+        separated.push_bind(id);
+    }
+    separated.push_unseparated(") RETURNING task_id");
+    let ids = query
+        .build_query_scalar::<i64>()
+        .fetch_all(&mut *transaction)
+        .await?;
+    let tasks = fetch_tasks(&mut *transaction, &ids).await?;
+    transaction.commit().await?;
+    Ok(tasks)
+}
+
+async fn reorder_tasks(ids: Vec<i64>) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    // The ids are bound as a single JSON array, so that any number of them fits in the statement.
+    // The n-th task in the new order takes the n-th smallest of their positions, and only the
+    // tasks whose position changes get an event.
+    let ids_json = format!(
+        "[{}]",
+        ids.iter().map(i64::to_string).collect::<Vec<_>>().join(",")
+    );
+    let mut transaction = pool.begin().await?;
+    let moved = sqlx::query_scalar::<_, i64>(
+        "WITH moved AS (SELECT todos.id, todos.position, ROW_NUMBER() OVER (ORDER BY ids.key) AS new_rank, ROW_NUMBER() OVER (ORDER BY todos.position) AS old_rank FROM json_each(?) AS ids JOIN todos ON todos.id = ids.value) INSERT INTO todo_events (task_id, kind, position) SELECT task.id, 'updated', slot.position FROM moved AS task JOIN moved AS slot ON slot.old_rank = task.new_rank WHERE task.position != slot.position RETURNING task_id",
+    )
+    .bind(ids_json)
+    .fetch_all(&mut *transaction)
+    .await?;
+    let tasks = fetch_tasks(&mut *transaction, &moved).await?;
+    transaction.commit().await?;
+    Ok(tasks)
+}
+
+async fn delete_task(id: i64) -> Result<i64, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    sqlx::query(
+        "INSERT INTO todo_events (task_id, kind) SELECT id, 'deleted' FROM todos WHERE id = ?",
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(id)
+}
+
+async fn delete_tasks(ids: Vec<i64>) -> Result<Vec<i64>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut query = QueryBuilder::<Sqlite>::new(
+        "INSERT INTO todo_events (task_id, kind) SELECT id, 'deleted' FROM todos WHERE id IN (",
+    );
+    let mut separated = query.separated(", ");
+    for id in ids {
+        separated.push_bind(id);
+    }
+    separated.push_unseparated(") RETURNING task_id");
+    let ids = query.build_query_scalar::<i64>().fetch_all(pool).await?;
+    Ok(ids)
+}
+
+async fn get_task_history(id: i64) -> Result<Vec<TaskEvent>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let events = sqlx::query_as::<_, TaskEvent>(
+        "SELECT id, task_id, kind, description, status, priority, position, field_id, field_value, occurred_at FROM todo_events WHERE task_id = ? ORDER BY id ASC",
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+    Ok(events)
+}
+
+async fn get_tasks_at(at: String) -> Result<Vec<Task>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let events = sqlx::query_as::<_, TaskEvent>(
+        "SELECT id, task_id, kind, description, status, priority, position, field_id, field_value, occurred_at FROM todo_events WHERE occurred_at <= ? ORDER BY id ASC",
+    )
+    .bind(at)
+    .fetch_all(pool)
+    .await?;
+    let mut tasks = BTreeMap::new();
+    for event in &events {
+        event.apply(&mut tasks);
+    }
+    let mut tasks = tasks.into_values().collect::<Vec<_>>();
+    tasks.sort_by_key(|task| task.position);
+    Ok(tasks)
+}
+
+async fn get_points() -> Result<PointsSummary, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let summary = sqlx::query_as::<_, PointsSummary>(
+        "SELECT COALESCE(SUM(points), 0) AS points, COUNT(*) AS completed, COALESCE(SUM(priority = ?), 0) AS high_priority FROM task_points",
+    )
+    .bind(Priority::High)
+    .fetch_one(pool)
+    .await?;
+    Ok(summary)
+}
+
+async fn get_achievements() -> Result<Vec<String>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let keys = sqlx::query_scalar::<_, String>("SELECT key FROM achievements ORDER BY unlocked_at")
+        .fetch_all(pool)
+        .await?;
+    Ok(keys)
+}
+
+async fn unlock_achievement(key: &str) -> Result<bool, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let result = sqlx::query("INSERT OR IGNORE INTO achievements (key) VALUES (?)")
+        .bind(key)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+async fn get_habit(task_id: i64) -> Result<Option<Vec<String>>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let is_habit = sqlx::query_scalar::<_, i64>("SELECT task_id FROM habits WHERE task_id = ?")
+        .bind(task_id)
+        .fetch_optional(pool)
+        .await?
+        .is_some();
+    if !is_habit {
+        return Ok(None);
+    }
+    let days = sqlx::query_scalar::<_, String>(
+        "SELECT day FROM habit_completions WHERE task_id = ? ORDER BY day DESC",
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(Some(days))
+}
+
+async fn set_habit(task_id: i64, habit: bool) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let query = if habit {
+        "INSERT OR IGNORE INTO habits (task_id) SELECT id FROM todos WHERE id = ?"
+    } else {
+        "DELETE FROM habits WHERE task_id = ?"
+    };
+    sqlx::query(query).bind(task_id).execute(pool).await?;
+    Ok(())
+}
+
+async fn set_habit_done(task_id: i64, day: &str, done: bool) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let query = if done {
+        "INSERT OR IGNORE INTO habit_completions (task_id, day) SELECT task_id, ? FROM habits WHERE task_id = ?"
+    } else {
+        "DELETE FROM habit_completions WHERE day = ? AND task_id = ?"
+    };
+    sqlx::query(query)
+        .bind(day)
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn get_custom_fields() -> Result<Vec<CustomField>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let fields = sqlx::query_as::<_, CustomField>(
+        "SELECT id, name, kind, options FROM custom_fields ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(fields)
+}
+
+async fn create_custom_field(
+    name: &str,
+    kind: FieldKind,
+    options: &str,
+) -> Result<CustomField, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let field = sqlx::query_as::<_, CustomField>(
+        "INSERT INTO custom_fields (name, kind, options) VALUES (?, ?, ?) RETURNING id, name, kind, options",
+    )
+    .bind(name)
+    .bind(kind)
+    .bind(options)
+    .fetch_one(pool)
+    .await?;
+    Ok(field)
+}
+
+async fn delete_custom_field(id: i64) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    sqlx::query("DELETE FROM custom_fields WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn get_field_values(task_id: Option<i64>) -> Result<Vec<FieldValue>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let values = sqlx::query_as::<_, FieldValue>(
+        "SELECT task_id, field_id, value FROM custom_field_values WHERE ?1 IS NULL OR task_id = ?1 ORDER BY task_id, field_id",
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(values)
+}
+
+async fn set_field_value(
+    task_id: i64,
+    field_id: i64,
+    value: Option<&str>,
+) -> Result<Task, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    // Only a value that changes is logged, the trigger of the 'field' events storing it.
+    sqlx::query(
+        "INSERT INTO todo_events (task_id, kind, field_id, field_value) SELECT todos.id, 'field', custom_fields.id, ? FROM todos, custom_fields WHERE todos.id = ? AND custom_fields.id = ? AND ? IS NOT (SELECT value FROM custom_field_values WHERE task_id = ? AND field_id = ?)",
+    )
+    .bind(value)
+    .bind(task_id)
+    .bind(field_id)
+    .bind(value)
+    .bind(task_id)
+    .bind(field_id)
+    .execute(pool)
+    .await?;
+    get_task(task_id).await
+}
+
+async fn get_setting(key: &str) -> Result<Option<String>, ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    let value = sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+    Ok(value)
+}
+
+async fn set_setting(key: &str, value: String) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    simulate_latency().await;
+
+    sqlx::query("INSERT INTO settings (key, value) VALUES (?, ?) ON CONFLICT (key) DO UPDATE SET value = excluded.value")
+        .bind(key)
+        .bind(value)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn get_sync_entries() -> Result<Vec<SyncEntry>, ServerError> {
+    let pool = &pool();
+
+    let entries = sqlx::query_as::<_, SyncEntry>(
+        "SELECT task_id, href, uid, etag, synced_content, synced_updated_at FROM caldav_sync",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+async fn set_sync_entry(entry: SyncEntry) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::query("INSERT OR REPLACE INTO caldav_sync (task_id, href, uid, etag, synced_content, synced_updated_at) VALUES (?, ?, ?, ?, ?, ?)")
+        .bind(entry.task_id)
+        .bind(entry.href)
+        .bind(entry.uid)
+        .bind(entry.etag)
+        .bind(entry.synced_content)
+        .bind(entry.synced_updated_at)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn delete_sync_entry(task_id: i64) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::query("DELETE FROM caldav_sync WHERE task_id = ?")
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn get_google_tasks_entries() -> Result<Vec<GoogleTasksEntry>, ServerError> {
+    let pool = &pool();
+
+    let entries = sqlx::query_as::<_, GoogleTasksEntry>(
+        "SELECT task_id, list_id, remote_id, etag, synced_updated_at FROM google_tasks_sync",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+async fn set_google_tasks_entry(entry: GoogleTasksEntry) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::query("INSERT OR REPLACE INTO google_tasks_sync (task_id, list_id, remote_id, etag, synced_updated_at) VALUES (?, ?, ?, ?, ?)")
+        .bind(entry.task_id)
+        .bind(entry.list_id)
+        .bind(entry.remote_id)
+        .bind(entry.etag)
+        .bind(entry.synced_updated_at)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn delete_google_tasks_entry(task_id: i64) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::query("DELETE FROM google_tasks_sync WHERE task_id = ?")
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn get_github_issues() -> Result<Vec<GitHubIssue>, ServerError> {
+    let pool = &pool();
+
+    let issues =
+        sqlx::query_as::<_, GitHubIssue>("SELECT task_id, repo, number FROM github_issues")
+            .fetch_all(pool)
+            .await?;
+    Ok(issues)
+}
+
+async fn set_github_issue(issue: GitHubIssue) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::query("INSERT OR REPLACE INTO github_issues (task_id, repo, number) VALUES (?, ?, ?)")
+        .bind(issue.task_id)
+        .bind(issue.repo)
+        .bind(issue.number)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn delete_github_issue(task_id: i64) -> Result<(), ServerError> {
+    let pool = &pool();
+
+    sqlx::query("DELETE FROM github_issues WHERE task_id = ?")
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+impl TaskStore for SqliteStore {
+    fn database_file(&self) -> Option<PathBuf> {
+        database_file()
+    }
+
+    fn migrate(&self) -> StoreFuture<'static, ()> {
+        Box::pin(migrate())
+    }
+
+    fn ping(&self) -> StoreFuture<'static, ()> {
+        Box::pin(ping())
+    }
+
+    fn backup<'a>(&self, path: &'a Path) -> StoreFuture<'a, ()> {
+        Box::pin(backup(path))
+    }
+
+    fn vacuum(&self) -> StoreFuture<'static, ()> {
+        Box::pin(vacuum())
+    }
+
+    fn get_tasks(&self, query: TaskQuery) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(get_tasks(query))
+    }
+
+    fn get_tasks_page(
+        &self,
+        query: TaskQuery,
+        offset: i64,
+        limit: i64,
+    ) -> StoreFuture<'static, (Vec<Task>, i64)> {
+        Box::pin(get_tasks_page(query, offset, limit))
+    }
+
+    fn get_task(&self, id: i64) -> StoreFuture<'static, Task> {
+        Box::pin(get_task(id))
+    }
+
+    fn get_tasks_since(&self, since: String) -> StoreFuture<'static, (Vec<Task>, Vec<i64>)> {
+        Box::pin(get_tasks_since(since))
+    }
+
+    fn get_stale_tasks(&self, days: u32) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(get_stale_tasks(days))
+    }
+
+    fn local_date(&self) -> StoreFuture<'static, String> {
+        Box::pin(local_date())
+    }
+
+    fn count_by_status(&self) -> StoreFuture<'static, StatusCounts> {
+        Box::pin(count_by_status())
+    }
+
+    fn create_task(&self, desc: String, priority: Priority) -> StoreFuture<'static, Task> {
+        Box::pin(create_task(desc, priority))
+    }
+
+    fn create_tasks(&self, new_tasks: Vec<(String, Priority)>) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(create_tasks(new_tasks))
+    }
+
+    fn update_task(
+        &self,
+        id: i64,
+        desc: String,
+        status: Status,
+        priority: Priority,
+    ) -> StoreFuture<'static, Task> {
+        Box::pin(update_task(id, desc, status, priority))
+    }
+
+    fn update_tasks(
+        &self,
+        updates: Vec<(i64, String, Status, Priority)>,
+    ) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(update_tasks(updates))
+    }
+
+    fn set_tasks_status(&self, ids: Vec<i64>, status: Status) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(set_tasks_status(ids, status))
+    }
+
+    fn reorder_tasks(&self, ids: Vec<i64>) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(reorder_tasks(ids))
+    }
+
+    fn delete_task(&self, id: i64) -> StoreFuture<'static, i64> {
+        Box::pin(delete_task(id))
+    }
+
+    fn delete_tasks(&self, ids: Vec<i64>) -> StoreFuture<'static, Vec<i64>> {
+        Box::pin(delete_tasks(ids))
+    }
+
+    fn get_task_history(&self, id: i64) -> StoreFuture<'static, Vec<TaskEvent>> {
+        Box::pin(get_task_history(id))
+    }
+
+    fn get_tasks_at(&self, at: String) -> StoreFuture<'static, Vec<Task>> {
+        Box::pin(get_tasks_at(at))
+    }
+
+    fn get_points(&self) -> StoreFuture<'static, PointsSummary> {
+        Box::pin(get_points())
+    }
+
+    fn get_achievements(&self) -> StoreFuture<'static, Vec<String>> {
+        Box::pin(get_achievements())
+    }
+
+    fn unlock_achievement<'a>(&self, key: &'a str) -> StoreFuture<'a, bool> {
+        Box::pin(unlock_achievement(key))
+    }
+
+    fn get_habit(&self, task_id: i64) -> StoreFuture<'static, Option<Vec<String>>> {
+        Box::pin(get_habit(task_id))
+    }
+
+    fn set_habit(&self, task_id: i64, habit: bool) -> StoreFuture<'static, ()> {
+        Box::pin(set_habit(task_id, habit))
+    }
+
+    fn set_habit_done<'a>(&self, task_id: i64, day: &'a str, done: bool) -> StoreFuture<'a, ()> {
+        Box::pin(set_habit_done(task_id, day, done))
+    }
+
+    fn get_custom_fields(&self) -> StoreFuture<'static, Vec<CustomField>> {
+        Box::pin(get_custom_fields())
+    }
+
+    fn create_custom_field<'a>(
+        &self,
+        name: &'a str,
+        kind: FieldKind,
+        options: &'a str,
+    ) -> StoreFuture<'a, CustomField> {
+        Box::pin(create_custom_field(name, kind, options))
+    }
+
+    fn delete_custom_field(&self, id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(delete_custom_field(id))
+    }
+
+    fn get_field_values(&self, task_id: Option<i64>) -> StoreFuture<'static, Vec<FieldValue>> {
+        Box::pin(get_field_values(task_id))
+    }
+
+    fn set_field_value<'a>(
+        &self,
+        task_id: i64,
+        field_id: i64,
+        value: Option<&'a str>,
+    ) -> StoreFuture<'a, Task> {
+        Box::pin(set_field_value(task_id, field_id, value))
+    }
+
+    fn get_setting<'a>(&self, key: &'a str) -> StoreFuture<'a, Option<String>> {
+        Box::pin(get_setting(key))
+    }
+
+    fn set_setting<'a>(&self, key: &'a str, value: String) -> StoreFuture<'a, ()> {
+        Box::pin(set_setting(key, value))
+    }
+
+    fn get_sync_entries(&self) -> StoreFuture<'static, Vec<SyncEntry>> {
+        Box::pin(get_sync_entries())
+    }
+
+    fn set_sync_entry(&self, entry: SyncEntry) -> StoreFuture<'static, ()> {
+        Box::pin(set_sync_entry(entry))
+    }
+
+    fn delete_sync_entry(&self, task_id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(delete_sync_entry(task_id))
+    }
+
+    fn get_google_tasks_entries(&self) -> StoreFuture<'static, Vec<GoogleTasksEntry>> {
+        Box::pin(get_google_tasks_entries())
+    }
+
+    fn set_google_tasks_entry(&self, entry: GoogleTasksEntry) -> StoreFuture<'static, ()> {
+        Box::pin(set_google_tasks_entry(entry))
+    }
+
+    fn delete_google_tasks_entry(&self, task_id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(delete_google_tasks_entry(task_id))
+    }
+
+    fn get_github_issues(&self) -> StoreFuture<'static, Vec<GitHubIssue>> {
+        Box::pin(get_github_issues())
+    }
+
+    fn set_github_issue(&self, issue: GitHubIssue) -> StoreFuture<'static, ()> {
+        Box::pin(set_github_issue(issue))
+    }
+
+    fn delete_github_issue(&self, task_id: i64) -> StoreFuture<'static, ()> {
+        Box::pin(delete_github_issue(task_id))
+    }
+}
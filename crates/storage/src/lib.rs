@@ -0,0 +1,5 @@
+//! Storage of the tasks: the SQLite and memory backends of `database`, and the `TaskRepository`
+//! trait over them. Frontends can depend on it without the GUI.
+
+pub mod database;
+pub mod repository;
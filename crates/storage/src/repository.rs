@@ -0,0 +1,176 @@
+//! Operations on the tasks behind a trait, so that the frontends can share one interface and run
+//! on another backend or on a mock in the tests.
+
+use my_todos_core::{CustomField, Priority, ServerError, Status, StatusCounts, Task};
+
+use crate::database::{
+    FieldValue, TaskQuery, count_by_status, create_task_with_priority,
+    create_tasks_with_priorities, delete_task, delete_tasks, get_custom_fields, get_field_values,
+    get_task, get_tasks, get_tasks_page, get_tasks_since, reorder_tasks, set_field_value,
+    set_tasks_status, update_task, update_tasks,
+};
+
+pub trait TaskRepository
+where
+    Self: 'static,
+{
+    fn fetch(query: TaskQuery) -> impl Future<Output = Result<Vec<Task>, ServerError>> + Send;
+    /// Returns `limit` tasks starting at `offset`, and the number of tasks matching the query.
+    fn fetch_page(
+        query: TaskQuery,
+        offset: i64,
+        limit: i64,
+    ) -> impl Future<Output = Result<(Vec<Task>, i64), ServerError>> + Send;
+    /// Returns the tasks changed since the `since` timestamp and the ids of the deleted ones.
+    fn fetch_since(
+        since: String,
+    ) -> impl Future<Output = Result<(Vec<Task>, Vec<i64>), ServerError>> + Send;
+    fn get(id: i64) -> impl Future<Output = Result<Task, ServerError>> + Send;
+    fn count_by_status() -> impl Future<Output = Result<StatusCounts, ServerError>> + Send;
+    /// The custom fields, in the order they were defined.
+    fn fetch_fields() -> impl Future<Output = Result<Vec<CustomField>, ServerError>> + Send;
+    /// The values of the custom fields of every task.
+    fn fetch_field_values() -> impl Future<Output = Result<Vec<FieldValue>, ServerError>> + Send;
+    /// Creates the task with its priority, in a single write.
+    fn create(
+        description: String,
+        priority: Priority,
+    ) -> impl Future<Output = Result<Task, ServerError>> + Send;
+    /// Creates the tasks with their priorities at the end of the user order, in a single
+    /// transaction.
+    fn create_many(
+        new_tasks: Vec<(String, Priority)>,
+    ) -> impl Future<Output = Result<Vec<Task>, ServerError>> + Send;
+    fn update(
+        id: i64,
+        description: String,
+        status: Status,
+        priority: Priority,
+    ) -> impl Future<Output = Result<Task, ServerError>> + Send;
+    fn update_many(
+        updates: Vec<(i64, String, Status, Priority)>,
+    ) -> impl Future<Output = Result<Vec<Task>, ServerError>> + Send;
+    fn set_status_many(
+        ids: Vec<i64>,
+        status: Status,
+    ) -> impl Future<Output = Result<Vec<Task>, ServerError>> + Send;
+    /// Stores the order chosen by the user, `ids` being sorted in the new order. Returns the tasks
+    /// whose position changed.
+    fn reorder(ids: Vec<i64>) -> impl Future<Output = Result<Vec<Task>, ServerError>> + Send;
+    /// Sets the value of a custom field of the task, removing it when `None`, and returns the task.
+    fn set_field(
+        task_id: i64,
+        field_id: i64,
+        value: Option<String>,
+    ) -> impl Future<Output = Result<Task, ServerError>> + Send;
+    fn delete(id: i64) -> impl Future<Output = Result<i64, ServerError>> + Send;
+    fn delete_many(ids: Vec<i64>) -> impl Future<Output = Result<Vec<i64>, ServerError>> + Send;
+    /// Returns the tasks whose description contains `search`, ignoring the case.
+    fn search(search: String) -> impl Future<Output = Result<Vec<Task>, ServerError>> + Send {
+        Self::fetch(TaskQuery {
+            search: Some(search),
+            ..Default::default()
+        })
+    }
+}
+
+/// The tasks stored by the `database` module, in SQLite unless `TODOS_BACKEND` chose the memory.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Database;
+
+impl TaskRepository for Database {
+    #[inline(always)]
+    async fn fetch(query: TaskQuery) -> Result<Vec<Task>, ServerError> {
+        get_tasks(query).await
+    }
+
+    #[inline(always)]
+    async fn fetch_page(
+        query: TaskQuery,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<Task>, i64), ServerError> {
+        get_tasks_page(query, offset, limit).await
+    }
+
+    #[inline(always)]
+    async fn fetch_since(since: String) -> Result<(Vec<Task>, Vec<i64>), ServerError> {
+        get_tasks_since(since).await
+    }
+
+    #[inline(always)]
+    async fn get(id: i64) -> Result<Task, ServerError> {
+        get_task(id).await
+    }
+
+    #[inline(always)]
+    async fn count_by_status() -> Result<StatusCounts, ServerError> {
+        count_by_status().await
+    }
+
+    #[inline(always)]
+    async fn fetch_fields() -> Result<Vec<CustomField>, ServerError> {
+        get_custom_fields().await
+    }
+
+    #[inline(always)]
+    async fn fetch_field_values() -> Result<Vec<FieldValue>, ServerError> {
+        get_field_values(None).await
+    }
+
+    #[inline(always)]
+    async fn create(description: String, priority: Priority) -> Result<Task, ServerError> {
+        create_task_with_priority(description, priority).await
+    }
+
+    #[inline(always)]
+    async fn create_many(new_tasks: Vec<(String, Priority)>) -> Result<Vec<Task>, ServerError> {
+        create_tasks_with_priorities(new_tasks).await
+    }
+
+    #[inline(always)]
+    async fn update(
+        id: i64,
+        description: String,
+        status: Status,
+        priority: Priority,
+    ) -> Result<Task, ServerError> {
+        update_task(id, description, status, priority).await
+    }
+
+    #[inline(always)]
+    async fn update_many(
+        updates: Vec<(i64, String, Status, Priority)>,
+    ) -> Result<Vec<Task>, ServerError> {
+        update_tasks(updates).await
+    }
+
+    #[inline(always)]
+    async fn set_status_many(ids: Vec<i64>, status: Status) -> Result<Vec<Task>, ServerError> {
+        set_tasks_status(ids, status).await
+    }
+
+    #[inline(always)]
+    async fn reorder(ids: Vec<i64>) -> Result<Vec<Task>, ServerError> {
+        reorder_tasks(ids).await
+    }
+
+    #[inline(always)]
+    async fn set_field(
+        task_id: i64,
+        field_id: i64,
+        value: Option<String>,
+    ) -> Result<Task, ServerError> {
+        set_field_value(task_id, field_id, value.as_deref()).await
+    }
+
+    #[inline(always)]
+    async fn delete(id: i64) -> Result<i64, ServerError> {
+        delete_task(id).await
+    }
+
+    #[inline(always)]
+    async fn delete_many(ids: Vec<i64>) -> Result<Vec<i64>, ServerError> {
+        delete_tasks(ids).await
+    }
+}
@@ -0,0 +1,35 @@
+//! Entry point of the Android app, built as a `cdylib` loaded by a `NativeActivity`, for example
+//! with `cargo apk run --lib`.
+
+use tracing::error;
+use xilem::EventLoop;
+use xilem::winit::platform::android::EventLoopBuilderExtAndroid;
+use xilem::winit::platform::android::activity::AndroidApp;
+
+use crate::database::{migrate, set_data_dir};
+use crate::{AppState, run};
+use crate::{crash, logging};
+
+#[unsafe(no_mangle)]
+fn android_main(app: AndroidApp) {
+    // The working directory isn't writable, the database lives in the storage of the app and is
+    // created on the first launch.
+    if let Some(dir) = app.internal_data_path() {
+        set_data_dir(dir);
+    }
+    logging::init(false);
+    crash::install_hook();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("can't start the migration runtime");
+    if let Err(error) = runtime.block_on(migrate()) {
+        error!(%error, "couldn't migrate the database");
+    }
+
+    let mut event_loop = EventLoop::with_user_event();
+    event_loop.with_android_app(app);
+    if let Err(error) = run(event_loop, AppState::default()) {
+        error!(%error, "the app stopped");
+    }
+}
@@ -0,0 +1,617 @@
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork, map_state};
+use xilem::masonry::layout::{AsUnit, Dim};
+use xilem::masonry::theme::default_property_set;
+use xilem::style::Style as _;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use xilem::view::{
+    FlexExt, MainAxisAlignment, flex_col, flex_row, label, portal, sized_box, text_button, worker,
+};
+use xilem::winit::dpi::LogicalSize;
+use xilem::winit::error::EventLoopError;
+use xilem::{EventLoopBuilder, WidgetView, WindowId, WindowView, Xilem, window};
+
+#[cfg(feature = "config")]
+use crate::config::Config;
+use crate::core::{Priority, Task};
+use crate::crash;
+use crate::service::{self, EXTERNAL, subscribe};
+#[cfg(feature = "llm")]
+use crate::ui::breakdown::TaskBreakdown;
+use crate::ui::component::AsyncList;
+use crate::ui::component::list::SelectionMode;
+use crate::ui::component::list::cache::CachedStorage;
+#[cfg(debug_assertions)]
+use crate::ui::component::list::chaos::ChaosStorage;
+use crate::ui::component::list::filter::AllOf;
+use crate::ui::component::list::search::SearchFilter;
+use crate::ui::connection::ConnectionHealth;
+use crate::ui::crash::CrashBanner;
+use crate::ui::custom_fields::CustomFieldPanel;
+use crate::ui::diagnostics::DiagnosticsPanel;
+use crate::ui::focus::FocusTask;
+use crate::ui::habit::HabitPanel;
+#[cfg(feature = "lock")]
+use crate::ui::lock::LockScreen;
+use crate::ui::logs::LogViewer;
+use crate::ui::picker::TaskPicker;
+use crate::ui::review::WeeklyReview;
+use crate::ui::session::{Session, SessionRestore};
+use crate::ui::settings::SettingsPage;
+use crate::ui::skeleton::skeleton;
+#[cfg(feature = "caldav")]
+use crate::ui::sync::CalDavSync;
+use crate::ui::task_list::{
+    self, FieldFilter, StatusFilter, TaskSorter, TaskStorage, bulk_actions_view, task_detail_view,
+};
+use crate::ui::theme::{BACKGROUND_COLOR, apply_theme};
+use crate::ui::toast::Toast;
+use crate::url_scheme::{self, UrlCommand};
+
+const PAGE_SIZE: usize = 50;
+const STORAGE_TIMEOUT: Duration = Duration::from_secs(15);
+/// Placeholder rows shown until the first tasks are fetched.
+const SKELETON_ROWS: usize = 6;
+
+/// Debug builds can inject failures under the cache with `TODOS_CHAOS`, see `chaos`.
+#[cfg(debug_assertions)]
+type AppStorage = CachedStorage<ChaosStorage<TaskStorage>>;
+#[cfg(not(debug_assertions))]
+type AppStorage = CachedStorage<TaskStorage>;
+
+/// Set when the app has to start again once its window is closed, see `restart_requested`.
+static RESTART: AtomicBool = AtomicBool::new(false);
+
+/// Whether the app has to start again after `run` returned, like once all the data was erased.
+pub fn restart_requested() -> bool {
+    RESTART.load(atomic::Ordering::Relaxed)
+}
+
+/// What is shown in place of the task list.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Page {
+    Tasks,
+    Settings,
+    Logs,
+    Diagnostics,
+    Review,
+}
+
+impl Page {
+    /// Name saved in the session, see `from_name`.
+    fn name(self) -> &'static str {
+        match self {
+            Page::Tasks => "",
+            Page::Settings => "settings",
+            Page::Logs => "logs",
+            Page::Diagnostics => "diagnostics",
+            Page::Review => "review",
+        }
+    }
+
+    fn from_name(name: &str) -> Self {
+        match name {
+            "settings" => Page::Settings,
+            "logs" => Page::Logs,
+            "diagnostics" => Page::Diagnostics,
+            "review" => Page::Review,
+            _ => Page::Tasks,
+        }
+    }
+}
+
+pub struct AppState {
+    running: bool,
+    main_window_id: WindowId,
+    /// Size of the window when it opens, chosen by the system if `None`.
+    window_size: Option<LogicalSize<f64>>,
+    connection: ConnectionHealth,
+    crash: CrashBanner,
+    #[cfg(feature = "caldav")]
+    sync: CalDavSync,
+    task_list: AsyncList<Task, AppStorage>,
+    /// Receives the `todos://` URLs forwarded by other instances, taken by the worker once started.
+    url_listener: Arc<Mutex<Option<url_scheme::Listener>>>,
+    /// URLs received before the task list was running.
+    pending_urls: Vec<UrlCommand>,
+    toast: Toast,
+    #[cfg(feature = "llm")]
+    breakdown: TaskBreakdown,
+    page: Page,
+    session: SessionRestore,
+    settings: SettingsPage,
+    #[cfg(feature = "lock")]
+    lock: LockScreen,
+    logs: LogViewer,
+    diagnostics: DiagnosticsPanel,
+    review: WeeklyReview,
+    picker: TaskPicker,
+    focus: FocusTask,
+    habit: HabitPanel,
+    custom_fields: CustomFieldPanel,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        let mut state = Self {
+            running: true,
+            main_window_id: WindowId::next(),
+            window_size: None,
+            connection: ConnectionHealth::default(),
+            crash: CrashBanner::default(),
+            #[cfg(feature = "caldav")]
+            sync: CalDavSync::default(),
+            task_list: AsyncList::new(true, true, true)
+                .with_optimistic_updates()
+                .with_selection(SelectionMode::Multi)
+                .with_page_size(PAGE_SIZE)
+                .with_timeout(STORAGE_TIMEOUT)
+                .with_loading_view(|| skeleton(SKELETON_ROWS))
+                .with_empty_view(|| label("No tasks yet, add one above")),
+            url_listener: Arc::default(),
+            pending_urls: Vec::new(),
+            toast: Toast::default(),
+            #[cfg(feature = "llm")]
+            breakdown: TaskBreakdown::default(),
+            page: Page::Tasks,
+            session: SessionRestore::default(),
+            settings: SettingsPage::default(),
+            #[cfg(feature = "lock")]
+            lock: LockScreen::default(),
+            logs: LogViewer::default(),
+            diagnostics: DiagnosticsPanel::default(),
+            review: WeeklyReview::default(),
+            picker: TaskPicker::default(),
+            focus: FocusTask::default(),
+            habit: HabitPanel::default(),
+            custom_fields: CustomFieldPanel::default(),
+        };
+        state.recover();
+        state
+    }
+}
+
+/// Runs the GUI, shared by the desktop binary and the Android entry point.
+pub fn run(event_loop: EventLoopBuilder, app_state: AppState) -> Result<(), EventLoopError> {
+    let mut def_props = default_property_set();
+    apply_theme(&mut def_props);
+
+    let app = Xilem::new(app_state, AppState::logic).with_default_properties(def_props);
+    app.run_in(event_loop)
+}
+
+/// Centers a page shown in place of the tasks.
+fn page_view<V>(page: V) -> impl WidgetView<Edit<AppState>> + use<V>
+where
+    V: WidgetView<Edit<AppState>>,
+{
+    flex_row(sized_box(page).width(1000.px()))
+        .main_axis_alignment(MainAxisAlignment::Center)
+        .padding(15.)
+}
+
+impl xilem::AppState for AppState {
+    fn keep_running(&self) -> bool {
+        self.running
+    }
+}
+
+impl AppState {
+    /// Handles the `todos://` URLs forwarded to `listener`, starting with `url` if the app was
+    /// launched with one.
+    pub fn with_url_handler(
+        mut self,
+        listener: url_scheme::Listener,
+        url: Option<UrlCommand>,
+    ) -> Self {
+        self.url_listener = Arc::new(Mutex::new(Some(listener)));
+        self.pending_urls.extend(url);
+        self
+    }
+
+    /// Shows the tasks matching `filter` instead of the filter saved in the settings.
+    pub fn with_filter(mut self, filter: StatusFilter) -> Self {
+        self.task_list.set_initial_filter(AllOf(
+            AllOf(SearchFilter::default(), filter),
+            FieldFilter::default(),
+        ));
+        self
+    }
+
+    /// Starts with the filter and the sorter of the configuration file, if valid.
+    #[cfg(feature = "config")]
+    pub fn with_config(mut self, config: &Config) -> Self {
+        match config.filter.as_deref().map(str::parse::<StatusFilter>) {
+            Some(Ok(filter)) => self = self.with_filter(filter),
+            Some(Err(error)) => warn!(%error, "ignored the filter of the configuration"),
+            None => (),
+        }
+        match config.sorter.as_deref().map(str::parse::<TaskSorter>) {
+            Some(Ok(sorter)) => self.task_list.set_initial_sorter(sorter),
+            Some(Err(error)) => warn!(%error, "ignored the sorter of the configuration"),
+            None => (),
+        }
+        self
+    }
+
+    /// Keeps the tasks read-only for this session, whatever the setting.
+    pub fn with_read_only(mut self) -> Self {
+        self.settings.read_only_mut().force();
+        self
+    }
+
+    /// Opens the window with a size of `width` by `height` logical pixels.
+    pub fn with_window_size(mut self, width: f64, height: f64) -> Self {
+        self.window_size = Some(LogicalSize::new(width, height));
+        self
+    }
+
+    /// Button showing `page`, or the tasks again once it is shown.
+    fn page_button(
+        &self,
+        page: Page,
+        name: &'static str,
+    ) -> impl WidgetView<Edit<AppState>> + use<> {
+        let name = if self.page == page {
+            "Back to the tasks"
+        } else {
+            name
+        };
+        text_button(name, move |state: &mut AppState| {
+            #[cfg(feature = "lock")]
+            state.lock.touch();
+            state.page = if state.page == page {
+                Page::Tasks
+            } else {
+                page
+            };
+        })
+    }
+
+    fn handle_url(&mut self, command: UrlCommand) {
+        match command {
+            UrlCommand::Add(description) if self.task_list.is_read_only() => {
+                self.toast.show(format!(
+                    "Didn't add “{description}”, the tasks are read-only"
+                ));
+            }
+            UrlCommand::Add(description) => {
+                self.toast.show(format!("Added “{description}”"));
+                self.task_list.create((description, Priority::default()));
+            }
+            UrlCommand::Open(id) => {
+                self.task_list.clear_selection();
+                self.task_list.select(id);
+            }
+        }
+    }
+
+    /// Puts the work not saved before the last crash back in the create form.
+    fn recover(&mut self) {
+        let drafts = crash::take_recovered();
+        if !drafts.is_empty() {
+            self.crash.set_recovered(drafts.len());
+            self.task_list.restore_drafts(drafts);
+        }
+    }
+
+    /// Opens the page and selects the tasks of the last launch, unless a URL already selected one.
+    fn restore(&mut self, session: Session) {
+        self.page = Page::from_name(&session.page);
+        if self.task_list.selection().is_empty() {
+            for id in session.selection {
+                self.task_list.select(id);
+            }
+        }
+    }
+
+    /// Refreshes the list after the changes of the other frontends, the list applying its own, and
+    /// synchronizes the changes made in this process.
+    fn handle_changes(&mut self, origins: &[&'static str]) {
+        #[cfg(feature = "lock")]
+        if origins.contains(&task_list::ORIGIN) {
+            self.lock.touch();
+        }
+        if origins.iter().any(|origin| *origin != task_list::ORIGIN) {
+            self.task_list.refresh();
+        }
+        self.focus.refresh();
+        self.settings.xp_mut().refresh();
+        #[cfg(feature = "caldav")]
+        if origins.iter().any(|origin| *origin != EXTERNAL) {
+            self.sync.push_local_changes();
+        }
+    }
+
+    pub fn logic(&mut self) -> impl Iterator<Item = WindowView<AppState>> + use<> {
+        // Nothing the running components hold is valid anymore.
+        if self.settings.is_erased() {
+            RESTART.store(true, atomic::Ordering::Relaxed);
+            self.running = false;
+        }
+        let read_only = self.settings.read_only().is_read_only();
+        self.task_list.set_read_only(read_only);
+        self.review.set_read_only(read_only);
+        self.picker.set_read_only(read_only);
+        self.habit.set_read_only(read_only);
+        self.habit
+            .set_task(self.task_list.selected().map(|task| task.id));
+        self.custom_fields.set_read_only(read_only);
+        self.custom_fields
+            .set_task(self.task_list.selected().map(|task| task.id));
+        if let Some(id) = self.picker.take_picked() {
+            self.task_list.clear_selection();
+            self.task_list.select(id);
+        }
+        service::set_read_only(read_only);
+        #[cfg(feature = "caldav")]
+        if self.sync.take_changed() {
+            self.task_list.reload();
+        }
+        #[cfg(feature = "llm")]
+        {
+            // Breakdowns create tasks, so none is offered while read-only.
+            let selected = self.task_list.selected().filter(|_| !read_only);
+            let selected = selected.map(|task| (task.id, task.description.clone()));
+            self.breakdown.set_task(selected);
+            for description in self.breakdown.take_accepted() {
+                self.task_list.create((description, Priority::default()));
+            }
+        }
+        for name in self.settings.xp_mut().take_unlocked() {
+            self.toast.show(format!("Achievement unlocked: {name}"));
+        }
+        if let Some(session) = self.session.take_loaded() {
+            self.restore(session);
+        }
+        // The database was recreated if erased, the next launch starting afresh.
+        if self.running {
+            self.session.save(Session {
+                page: self.page.name().to_string(),
+                selection: self.task_list.selection().to_vec(),
+            });
+        }
+        crash::set_unsaved(self.task_list.unsaved_drafts());
+        self.diagnostics
+            .set_pending(self.task_list.pending_operations());
+        if self.task_list.is_running() {
+            for command in std::mem::take(&mut self.pending_urls) {
+                self.handle_url(command);
+            }
+        }
+        let task_list = flex_row(sized_box(self.task_list.view()).width(1000.px()))
+            .main_axis_alignment(MainAxisAlignment::Center)
+            .width(Dim::Stretch)
+            .padding(15.);
+        let portal = portal(task_list).flex(1.);
+        let error = self.task_list.error_view().map(|error_view| {
+            flex_row(error_view)
+                .main_axis_alignment(MainAxisAlignment::Center)
+                .padding(15.)
+        });
+        let bulk_actions = bulk_actions_view(&self.task_list).filter(|_| !read_only);
+        let detail = task_detail_view(&self.task_list).map(|detail| {
+            flex_row(sized_box(detail).width(1000.px()))
+                .main_axis_alignment(MainAxisAlignment::Center)
+                .padding(5.)
+        });
+        let counts = self.task_list.summary();
+        let footer = flex_row(label(format!(
+            "{} to do · {} in progress · {} done",
+            counts.to_do, counts.in_progress, counts.done
+        )))
+        .main_axis_alignment(MainAxisAlignment::Center)
+        .padding(5.);
+        let connection = map_state(self.connection.view(), |state: &mut AppState, ()| {
+            &mut state.connection
+        });
+        let crash = map_state(self.crash.view(), |state: &mut AppState, ()| {
+            &mut state.crash
+        });
+        let session = map_state(self.session.view(), |state: &mut AppState, ()| {
+            &mut state.session
+        });
+        let task_list = map_state(
+            flex_col((portal, error, detail, bulk_actions, footer)).gap(0.px()),
+            |state: &mut AppState, ()| &mut state.task_list,
+        );
+        #[cfg(feature = "caldav")]
+        let sync = flex_row(map_state(self.sync.view(), |state: &mut AppState, ()| {
+            &mut state.sync
+        }))
+        .main_axis_alignment(MainAxisAlignment::Center);
+        #[cfg(not(feature = "caldav"))]
+        let sync = ();
+        let task_list = if self.page == Page::Diagnostics {
+            let diagnostics = map_state(self.diagnostics.view(), |state: &mut AppState, ()| {
+                &mut state.diagnostics
+            });
+            Either::A(portal(page_view(diagnostics)))
+        } else {
+            Either::B(task_list)
+        };
+        let task_list = if self.page == Page::Review {
+            let review = map_state(self.review.view(), |state: &mut AppState, ()| {
+                &mut state.review
+            });
+            Either::A(portal(page_view(review)))
+        } else {
+            Either::B(task_list)
+        };
+        let task_list = if self.page == Page::Logs {
+            let logs = map_state(self.logs.view(), |state: &mut AppState, ()| &mut state.logs);
+            Either::A(portal(page_view(logs)))
+        } else {
+            Either::B(task_list)
+        };
+        let task_list = if self.page == Page::Settings {
+            let settings = map_state(self.settings.view(), |state: &mut AppState, ()| {
+                &mut state.settings
+            });
+            Either::A(portal(page_view(settings)))
+        } else {
+            Either::B(task_list)
+        };
+        let task_list = task_list.flex(1.);
+        let review_button = self.page_button(Page::Review, "Review");
+        let settings_button = self.page_button(Page::Settings, "Settings");
+        let read_only_badge = map_state(
+            self.settings.read_only_mut().view(),
+            |state: &mut AppState, ()| state.settings.read_only_mut(),
+        );
+        let xp = map_state(self.settings.xp_mut().view(), |state: &mut AppState, ()| {
+            state.settings.xp_mut()
+        });
+        let logs_button = self.page_button(Page::Logs, "Logs");
+        let diagnostics_button = self.page_button(Page::Diagnostics, "Diagnostics");
+        let sync = flex_row((
+            read_only_badge,
+            xp,
+            sync,
+            review_button,
+            settings_button,
+            logs_button,
+            diagnostics_button,
+        ))
+        .main_axis_alignment(MainAxisAlignment::Center);
+        #[cfg(feature = "llm")]
+        let breakdown = flex_row(
+            sized_box(map_state(
+                self.breakdown.view(),
+                |state: &mut AppState, ()| &mut state.breakdown,
+            ))
+            .width(1000.px()),
+        )
+        .main_axis_alignment(MainAxisAlignment::Center);
+        #[cfg(not(feature = "llm"))]
+        let breakdown = ();
+        let focus = (self.page == Page::Tasks).then(|| {
+            flex_row(
+                sized_box(map_state(self.focus.view(), |state: &mut AppState, ()| {
+                    &mut state.focus
+                }))
+                .width(1000.px()),
+            )
+            .main_axis_alignment(MainAxisAlignment::Center)
+            .padding(5.)
+        });
+        let details =
+            (self.page == Page::Tasks && self.task_list.selected().is_some()).then(|| {
+                flex_row(
+                    sized_box(flex_col((
+                        map_state(self.habit.view(), |state: &mut AppState, ()| {
+                            &mut state.habit
+                        }),
+                        map_state(self.custom_fields.view(), |state: &mut AppState, ()| {
+                            &mut state.custom_fields
+                        }),
+                    )))
+                    .width(1000.px()),
+                )
+                .main_axis_alignment(MainAxisAlignment::Center)
+                .padding(5.)
+            });
+        let picker = (self.page == Page::Tasks).then(|| {
+            flex_row(map_state(self.picker.view(), |state: &mut AppState, ()| {
+                &mut state.picker
+            }))
+            .main_axis_alignment(MainAxisAlignment::Center)
+        });
+        let toast = flex_row(map_state(self.toast.view(), |state: &mut AppState, ()| {
+            &mut state.toast
+        }))
+        .main_axis_alignment(MainAxisAlignment::Center);
+        let url_listener = self.url_listener.clone();
+        #[cfg(feature = "lock")]
+        let (lock, locked) = (
+            map_state(self.lock.view(), |state: &mut AppState, ()| &mut state.lock),
+            self.lock.is_locked(),
+        );
+        #[cfg(not(feature = "lock"))]
+        let (lock, locked) = ((), false);
+        // The banners stay visible while locked, they don't show any task.
+        let body = if locked {
+            Either::A(flex_col(()))
+        } else {
+            Either::B(
+                flex_col((focus, task_list, details, picker, breakdown, toast, sync)).gap(0.px()),
+            )
+        };
+        let content = fork(
+            flex_col((session, crash, connection, lock, body.flex(1.))).gap(0.px()),
+            worker(
+                move |proxy, _rx: UnboundedReceiver<()>| {
+                    let listener = url_listener.lock().unwrap().take();
+                    async move {
+                        let Some(listener) = listener else {
+                            return;
+                        };
+                        let (url_sender, mut urls) = unbounded_channel();
+                        url_scheme::listen(listener, move |url| url_sender.send(url).is_ok());
+                        while let Some(url) = urls.recv().await {
+                            match url.parse::<UrlCommand>() {
+                                Ok(command) => {
+                                    if proxy.message(command).is_err() {
+                                        break;
+                                    }
+                                }
+                                Err(error) => warn!(%error, "ignored a forwarded URL"),
+                            }
+                        }
+                    }
+                },
+                |_: &mut AppState, _: UnboundedSender<()>| {},
+                |state: &mut AppState, command: UrlCommand| {
+                    if state.task_list.is_running() {
+                        state.handle_url(command);
+                    } else {
+                        state.pending_urls.push(command);
+                    }
+                },
+            ),
+        );
+        let content = fork(
+            content,
+            worker(
+                |proxy, _rx: UnboundedReceiver<()>| async move {
+                    let mut events = subscribe();
+                    loop {
+                        // The events received together are handled at once.
+                        let mut origins = match events.recv().await {
+                            Ok((origin, _)) => vec![origin],
+                            // The missed events are treated like changes made elsewhere.
+                            Err(RecvError::Lagged(_)) => vec![EXTERNAL],
+                            Err(RecvError::Closed) => break,
+                        };
+                        while let Ok((origin, _)) = events.try_recv() {
+                            origins.push(origin);
+                        }
+                        if proxy.message(origins).is_err() {
+                            break;
+                        }
+                    }
+                },
+                |_: &mut AppState, _: UnboundedSender<()>| {},
+                |state: &mut AppState, origins: Vec<&'static str>| state.handle_changes(&origins),
+            ),
+        );
+        let window_size = self.window_size;
+        std::iter::once(
+            window(self.main_window_id, "Todos", content)
+                .with_options(|options| {
+                    let options = options.on_close(|state: &mut AppState| state.running = false);
+                    match window_size {
+                        Some(size) => options.with_initial_inner_size(size),
+                        None => options,
+                    }
+                })
+                .with_base_color(BACKGROUND_COLOR),
+        )
+    }
+}
@@ -0,0 +1,792 @@
+//! Command line companion of the GUI, working on the same database.
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use todos::core::{CustomField, FieldKind, Status, Task};
+use todos::database::{
+    TaskEvent, TaskEventKind, TaskOrder, TaskQuery, create_custom_field, create_task,
+    delete_custom_field, delete_task, get_custom_fields, get_task, get_task_history, get_tasks,
+    get_tasks_at, migrate, set_field_value, set_tasks_status,
+};
+use todos::headless::export_csv;
+#[cfg(feature = "github")]
+use todos::import::github;
+use todos::import::{self, ImportedTask, todoist};
+#[cfg(feature = "llm")]
+use todos::llm::LlmConfig;
+#[cfg(feature = "lock")]
+use todos::lock;
+#[cfg(feature = "mail")]
+use todos::mail;
+use todos::notify::{Category, set_muted};
+use todos::profile;
+#[cfg(any(feature = "caldav", feature = "google-tasks"))]
+use todos::sync::SyncReport;
+#[cfg(feature = "caldav")]
+use todos::sync::caldav;
+#[cfg(feature = "google-tasks")]
+use todos::sync::google_tasks::{self, GoogleTasksConfig};
+
+const USAGE: &str = "usage: todos-cli [--profile <name>] <command>
+
+commands:
+    add <description>   add a task
+    list [search]       list the tasks, optionally only the ones matching `search`
+                        add --where <field>=<value> to only list the tasks with the value of a
+                        custom field, and --sort-by <field> to order them by the field
+    done <id>...        mark tasks as done
+    rm <id>...          delete tasks
+    export              print all the tasks as CSV, with a column per custom field
+    fields              list the custom fields
+    field-add <name> <kind> [choice]...
+                        define a custom field of kind text, number, date (like 2026-10-15) or
+                        enum, with the choices of an enum
+    field-rm <name>     delete a custom field with its values
+    set-field <id> <field> [value]
+                        set the value of a custom field of a task, or remove it without a value
+    history <id>        show the changes of a task
+    at <timestamp>      list the tasks as they were at a UTC time like \"2026-10-15 09:30:00\"
+    sync                synchronize with the CalDAV server of TODOS_CALDAV_URL and with the
+                        Google Tasks lists of the TODOS_GOOGLE_CLIENT_ID client
+    google-auth         grant access to Google Tasks in the browser
+    google-lists [id]...
+                        show the Google Tasks lists, or choose the ones to synchronize
+    import <file.csv>   import the tasks of a Todoist CSV export, after a preview
+    import --todoist    import the tasks of the Todoist account of TODOIST_TOKEN
+    import --microsoft  import the Microsoft To Do lists, signing in with the Azure application
+                        of TODOS_MICROSOFT_CLIENT_ID
+                        add --yes to skip the confirmation
+    github-link <owner/name> [--close-issues]
+                        link the tasks to a GitHub repository, optionally closing the issues
+                        of done tasks
+    github-import       import the open issues assigned to the owner of TODOS_GITHUB_TOKEN,
+                        after a preview, and close the issues of done tasks
+    mute <category>     stop showing the notifications of a category, sync-failure or
+                        external-task
+    unmute <category>   show the notifications of a category again
+    mail-setup <server> <username> [folder]
+                        create tasks from the unread messages of an IMAP folder, INBOX by
+                        default, asking for the password kept in the keychain
+    caldav-password <username>
+                        save the CalDAV password in the keychain, TODOS_CALDAV_PASSWORD
+                        taking precedence
+    llm-key <url>       save the API key of a model endpoint in the keychain,
+                        TODOS_LLM_API_KEY taking precedence
+    lock [minutes]      ask for a PIN to show the tasks in the GUI, again after `minutes`
+                        without changes if given
+    lock --remove       stop asking for the PIN
+    profiles            list the profiles, each with its own tasks and settings";
+
+enum Command {
+    Add(String),
+    List {
+        search: Option<String>,
+        /// Name and value of a custom field the tasks have.
+        field: Option<(String, String)>,
+        /// Name of a custom field ordering the tasks.
+        sort_by: Option<String>,
+    },
+    Done(Vec<i64>),
+    Remove(Vec<i64>),
+    Export,
+    Fields,
+    AddField {
+        name: String,
+        kind: FieldKind,
+        choices: Vec<String>,
+    },
+    RemoveField(String),
+    SetField {
+        id: i64,
+        field: String,
+        value: Option<String>,
+    },
+    History(i64),
+    At(String),
+    Sync,
+    GoogleAuth,
+    GoogleLists(Vec<String>),
+    Import {
+        source: ImportSource,
+        yes: bool,
+    },
+    GitHubLink {
+        repo: String,
+        close_issues: bool,
+    },
+    GitHubImport {
+        yes: bool,
+    },
+    Mute(Category, bool),
+    MailSetup {
+        server: String,
+        username: String,
+        folder: String,
+    },
+    CalDavPassword(String),
+    LlmKey(String),
+    Lock(Option<u64>),
+    RemoveLock,
+    Profiles,
+}
+
+enum ImportSource {
+    Csv(String),
+    Todoist,
+    MicrosoftTodo,
+}
+
+fn parse_ids(args: &[String]) -> Result<Vec<i64>, String> {
+    if args.is_empty() {
+        return Err("expected at least one task id".to_string());
+    }
+    args.iter()
+        .map(|arg| arg.parse().map_err(|_| format!("invalid task id: {arg}")))
+        .collect()
+}
+
+fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, args) = args.split_first().ok_or_else(|| USAGE.to_string())?;
+    match command.as_str() {
+        "add" if !args.is_empty() => Ok(Command::Add(args.join(" "))),
+        "add" => Err("expected a description".to_string()),
+        "list" => {
+            let mut search = Vec::new();
+            let (mut field, mut sort_by) = (None, None);
+            let mut args = args.iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--where" => {
+                        let condition =
+                            args.next().ok_or("expected a condition like name=value")?;
+                        let (name, value) = condition
+                            .split_once('=')
+                            .ok_or_else(|| format!("invalid condition: {condition}"))?;
+                        field = Some((name.to_string(), value.to_string()));
+                    }
+                    "--sort-by" => {
+                        sort_by = Some(args.next().ok_or("expected a field name")?.clone());
+                    }
+                    _ => search.push(arg.as_str()),
+                }
+            }
+            Ok(Command::List {
+                search: (!search.is_empty()).then(|| search.join(" ")),
+                field,
+                sort_by,
+            })
+        }
+        "done" => parse_ids(args).map(Command::Done),
+        "rm" => parse_ids(args).map(Command::Remove),
+        "export" => Ok(Command::Export),
+        "fields" => Ok(Command::Fields),
+        "field-add" => match args {
+            [name, kind, choices @ ..] => {
+                let kind = kind.parse::<FieldKind>()?;
+                if (kind == FieldKind::Choice) == choices.is_empty() {
+                    return Err("expected choices for an enum field only".to_string());
+                }
+                Ok(Command::AddField {
+                    name: name.clone(),
+                    kind,
+                    choices: choices.to_vec(),
+                })
+            }
+            _ => Err("expected a name and a kind".to_string()),
+        },
+        "field-rm" => match args {
+            [name] => Ok(Command::RemoveField(name.clone())),
+            _ => Err("expected a field name".to_string()),
+        },
+        "set-field" => match args {
+            [id, field, value @ ..] => Ok(Command::SetField {
+                id: id.parse().map_err(|_| format!("invalid task id: {id}"))?,
+                field: field.clone(),
+                value: (!value.is_empty()).then(|| value.join(" ")),
+            }),
+            _ => Err("expected a task id and a field name".to_string()),
+        },
+        "history" => match parse_ids(args)?.as_slice() {
+            [id] => Ok(Command::History(*id)),
+            _ => Err("expected a single task id".to_string()),
+        },
+        "at" if !args.is_empty() => Ok(Command::At(args.join(" "))),
+        "at" => Err("expected a timestamp".to_string()),
+        "sync" => Ok(Command::Sync),
+        "google-auth" => Ok(Command::GoogleAuth),
+        "google-lists" => Ok(Command::GoogleLists(args.to_vec())),
+        "import" => {
+            let yes = args.iter().any(|arg| arg == "--yes");
+            let mut sources = args.iter().filter(|arg| *arg != "--yes");
+            let source = match (sources.next().map(String::as_str), sources.next()) {
+                (Some("--todoist"), None) => ImportSource::Todoist,
+                (Some("--microsoft"), None) => ImportSource::MicrosoftTodo,
+                (Some(path), None) => ImportSource::Csv(path.to_string()),
+                _ => return Err("expected a CSV file, --todoist or --microsoft".to_string()),
+            };
+            Ok(Command::Import { source, yes })
+        }
+        "github-link" => {
+            let close_issues = args.iter().any(|arg| arg == "--close-issues");
+            let mut repos = args.iter().filter(|arg| *arg != "--close-issues");
+            match (repos.next(), repos.next()) {
+                (Some(repo), None) if repo.contains('/') => Ok(Command::GitHubLink {
+                    repo: repo.clone(),
+                    close_issues,
+                }),
+                _ => Err("expected a repository like owner/name".to_string()),
+            }
+        }
+        "mute" | "unmute" => match args {
+            [category] => Ok(Command::Mute(category.parse()?, command == "mute")),
+            _ => Err("expected a notification category".to_string()),
+        },
+        "github-import" => Ok(Command::GitHubImport {
+            yes: args.iter().any(|arg| arg == "--yes"),
+        }),
+        "mail-setup" => match args {
+            [server, username] | [server, username, _] => Ok(Command::MailSetup {
+                server: server.clone(),
+                username: username.clone(),
+                folder: args.get(2).cloned().unwrap_or_else(|| "INBOX".to_string()),
+            }),
+            _ => Err("expected an IMAP server and a username".to_string()),
+        },
+        "caldav-password" => match args {
+            [username] => Ok(Command::CalDavPassword(username.clone())),
+            _ => Err("expected a username".to_string()),
+        },
+        "llm-key" => match args {
+            [url] => Ok(Command::LlmKey(url.clone())),
+            _ => Err("expected the URL of the endpoint".to_string()),
+        },
+        "lock" => match args {
+            [] => Ok(Command::Lock(None)),
+            [remove] if remove == "--remove" => Ok(Command::RemoveLock),
+            [minutes] => minutes
+                .parse()
+                .map(|minutes| Command::Lock(Some(minutes)))
+                .map_err(|_| format!("invalid number of minutes: {minutes}")),
+            _ => Err("expected a number of minutes or --remove".to_string()),
+        },
+        "profiles" => Ok(Command::Profiles),
+        "help" | "--help" | "-h" => Err(USAGE.to_string()),
+        command => Err(format!("unknown command: {command}\n\n{USAGE}")),
+    }
+}
+
+fn print_task(task: &Task) {
+    let check = if task.status == Status::Done {
+        "x"
+    } else {
+        " "
+    };
+    println!(
+        "[{check}] {:>4}  {:<11}  {:<6}  {}",
+        task.id,
+        task.status.to_string(),
+        task.priority.to_string(),
+        task.description
+    );
+}
+
+fn print_event(event: &TaskEvent, fields: &[CustomField]) {
+    let kind = match event.kind {
+        TaskEventKind::Created => "created",
+        TaskEventKind::Updated => "updated",
+        TaskEventKind::Deleted => "deleted",
+        TaskEventKind::Field => "field",
+    };
+    let mut changes = Vec::new();
+    if let Some(status) = event.status {
+        changes.push(format!("status: {status}"));
+    }
+    if let Some(priority) = event.priority {
+        changes.push(format!("priority: {priority}"));
+    }
+    if let Some(position) = event.position {
+        changes.push(format!("position: {position}"));
+    }
+    if let Some(description) = &event.description {
+        changes.push(format!("description: {description}"));
+    }
+    if let Some(field_id) = event.field_id {
+        // The field may have been deleted since.
+        let name = fields
+            .iter()
+            .find(|field| field.id == field_id)
+            .map_or_else(|| format!("field {field_id}"), |field| field.name.clone());
+        let value = event.field_value.as_deref().unwrap_or("cleared");
+        changes.push(format!("{name}: {value}"));
+    }
+    println!("{}  {kind:<7}  {}", event.occurred_at, changes.join(", "));
+}
+
+#[cfg(any(feature = "caldav", feature = "google-tasks"))]
+fn print_report(name: &str, report: SyncReport) {
+    println!(
+        "{name}: pushed {}, pulled {}, deleted {} locally and {} remotely",
+        report.pushed, report.pulled, report.deleted_locally, report.deleted_remotely
+    );
+    for id in report.conflicts {
+        println!("{name}: task {id} changed on both sides, the server version was kept");
+    }
+}
+
+/// Returns `false` if no CalDAV server is configured.
+#[cfg(feature = "caldav")]
+async fn sync_caldav() -> Result<bool, Box<dyn Error>> {
+    let Some(config) = caldav::CalDavConfig::from_env() else {
+        return Ok(false);
+    };
+    print_report("CalDAV", caldav::sync(config).await?);
+    Ok(true)
+}
+
+#[cfg(not(feature = "caldav"))]
+async fn sync_caldav() -> Result<bool, Box<dyn Error>> {
+    Ok(false)
+}
+
+/// Returns `false` if no Google client is configured.
+#[cfg(feature = "google-tasks")]
+async fn sync_google_tasks() -> Result<bool, Box<dyn Error>> {
+    let Some(config) = GoogleTasksConfig::from_env() else {
+        return Ok(false);
+    };
+    print_report("Google Tasks", google_tasks::sync(config).await?);
+    Ok(true)
+}
+
+#[cfg(not(feature = "google-tasks"))]
+async fn sync_google_tasks() -> Result<bool, Box<dyn Error>> {
+    Ok(false)
+}
+
+async fn sync() -> Result<(), Box<dyn Error>> {
+    let synced = [sync_caldav().await?, sync_google_tasks().await?];
+    if !synced.contains(&true) {
+        return Err(
+            "nothing to synchronize, set TODOS_CALDAV_URL or TODOS_GOOGLE_CLIENT_ID".into(),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "google-tasks")]
+fn google_config() -> Result<GoogleTasksConfig, Box<dyn Error>> {
+    Ok(GoogleTasksConfig::from_env().ok_or("TODOS_GOOGLE_CLIENT_ID isn't set")?)
+}
+
+/// Opens the consent page and waits for its redirection on a local port.
+#[cfg(feature = "google-tasks")]
+async fn google_auth() -> Result<(), Box<dyn Error>> {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    let config = google_config()?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let redirect_uri = format!("http://127.0.0.1:{}", listener.local_addr()?.port());
+    println!("open this page to grant access to Google Tasks:");
+    println!("{}", google_tasks::authorize_url(&config, &redirect_uri));
+    let (mut stream, _) = listener.accept()?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    stream.write_all(b"HTTP/1.1 200 OK\r\n\r\nYou can close this page.")?;
+    let path = request_line.split(' ').nth(1).unwrap_or_default();
+    let url = reqwest::Url::parse(&redirect_uri)?.join(path)?;
+    let code = url
+        .query_pairs()
+        .find(|(name, _)| name == "code")
+        .map(|(_, code)| code.into_owned())
+        .ok_or("access wasn't granted")?;
+    google_tasks::authorize(&config, &code, &redirect_uri).await?;
+    println!("access granted");
+    Ok(())
+}
+
+#[cfg(feature = "google-tasks")]
+async fn google_lists(ids: Vec<String>) -> Result<(), Box<dyn Error>> {
+    if !ids.is_empty() {
+        google_tasks::set_lists(ids).await?;
+        return Ok(());
+    }
+    let selected = google_tasks::lists().await?;
+    for list in google_tasks::available_lists(&google_config()?).await? {
+        let check = if selected.contains(&list.id) {
+            "x"
+        } else {
+            " "
+        };
+        println!("[{check}] {}  {}", list.id, list.title);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "google-tasks"))]
+async fn google_auth() -> Result<(), Box<dyn Error>> {
+    Err("Google Tasks requires building with the `google-tasks` feature".into())
+}
+
+#[cfg(not(feature = "google-tasks"))]
+async fn google_lists(_ids: Vec<String>) -> Result<(), Box<dyn Error>> {
+    Err("Google Tasks requires building with the `google-tasks` feature".into())
+}
+
+#[cfg(feature = "todoist")]
+async fn fetch_todoist() -> Result<Vec<ImportedTask>, Box<dyn Error>> {
+    let token = std::env::var("TODOIST_TOKEN").map_err(|_| "TODOIST_TOKEN isn't set")?;
+    Ok(todoist::fetch(&token).await?)
+}
+
+#[cfg(not(feature = "todoist"))]
+async fn fetch_todoist() -> Result<Vec<ImportedTask>, Box<dyn Error>> {
+    Err("importing from the API requires building with the `todoist` feature".into())
+}
+
+#[cfg(feature = "microsoft-todo")]
+async fn fetch_microsoft_todo() -> Result<Vec<ImportedTask>, Box<dyn Error>> {
+    use todos::import::microsoft_todo;
+
+    let client_id = std::env::var("TODOS_MICROSOFT_CLIENT_ID")
+        .map_err(|_| "TODOS_MICROSOFT_CLIENT_ID isn't set")?;
+    let device_code = microsoft_todo::request_device_code(&client_id).await?;
+    println!("{}", device_code.message);
+    let token = microsoft_todo::wait_for_token(&client_id, &device_code).await?;
+    Ok(microsoft_todo::fetch(&token).await?)
+}
+
+#[cfg(not(feature = "microsoft-todo"))]
+async fn fetch_microsoft_todo() -> Result<Vec<ImportedTask>, Box<dyn Error>> {
+    Err("importing from Microsoft To Do requires building with the `microsoft-todo` feature".into())
+}
+
+#[cfg(feature = "github")]
+async fn github_link(repo: String, close_issues: bool) -> Result<(), Box<dyn Error>> {
+    github::link(repo).await?;
+    github::set_close_issues(close_issues).await?;
+    Ok(())
+}
+
+#[cfg(feature = "github")]
+async fn github_import(yes: bool) -> Result<(), Box<dyn Error>> {
+    let token = std::env::var("TODOS_GITHUB_TOKEN").map_err(|_| "TODOS_GITHUB_TOKEN isn't set")?;
+    let closed = github::close_completed(&token).await?;
+    if closed > 0 {
+        println!("closed {closed} issues");
+    }
+    let issues = github::fetch(&token).await?;
+    let tasks = issues
+        .iter()
+        .map(|issue| issue.task.clone())
+        .collect::<Vec<_>>();
+    if tasks.is_empty() {
+        println!("no new issues");
+    } else if confirm_import(&tasks, yes)? {
+        let tasks = github::commit(issues).await?;
+        println!("imported {} issues", tasks.len());
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "github"))]
+async fn github_link(_repo: String, _close_issues: bool) -> Result<(), Box<dyn Error>> {
+    Err("GitHub requires building with the `github` feature".into())
+}
+
+#[cfg(not(feature = "github"))]
+async fn github_import(_yes: bool) -> Result<(), Box<dyn Error>> {
+    Err("GitHub requires building with the `github` feature".into())
+}
+
+#[cfg(feature = "mail")]
+async fn mail_setup(
+    server: String,
+    username: String,
+    folder: String,
+) -> Result<(), Box<dyn Error>> {
+    print!("password of {username}: ");
+    io::stdout().flush()?;
+    let mut password = String::new();
+    io::stdin().read_line(&mut password)?;
+    let config = mail::MailConfig {
+        server,
+        username,
+        folder,
+    };
+    mail::configure(config, password.trim_end_matches(['\r', '\n'])).await?;
+    println!("the app now creates tasks from the unread messages");
+    Ok(())
+}
+
+#[cfg(not(feature = "mail"))]
+async fn mail_setup(
+    _server: String,
+    _username: String,
+    _folder: String,
+) -> Result<(), Box<dyn Error>> {
+    Err("the mail watcher requires building with the `mail` feature".into())
+}
+
+/// Asks for a secret on the standard input.
+#[cfg(any(feature = "caldav", feature = "llm"))]
+fn read_secret(prompt: &str) -> io::Result<String> {
+    print!("{prompt}: ");
+    io::stdout().flush()?;
+    let mut secret = String::new();
+    io::stdin().read_line(&mut secret)?;
+    Ok(secret.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(feature = "caldav")]
+fn caldav_password(username: &str) -> Result<(), Box<dyn Error>> {
+    let password = read_secret(&format!("CalDAV password of {username}"))?;
+    caldav::CalDavConfig::save_password(username, &password)?;
+    println!("saved the password, set TODOS_CALDAV_USERNAME={username} to use it");
+    Ok(())
+}
+
+#[cfg(not(feature = "caldav"))]
+fn caldav_password(_username: &str) -> Result<(), Box<dyn Error>> {
+    Err("CalDAV requires building with the `caldav` feature".into())
+}
+
+#[cfg(feature = "llm")]
+fn llm_key(url: &str) -> Result<(), Box<dyn Error>> {
+    let api_key = read_secret(&format!("API key of {url}"))?;
+    LlmConfig::save_api_key(url, &api_key)?;
+    println!("saved the API key, set TODOS_LLM_URL={url} to use it");
+    Ok(())
+}
+
+#[cfg(not(feature = "llm"))]
+fn llm_key(_url: &str) -> Result<(), Box<dyn Error>> {
+    Err("the suggestions require building with the `llm` feature".into())
+}
+
+#[cfg(feature = "lock")]
+async fn set_lock(idle_minutes: Option<u64>) -> Result<(), Box<dyn Error>> {
+    print!("PIN, at least {} digits: ", lock::MIN_PIN_LENGTH);
+    io::stdout().flush()?;
+    let mut pin = String::new();
+    io::stdin().read_line(&mut pin)?;
+    lock::set_pin(pin.trim(), idle_minutes).await?;
+    println!("the GUI now asks for the PIN to show the tasks");
+    Ok(())
+}
+
+#[cfg(feature = "lock")]
+async fn remove_lock() -> Result<(), Box<dyn Error>> {
+    lock::remove().await?;
+    println!("the GUI no longer asks for a PIN");
+    Ok(())
+}
+
+#[cfg(not(feature = "lock"))]
+async fn set_lock(_idle_minutes: Option<u64>) -> Result<(), Box<dyn Error>> {
+    Err("the lock requires building with the `lock` feature".into())
+}
+
+#[cfg(not(feature = "lock"))]
+async fn remove_lock() -> Result<(), Box<dyn Error>> {
+    Err("the lock requires building with the `lock` feature".into())
+}
+
+fn find_field<'a>(fields: &'a [CustomField], name: &str) -> Result<&'a CustomField, String> {
+    fields
+        .iter()
+        .find(|field| field.name == name)
+        .ok_or_else(|| format!("there is no field named {name}"))
+}
+
+/// Shows the tasks to import and asks for a confirmation, unless `yes` is set.
+fn confirm_import(tasks: &[ImportedTask], yes: bool) -> Result<bool, Box<dyn Error>> {
+    for task in tasks {
+        println!("  {}", task.preview());
+    }
+    if tasks.iter().any(ImportedTask::is_partial) {
+        println!("projects, due dates and tags aren't imported");
+    }
+    if yes {
+        return Ok(true);
+    }
+    print!("import {} tasks? [y/N] ", tasks.len());
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+async fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Add(description) => {
+            let task = create_task(description).await?;
+            print_task(&task);
+        }
+        Command::List {
+            search,
+            field,
+            sort_by,
+        } => {
+            let fields = get_custom_fields().await?;
+            let field = match field {
+                Some((name, value)) => {
+                    let field = find_field(&fields, &name)?;
+                    Some((field.id, field.parse_value(&value)?))
+                }
+                None => None,
+            };
+            let field_order = match sort_by {
+                Some(name) => Some(find_field(&fields, &name)?.id),
+                None => None,
+            };
+            let tasks = get_tasks(TaskQuery {
+                search,
+                order: Some(TaskOrder::StatusFirst),
+                field,
+                field_order,
+                ..Default::default()
+            })
+            .await?;
+            tasks.iter().for_each(print_task);
+        }
+        Command::Done(ids) => {
+            let tasks = set_tasks_status(ids, Status::Done).await?;
+            tasks.iter().for_each(print_task);
+        }
+        Command::Remove(ids) => {
+            for id in ids {
+                delete_task(id).await?;
+                println!("deleted task {id}");
+            }
+        }
+        Command::Export => {
+            let (_, csv) = export_csv().await?;
+            print!("{csv}");
+        }
+        Command::Fields => {
+            for field in get_custom_fields().await? {
+                let choices = field.choices().collect::<Vec<_>>().join(", ");
+                println!(
+                    "{:<20}  {:<6}  {choices}",
+                    field.name,
+                    field.kind.to_string()
+                );
+            }
+        }
+        Command::AddField {
+            name,
+            kind,
+            choices,
+        } => {
+            create_custom_field(&name, kind, &choices.join("\n")).await?;
+            println!("added the field {name}");
+        }
+        Command::RemoveField(name) => {
+            let field = find_field(&get_custom_fields().await?, &name)?.clone();
+            delete_custom_field(field.id).await?;
+            println!("deleted the field {name}");
+        }
+        Command::SetField { id, field, value } => {
+            let task = get_task(id).await?;
+            let field = find_field(&get_custom_fields().await?, &field)?.clone();
+            let value = value.map(|value| field.parse_value(&value)).transpose()?;
+            set_field_value(task.id, field.id, value.as_deref()).await?;
+            match value {
+                Some(value) => println!("{} of task {id}: {value}", field.name),
+                None => println!("removed the {} of task {id}", field.name),
+            }
+        }
+        Command::History(id) => {
+            let events = get_task_history(id).await?;
+            if events.is_empty() {
+                return Err(format!("there is no history for task {id}").into());
+            }
+            let fields = get_custom_fields().await?;
+            for event in &events {
+                print_event(event, &fields);
+            }
+        }
+        Command::At(at) => {
+            let tasks = get_tasks_at(at).await?;
+            tasks.iter().for_each(print_task);
+        }
+        Command::Sync => sync().await?,
+        Command::GoogleAuth => google_auth().await?,
+        Command::GoogleLists(ids) => google_lists(ids).await?,
+        Command::Import { source, yes } => {
+            let tasks = match source {
+                ImportSource::Csv(path) => todoist::parse_csv(&fs::read_to_string(path)?)?,
+                ImportSource::Todoist => fetch_todoist().await?,
+                ImportSource::MicrosoftTodo => fetch_microsoft_todo().await?,
+            };
+            if tasks.is_empty() {
+                println!("nothing to import");
+            } else if confirm_import(&tasks, yes)? {
+                let tasks = import::commit(tasks).await?;
+                println!("imported {} tasks", tasks.len());
+            }
+        }
+        Command::GitHubLink { repo, close_issues } => github_link(repo, close_issues).await?,
+        Command::GitHubImport { yes } => github_import(yes).await?,
+        Command::Mute(category, muted) => set_muted(category, muted).await?,
+        Command::MailSetup {
+            server,
+            username,
+            folder,
+        } => mail_setup(server, username, folder).await?,
+        Command::CalDavPassword(username) => caldav_password(&username)?,
+        Command::LlmKey(url) => llm_key(&url)?,
+        Command::Lock(idle_minutes) => set_lock(idle_minutes).await?,
+        Command::RemoveLock => remove_lock().await?,
+        Command::Profiles => {
+            println!("{}", profile::DEFAULT_PROFILE);
+            for name in profile::list() {
+                println!("{name}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let with_profile = args.first().is_some_and(|arg| arg == "--profile");
+    if with_profile {
+        let Some(name) = args.get(1).cloned() else {
+            eprintln!("expected a profile name");
+            return ExitCode::FAILURE;
+        };
+        if let Err(error) = profile::select(&name) {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+        args.drain(..2);
+    }
+    let command = match parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("can't start the runtime");
+    // The database of the profile may have just been created.
+    if with_profile && let Err(error) = runtime.block_on(migrate()) {
+        eprintln!("{error}");
+        return ExitCode::FAILURE;
+    }
+    match runtime.block_on(run(command)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Model Context Protocol server over stdio, to add to the configuration of LLM clients like any
+//! other stdio server.
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use todos::mcp::handle;
+
+fn main() -> ExitCode {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("can't start the runtime");
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                eprintln!("can't read the request: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = runtime.block_on(handle(&line)) else {
+            continue;
+        };
+        if writeln!(stdout, "{response}")
+            .and_then(|()| stdout.flush())
+            .is_err()
+        {
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,237 @@
+//! Terminal frontend working on the same database as the GUI, usable over SSH.
+
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use todos::core::{Priority, ServerError, Status, Task};
+use todos::database::{TaskOrder, TaskQuery, create_task, delete_task, get_tasks, update_task};
+use tokio::runtime::Runtime;
+
+const HELP: &str =
+    "j/k move · space status · p priority · a add · e edit · d delete · r reload · q quit";
+
+fn status_color(status: Status) -> Color {
+    match status {
+        Status::ToDo => Color::Blue,
+        Status::InProgress => Color::LightRed,
+        Status::Done => Color::Green,
+    }
+}
+
+fn priority_color(priority: Priority) -> Color {
+    match priority {
+        Priority::Low => Color::Gray,
+        Priority::Medium => Color::Yellow,
+        Priority::High => Color::Red,
+    }
+}
+
+enum Input {
+    Add(String),
+    Edit(i64, String),
+}
+
+struct App {
+    runtime: Runtime,
+    tasks: Vec<Task>,
+    list_state: ListState,
+    input: Option<Input>,
+    message: Option<String>,
+    running: bool,
+}
+
+impl App {
+    fn new() -> io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            runtime,
+            tasks: Vec::new(),
+            list_state: ListState::default(),
+            input: None,
+            message: None,
+            running: true,
+        })
+    }
+
+    /// Runs a database operation, showing its error if it fails.
+    fn run<R>(&mut self, future: impl Future<Output = Result<R, ServerError>>) -> Option<R> {
+        match self.runtime.block_on(future) {
+            Ok(result) => Some(result),
+            Err(error) => {
+                self.message = Some(error.to_string());
+                None
+            }
+        }
+    }
+
+    fn reload(&mut self) {
+        let query = TaskQuery {
+            order: Some(TaskOrder::StatusFirst),
+            ..Default::default()
+        };
+        if let Some(tasks) = self.run(get_tasks(query)) {
+            self.tasks = tasks;
+            let selected = self.list_state.selected().unwrap_or(0);
+            self.list_state
+                .select((!self.tasks.is_empty()).then(|| selected.min(self.tasks.len() - 1)));
+        }
+    }
+
+    fn selected(&self) -> Option<Task> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.tasks.get(index))
+            .cloned()
+    }
+
+    fn update(&mut self, task: Task) {
+        let updated = self.run(update_task(
+            task.id,
+            task.description.to_string(),
+            task.status,
+            task.priority,
+        ));
+        if updated.is_some() {
+            self.reload();
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        self.message = None;
+        if let Some(input) = &mut self.input {
+            let text = match input {
+                Input::Add(text) | Input::Edit(_, text) => text,
+            };
+            match code {
+                KeyCode::Char(c) => text.push(c),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Enter => self.submit(),
+                _ => (),
+            }
+            return;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            KeyCode::Char('j') | KeyCode::Down => self.list_state.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.list_state.select_previous(),
+            KeyCode::Char('r') => self.reload(),
+            KeyCode::Char('a') => self.input = Some(Input::Add(String::new())),
+            KeyCode::Char('e') => {
+                if let Some(task) = self.selected() {
+                    self.input = Some(Input::Edit(task.id, task.description.to_string()));
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(task) = self.selected() {
+                    let status = task.status.next();
+                    self.update(Task { status, ..task });
+                }
+            }
+            KeyCode::Char('p') => {
+                if let Some(task) = self.selected() {
+                    let priority = task.priority.next();
+                    self.update(Task { priority, ..task });
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(task) = self.selected()
+                    && self.run(delete_task(task.id)).is_some()
+                {
+                    self.reload();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn submit(&mut self) {
+        match self.input.take() {
+            Some(Input::Add(description)) if !description.trim().is_empty() => {
+                if self.run(create_task(description)).is_some() {
+                    self.reload();
+                }
+            }
+            Some(Input::Edit(id, description)) if !description.trim().is_empty() => {
+                if let Some(task) = self.tasks.iter().find(|task| task.id == id).cloned() {
+                    self.update(Task {
+                        description: description.into(),
+                        ..task
+                    });
+                }
+            }
+            _ => self.message = Some("description is required".to_string()),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [list_area, input_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let items = self.tasks.iter().map(|task| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<11}", task.status.to_string()),
+                    Style::new().fg(status_color(task.status)),
+                ),
+                Span::styled(
+                    format!("{:<7}", task.priority.to_string()),
+                    Style::new().fg(priority_color(task.priority)),
+                ),
+                Span::raw(task.description.to_string()),
+            ]))
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title(" Todos "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        let (title, text) = match &self.input {
+            Some(Input::Add(text)) => (" New task ", text.as_str()),
+            Some(Input::Edit(_, text)) => (" Edit task ", text.as_str()),
+            None => (" ", self.message.as_deref().unwrap_or("")),
+        };
+        let input = Paragraph::new(text).block(Block::bordered().title(title));
+        let input = if self.input.is_none() && self.message.is_some() {
+            input.red()
+        } else {
+            input
+        };
+        frame.render_widget(input, input_area);
+        frame.render_widget(Paragraph::new(HELP).dark_gray(), help_area);
+    }
+
+    fn run_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.reload();
+        while self.running {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key.code);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut app = App::new()?;
+    let mut terminal = ratatui::init();
+    let result = app.run_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,195 @@
+//! Configuration read from `config.toml` in the configuration directory of the platform, like
+//! `~/.config/todos/config.toml` on Linux, and reloaded by `watch` when the file changes. The
+//! settings changed from the GUI stay in the database, this file being meant for the choices made
+//! by hand or provisioned with the app.
+//!
+//! ```toml
+//! database = "/srv/todos/Todos.db"
+//! filter = "active"
+//! sorter = "priority_first"
+//!
+//! [backup]
+//! dir = "/srv/todos/backups"
+//! interval_hours = 24
+//! ```
+//!
+//! Environment variables override the file, so that containers can be configured without one:
+//! `TODOS_DB`, `TODOS_FILTER`, `TODOS_SORTER`, `TODOS_BACKUP_DIR` and
+//! `TODOS_BACKUP_INTERVAL_HOURS`. The command line flags override both. `TODOS_LOG` sets the log
+//! level, see `logging::init`.
+
+use std::env::{self, VarError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use tracing::{error, info, warn};
+
+use crate::database::backup;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often `run_backups` checks whether a backup is due.
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+static CONFIG: LazyLock<RwLock<Config>> = LazyLock::new(RwLock::default);
+
+#[derive(Deserialize, Default, PartialEq, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Database file opened instead of the default one, unless `--db` is given.
+    pub database: Option<PathBuf>,
+    /// Status filter shown at startup instead of the saved one: all, active or completed.
+    pub filter: Option<String>,
+    /// Order shown at startup instead of the saved one: status_first, priority_first or manual.
+    pub sorter: Option<String>,
+    pub backup: Option<BackupConfig>,
+}
+
+/// Copies of the database made by `run_backups` while the app is running.
+#[derive(Deserialize, PartialEq, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BackupConfig {
+    pub dir: PathBuf,
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u64,
+}
+
+fn default_interval_hours() -> u64 {
+    24
+}
+
+impl Config {
+    /// Replaces the fields set in the environment, read with `var` like `env::var`. Invalid
+    /// values are ignored.
+    pub fn override_with(mut self, var: impl Fn(&str) -> Result<String, VarError>) -> Self {
+        if let Ok(database) = var("TODOS_DB") {
+            self.database = Some(database.into());
+        }
+        if let Ok(filter) = var("TODOS_FILTER") {
+            self.filter = Some(filter);
+        }
+        if let Ok(sorter) = var("TODOS_SORTER") {
+            self.sorter = Some(sorter);
+        }
+        if let Ok(dir) = var("TODOS_BACKUP_DIR") {
+            let interval_hours = self
+                .backup
+                .as_ref()
+                .map_or_else(default_interval_hours, |backup| backup.interval_hours);
+            self.backup = Some(BackupConfig {
+                dir: dir.into(),
+                interval_hours,
+            });
+        }
+        if let (Some(backup), Ok(hours)) = (&mut self.backup, var("TODOS_BACKUP_INTERVAL_HOURS")) {
+            match hours.parse() {
+                Ok(hours) => backup.interval_hours = hours,
+                Err(_) => warn!("ignored the invalid TODOS_BACKUP_INTERVAL_HOURS {hours}"),
+            }
+        }
+        self
+    }
+}
+
+/// Path of the configuration file, `None` on platforms without a configuration directory.
+pub fn path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("todos").join("config.toml"))
+}
+
+/// Reads the configuration file, the defaults being used if it is missing or invalid, then the
+/// environment variables.
+pub fn load() -> Config {
+    let config = path()
+        .and_then(|path| read(&path))
+        .unwrap_or_default()
+        .override_with(env::var);
+    *CONFIG.write().unwrap() = config.clone();
+    config
+}
+
+/// The configuration loaded last.
+pub fn get() -> Config {
+    CONFIG.read().unwrap().clone()
+}
+
+/// Returns `None` if the file is invalid, a missing file giving the defaults.
+fn read(path: &Path) -> Option<Config> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Some(Config::default()),
+        Err(error) => {
+            warn!(%error, "can't read {}", path.display());
+            return None;
+        }
+    };
+    toml::from_str(&text)
+        .inspect_err(|error| warn!(%error, "ignored the invalid {}", path.display()))
+        .ok()
+}
+
+/// Reloads the configuration whenever the file changes, keeping the previous one while the file
+/// is invalid. The database and the list settings are only read at startup.
+pub async fn watch() {
+    let Some(path) = path() else {
+        return;
+    };
+    let modified = || {
+        fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
+    let mut last_modified = modified();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let current = modified();
+        if current == last_modified {
+            continue;
+        }
+        last_modified = current;
+        if let Some(config) = read(&path) {
+            let config = config.override_with(env::var);
+            info!("reloaded {}", path.display());
+            *CONFIG.write().unwrap() = config;
+        }
+    }
+}
+
+/// Backs up the database into the backup directory of the configuration, once the newest backup
+/// there is older than the interval.
+pub async fn run_backups() {
+    loop {
+        tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+        let Some(config) = get().backup else {
+            continue;
+        };
+        let interval = Duration::from_secs(config.interval_hours * 60 * 60);
+        let due = last_backup(&config.dir)
+            .is_none_or(|last| last.elapsed().unwrap_or_default() >= interval);
+        if !due {
+            continue;
+        }
+        if let Err(error) = fs::create_dir_all(&config.dir) {
+            error!(%error, "can't create {}", config.dir.display());
+            continue;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let path = config.dir.join(format!("Todos-{}.db", now.as_secs()));
+        match backup(&path).await {
+            Ok(()) => info!("backed up the database to {}", path.display()),
+            Err(error) => error!(%error, "can't back up the database"),
+        }
+    }
+}
+
+/// When the newest file of `dir` was written.
+fn last_backup(dir: &Path) -> Option<SystemTime> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+        .max()
+}
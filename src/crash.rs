@@ -0,0 +1,90 @@
+//! Crash reports written by the panic hook next to the database, so that the next launch can offer
+//! to show them instead of the app silently disappearing. The work not saved yet, kept up to date
+//! with `set_unsaved`, is written to a recovery file at the same time.
+
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::database::data_dir;
+use crate::logging::recent;
+
+/// Number of log lines included in the report.
+const LOG_LINES: usize = 100;
+
+static UNSAVED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// File of the report of the last crash, until it is dismissed.
+pub fn report_path() -> PathBuf {
+    data_dir().join("crash-report.txt")
+}
+
+/// File of the work not saved when the app crashed, until it is recovered.
+pub fn recovery_path() -> PathBuf {
+    data_dir().join("recovery.txt")
+}
+
+/// Writes a crash report and the unsaved work on every panic, then runs the previous hook, which
+/// prints the panic.
+pub fn install_hook() {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Err(error) = fs::write(report_path(), report(info)) {
+            eprintln!("can't write the crash report: {error}");
+        }
+        // The lock is skipped if the panic happened while holding it.
+        if let Ok(unsaved) = UNSAVED.try_lock()
+            && !unsaved.is_empty()
+            && let Err(error) = fs::write(recovery_path(), unsaved.join("\n"))
+        {
+            eprintln!("can't write the unsaved work: {error}");
+        }
+        previous_hook(info);
+    }));
+}
+
+/// Replaces the work written to the recovery file if the app crashes, like the text of the forms.
+pub fn set_unsaved(unsaved: Vec<String>) {
+    *UNSAVED.lock().unwrap() = unsaved;
+}
+
+/// Takes the work recovered from the last crash, one draft per line, and deletes the file.
+pub fn take_recovered() -> Vec<String> {
+    let Ok(recovered) = fs::read_to_string(recovery_path()) else {
+        return Vec::new();
+    };
+    let _ = fs::remove_file(recovery_path());
+    recovered.lines().map(str::to_string).collect()
+}
+
+fn report(info: &PanicHookInfo) -> String {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let thread = std::thread::current();
+    let logs = recent();
+    let logs = &logs[logs.len().saturating_sub(LOG_LINES)..];
+    format!(
+        "todos {} on {} {}, crashed at {} (Unix time)\n\nthread '{}' {info}\n\n{}\n\nlast logs:\n{}",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        time.as_secs(),
+        thread.name().unwrap_or("<unnamed>"),
+        Backtrace::force_capture(),
+        logs.join("\n"),
+    )
+}
+
+/// The report of the last crash, if not dismissed yet.
+pub fn last_report() -> Option<String> {
+    fs::read_to_string(report_path()).ok()
+}
+
+/// Deletes the report of the last crash.
+pub fn dismiss() {
+    let _ = fs::remove_file(report_path());
+}
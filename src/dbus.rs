@@ -0,0 +1,110 @@
+//! D-Bus service on the session bus, so that desktop widgets and scripts can read and add tasks
+//! without opening the database themselves, e.g.
+//! `busctl --user call org.my_todos.Tasks /org/my_todos/Tasks org.my_todos.Tasks AddTask s "Call Bob"`.
+
+use std::time::Duration;
+
+use zbus::object_server::SignalEmitter;
+use zbus::{connection, fdo, interface};
+
+use crate::core::ServerError;
+use crate::database::{TaskOrder, TaskQuery, TaskWatcher, get_tasks};
+use crate::notify::{Category, notify};
+use crate::repository::Database;
+use crate::service::{Command, execute, execute_one};
+use crate::{Priority, Status};
+
+pub const BUS_NAME: &str = "org.my_todos.Tasks";
+pub const OBJECT_PATH: &str = "/org/my_todos/Tasks";
+/// How often the database is checked for changes to signal.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+const ORIGIN: &str = "D-Bus";
+
+fn failed(error: ServerError) -> fdo::Error {
+    fdo::Error::Failed(error.to_string())
+}
+
+struct Tasks;
+
+#[interface(name = "org.my_todos.Tasks")]
+impl Tasks {
+    /// Adds a task and returns its id.
+    async fn add_task(&self, description: String) -> fdo::Result<i64> {
+        let command = Command::CreateTask {
+            description,
+            priority: Priority::default(),
+        };
+        let task = execute_one::<Database>(ORIGIN, command)
+            .await
+            .map_err(failed)?;
+        tokio::spawn(notify(
+            Category::ExternalTask,
+            "Task added".to_string(),
+            task.description.to_string(),
+        ));
+        Ok(task.id)
+    }
+
+    /// Returns the id, description, status and priority of every task, the ones to do first.
+    async fn list_tasks(&self) -> fdo::Result<Vec<(i64, String, String, String)>> {
+        let tasks = get_tasks(TaskQuery {
+            order: Some(TaskOrder::StatusFirst),
+            ..Default::default()
+        })
+        .await
+        .map_err(failed)?;
+        let tasks = tasks
+            .into_iter()
+            .map(|task| {
+                (
+                    task.id,
+                    task.description.to_string(),
+                    task.status.to_string(),
+                    task.priority.to_string(),
+                )
+            })
+            .collect();
+        Ok(tasks)
+    }
+
+    async fn complete_task(&self, id: i64) -> fdo::Result<()> {
+        let command = Command::ChangeStatus {
+            ids: vec![id],
+            status: Status::Done,
+        };
+        let events = execute::<Database>(ORIGIN, command).await.map_err(failed)?;
+        if events.is_empty() {
+            return Err(fdo::Error::InvalidArgs(format!("there is no task {id}")));
+        }
+        Ok(())
+    }
+
+    /// Emitted when tasks are created, updated or deleted, from any application.
+    #[zbus(signal)]
+    async fn tasks_changed(
+        emitter: &SignalEmitter<'_>,
+        changed: Vec<i64>,
+        deleted: Vec<i64>,
+    ) -> zbus::Result<()>;
+}
+
+/// Serves the interface and signals the changes until the process exits.
+pub async fn serve() -> zbus::Result<()> {
+    let connection = connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, Tasks)?
+        .build()
+        .await?;
+    let emitter = SignalEmitter::new(&connection, OBJECT_PATH)?;
+    let server_error = |error: ServerError| zbus::Error::Failure(error.to_string());
+    let mut watcher = TaskWatcher::new().await.map_err(server_error)?;
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        let (tasks, deleted) = watcher.changes().await.map_err(server_error)?;
+        if tasks.is_empty() && deleted.is_empty() {
+            continue;
+        }
+        let changed = tasks.iter().map(|task| task.id).collect();
+        Tasks::tasks_changed(&emitter, changed, deleted).await?;
+    }
+}
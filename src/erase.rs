@@ -0,0 +1,149 @@
+//! "Erase all my data", deleting for good what the app keeps in the current profile: the database
+//! with the settings, the backups, the unsaved drafts, the crash report, the logs and the
+//! credentials. The files out of the database being shared by all the profiles, only the database
+//! is erased in the other profiles than the default one. Files are overwritten with zeros before
+//! being deleted, which doesn't reach the copies that SSDs and copy-on-write file systems may
+//! keep.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::core::ServerError;
+use crate::crash;
+use crate::database::{database_file, migrate, pool, reconnect};
+use crate::logging::{clear_recent, log_dir};
+use crate::profile;
+#[cfg(feature = "secrets")]
+use crate::secrets::{self, SecretsError};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Error)]
+pub enum EraseError {
+    #[error(transparent)]
+    Server(#[from] ServerError),
+    #[error("couldn't erase a file: {0}")]
+    Io(#[from] io::Error),
+    #[cfg(feature = "secrets")]
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
+}
+
+/// Overwrites the file with zeros, then deletes it. Missing files are ignored.
+pub fn shred(path: &Path) -> io::Result<()> {
+    let length = match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let zeros = [0; CHUNK_SIZE];
+    let mut remaining = length;
+    while remaining > 0 {
+        let chunk = remaining.min(CHUNK_SIZE as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()?;
+    drop(file);
+    fs::remove_file(path)
+}
+
+/// Erases everything, the app being in the state of its first run afterwards. The other
+/// operations on the database fail while it is recreated.
+pub async fn erase_all() -> Result<(), EraseError> {
+    if !profile::is_default() {
+        erase_database().await?;
+        info!("erased the database of the profile");
+        return Ok(());
+    }
+    // The accounts are read from the settings, so before the database is erased.
+    forget_credentials().await?;
+    crash::set_unsaved(Vec::new());
+    shred(&crash::recovery_path())?;
+    shred(&crash::report_path())?;
+    #[cfg(feature = "config")]
+    if let Some(backup) = crate::config::get().backup {
+        erase_backups(&backup.dir)?;
+    }
+    erase_database().await?;
+    erase_logs();
+    info!("erased all the data");
+    Ok(())
+}
+
+async fn forget_credentials() -> Result<(), EraseError> {
+    #[cfg(feature = "mail")]
+    if let Some(config) = crate::mail::config().await? {
+        crate::mail::forget_password(&config.username)?;
+    }
+    #[cfg(feature = "google-tasks")]
+    if let Some(config) = crate::sync::google_tasks::GoogleTasksConfig::from_env() {
+        crate::sync::google_tasks::forget(&config)?;
+    }
+    #[cfg(feature = "caldav")]
+    if let Some(config) = crate::sync::caldav::CalDavConfig::from_env() {
+        crate::sync::caldav::CalDavConfig::forget_password(&config.username)?;
+    }
+    #[cfg(feature = "llm")]
+    if let Some(config) = crate::llm::LlmConfig::from_env() {
+        crate::llm::LlmConfig::forget_api_key(&config.url)?;
+    }
+    #[cfg(feature = "secrets")]
+    shred(&secrets::file_path())?;
+    Ok(())
+}
+
+/// Erases the backups made by `config::run_backups`, the other files of the directory being kept.
+#[cfg(feature = "config")]
+fn erase_backups(dir: &Path) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if name.starts_with("Todos-") && name.ends_with(".db") {
+            shred(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Closes the pool, shreds the database with its journals, and creates it again, empty.
+async fn erase_database() -> Result<(), EraseError> {
+    let Some(path) = database_file() else {
+        return Ok(());
+    };
+    pool().close().await;
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        let mut file = path.clone().into_os_string();
+        file.push(suffix);
+        shred(Path::new(&file))?;
+    }
+    reconnect();
+    migrate().await?;
+    Ok(())
+}
+
+/// The file being written may not be deletable on some platforms, so failures are only logged.
+fn erase_logs() {
+    clear_recent();
+    let Ok(entries) = fs::read_dir(log_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if let Err(error) = shred(&entry.path()) {
+            warn!(%error, "can't erase {}", entry.path().display());
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Points, levels and achievements rewarding the completed tasks.
+//!
+//! The database awards the points of a task the first time it is done, `10` for a low priority up
+//! to `30` for a high one, tasks having no due dates. `update` unlocks the achievements reached
+//! since the last time, the points being awarded even while the XP bar is hidden.
+
+use crate::core::ServerError;
+use crate::database::{PointsSummary, get_achievements, get_points, unlock_achievement};
+
+#[derive(Debug)]
+pub struct Achievement {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    reached: fn(&PointsSummary) -> bool,
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        key: "first_task",
+        name: "First step",
+        description: "Complete a task",
+        reached: |summary| summary.completed >= 1,
+    },
+    Achievement {
+        key: "ten_tasks",
+        name: "Getting things done",
+        description: "Complete 10 tasks",
+        reached: |summary| summary.completed >= 10,
+    },
+    Achievement {
+        key: "hundred_tasks",
+        name: "Centurion",
+        description: "Complete 100 tasks",
+        reached: |summary| summary.completed >= 100,
+    },
+    Achievement {
+        key: "high_priority",
+        name: "First things first",
+        description: "Complete 5 high priority tasks",
+        reached: |summary| summary.high_priority >= 5,
+    },
+    Achievement {
+        key: "level_5",
+        name: "Seasoned",
+        description: "Reach level 5",
+        reached: |summary| level(summary.points) >= 5,
+    },
+];
+
+/// Points needed to reach `level`, each level asking `50` more points than the previous one.
+pub fn points_for_level(level: u32) -> i64 {
+    let level = i64::from(level.max(1));
+    50 * level * (level - 1) / 2
+}
+
+/// Level reached with `points`, starting at `1`.
+pub fn level(points: i64) -> u32 {
+    let mut level = 1;
+    while points >= points_for_level(level + 1) {
+        level += 1;
+    }
+    level
+}
+
+/// Progress from the current level to the next one, between `0.0` and `1.0`.
+pub fn level_progress(points: i64) -> f64 {
+    let level = level(points);
+    let start = points_for_level(level);
+    let end = points_for_level(level + 1);
+    (points - start) as f64 / (end - start) as f64
+}
+
+/// The points and the unlocked achievements.
+#[derive(Default, Clone, Debug)]
+pub struct Progress {
+    pub summary: PointsSummary,
+    /// Keys of the unlocked achievements.
+    pub unlocked: Vec<String>,
+    /// Achievements unlocked by this update.
+    pub new: Vec<&'static Achievement>,
+}
+
+/// Unlocks the achievements reached since the last update.
+pub async fn update() -> Result<Progress, ServerError> {
+    let summary = get_points().await?;
+    let mut new = Vec::new();
+    for achievement in ACHIEVEMENTS {
+        if (achievement.reached)(&summary) && unlock_achievement(achievement.key).await? {
+            new.push(achievement);
+        }
+    }
+    let unlocked = get_achievements().await?;
+    Ok(Progress {
+        summary,
+        unlocked,
+        new,
+    })
+}
@@ -0,0 +1,243 @@
+//! gRPC service over the `database` functions, generated from `proto/todos/v1/todos.proto`.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use sqlx::Error as SqlxError;
+use tokio::sync::mpsc::{Sender, channel};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Server;
+use tonic::{Request, Response};
+
+use crate::core::ServerError;
+use crate::database::{TaskOrder, TaskQuery, TaskWatcher, get_task, get_tasks};
+use crate::notify::{Category, notify};
+use crate::repository::Database;
+use crate::service::{Command, execute, execute_one};
+use crate::{Priority, Status, Task};
+
+pub mod proto {
+    tonic::include_proto!("todos.v1");
+}
+
+use proto::tasks_server::{Tasks, TasksServer};
+use proto::{
+    CreateTaskRequest, DeleteTaskRequest, DeleteTaskResponse, GetTaskRequest, ListTasksRequest,
+    ListTasksResponse, TaskEvent, TaskPriority, TaskStatus, UpdateTaskRequest, WatchTasksRequest,
+    task_event,
+};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:50051";
+/// How often the database is checked for changes to stream.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+const ORIGIN: &str = "gRPC";
+
+impl From<Status> for TaskStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::ToDo => TaskStatus::ToDo,
+            Status::InProgress => TaskStatus::InProgress,
+            Status::Done => TaskStatus::Done,
+        }
+    }
+}
+
+impl From<TaskStatus> for Status {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::ToDo => Status::ToDo,
+            TaskStatus::InProgress => Status::InProgress,
+            TaskStatus::Done => Status::Done,
+        }
+    }
+}
+
+impl From<Priority> for TaskPriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => TaskPriority::Low,
+            Priority::Medium => TaskPriority::Medium,
+            Priority::High => TaskPriority::High,
+        }
+    }
+}
+
+impl From<TaskPriority> for Priority {
+    fn from(priority: TaskPriority) -> Self {
+        match priority {
+            TaskPriority::Low => Priority::Low,
+            TaskPriority::Medium => Priority::Medium,
+            TaskPriority::High => Priority::High,
+        }
+    }
+}
+
+impl From<Task> for proto::Task {
+    fn from(task: Task) -> Self {
+        Self {
+            id: task.id,
+            description: task.description.to_string(),
+            status: TaskStatus::from(task.status).into(),
+            priority: TaskPriority::from(task.priority).into(),
+            updated_at: task.updated_at,
+            position: task.position,
+        }
+    }
+}
+
+/// Answers a `ServerError` with the matching gRPC status.
+fn error_status(error: ServerError) -> tonic::Status {
+    match &error {
+        ServerError::Database(SqlxError::RowNotFound) => {
+            tonic::Status::not_found(error.to_string())
+        }
+        ServerError::Database(_) => tonic::Status::internal(error.to_string()),
+        ServerError::Timeout | ServerError::ConnectionLost(_) => {
+            tonic::Status::unavailable(error.to_string())
+        }
+        ServerError::ReadOnly => tonic::Status::failed_precondition(error.to_string()),
+    }
+}
+
+fn status(status: i32) -> Result<Status, tonic::Status> {
+    TaskStatus::try_from(status)
+        .map(Status::from)
+        .map_err(|_| tonic::Status::invalid_argument(format!("invalid status {status}")))
+}
+
+fn priority(priority: i32) -> Result<Priority, tonic::Status> {
+    TaskPriority::try_from(priority)
+        .map(Priority::from)
+        .map_err(|_| tonic::Status::invalid_argument(format!("invalid priority {priority}")))
+}
+
+/// Sends the changes made to the tasks until the client goes away.
+async fn watch(sender: Sender<Result<TaskEvent, tonic::Status>>) {
+    let mut watcher = match TaskWatcher::new().await {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            let _ = sender.send(Err(error_status(error))).await;
+            return;
+        }
+    };
+    while !sender.is_closed() {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        let (tasks, deleted) = match watcher.changes().await {
+            Ok(changes) => changes,
+            Err(error) => {
+                let _ = sender.send(Err(error_status(error))).await;
+                return;
+            }
+        };
+        let changed = tasks
+            .into_iter()
+            .map(|task| task_event::Event::Changed(task.into()));
+        let deleted = deleted.into_iter().map(task_event::Event::Deleted);
+        for event in changed.chain(deleted) {
+            let event = TaskEvent { event: Some(event) };
+            if sender.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TaskService;
+
+#[tonic::async_trait]
+impl Tasks for TaskService {
+    type WatchTasksStream = ReceiverStream<Result<TaskEvent, tonic::Status>>;
+
+    async fn list_tasks(
+        &self,
+        request: Request<ListTasksRequest>,
+    ) -> Result<Response<ListTasksResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let statuses = request.status.map(status).transpose()?;
+        let tasks = get_tasks(TaskQuery {
+            statuses: statuses.map(|status| vec![status]),
+            search: request.search,
+            order: Some(TaskOrder::StatusFirst),
+            ..Default::default()
+        })
+        .await
+        .map_err(error_status)?;
+        let tasks = tasks.into_iter().map(proto::Task::from).collect();
+        Ok(Response::new(ListTasksResponse { tasks }))
+    }
+
+    async fn get_task(
+        &self,
+        request: Request<GetTaskRequest>,
+    ) -> Result<Response<proto::Task>, tonic::Status> {
+        let task = get_task(request.into_inner().id)
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(task.into()))
+    }
+
+    async fn create_task(
+        &self,
+        request: Request<CreateTaskRequest>,
+    ) -> Result<Response<proto::Task>, tonic::Status> {
+        let command = Command::CreateTask {
+            description: request.into_inner().description,
+            priority: Priority::default(),
+        };
+        let task = execute_one::<Database>(ORIGIN, command)
+            .await
+            .map_err(error_status)?;
+        tokio::spawn(notify(
+            Category::ExternalTask,
+            "Task added".to_string(),
+            task.description.to_string(),
+        ));
+        Ok(Response::new(task.into()))
+    }
+
+    async fn update_task(
+        &self,
+        request: Request<UpdateTaskRequest>,
+    ) -> Result<Response<proto::Task>, tonic::Status> {
+        let request = request.into_inner();
+        let command = Command::UpdateTask {
+            id: request.id,
+            description: request.description,
+            status: status(request.status)?,
+            priority: priority(request.priority)?,
+        };
+        let task = execute_one::<Database>(ORIGIN, command)
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(task.into()))
+    }
+
+    async fn delete_task(
+        &self,
+        request: Request<DeleteTaskRequest>,
+    ) -> Result<Response<DeleteTaskResponse>, tonic::Status> {
+        let id = request.into_inner().id;
+        execute::<Database>(ORIGIN, Command::DeleteTask(id))
+            .await
+            .map_err(error_status)?;
+        Ok(Response::new(DeleteTaskResponse { id }))
+    }
+
+    async fn watch_tasks(
+        &self,
+        _request: Request<WatchTasksRequest>,
+    ) -> Result<Response<Self::WatchTasksStream>, tonic::Status> {
+        let (sender, receiver) = channel(16);
+        tokio::spawn(watch(sender));
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/// Serves the gRPC service until the process exits.
+pub async fn serve(addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(TasksServer::new(TaskService))
+        .serve(addr)
+        .await
+}
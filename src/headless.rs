@@ -0,0 +1,172 @@
+//! Operations run by `todos --headless` without opening a window, so that cron jobs and CI can
+//! drive the app. The exit code is `0` on success, `1` when the operation failed and `2` for
+//! invalid arguments.
+
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::core::{CustomField, ServerError};
+use crate::database::{
+    FieldValue, TaskQuery, backup, get_custom_fields, get_field_values, get_tasks, migrate, vacuum,
+};
+use crate::import::{self, todoist};
+use crate::repository::Database;
+use crate::service::{Command, execute};
+use crate::{Priority, Task};
+
+const ORIGIN: &str = "headless";
+/// Number of tasks created by `seed` without a count.
+const DEFAULT_SEED_COUNT: usize = 20;
+
+pub const USAGE: &str = "usage: todos --headless <operation>
+
+operations:
+    import <file.csv>   import the tasks of a Todoist CSV export
+    export <file.csv>   write all the tasks as CSV
+    backup <file>       write a copy of the database, the file must not exist
+    vacuum              reclaim the space of the deleted tasks
+    seed [count]        add sample tasks, 20 by default";
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Operation {
+    Import(PathBuf),
+    Export(PathBuf),
+    Backup(PathBuf),
+    Vacuum,
+    Seed(usize),
+}
+
+impl Operation {
+    /// Parses the arguments following `--headless`.
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let (operation, args) = args.split_first().ok_or_else(|| USAGE.to_string())?;
+        match (operation.as_str(), args) {
+            ("import", [path]) => Ok(Operation::Import(path.into())),
+            ("export", [path]) => Ok(Operation::Export(path.into())),
+            ("backup", [path]) => Ok(Operation::Backup(path.into())),
+            ("import" | "export" | "backup", _) => Err("expected a single file".to_string()),
+            ("vacuum", []) => Ok(Operation::Vacuum),
+            ("seed", []) => Ok(Operation::Seed(DEFAULT_SEED_COUNT)),
+            ("seed", [count]) => count
+                .parse()
+                .map(Operation::Seed)
+                .map_err(|_| format!("invalid count: {count}")),
+            ("vacuum" | "seed", _) => Err(format!("too many arguments\n\n{USAGE}")),
+            (operation, _) => Err(format!("unknown operation: {operation}\n\n{USAGE}")),
+        }
+    }
+
+    /// Runs the operation after applying the migrations, and returns what to report.
+    pub async fn run(self) -> Result<String, Box<dyn Error>> {
+        migrate().await?;
+        match self {
+            Operation::Import(path) => {
+                let tasks = todoist::parse_csv(&fs::read_to_string(path)?)?;
+                let tasks = import::commit(tasks).await?;
+                Ok(format!("imported {} tasks", tasks.len()))
+            }
+            Operation::Export(path) => {
+                let (count, csv) = export_csv().await?;
+                fs::write(&path, csv)?;
+                Ok(format!("exported {count} tasks to {}", path.display()))
+            }
+            Operation::Backup(path) => {
+                backup(&path).await?;
+                Ok(format!("backed up the database to {}", path.display()))
+            }
+            Operation::Vacuum => {
+                vacuum().await?;
+                Ok("vacuumed the database".to_string())
+            }
+            Operation::Seed(count) => {
+                let priorities = [Priority::Low, Priority::Medium, Priority::High];
+                let tasks = (1..=count)
+                    .zip(priorities.into_iter().cycle())
+                    .map(|(n, priority)| (format!("Sample task {n}"), priority))
+                    .collect();
+                let events = execute::<Database>(ORIGIN, Command::CreateTasks(tasks)).await?;
+                Ok(format!("added {} sample tasks", events.len()))
+            }
+        }
+    }
+}
+
+/// Runs the operation of the arguments following `--headless` to completion.
+pub fn run(args: &[String]) -> ExitCode {
+    let operation = match Operation::parse(args) {
+        Ok(operation) => operation,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("can't start the runtime");
+    match runtime.block_on(operation.run()) {
+        Ok(report) => {
+            println!("{report}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Writes the tasks as CSV with a header, quoting the fields following RFC 4180.
+pub fn to_csv(tasks: &[Task]) -> String {
+    to_csv_with_fields(tasks, &[], &[])
+}
+
+/// Like `to_csv`, followed by a column per custom field, named after it, empty for the tasks
+/// without a value.
+pub fn to_csv_with_fields(tasks: &[Task], fields: &[CustomField], values: &[FieldValue]) -> String {
+    let mut csv = "id,description,status,priority,updated_at".to_string();
+    for field in fields {
+        csv.push(',');
+        csv.push_str(&csv_field(&field.name));
+    }
+    csv.push('\n');
+    for task in tasks {
+        csv.push_str(&format!(
+            "{},{},{},{},{}",
+            task.id,
+            csv_field(&task.description),
+            csv_field(&task.status.to_string()),
+            csv_field(&task.priority.to_string()),
+            csv_field(&task.updated_at)
+        ));
+        for field in fields {
+            let value = values
+                .iter()
+                .find(|value| value.task_id == task.id && value.field_id == field.id)
+                .map_or("", |value| &value.value);
+            csv.push(',');
+            csv.push_str(&csv_field(value));
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// All the tasks as CSV with their custom fields, and their number.
+pub async fn export_csv() -> Result<(usize, String), ServerError> {
+    let tasks = get_tasks(TaskQuery::default()).await?;
+    let fields = get_custom_fields().await?;
+    let values = get_field_values(None).await?;
+    Ok((tasks.len(), to_csv_with_fields(&tasks, &fields, &values)))
+}
+
+/// Quotes the field if needed.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,82 @@
+//! Importers reading the tasks of other applications into `ImportedTask`s, so that they can be
+//! previewed before `commit` creates them.
+
+#[cfg(feature = "github")]
+pub mod github;
+#[cfg(feature = "microsoft-todo")]
+pub mod microsoft_todo;
+pub mod todoist;
+
+use crate::core::ServerError;
+use crate::database::{create_tasks, update_tasks};
+use crate::{Priority, Status, Task};
+
+/// A task read from another application, before it is created.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ImportedTask {
+    pub description: String,
+    pub status: Status,
+    pub priority: Priority,
+    pub project: Option<String>,
+    /// Due date as written by the other application, like `every monday` or `2026-10-15`.
+    pub due: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl ImportedTask {
+    /// Whether some fields have no equivalent in tasks, and won't be imported.
+    pub fn is_partial(&self) -> bool {
+        self.project.is_some() || self.due.is_some() || !self.tags.is_empty()
+    }
+
+    /// One line summary shown in the preview.
+    pub fn preview(&self) -> String {
+        let mut preview = format!("[{}] {}", self.priority, self.description);
+        if let Some(project) = &self.project {
+            preview.push_str(&format!(" #{project}"));
+        }
+        for tag in &self.tags {
+            preview.push_str(&format!(" @{tag}"));
+        }
+        if let Some(due) = &self.due {
+            preview.push_str(&format!(" (due {due})"));
+        }
+        preview
+    }
+}
+
+/// Creates the previewed tasks, at the end of the user order.
+pub async fn commit(tasks: Vec<ImportedTask>) -> Result<Vec<Task>, ServerError> {
+    let descriptions = tasks.iter().map(|task| task.description.clone()).collect();
+    let created = create_tasks(descriptions).await?;
+    let updates = created
+        .iter()
+        .zip(&tasks)
+        .filter(|(task, imported)| {
+            task.status != imported.status || task.priority != imported.priority
+        })
+        .map(|(task, imported)| {
+            (
+                task.id,
+                task.description.to_string(),
+                imported.status,
+                imported.priority,
+            )
+        })
+        .collect::<Vec<_>>();
+    if updates.is_empty() {
+        return Ok(created);
+    }
+    let updated = update_tasks(updates).await?;
+    let tasks = created
+        .into_iter()
+        .map(|task| {
+            updated
+                .iter()
+                .find(|updated| updated.id == task.id)
+                .cloned()
+                .unwrap_or(task)
+        })
+        .collect();
+    Ok(tasks)
+}
@@ -0,0 +1,224 @@
+//! Import of the open GitHub issues assigned to the user, from the repository chosen with `link`.
+//!
+//! Imported issues stay linked to their task, so that they aren't imported twice and, if enabled
+//! with `set_close_issues`, so that `close_completed` closes them once their task is done. Tasks
+//! have no tags or due dates yet, so labels and milestone due dates are only shown in the preview.
+
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::ServerError;
+use crate::database::{
+    GitHubIssue, TaskQuery, delete_github_issue, get_github_issues, get_setting, get_tasks,
+    set_github_issue, set_setting,
+};
+use crate::import::{self, ImportedTask};
+use crate::{Priority, Status, Task};
+
+const API_URL: &str = "https://api.github.com";
+const PER_PAGE: usize = 100;
+
+const REPO_SETTING: &str = "github.repo";
+const CLOSE_ISSUES_SETTING: &str = "github.close_issues";
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("couldn't reach GitHub: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("GitHub answered {0}")]
+    Status(StatusCode),
+    #[error("no GitHub repository is linked")]
+    NotLinked,
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+/// An open issue, before it is imported.
+#[derive(Clone, Debug)]
+pub struct ImportedIssue {
+    pub number: i64,
+    pub task: ImportedTask,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Milestone {
+    due_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    number: i64,
+    title: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    milestone: Option<Milestone>,
+    /// Only set for pull requests, which are listed with the issues.
+    pull_request: Option<IgnoredAny>,
+}
+
+#[derive(Serialize)]
+struct IssueState {
+    state: &'static str,
+}
+
+/// Links the tasks to the repository `repo`, like `owner/name`.
+pub async fn link(repo: String) -> Result<(), ServerError> {
+    set_setting(REPO_SETTING, repo).await
+}
+
+pub async fn linked_repo() -> Result<Option<String>, ServerError> {
+    get_setting(REPO_SETTING).await
+}
+
+/// Whether the issues are closed once their task is done, disabled by default.
+pub async fn set_close_issues(close_issues: bool) -> Result<(), ServerError> {
+    set_setting(CLOSE_ISSUES_SETTING, close_issues.to_string()).await
+}
+
+pub async fn close_issues() -> Result<bool, ServerError> {
+    Ok(get_setting(CLOSE_ISSUES_SETTING).await?.as_deref() == Some("true"))
+}
+
+struct GitHubClient {
+    client: Client,
+    token: String,
+}
+
+impl GitHubClient {
+    fn new(token: &str) -> Self {
+        Self {
+            client: Client::new(),
+            token: token.to_string(),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{API_URL}/{path}"))
+            .bearer_auth(&self.token)
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "my-todos")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, ImportError> {
+        let response = request.send().await?;
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            status => Err(ImportError::Status(status)),
+        }
+    }
+
+    async fn open_issues(&self, repo: &str, assignee: &str) -> Result<Vec<Issue>, ImportError> {
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let request = self
+                .request(Method::GET, &format!("repos/{repo}/issues"))
+                .query(&[("state", "open"), ("assignee", assignee)])
+                .query(&[("per_page", PER_PAGE), ("page", page)]);
+            let page = self.send(request).await?.json::<Vec<Issue>>().await?;
+            let last_page = page.len() < PER_PAGE;
+            issues.extend(page);
+            if last_page {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// Fetches the open issues of the linked repository assigned to the owner of `token`, except
+/// the ones already imported.
+pub async fn fetch(token: &str) -> Result<Vec<ImportedIssue>, ImportError> {
+    let repo = linked_repo().await?.ok_or(ImportError::NotLinked)?;
+    let client = GitHubClient::new(token);
+    let user = client
+        .send(client.request(Method::GET, "user"))
+        .await?
+        .json::<User>()
+        .await?;
+    let imported = get_github_issues().await?;
+    let issues = client
+        .open_issues(&repo, &user.login)
+        .await?
+        .into_iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .filter(|issue| {
+            !imported
+                .iter()
+                .any(|imported| imported.repo == repo && imported.number == issue.number)
+        })
+        .map(|issue| ImportedIssue {
+            number: issue.number,
+            task: ImportedTask {
+                description: issue.title,
+                status: Status::ToDo,
+                priority: Priority::default(),
+                project: Some(repo.clone()),
+                due: issue
+                    .milestone
+                    .and_then(|milestone| milestone.due_on)
+                    .map(|due_on| due_on.split('T').next().unwrap_or_default().to_string()),
+                tags: issue.labels.into_iter().map(|label| label.name).collect(),
+            },
+        })
+        .collect();
+    Ok(issues)
+}
+
+/// Creates the previewed issues and links them to their task.
+pub async fn commit(issues: Vec<ImportedIssue>) -> Result<Vec<Task>, ImportError> {
+    let repo = linked_repo().await?.ok_or(ImportError::NotLinked)?;
+    let tasks = import::commit(issues.iter().map(|issue| issue.task.clone()).collect()).await?;
+    for (task, issue) in tasks.iter().zip(&issues) {
+        set_github_issue(GitHubIssue {
+            task_id: task.id,
+            repo: repo.clone(),
+            number: issue.number,
+        })
+        .await?;
+    }
+    Ok(tasks)
+}
+
+/// Closes the issues whose task is done, if enabled with `set_close_issues`. Returns how many
+/// issues were closed.
+pub async fn close_completed(token: &str) -> Result<usize, ImportError> {
+    if !close_issues().await? {
+        return Ok(0);
+    }
+    let client = GitHubClient::new(token);
+    let tasks = get_tasks(TaskQuery::default()).await?;
+    let mut closed = 0;
+    for issue in get_github_issues().await? {
+        match tasks.iter().find(|task| task.id == issue.task_id) {
+            Some(task) if task.status == Status::Done => {
+                let request = client
+                    .request(
+                        Method::PATCH,
+                        &format!("repos/{}/issues/{}", issue.repo, issue.number),
+                    )
+                    .json(&IssueState { state: "closed" });
+                client.send(request).await?;
+                delete_github_issue(issue.task_id).await?;
+                closed += 1;
+            }
+            Some(_) => (),
+            None => delete_github_issue(issue.task_id).await?,
+        }
+    }
+    Ok(closed)
+}
@@ -0,0 +1,208 @@
+//! Import of Microsoft To Do lists through the Microsoft Graph API.
+//!
+//! Access is granted with the device code flow: `request_device_code` gives a code to enter on a
+//! Microsoft page, and `wait_for_token` returns the access token once it was entered. Lists become
+//! the project of their tasks. Tasks have no checklists, so the steps of a task are imported as
+//! tasks of their own, right after it and named after it.
+
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::import::ImportedTask;
+use crate::{Priority, Status};
+
+const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode";
+const TOKEN_URL: &str = "https://login.microsoftonline.com/common/oauth2/v2.0/token";
+const GRAPH_URL: &str = "https://graph.microsoft.com/v1.0";
+const SCOPE: &str = "Tasks.Read";
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("couldn't reach Microsoft Graph: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Microsoft Graph answered {0}")]
+    Status(StatusCode),
+    #[error("access wasn't granted: {0}")]
+    NotAuthorized(String),
+}
+
+/// Code to enter at `verification_uri` to grant access.
+#[derive(Deserialize, Clone, Debug)]
+pub struct DeviceCode {
+    device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// Instructions to show, in the language of the user.
+    pub message: String,
+    /// Seconds between two checks.
+    interval: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct TokenError {
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    value: Vec<T>,
+    #[serde(rename = "@odata.nextLink")]
+    next_link: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TodoList {
+    id: String,
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DateTimeTimeZone {
+    date_time: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChecklistItem {
+    display_name: String,
+    is_checked: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TodoTask {
+    title: String,
+    status: String,
+    importance: String,
+    due_date_time: Option<DateTimeTimeZone>,
+    #[serde(default)]
+    checklist_items: Vec<ChecklistItem>,
+}
+
+/// Starts the device code flow of the Azure application `client_id`, registered as a public
+/// client.
+pub async fn request_device_code(client_id: &str) -> Result<DeviceCode, ImportError> {
+    let response = Client::new()
+        .post(DEVICE_CODE_URL)
+        .form(&[("client_id", client_id), ("scope", SCOPE)])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(ImportError::Status(response.status()));
+    }
+    Ok(response.json().await?)
+}
+
+/// Waits until the device code is entered, and returns the access token.
+pub async fn wait_for_token(
+    client_id: &str,
+    device_code: &DeviceCode,
+) -> Result<String, ImportError> {
+    let client = Client::new();
+    let mut interval = Duration::from_secs(device_code.interval.max(1));
+    loop {
+        tokio::time::sleep(interval).await;
+        let response = client
+            .post(TOKEN_URL)
+            .form(&[
+                ("client_id", client_id),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("device_code", device_code.device_code.as_str()),
+            ])
+            .send()
+            .await?;
+        if response.status().is_success() {
+            return Ok(response.json::<TokenResponse>().await?.access_token);
+        }
+        let error = response.json::<TokenError>().await?.error;
+        match error.as_str() {
+            "authorization_pending" => (),
+            "slow_down" => interval += Duration::from_secs(5),
+            _ => return Err(ImportError::NotAuthorized(error)),
+        }
+    }
+}
+
+/// Fetches all the pages of a collection.
+async fn pages<T>(client: &Client, token: &str, url: String) -> Result<Vec<T>, ImportError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let mut items = Vec::new();
+    let mut next_link = Some(url);
+    while let Some(url) = next_link {
+        let response = client.get(url).bearer_auth(token).send().await?;
+        if !response.status().is_success() {
+            return Err(ImportError::Status(response.status()));
+        }
+        let page = response.json::<Page<T>>().await?;
+        items.extend(page.value);
+        next_link = page.next_link;
+    }
+    Ok(items)
+}
+
+fn status(status: &str) -> Status {
+    match status {
+        "completed" => Status::Done,
+        "inProgress" => Status::InProgress,
+        _ => Status::ToDo,
+    }
+}
+
+fn priority(importance: &str) -> Priority {
+    match importance {
+        "high" => Priority::High,
+        "normal" => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+/// Fetches the tasks of all the lists, with their steps.
+pub async fn fetch(token: &str) -> Result<Vec<ImportedTask>, ImportError> {
+    let client = Client::new();
+    let lists = pages::<TodoList>(&client, token, format!("{GRAPH_URL}/me/todo/lists")).await?;
+    let mut tasks = Vec::new();
+    for list in lists {
+        let url = format!(
+            "{GRAPH_URL}/me/todo/lists/{}/tasks?$expand=checklistItems",
+            list.id
+        );
+        for todo in pages::<TodoTask>(&client, token, url).await? {
+            let task = ImportedTask {
+                description: todo.title.clone(),
+                status: status(&todo.status),
+                priority: priority(&todo.importance),
+                project: Some(list.display_name.clone()),
+                due: todo.due_date_time.map(|due| {
+                    let date = due.date_time.split('T').next().unwrap_or_default();
+                    date.to_string()
+                }),
+                tags: Vec::new(),
+            };
+            let steps = todo.checklist_items.into_iter().map(|step| ImportedTask {
+                description: format!("{}: {}", todo.title, step.display_name),
+                status: if step.is_checked {
+                    Status::Done
+                } else {
+                    Status::ToDo
+                },
+                due: None,
+                ..task.clone()
+            });
+            tasks.push(task.clone());
+            tasks.extend(steps);
+        }
+    }
+    Ok(tasks)
+}
@@ -0,0 +1,164 @@
+//! Import of Todoist tasks, from the CSV export of a project or, with the `todoist` feature,
+//! through the REST API with a personal token.
+//!
+//! Tasks have no projects, due dates or tags yet, so these are only shown in the preview.
+
+use thiserror::Error;
+
+use crate::core::ServerError;
+use crate::import::ImportedTask;
+use crate::{Priority, Status};
+
+#[cfg(feature = "todoist")]
+const API_URL: &str = "https://api.todoist.com/rest/v2";
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("invalid Todoist export: {0}")]
+    Csv(String),
+    #[cfg(feature = "todoist")]
+    #[error("couldn't reach Todoist: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+/// Todoist priorities go from `1`, the default, to `4` for p1 in the API, while the CSV export
+/// writes them the other way around.
+fn priority(api_priority: u8) -> Priority {
+    match api_priority {
+        4 => Priority::High,
+        2 | 3 => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+/// Splits the CSV into records, following RFC 4180.
+fn records(csv: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => (),
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Reads the tasks of a project exported as CSV from Todoist. Sections and comments are skipped.
+pub fn parse_csv(csv: &str) -> Result<Vec<ImportedTask>, ImportError> {
+    let mut records = records(csv.trim_start_matches('\u{feff}')).into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| ImportError::Csv("the file is empty".to_string()))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name))
+            .ok_or_else(|| ImportError::Csv(format!("missing the {name} column")))
+    };
+    let (kind, content, priority_column) =
+        (column("TYPE")?, column("CONTENT")?, column("PRIORITY")?);
+    let date = column("DATE").ok();
+    let tasks = records
+        .filter(|record| record.get(kind).map(String::as_str) == Some("task"))
+        .filter_map(|record| {
+            let description = record.get(content)?.trim().to_string();
+            let csv_priority = record.get(priority_column)?.trim().parse().unwrap_or(4);
+            let due = date
+                .and_then(|date| record.get(date))
+                .filter(|due| !due.is_empty())
+                .cloned();
+            Some(ImportedTask {
+                description,
+                status: Status::ToDo,
+                priority: priority(5u8.saturating_sub(csv_priority)),
+                project: None,
+                due,
+                tags: Vec::new(),
+            })
+        })
+        .filter(|task| !task.description.is_empty())
+        .collect();
+    Ok(tasks)
+}
+
+#[cfg(feature = "todoist")]
+mod api {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Project {
+        pub id: String,
+        pub name: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Due {
+        pub string: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Task {
+        pub content: String,
+        pub priority: u8,
+        pub project_id: String,
+        pub is_completed: bool,
+        pub due: Option<Due>,
+        #[serde(default)]
+        pub labels: Vec<String>,
+    }
+}
+
+/// Fetches the active tasks of the account owning `token`.
+#[cfg(feature = "todoist")]
+pub async fn fetch(token: &str) -> Result<Vec<ImportedTask>, ImportError> {
+    let client = reqwest::Client::new();
+    let get = |path: &str| {
+        client
+            .get(format!("{API_URL}/{path}"))
+            .bearer_auth(token)
+            .send()
+    };
+    let projects: Vec<api::Project> = get("projects").await?.error_for_status()?.json().await?;
+    let tasks: Vec<api::Task> = get("tasks").await?.error_for_status()?.json().await?;
+    let tasks = tasks
+        .into_iter()
+        .map(|task| ImportedTask {
+            description: task.content,
+            status: if task.is_completed {
+                Status::Done
+            } else {
+                Status::ToDo
+            },
+            priority: priority(task.priority),
+            project: projects
+                .iter()
+                .find(|project| project.id == task.project_id)
+                .map(|project| project.name.clone()),
+            due: task.due.map(|due| due.string),
+            tags: task.labels,
+        })
+        .collect();
+    Ok(tasks)
+}
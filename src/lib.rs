@@ -1,63 +1,53 @@
+#[cfg(all(feature = "gui", target_os = "android"))]
+mod android;
+#[cfg(feature = "gui")]
+mod app;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod crash;
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub mod dbus;
+pub mod erase;
+pub mod gamification;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod headless;
+pub mod import;
+#[cfg(feature = "llm")]
+pub mod llm;
+#[cfg(feature = "lock")]
+pub mod lock;
+pub mod logging;
+#[cfg(feature = "mail")]
+pub mod mail;
+#[cfg(feature = "mcp")]
+pub mod mcp;
+pub mod notify;
+pub mod profile;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+#[cfg(feature = "secrets")]
+pub mod secrets;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod service;
+#[cfg(feature = "spellcheck")]
+pub mod spellcheck;
+#[cfg(any(feature = "caldav", feature = "google-tasks"))]
+pub mod sync;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+#[cfg(feature = "gui")]
 pub mod ui;
+pub mod url_scheme;
+#[cfg(feature = "voice")]
+pub mod voice;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
 
-use xilem::core::map_state;
-use xilem::masonry::layout::{AsUnit, Dim};
-use xilem::style::Style as _;
-use xilem::view::{FlexExt, MainAxisAlignment, flex_col, flex_row, portal, sized_box};
-use xilem::{WindowId, WindowView, window};
+pub use my_todos_core as core;
+pub use my_todos_storage::{database, repository};
 
+#[cfg(feature = "gui")]
+pub use crate::app::{AppState, restart_requested, run};
 use crate::core::{Priority, Status, Task};
-use crate::ui::component::AsyncList;
-use crate::ui::task_list::TaskStorage;
-use crate::ui::theme::BACKGROUND_COLOR;
-
-pub mod core;
-pub mod database;
-
-pub struct AppState {
-    running: bool,
-    main_window_id: WindowId,
-    task_list: AsyncList<Task, TaskStorage>,
-}
-
-impl Default for AppState {
-    fn default() -> Self {
-        Self {
-            running: true,
-            main_window_id: WindowId::next(),
-            task_list: AsyncList::new(true, true),
-        }
-    }
-}
-
-impl xilem::AppState for AppState {
-    fn keep_running(&self) -> bool {
-        self.running
-    }
-}
-
-impl AppState {
-    pub fn logic(&mut self) -> impl Iterator<Item = WindowView<AppState>> + use<> {
-        let task_list = flex_row(sized_box(self.task_list.view()).width(1000.px()))
-            .main_axis_alignment(MainAxisAlignment::Center)
-            .width(Dim::Stretch)
-            .padding(15.);
-        let portal = portal(task_list).flex(1.);
-        let error = self.task_list.error_view().map(|error_view| {
-            flex_row(error_view)
-                .main_axis_alignment(MainAxisAlignment::Center)
-                .padding(15.)
-        });
-        let content = map_state(
-            flex_col((portal, error)).gap(0.px()),
-            |state: &mut AppState, ()| &mut state.task_list,
-        );
-        std::iter::once(
-            window(self.main_window_id, "Todos", content)
-                .with_options(|options| {
-                    options.on_close(|state: &mut AppState| state.running = false)
-                })
-                .with_base_color(BACKGROUND_COLOR),
-        )
-    }
-}
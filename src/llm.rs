@@ -0,0 +1,150 @@
+//! Suggestions from a language model served behind an OpenAI compatible chat completions API,
+//! like Ollama or llama.cpp locally, or a hosted model.
+//!
+//! The endpoint is configured with `TODOS_LLM_URL`, like `http://localhost:11434/v1`, and
+//! `TODOS_LLM_MODEL`, with `TODOS_LLM_API_KEY` for the endpoints requiring a key. The key can be
+//! saved in the keychain instead with `LlmConfig::save_api_key`.
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::Priority;
+use crate::secrets::{self, SecretsError};
+
+const SECRETS_SERVICE: &str = "my-todos-llm";
+const BREAK_DOWN_PROMPT: &str = "Break down the task given by the user into 2 to 8 concrete \
+    subtasks. Answer with one subtask per line and nothing else.";
+const PRIORITY_PROMPT: &str = "Rate the priority of the task given by the user. Answer with a \
+    single word: Low, Medium or High.";
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("couldn't reach the model: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("the model endpoint answered {0}")]
+    Status(StatusCode),
+    #[error("the model didn't answer")]
+    EmptyAnswer,
+}
+
+#[derive(Clone, Debug)]
+pub struct LlmConfig {
+    pub url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+}
+
+impl LlmConfig {
+    /// Reads `TODOS_LLM_URL`, `TODOS_LLM_MODEL` and `TODOS_LLM_API_KEY`, the key saved for the
+    /// endpoint being used if the latter isn't set. Returns `None` if no endpoint is configured.
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("TODOS_LLM_URL").ok()?;
+        let url = url.trim_end_matches('/').to_string();
+        let model = std::env::var("TODOS_LLM_MODEL").ok()?;
+        let api_key = std::env::var("TODOS_LLM_API_KEY").ok().or_else(|| {
+            secrets::get(SECRETS_SERVICE, &url)
+                .inspect_err(|error| warn!(%error, "can't read the model API key"))
+                .ok()
+                .flatten()
+        });
+        Some(Self {
+            url,
+            model,
+            api_key,
+        })
+    }
+
+    /// Saves the API key of the endpoint at `url` in the keychain, see `secrets`.
+    pub fn save_api_key(url: &str, api_key: &str) -> Result<(), SecretsError> {
+        secrets::set(SECRETS_SERVICE, url.trim_end_matches('/'), api_key)
+    }
+
+    /// Deletes the API key of the endpoint at `url` from the keychain.
+    pub fn forget_api_key(url: &str) -> Result<(), SecretsError> {
+        secrets::delete(SECRETS_SERVICE, url.trim_end_matches('/'))
+    }
+}
+
+#[derive(Deserialize)]
+struct Message {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize)]
+struct Completion {
+    choices: Vec<Choice>,
+}
+
+/// Answer of the model to `prompt`, following the `system` instructions.
+async fn complete(config: &LlmConfig, system: &str, prompt: &str) -> Result<String, LlmError> {
+    let mut request = Client::new()
+        .post(format!("{}/chat/completions", config.url))
+        .json(&json!({
+            "model": config.model,
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt },
+            ],
+        }));
+    if let Some(api_key) = &config.api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(LlmError::Status(response.status()));
+    }
+    response
+        .json::<Completion>()
+        .await?
+        .choices
+        .into_iter()
+        .find_map(|choice| choice.message.content)
+        .filter(|content| !content.trim().is_empty())
+        .ok_or(LlmError::EmptyAnswer)
+}
+
+/// Removes the list markers models like to add, like `- `, `* ` or `1. `.
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim();
+    let line = line.strip_prefix(['-', '*', '•']).unwrap_or_else(|| {
+        let digits = line.trim_start_matches(|c: char| c.is_ascii_digit());
+        match digits.strip_prefix(['.', ')']) {
+            Some(rest) if digits.len() < line.len() => rest,
+            _ => line,
+        }
+    });
+    line.trim()
+}
+
+/// Suggests a priority for the task `description`.
+pub async fn suggest_priority(config: &LlmConfig, description: &str) -> Result<Priority, LlmError> {
+    let answer = complete(config, PRIORITY_PROMPT, description).await?;
+    let answer = answer.to_lowercase();
+    [Priority::High, Priority::Medium, Priority::Low]
+        .into_iter()
+        .find(|priority| answer.contains(&priority.to_string().to_lowercase()))
+        .ok_or(LlmError::EmptyAnswer)
+}
+
+/// Proposes subtasks for the task `description`.
+pub async fn break_down(config: &LlmConfig, description: &str) -> Result<Vec<String>, LlmError> {
+    let answer = complete(config, BREAK_DOWN_PROMPT, description).await?;
+    let subtasks = answer
+        .lines()
+        .map(strip_list_marker)
+        .filter(|subtask| !subtask.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if subtasks.is_empty() {
+        return Err(LlmError::EmptyAnswer);
+    }
+    Ok(subtasks)
+}
@@ -0,0 +1,116 @@
+//! Optional PIN hiding the tasks at startup and after a while without changes, for shared
+//! computers. The PIN is kept in the settings as a salted Argon2 hash, set with `todos-cli lock`.
+//!
+//! Anyone able to read the database can still read the tasks, the lock only hides them in the
+//! GUI.
+
+use std::time::Duration;
+
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{self, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use thiserror::Error;
+
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+
+const HASH_SETTING: &str = "lock.hash";
+const IDLE_SETTING: &str = "lock.idle_minutes";
+/// Wrong PINs typed in a row, kept so that restarting the app doesn't skip `retry_delay`.
+const FAILURES_SETTING: &str = "lock.failures";
+/// PINs are short enough to type on the keypad of the lock screen, long enough not to be guessed
+/// at a glance.
+pub const MIN_PIN_LENGTH: usize = 4;
+/// Wrong PINs that can be typed in a row before having to wait, see `retry_delay`.
+const FREE_ATTEMPTS: u32 = 3;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("the PIN must have at least {MIN_PIN_LENGTH} digits")]
+    InvalidPin,
+    #[error("couldn't hash the PIN: {0}")]
+    Hash(#[from] password_hash::Error),
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+#[derive(Clone, Debug)]
+pub struct Lock {
+    hash: String,
+    /// The app locks itself again after this long without changes, never if `None`.
+    pub idle_timeout: Option<Duration>,
+    /// Wrong PINs typed in a row, see `retry_delay`.
+    pub failures: u32,
+}
+
+impl Lock {
+    pub fn verify(&self, pin: &str) -> bool {
+        PasswordHash::new(&self.hash).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(pin.as_bytes(), &hash)
+                .is_ok()
+        })
+    }
+}
+
+/// Time to wait before typing another PIN after `failures` wrong ones in a row, doubling from 5
+/// seconds past the free attempts, so that the short PINs can't be tried one after another.
+pub fn retry_delay(failures: u32) -> Duration {
+    let Some(delayed) = failures.checked_sub(FREE_ATTEMPTS) else {
+        return Duration::ZERO;
+    };
+    Duration::from_secs(5)
+        .saturating_mul(1 << delayed.min(16))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// The lock, `None` if no PIN is set.
+pub async fn load() -> Result<Option<Lock>, ServerError> {
+    let Some(hash) = get_setting(HASH_SETTING)
+        .await?
+        .filter(|hash| !hash.is_empty())
+    else {
+        return Ok(None);
+    };
+    let idle_timeout = get_setting(IDLE_SETTING)
+        .await?
+        .and_then(|minutes| minutes.parse::<u64>().ok())
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| Duration::from_secs(minutes.saturating_mul(60)));
+    let failures = get_setting(FAILURES_SETTING)
+        .await?
+        .and_then(|failures| failures.parse().ok())
+        .unwrap_or_default();
+    Ok(Some(Lock {
+        hash,
+        idle_timeout,
+        failures,
+    }))
+}
+
+/// Stores the number of wrong PINs typed in a row.
+pub async fn set_failures(failures: u32) -> Result<(), ServerError> {
+    set_setting(FAILURES_SETTING, failures.to_string()).await
+}
+
+/// Sets the PIN, made of digits only, and the minutes without changes after which the app locks
+/// itself again.
+pub async fn set_pin(pin: &str, idle_minutes: Option<u64>) -> Result<(), LockError> {
+    if pin.len() < MIN_PIN_LENGTH || !pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err(LockError::InvalidPin);
+    }
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)?
+        .to_string();
+    set_setting(HASH_SETTING, hash).await?;
+    set_setting(IDLE_SETTING, idle_minutes.unwrap_or_default().to_string()).await?;
+    set_failures(0).await?;
+    Ok(())
+}
+
+/// Removes the PIN, the app no longer locking itself.
+pub async fn remove() -> Result<(), ServerError> {
+    set_setting(HASH_SETTING, String::new()).await
+}
@@ -0,0 +1,99 @@
+//! Logs of the app, written to stderr, to files rotated every day in the data directory and kept
+//! in memory for the log viewer.
+
+use std::collections::VecDeque;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+
+use tracing::Level;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{Layer, fmt};
+
+use crate::database::data_dir;
+use crate::database::metrics::MetricsLayer;
+
+/// Number of lines kept for the log viewer.
+const RECENT_CAPACITY: usize = 500;
+/// Number of daily log files kept.
+const MAX_LOG_FILES: usize = 7;
+
+static RECENT: LazyLock<Mutex<VecDeque<String>>> = LazyLock::new(Mutex::default);
+
+/// Directory of the log files.
+pub fn log_dir() -> PathBuf {
+    data_dir().join("logs")
+}
+
+/// Starts logging, including the database operations if `verbose`, and recording their metrics.
+/// Without `verbose`, the level is the one of `TODOS_LOG`, like `warn` or `debug`, or `info`.
+/// Only the first call has an effect.
+pub fn init(verbose: bool) {
+    let level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        env::var("TODOS_LOG")
+            .ok()
+            .and_then(|level| level.parse().ok())
+            .unwrap_or(LevelFilter::INFO)
+    };
+    let file = Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("todos")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir())
+        .inspect_err(|error| eprintln!("can't write the logs to {}: {error}", log_dir().display()))
+        .ok();
+    // The metrics need the spans of the database whatever the level.
+    let database = Targets::new().with_target("my_todos_storage::database", Level::DEBUG);
+    let _ = tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(io::stderr).with_filter(level))
+        .with(file.map(|file| {
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(file)
+                .with_filter(level)
+        }))
+        .with(
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(|| RecentWriter)
+                .with_filter(level),
+        )
+        .with(MetricsLayer.with_filter(database))
+        .try_init();
+}
+
+/// Returns the last lines logged, the oldest first.
+pub fn recent() -> Vec<String> {
+    RECENT.lock().unwrap().iter().cloned().collect()
+}
+
+/// Forgets the lines kept for the log viewer.
+pub fn clear_recent() {
+    RECENT.lock().unwrap().clear();
+}
+
+/// Keeps the lines in `RECENT`, every event being written at once.
+struct RecentWriter;
+
+impl io::Write for RecentWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut recent = RECENT.lock().unwrap();
+        if recent.len() == RECENT_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(String::from_utf8_lossy(buf).trim_end().to_string());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
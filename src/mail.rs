@@ -0,0 +1,155 @@
+//! Mail watcher turning the unread messages of an IMAP folder into tasks named after their
+//! subject. Tasks have no notes yet, so the body of the messages isn't kept.
+//!
+//! Messages are marked as read once their task is created. The account is configured with
+//! `configure`, which keeps the password with `secrets` and the rest in the settings.
+
+use std::net::TcpStream;
+use std::time::Duration;
+
+use imap::Session;
+use mailparse::MailHeaderMap;
+use native_tls::{TlsConnector, TlsStream};
+use thiserror::Error;
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use crate::Priority;
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+use crate::notify::{Category, notify};
+use crate::repository::Database;
+use crate::secrets::{self, SecretsError};
+use crate::service::{Command, execute};
+
+const SERVER_SETTING: &str = "mail.server";
+const USERNAME_SETTING: &str = "mail.username";
+const FOLDER_SETTING: &str = "mail.folder";
+const SECRETS_SERVICE: &str = "my-todos-imap";
+const IMAP_PORT: u16 = 993;
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+const ORIGIN: &str = "mail";
+
+#[derive(Debug, Error)]
+pub enum MailError {
+    #[error("IMAP error: {0}")]
+    Imap(#[from] imap::Error),
+    #[error("couldn't secure the connection: {0}")]
+    Tls(#[from] native_tls::Error),
+    #[error("couldn't read a message: {0}")]
+    Parse(#[from] mailparse::MailParseError),
+    #[error("no password was saved for {0}")]
+    NoPassword(String),
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+/// IMAP account, reached with TLS on port 993.
+#[derive(Clone, Debug)]
+pub struct MailConfig {
+    pub server: String,
+    pub username: String,
+    /// Folder watched for new messages, like `INBOX` or `INBOX/Tasks`.
+    pub folder: String,
+}
+
+/// Deletes the password of `username` from the keychain.
+pub fn forget_password(username: &str) -> Result<(), SecretsError> {
+    secrets::delete(SECRETS_SERVICE, username)
+}
+
+/// The configured account, `None` if the watcher isn't configured.
+pub async fn config() -> Result<Option<MailConfig>, ServerError> {
+    let (Some(server), Some(username), Some(folder)) = (
+        get_setting(SERVER_SETTING).await?,
+        get_setting(USERNAME_SETTING).await?,
+        get_setting(FOLDER_SETTING).await?,
+    ) else {
+        return Ok(None);
+    };
+    Ok(Some(MailConfig {
+        server,
+        username,
+        folder,
+    }))
+}
+
+/// Saves the account, with its password in the keychain, see `secrets`.
+pub async fn configure(config: MailConfig, password: &str) -> Result<(), MailError> {
+    secrets::set(SECRETS_SERVICE, &config.username, password)?;
+    set_setting(SERVER_SETTING, config.server).await?;
+    set_setting(USERNAME_SETTING, config.username).await?;
+    set_setting(FOLDER_SETTING, config.folder).await?;
+    Ok(())
+}
+
+fn connect(config: &MailConfig) -> Result<Session<TlsStream<TcpStream>>, MailError> {
+    let password = secrets::get(SECRETS_SERVICE, &config.username)?
+        .ok_or_else(|| MailError::NoPassword(config.username.clone()))?;
+    let tls = TlsConnector::new()?;
+    let client = imap::connect((config.server.as_str(), IMAP_PORT), &config.server, &tls)?;
+    let mut session = client
+        .login(&config.username, &password)
+        .map_err(|(error, _)| error)?;
+    session.select(&config.folder)?;
+    Ok(session)
+}
+
+/// Creates a task per unread message of the folder. Returns how many were created.
+///
+/// Blocks the current thread, which must not be a thread of the Tokio runtime of `handle`.
+pub fn check(handle: &Handle, config: &MailConfig) -> Result<usize, MailError> {
+    let mut session = connect(config)?;
+    let mut uids = session
+        .uid_search("UNSEEN")?
+        .into_iter()
+        .collect::<Vec<_>>();
+    uids.sort_unstable();
+    for uid in &uids {
+        // PEEK leaves the message unread until its task is created.
+        let fetches = session.uid_fetch(uid.to_string(), "BODY.PEEK[HEADER]")?;
+        let Some(header) = fetches.iter().find_map(|fetch| fetch.header()) else {
+            continue;
+        };
+        let (headers, _) = mailparse::parse_headers(header)?;
+        let subject = headers
+            .get_first_value("Subject")
+            .map(|subject| subject.trim().to_string())
+            .filter(|subject| !subject.is_empty())
+            .unwrap_or_else(|| "(no subject)".to_string());
+        let command = Command::CreateTask {
+            description: subject.clone(),
+            priority: Priority::default(),
+        };
+        handle.block_on(execute::<Database>(ORIGIN, command))?;
+        session.uid_store(uid.to_string(), "+FLAGS (\\Seen)")?;
+        handle.block_on(notify(
+            Category::ExternalTask,
+            "Task added".to_string(),
+            subject,
+        ));
+    }
+    session.logout()?;
+    Ok(uids.len())
+}
+
+/// Checks the folder every minute, while the account is configured.
+///
+/// Blocks the current thread, which must not be a thread of a Tokio runtime.
+pub fn watch() {
+    let runtime = tokio::runtime::Runtime::new().expect("can't start the mail runtime");
+    loop {
+        match runtime.block_on(config()) {
+            Ok(Some(config)) => {
+                if let Err(error) = check(runtime.handle(), &config) {
+                    warn!(%error, "couldn't check the mail");
+                }
+            }
+            Ok(None) => (),
+            Err(error) => warn!(%error, "couldn't read the mail settings"),
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
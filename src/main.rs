@@ -4,20 +4,376 @@
     windows_subsystem = "windows"
 )]
 
-use todos::AppState;
-use todos::ui::theme::apply_theme;
-use xilem::masonry::theme::default_property_set;
-use xilem::winit::error::EventLoopError;
-use xilem::{EventLoop, EventLoopBuilder, Xilem};
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
 
-fn run(event_loop: EventLoopBuilder) -> Result<(), EventLoopError> {
-    let mut def_props = default_property_set();
-    apply_theme(&mut def_props);
+use clap::Parser;
+use todos::database::{migrate, ping, set_database_path};
+use todos::profile;
+use todos::ui::profile::{ProfilePicker, run_picker};
+use todos::ui::task_list::StatusFilter;
+use todos::url_scheme::{self, Instance, UrlCommand};
+use todos::{AppState, restart_requested, run};
+use todos::{crash, headless, logging};
+use tracing::{error, warn};
+use xilem::EventLoop;
 
-    let app = Xilem::new(AppState::default(), AppState::logic).with_default_properties(def_props);
-    app.run_in(event_loop)
+/// Task manager
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Also log the database operations
+    #[arg(long)]
+    verbose: bool,
+    /// Open this database file instead of the default one, created if missing
+    #[arg(long, value_name = "PATH")]
+    db: Option<PathBuf>,
+    /// Open the tasks of this profile, created if missing, instead of asking which one
+    #[arg(long, value_name = "NAME", conflicts_with = "db")]
+    profile: Option<String>,
+    /// Show the tasks matching the filter: all, active or completed
+    #[arg(long)]
+    filter: Option<StatusFilter>,
+    /// Hide the actions changing the tasks and refuse their changes, for a shared screen
+    #[arg(long)]
+    read_only: bool,
+    /// Open the window with this size, like 800x600
+    #[arg(long, value_name = "WxH", value_parser = parse_window_size)]
+    window_size: Option<(f64, f64)>,
+    /// Add a task, then open the app
+    #[arg(long, value_name = "TEXT", conflicts_with_all = ["share", "url"])]
+    add: Option<String>,
+    /// Add the shared text as a task, then open the app
+    #[arg(
+        long,
+        value_name = "TEXT",
+        num_args = 1..,
+        allow_hyphen_values = true,
+        conflicts_with = "url"
+    )]
+    share: Option<Vec<String>>,
+    /// Serve the HTTP API next to the GUI, on ADDR or the default address. Other addresses than
+    /// the loopback ones need TODOS_API_TOKEN
+    #[arg(long, value_name = "ADDR", num_args = 0..=1)]
+    serve: Option<Option<String>>,
+    /// Serve the gRPC service next to the GUI, on ADDR or the default address
+    #[arg(long, value_name = "ADDR", num_args = 0..=1)]
+    grpc: Option<Option<String>>,
+    /// Serve the D-Bus interface on the session bus
+    #[arg(long)]
+    dbus: bool,
+    /// Run an import, export, backup, vacuum or seed without opening a window, then exit
+    #[arg(long, value_name = "OPERATION", num_args = 1.., allow_hyphen_values = true)]
+    headless: Option<Vec<String>>,
+    /// `todos://` URL to handle, forwarded to the running instance if any
+    url: Option<String>,
 }
 
-fn main() -> Result<(), EventLoopError> {
-    run(EventLoop::with_user_event())
+/// Parses a window size like `800x600`.
+fn parse_window_size(size: &str) -> Result<(f64, f64), String> {
+    let invalid = || format!("invalid window size {size}, expected WIDTHxHEIGHT like 800x600");
+    let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+    match (width.parse(), height.parse()) {
+        (Ok(width), Ok(height)) if width > 0. && height > 0. => Ok((width, height)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Serves the HTTP API on its own thread, so that it keeps running next to the GUI.
+#[cfg(feature = "server")]
+fn serve(addr: Option<String>) {
+    let addr = addr.as_deref().unwrap_or(todos::server::DEFAULT_ADDR);
+    let addr = match addr.parse() {
+        Ok(addr) => addr,
+        Err(error) => {
+            error!(%error, "invalid server address {addr}");
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().expect("can't start the server runtime");
+        if let Err(error) = runtime.block_on(todos::server::serve(addr)) {
+            error!(%error, "the server stopped");
+        }
+    });
+}
+
+#[cfg(not(feature = "server"))]
+fn serve(_addr: Option<String>) {
+    warn!("--serve requires building with the `server` feature");
+}
+
+/// Serves the gRPC service on its own thread, like `serve`.
+#[cfg(feature = "grpc")]
+fn serve_grpc(addr: Option<String>) {
+    let addr = addr.as_deref().unwrap_or(todos::grpc::DEFAULT_ADDR);
+    let addr = match addr.parse() {
+        Ok(addr) => addr,
+        Err(error) => {
+            error!(%error, "invalid gRPC address {addr}");
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().expect("can't start the gRPC runtime");
+        if let Err(error) = runtime.block_on(todos::grpc::serve(addr)) {
+            error!(%error, "the gRPC service stopped");
+        }
+    });
+}
+
+#[cfg(not(feature = "grpc"))]
+fn serve_grpc(_addr: Option<String>) {
+    warn!("--grpc requires building with the `grpc` feature");
+}
+
+/// Serves the D-Bus interface on its own thread, like `serve`.
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+fn serve_dbus() {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Runtime::new().expect("can't start the D-Bus runtime");
+        if let Err(error) = runtime.block_on(todos::dbus::serve()) {
+            error!(%error, "the D-Bus service stopped");
+        }
+    });
+}
+
+#[cfg(not(all(feature = "dbus", target_os = "linux")))]
+fn serve_dbus() {
+    warn!("--dbus requires building with the `dbus` feature on Linux");
+}
+
+/// Publishes the changes made by other processes and keeps the audit log of every change, on
+/// their own thread.
+fn watch_changes() {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("can't start the service runtime");
+        runtime.spawn(todos::service::audit());
+        runtime.block_on(todos::service::watch());
+    });
+}
+
+/// Opens the database while the window shows up, the pool connecting lazily, so that the first
+/// fetch of the task list doesn't wait for it.
+fn warm_pool() {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("can't start the warm-up runtime");
+        if let Err(error) = runtime.block_on(ping()) {
+            warn!(%error, "can't open the database");
+        }
+    });
+}
+
+/// Reloads the configuration when it changes and makes its backups, on their own thread.
+#[cfg(feature = "config")]
+fn watch_config() {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("can't start the configuration runtime");
+        runtime.spawn(todos::config::run_backups());
+        runtime.block_on(todos::config::watch());
+    });
+}
+
+/// Delivers the webhooks on their own thread, like `serve`.
+#[cfg(feature = "webhooks")]
+fn dispatch_webhooks() {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Runtime::new().expect("can't start the webhooks runtime");
+        if let Err(error) = runtime.block_on(todos::webhooks::dispatch()) {
+            error!(%error, "the webhooks stopped");
+        }
+    });
+}
+
+/// Counts the usage and sends the telemetry reports on their own thread, like `serve`.
+#[cfg(feature = "telemetry")]
+fn send_telemetry() {
+    std::thread::spawn(|| {
+        let runtime = tokio::runtime::Runtime::new().expect("can't start the telemetry runtime");
+        if let Err(error) = runtime.block_on(todos::telemetry::run()) {
+            error!(%error, "the telemetry stopped");
+        }
+    });
+}
+
+/// Runs the automation script on its own thread.
+#[cfg(feature = "scripting")]
+fn run_scripts() {
+    std::thread::spawn(|| {
+        if let Err(error) = todos::scripting::run() {
+            error!(%error, "the scripts stopped");
+        }
+    });
+}
+
+/// Watches the mail folder configured with `todos-cli mail-setup` on its own thread.
+#[cfg(feature = "mail")]
+fn watch_mail() {
+    std::thread::spawn(todos::mail::watch);
+}
+
+/// Applies the migrations to the database of a profile, which may have just been created.
+fn migrate_profile() -> Result<(), ExitCode> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("can't start the migration runtime");
+    runtime.block_on(migrate()).map_err(|error| {
+        error!(%error, "can't migrate the database of the profile");
+        ExitCode::FAILURE
+    })
+}
+
+/// Asks which profile to open, the app being launched again with it.
+fn pick_profile() -> ExitCode {
+    let args = std::env::args().skip(1).collect();
+    match run_picker(EventLoop::with_user_event(), ProfilePicker::new(args)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            error!(%error, "the event loop failed");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the GUI until its window is closed, then launches it again with `restart_args` if it
+/// asked to restart.
+fn open(app_state: AppState, restart_args: &[String]) -> ExitCode {
+    match run(EventLoop::with_user_event(), app_state) {
+        Ok(()) if restart_requested() => {
+            let launched = std::env::current_exe()
+                .and_then(|exe| Command::new(exe).args(restart_args).spawn());
+            match launched {
+                Ok(_) => ExitCode::SUCCESS,
+                Err(error) => {
+                    error!(%error, "can't restart the app");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            error!(%error, "the event loop failed");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    logging::init(args.verbose);
+    crash::install_hook();
+    let pick = args.db.is_none() && args.profile.is_none();
+    // The same database is opened when restarting, without the tasks to add again.
+    let restart_args = match (&args.db, &args.profile) {
+        (Some(path), _) => vec!["--db".to_string(), path.display().to_string()],
+        (None, Some(name)) => vec!["--profile".to_string(), name.clone()],
+        (None, None) => Vec::new(),
+    };
+    if let Some(path) = args.db {
+        set_database_path(path);
+    }
+    if let Some(name) = &args.profile {
+        if let Err(error) = profile::select(name) {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+        if let Err(code) = migrate_profile() {
+            return code;
+        }
+    }
+    #[cfg(feature = "config")]
+    let config = todos::config::load();
+    // Ignored after `--db`, the path being only set once.
+    #[cfg(feature = "config")]
+    if let Some(path) = config.database.clone() {
+        set_database_path(path);
+    }
+    // The profiles are only offered to people opening the window, not to the commands forwarded
+    // to a running instance.
+    #[cfg(feature = "config")]
+    let pick = pick && config.database.is_none();
+    let forwarding = args.url.is_some() || args.add.is_some() || args.share.is_some();
+    if pick && args.headless.is_none() && !forwarding && !profile::list().is_empty() {
+        return pick_profile();
+    }
+    if let Some(operation) = args.headless {
+        return headless::run(&operation);
+    }
+    if let Some(addr) = args.serve {
+        serve(addr);
+    }
+    if let Some(addr) = args.grpc {
+        serve_grpc(addr);
+    }
+    if args.dbus {
+        serve_dbus();
+    }
+    // Added and shared texts go through the same path as `todos://add` URLs.
+    let text = args.add.or_else(|| args.share.map(|words| words.join(" ")));
+    let url = match text {
+        Some(text) => Some(UrlCommand::Add(text).to_string()),
+        None => args.url,
+    };
+    warm_pool();
+    let mut app_state = AppState::default();
+    #[cfg(feature = "config")]
+    {
+        app_state = app_state.with_config(&config);
+    }
+    if let Some(filter) = args.filter {
+        app_state = app_state.with_filter(filter);
+    }
+    if args.read_only {
+        app_state = app_state.with_read_only();
+    }
+    if let Some((width, height)) = args.window_size {
+        app_state = app_state.with_window_size(width, height);
+    }
+    let primary = match url_scheme::claim_instance(url.as_deref()) {
+        Ok(Instance::Primary(listener)) => {
+            let command = url.and_then(|url| match url.parse::<UrlCommand>() {
+                Ok(command) => Some(command),
+                Err(error) => {
+                    warn!(%error, "ignored the URL");
+                    None
+                }
+            });
+            app_state = app_state.with_url_handler(listener, command);
+            true
+        }
+        Ok(Instance::Forwarded) => return ExitCode::SUCCESS,
+        Ok(Instance::Secondary) => false,
+        Err(error) => {
+            warn!(%error, "can't handle {}:// URLs", url_scheme::SCHEME);
+            true
+        }
+    };
+    // The events only reach the subscribers of their process, so every window follows the changes
+    // of the others and keeps its own audit log.
+    watch_changes();
+    // The running instance already makes the backups, delivers the webhooks, runs the scripts,
+    // watches the mail and sends the telemetry.
+    if primary {
+        #[cfg(feature = "config")]
+        watch_config();
+        #[cfg(feature = "webhooks")]
+        dispatch_webhooks();
+        #[cfg(feature = "scripting")]
+        run_scripts();
+        #[cfg(feature = "mail")]
+        watch_mail();
+        #[cfg(feature = "telemetry")]
+        send_telemetry();
+    }
+    open(app_state, &restart_args)
 }
@@ -0,0 +1,246 @@
+//! Model Context Protocol server, so that LLM clients can manage the tasks with tools going
+//! through the same `database` functions as the GUI.
+//!
+//! Messages are JSON-RPC 2.0 requests, one per line on stdio, each given to `handle`.
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::core::ServerError;
+use crate::database::{
+    TaskOrder, TaskQuery, create_task, get_task, get_tasks, set_tasks_status, update_task,
+};
+use crate::{Priority, Status};
+
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Deserialize)]
+struct Request {
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct ToolCall {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+#[derive(Deserialize)]
+struct ListTasksArgs {
+    status: Option<Status>,
+    search: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AddTaskArgs {
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct CompleteTaskArgs {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct UpdatePriorityArgs {
+    id: i64,
+    priority: Priority,
+}
+
+/// Error of a tool, given back to the model instead of failing the request.
+enum ToolError {
+    InvalidArguments(serde_json::Error),
+    Server(ServerError),
+    NotFound(i64),
+}
+
+impl From<ServerError> for ToolError {
+    fn from(error: ServerError) -> Self {
+        ToolError::Server(error)
+    }
+}
+
+impl ToolError {
+    fn message(&self) -> String {
+        match self {
+            ToolError::InvalidArguments(error) => format!("invalid arguments: {error}"),
+            ToolError::Server(error) => error.to_string(),
+            ToolError::NotFound(id) => format!("there is no task {id}"),
+        }
+    }
+}
+
+fn tools() -> Value {
+    let status = json!({ "type": "string", "enum": ["ToDo", "InProgress", "Done"] });
+    let priority = json!({ "type": "string", "enum": ["Low", "Medium", "High"] });
+    json!([
+        {
+            "name": "list_tasks",
+            "description": "Lists the tasks, the ones to do first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "status": status,
+                    "search": {
+                        "type": "string",
+                        "description": "Only the tasks whose description contains this text",
+                    },
+                },
+            },
+        },
+        {
+            "name": "add_task",
+            "description": "Adds a task to do.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "description": { "type": "string" } },
+                "required": ["description"],
+            },
+        },
+        {
+            "name": "complete_task",
+            "description": "Marks a task as done.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "integer" } },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "update_priority",
+            "description": "Changes the priority of a task.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "integer" }, "priority": priority },
+                "required": ["id", "priority"],
+            },
+        },
+    ])
+}
+
+fn arguments<T>(arguments: Value) -> Result<T, ToolError>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_value(arguments).map_err(ToolError::InvalidArguments)
+}
+
+/// Runs the tool and returns its result as JSON text.
+async fn call_tool(name: &str, args: Value) -> Option<Result<String, ToolError>> {
+    let result = match name {
+        "list_tasks" => list_tasks(args).await,
+        "add_task" => add_task(args).await,
+        "complete_task" => complete_task(args).await,
+        "update_priority" => update_priority(args).await,
+        _ => return None,
+    };
+    Some(result.map(|value| value.to_string()))
+}
+
+async fn list_tasks(args: Value) -> Result<Value, ToolError> {
+    let args = arguments::<ListTasksArgs>(args)?;
+    let tasks = get_tasks(TaskQuery {
+        statuses: args.status.map(|status| vec![status]),
+        search: args.search,
+        order: Some(TaskOrder::StatusFirst),
+        ..Default::default()
+    })
+    .await?;
+    Ok(json!(tasks))
+}
+
+async fn add_task(args: Value) -> Result<Value, ToolError> {
+    let args = arguments::<AddTaskArgs>(args)?;
+    Ok(json!(create_task(args.description).await?))
+}
+
+async fn complete_task(args: Value) -> Result<Value, ToolError> {
+    let args = arguments::<CompleteTaskArgs>(args)?;
+    let tasks = set_tasks_status(vec![args.id], Status::Done).await?;
+    let task = tasks.first().ok_or(ToolError::NotFound(args.id))?;
+    Ok(json!(task))
+}
+
+async fn update_priority(args: Value) -> Result<Value, ToolError> {
+    let args = arguments::<UpdatePriorityArgs>(args)?;
+    let task = match get_task(args.id).await {
+        Err(ServerError::Database(sqlx::Error::RowNotFound)) => {
+            return Err(ToolError::NotFound(args.id));
+        }
+        task => task?,
+    };
+    let task = update_task(
+        task.id,
+        task.description.to_string(),
+        task.status,
+        args.priority,
+    )
+    .await?;
+    Ok(json!(task))
+}
+
+fn error(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+/// Handles a JSON-RPC message and returns the response to write back, `None` for notifications.
+pub async fn handle(message: &str) -> Option<Value> {
+    let request = match serde_json::from_str::<Request>(message) {
+        Ok(request) => request,
+        Err(parse_error) => return Some(error(Value::Null, PARSE_ERROR, parse_error.to_string())),
+    };
+    let id = request.id?;
+    let result = match request.method.as_str() {
+        "initialize" => json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "my-todos", "version": env!("CARGO_PKG_VERSION") },
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tools() }),
+        "tools/call" => {
+            let call = match serde_json::from_value::<ToolCall>(request.params) {
+                Ok(call) => call,
+                Err(params_error) => {
+                    return Some(error(id, INVALID_PARAMS, params_error.to_string()));
+                }
+            };
+            let Some(result) = call_tool(&call.name, call.arguments).await else {
+                return Some(error(
+                    id,
+                    INVALID_PARAMS,
+                    format!("unknown tool {}", call.name),
+                ));
+            };
+            let (text, is_error) = match result {
+                Ok(text) => (text, false),
+                Err(tool_error) => (tool_error.message(), true),
+            };
+            json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": is_error,
+            })
+        }
+        method => {
+            return Some(error(
+                id,
+                METHOD_NOT_FOUND,
+                format!("unknown method {method}"),
+            ));
+        }
+    };
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
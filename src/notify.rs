@@ -0,0 +1,85 @@
+//! Desktop notifications, shown with the native API of each platform when built with the
+//! `notifications` feature. Each category can be muted in the settings.
+
+use std::fmt;
+use std::str::FromStr;
+
+use tracing::warn;
+
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Category {
+    /// A synchronization with another service failed.
+    SyncFailure,
+    /// A task was added from outside the GUI, through the HTTP, gRPC or D-Bus APIs.
+    ExternalTask,
+}
+
+impl Category {
+    pub const ALL: [Category; 2] = [Category::SyncFailure, Category::ExternalTask];
+
+    fn setting_key(&self) -> String {
+        format!("notifications.muted.{self}")
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Category::SyncFailure => write!(f, "sync-failure"),
+            Category::ExternalTask => write!(f, "external-task"),
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Category::ALL
+            .into_iter()
+            .find(|category| category.to_string() == s)
+            .ok_or_else(|| format!("unknown notification category: {s}"))
+    }
+}
+
+pub async fn is_muted(category: Category) -> Result<bool, ServerError> {
+    Ok(get_setting(&category.setting_key()).await?.as_deref() == Some("true"))
+}
+
+pub async fn set_muted(category: Category, muted: bool) -> Result<(), ServerError> {
+    set_setting(&category.setting_key(), muted.to_string()).await
+}
+
+/// Shows a notification unless its category is muted. Failures are only logged, notifications
+/// are never worth interrupting what triggered them, which can also spawn this instead of
+/// waiting for it.
+pub async fn notify(category: Category, summary: String, body: String) {
+    match is_muted(category).await {
+        Ok(true) => return,
+        Ok(false) => (),
+        Err(error) => warn!(%error, "can't read the notification settings"),
+    }
+    show(summary, body).await;
+}
+
+#[cfg(feature = "notifications")]
+async fn show(summary: String, body: String) {
+    let shown = tokio::task::spawn_blocking(move || {
+        notify_rust::Notification::new()
+            .appname("Todos")
+            .summary(&summary)
+            .body(&body)
+            .show()
+            .map(|_| ())
+    })
+    .await;
+    if let Ok(Err(error)) = shown {
+        warn!(%error, "can't show the notification");
+    }
+}
+
+#[cfg(not(feature = "notifications"))]
+async fn show(_summary: String, _body: String) {}
@@ -0,0 +1,80 @@
+//! Named profiles, each with its own database and so its own settings, so that the people sharing
+//! a machine keep separate lists. A profile is a directory of `profiles/` in the data directory,
+//! the default profile being the database of the data directory itself.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use thiserror::Error;
+
+use crate::database::{data_dir, set_database_path};
+
+/// Name of the profile using the database of the data directory.
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("invalid profile name {0:?}, only letters, digits, - and _ are allowed")]
+    InvalidName(String),
+    #[error("couldn't create the profile: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Profile given to `select`, unless it's the default one.
+static SELECTED: OnceLock<String> = OnceLock::new();
+
+fn profiles_dir() -> PathBuf {
+    data_dir().join("profiles")
+}
+
+/// Checks that `name` can be used as a directory name on every platform.
+pub fn validate(name: &str) -> Result<(), ProfileError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(ProfileError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Names of the created profiles in alphabetical order, without the default one.
+pub fn list() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(profiles_dir()) else {
+        return Vec::new();
+    };
+    let mut profiles = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            entry.file_type().ok()?.is_dir().then_some(())?;
+            entry.file_name().into_string().ok()
+        })
+        .filter(|name| validate(name).is_ok())
+        .collect::<Vec<_>>();
+    profiles.sort();
+    profiles
+}
+
+/// Opens the database of the profile `name` instead of the default one, creating the profile if
+/// missing. Like `set_database_path`, must be called before the first query, and is ignored after
+/// it.
+pub fn select(name: &str) -> Result<(), ProfileError> {
+    validate(name)?;
+    if name == DEFAULT_PROFILE {
+        return Ok(());
+    }
+    let dir = profiles_dir().join(name);
+    fs::create_dir_all(&dir)?;
+    set_database_path(dir.join("Todos.db"));
+    let _ = SELECTED.set(name.to_string());
+    Ok(())
+}
+
+/// Whether the default profile is opened. The files out of the database, like the logs, the crash
+/// report and the credentials, are shared by all the profiles and only belong to the default one.
+pub fn is_default() -> bool {
+    SELECTED.get().is_none()
+}
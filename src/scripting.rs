@@ -0,0 +1,326 @@
+//! Automation scripts written in Rhai, like:
+//!
+//! ```rhai
+//! weekly("monday", "08:00", || {
+//!     create_task("Take out the trash");
+//!     create_task("Water the plants");
+//! });
+//! ```
+//!
+//! The script is kept in the settings and edited from the Scripts panel. `run` runs its top level
+//! once, then calls back the functions given to the timers `every`, `daily` and `weekly`. The
+//! script starts over when it is edited.
+//!
+//! Tasks are maps with `id`, `description`, `status` (`"ToDo"`, `"InProgress"` or `"Done"`) and
+//! `priority` (`"Low"`, `"Medium"` or `"High"`), queried with `tasks()` or `tasks(search)`, and
+//! changed with `create_task(description)`, `update_task(id, changes)` and `complete_task(id)`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeDelta, TimeZone, Weekday};
+use rhai::{AST, Array, Dynamic, Engine, EvalAltResult, FnPtr, Map, ParseError};
+use thiserror::Error;
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use crate::core::ServerError;
+use crate::database::{TaskQuery, get_setting, get_task, get_tasks, set_setting};
+use crate::repository::Database;
+use crate::service::{Command, execute, execute_one};
+use crate::{Priority, Status, Task};
+
+const SCRIPT_SETTING: &str = "scripting.script";
+const TICK: Duration = Duration::from_secs(1);
+const ORIGIN: &str = "script";
+
+#[derive(Debug, Error)]
+pub enum ScriptError {
+    #[error("the script doesn't compile: {0}")]
+    Parse(#[from] ParseError),
+    #[error("the script failed: {0}")]
+    Eval(#[from] Box<EvalAltResult>),
+}
+
+pub async fn script() -> Result<Option<String>, ServerError> {
+    get_setting(SCRIPT_SETTING).await
+}
+
+pub async fn set_script(source: String) -> Result<(), ServerError> {
+    set_setting(SCRIPT_SETTING, source).await
+}
+
+/// Checks that the script compiles, without running it.
+pub fn check(source: &str) -> Result<(), ScriptError> {
+    Engine::new().compile(source)?;
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Schedule {
+    Every(TimeDelta),
+    Daily(NaiveTime),
+    Weekly(Weekday, NaiveTime),
+}
+
+impl Schedule {
+    /// The first time strictly after `now`.
+    fn next_after(&self, now: DateTime<Local>) -> DateTime<Local> {
+        let (weekday, time) = match *self {
+            Schedule::Every(interval) => return now + interval,
+            Schedule::Daily(time) => (None, time),
+            Schedule::Weekly(weekday, time) => (Some(weekday), time),
+        };
+        (0..=7)
+            .filter_map(|days| {
+                let date = now.date_naive() + TimeDelta::days(days);
+                let on_weekday = weekday.is_none_or(|weekday| date.weekday() == weekday);
+                on_weekday
+                    .then(|| Local.from_local_datetime(&date.and_time(time)).earliest())
+                    .flatten()
+            })
+            .find(|next| *next > now)
+            .unwrap_or(now + TimeDelta::days(1))
+    }
+}
+
+struct Timer {
+    schedule: Schedule,
+    callback: FnPtr,
+    next: DateTime<Local>,
+}
+
+type Timers = Rc<RefCell<Vec<Timer>>>;
+
+fn runtime_error(error: impl ToString) -> Box<EvalAltResult> {
+    error.to_string().into()
+}
+
+fn status(name: &str) -> Result<Status, Box<EvalAltResult>> {
+    match name {
+        "ToDo" => Ok(Status::ToDo),
+        "InProgress" => Ok(Status::InProgress),
+        "Done" => Ok(Status::Done),
+        _ => Err(runtime_error(format!("unknown status {name}"))),
+    }
+}
+
+fn priority(name: &str) -> Result<Priority, Box<EvalAltResult>> {
+    match name {
+        "Low" => Ok(Priority::Low),
+        "Medium" => Ok(Priority::Medium),
+        "High" => Ok(Priority::High),
+        _ => Err(runtime_error(format!("unknown priority {name}"))),
+    }
+}
+
+fn task_map(task: Task) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("id".into(), task.id.into());
+    map.insert("description".into(), task.description.to_string().into());
+    map.insert("status".into(), format!("{:?}", task.status).into());
+    map.insert("priority".into(), format!("{:?}", task.priority).into());
+    map.into()
+}
+
+fn time(time: &str) -> Result<NaiveTime, Box<EvalAltResult>> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| runtime_error(format!("invalid time {time}, expected like 08:30")))
+}
+
+fn add_timer(timers: &Timers, schedule: Schedule, callback: FnPtr) {
+    timers.borrow_mut().push(Timer {
+        schedule,
+        callback,
+        next: schedule.next_after(Local::now()),
+    });
+}
+
+/// Engine with the task API and the timers, which are added to `timers`.
+fn engine(handle: Handle, timers: Timers) -> Engine {
+    let mut engine = Engine::new();
+
+    let tasks_handle = handle.clone();
+    let search_handle = handle.clone();
+    engine
+        .register_fn("tasks", move || -> Result<Array, Box<EvalAltResult>> {
+            let tasks = tasks_handle.block_on(get_tasks(TaskQuery::default()));
+            Ok(tasks
+                .map_err(runtime_error)?
+                .into_iter()
+                .map(task_map)
+                .collect())
+        })
+        .register_fn(
+            "tasks",
+            move |search: &str| -> Result<Array, Box<EvalAltResult>> {
+                let query = TaskQuery {
+                    search: Some(search.to_string()),
+                    ..TaskQuery::default()
+                };
+                let tasks = search_handle.block_on(get_tasks(query));
+                Ok(tasks
+                    .map_err(runtime_error)?
+                    .into_iter()
+                    .map(task_map)
+                    .collect())
+            },
+        );
+
+    let create_handle = handle.clone();
+    let update_handle = handle.clone();
+    let complete_handle = handle;
+    engine
+        .register_fn("create_task", move |description: &str| {
+            let command = Command::CreateTask {
+                description: description.to_string(),
+                priority: Priority::default(),
+            };
+            let task = create_handle.block_on(execute_one::<Database>(ORIGIN, command));
+            task.map(task_map).map_err(runtime_error)
+        })
+        .register_fn(
+            "update_task",
+            move |id: i64, changes: Map| -> Result<Dynamic, Box<EvalAltResult>> {
+                let task = update_handle
+                    .block_on(get_task(id))
+                    .map_err(runtime_error)?;
+                let text = |key: &str| -> Result<Option<String>, Box<EvalAltResult>> {
+                    changes
+                        .get(key)
+                        .map(|value| value.clone().into_string().map_err(runtime_error))
+                        .transpose()
+                };
+                let description =
+                    text("description")?.unwrap_or_else(|| task.description.to_string());
+                let status = match text("status")? {
+                    Some(name) => status(&name)?,
+                    None => task.status,
+                };
+                let priority = match text("priority")? {
+                    Some(name) => priority(&name)?,
+                    None => task.priority,
+                };
+                let command = Command::UpdateTask {
+                    id,
+                    description,
+                    status,
+                    priority,
+                };
+                let task = update_handle.block_on(execute_one::<Database>(ORIGIN, command));
+                task.map(task_map).map_err(runtime_error)
+            },
+        )
+        .register_fn("complete_task", move |id: i64| {
+            let command = Command::ChangeStatus {
+                ids: vec![id],
+                status: Status::Done,
+            };
+            let events = complete_handle.block_on(execute::<Database>(ORIGIN, command));
+            events.map(|_| ()).map_err(runtime_error)
+        });
+
+    let every_timers = timers.clone();
+    let daily_timers = timers.clone();
+    let weekly_timers = timers;
+    engine
+        .register_fn(
+            "every",
+            move |seconds: i64, callback: FnPtr| -> Result<(), Box<EvalAltResult>> {
+                if seconds <= 0 {
+                    return Err(runtime_error("timers run at least every second"));
+                }
+                let schedule = Schedule::Every(TimeDelta::seconds(seconds));
+                add_timer(&every_timers, schedule, callback);
+                Ok(())
+            },
+        )
+        .register_fn(
+            "daily",
+            move |at: &str, callback: FnPtr| -> Result<(), Box<EvalAltResult>> {
+                add_timer(&daily_timers, Schedule::Daily(time(at)?), callback);
+                Ok(())
+            },
+        )
+        .register_fn(
+            "weekly",
+            move |day: &str, at: &str, callback: FnPtr| -> Result<(), Box<EvalAltResult>> {
+                let weekday = day
+                    .parse::<Weekday>()
+                    .map_err(|_| runtime_error(format!("unknown day {day}")))?;
+                add_timer(
+                    &weekly_timers,
+                    Schedule::Weekly(weekday, time(at)?),
+                    callback,
+                );
+                Ok(())
+            },
+        );
+    engine
+}
+
+/// A started script, with its timers.
+struct Script {
+    engine: Engine,
+    ast: AST,
+    timers: Timers,
+}
+
+impl Script {
+    fn start(handle: Handle, source: &str) -> Result<Self, ScriptError> {
+        let timers = Timers::default();
+        let engine = engine(handle, timers.clone());
+        let ast = engine.compile(source)?;
+        engine.run_ast(&ast)?;
+        Ok(Self {
+            engine,
+            ast,
+            timers,
+        })
+    }
+
+    fn fire_due_timers(&self) {
+        let now = Local::now();
+        // The callbacks may add timers, so they are called once the timers are released.
+        let due = self
+            .timers
+            .borrow_mut()
+            .iter_mut()
+            .filter(|timer| timer.next <= now)
+            .map(|timer| {
+                timer.next = timer.schedule.next_after(now);
+                timer.callback.clone()
+            })
+            .collect::<Vec<_>>();
+        for callback in due {
+            if let Err(error) = callback.call::<Dynamic>(&self.engine, &self.ast, ()) {
+                warn!(%error, "a script timer failed");
+            }
+        }
+    }
+}
+
+/// Runs the script, starting it over when it is edited, until the settings can't be read.
+///
+/// Blocks the current thread, which must not be a thread of a Tokio runtime.
+pub fn run() -> Result<(), ServerError> {
+    let runtime = tokio::runtime::Runtime::new().expect("can't start the scripting runtime");
+    let mut source = None;
+    let mut script = None;
+    loop {
+        let current = runtime.block_on(self::script())?;
+        if current != source {
+            source = current;
+            script = source.as_deref().and_then(|source| {
+                Script::start(runtime.handle().clone(), source)
+                    .inspect_err(|error| warn!(%error, "the script didn't start"))
+                    .ok()
+            });
+        }
+        if let Some(script) = &script {
+            script.fire_due_timers();
+        }
+        std::thread::sleep(TICK);
+    }
+}
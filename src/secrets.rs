@@ -0,0 +1,203 @@
+//! Credentials of the integrations, like the IMAP password or the Google Tasks refresh token, kept
+//! in the keychain of the OS (Secret Service on Linux, Credential Manager on Windows, Keychain on
+//! macOS) instead of in plain text in the settings or `config.toml`.
+//!
+//! Where no keychain can be reached, like on a server without a session bus, they are kept in
+//! `secrets.txt` next to the database instead, each encrypted with ChaCha20-Poly1305 under a key
+//! derived by Argon2 from `TODOS_SECRETS_PASSPHRASE`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use keyring::Entry;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::database::data_dir;
+
+const PASSPHRASE_VAR: &str = "TODOS_SECRETS_PASSPHRASE";
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum SecretsError {
+    #[error("keychain error: {0}")]
+    Keyring(#[from] keyring::Error),
+    #[error("no keychain is available and {PASSPHRASE_VAR} isn't set")]
+    NoPassphrase,
+    #[error("couldn't access the secrets file: {0}")]
+    Io(#[from] io::Error),
+    #[error("the secrets file is corrupted or {PASSPHRASE_VAR} changed")]
+    Corrupted,
+}
+
+/// File holding the secrets when no keychain is available.
+pub fn file_path() -> PathBuf {
+    data_dir().join("secrets.txt")
+}
+
+/// Whether the keychain can't be reached at all, rather than not having the secret.
+fn unavailable(error: &keyring::Error) -> bool {
+    matches!(
+        error,
+        keyring::Error::NoStorageAccess(_) | keyring::Error::PlatformFailure(_)
+    )
+}
+
+/// The secret of `user` for `service`, `None` if it was never saved.
+pub fn get(service: &str, user: &str) -> Result<Option<String>, SecretsError> {
+    match Entry::new(service, user).and_then(|entry| entry.get_password()) {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) if unavailable(&error) => {
+            warn!(%error, "no keychain, reading {service} from the secrets file");
+            SecretsFile::read()?.get(service, user)
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Saves the secret of `user` for `service`, replacing the previous one.
+pub fn set(service: &str, user: &str, secret: &str) -> Result<(), SecretsError> {
+    match Entry::new(service, user).and_then(|entry| entry.set_password(secret)) {
+        Ok(()) => Ok(()),
+        Err(error) if unavailable(&error) => {
+            warn!(%error, "no keychain, writing {service} to the secrets file");
+            let mut file = SecretsFile::read()?;
+            file.set(service, user, secret)?;
+            file.write()
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Forgets the secret of `user` for `service`, if any.
+pub fn delete(service: &str, user: &str) -> Result<(), SecretsError> {
+    match Entry::new(service, user).and_then(|entry| entry.delete_credential()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) if unavailable(&error) => {
+            let mut file = SecretsFile::read()?;
+            file.entries.remove(&entry_name(service, user));
+            file.write()
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn entry_name(service: &str, user: &str) -> String {
+    format!("{service}/{user}")
+}
+
+/// The fallback file: the salt of the key on the first line, then a line per secret with its
+/// name, its nonce and its ciphertext in hexadecimal. The name is authenticated with the secret,
+/// so that secrets can't be swapped.
+struct SecretsFile {
+    key: Key,
+    salt: [u8; SALT_LENGTH],
+    /// Name to nonce followed by the ciphertext.
+    entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl SecretsFile {
+    fn read() -> Result<Self, SecretsError> {
+        let passphrase = std::env::var(PASSPHRASE_VAR).map_err(|_| SecretsError::NoPassphrase)?;
+        let text = match fs::read_to_string(file_path()) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+        let mut lines = text.lines();
+        let salt = match lines.next() {
+            Some(salt) => from_hex(salt)
+                .and_then(|salt| salt.try_into().ok())
+                .ok_or(SecretsError::Corrupted)?,
+            None => {
+                let mut salt = [0; SALT_LENGTH];
+                OsRng.fill_bytes(&mut salt);
+                salt
+            }
+        };
+        let mut entries = BTreeMap::new();
+        for line in lines {
+            let (name, sealed) = line.split_once(' ').ok_or(SecretsError::Corrupted)?;
+            let name = from_hex(name)
+                .and_then(|name| String::from_utf8(name).ok())
+                .ok_or(SecretsError::Corrupted)?;
+            let sealed = from_hex(sealed)
+                .filter(|sealed| sealed.len() > NONCE_LENGTH)
+                .ok_or(SecretsError::Corrupted)?;
+            entries.insert(name, sealed);
+        }
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|_| SecretsError::Corrupted)?;
+        Ok(Self { key, salt, entries })
+    }
+
+    fn get(&self, service: &str, user: &str) -> Result<Option<String>, SecretsError> {
+        let name = entry_name(service, user);
+        let Some(sealed) = self.entries.get(&name) else {
+            return Ok(None);
+        };
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: name.as_bytes(),
+        };
+        let secret = ChaCha20Poly1305::new(&self.key)
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| SecretsError::Corrupted)?;
+        String::from_utf8(secret)
+            .map(Some)
+            .map_err(|_| SecretsError::Corrupted)
+    }
+
+    fn set(&mut self, service: &str, user: &str, secret: &str) -> Result<(), SecretsError> {
+        let name = entry_name(service, user);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: secret.as_bytes(),
+            aad: name.as_bytes(),
+        };
+        let ciphertext = ChaCha20Poly1305::new(&self.key)
+            .encrypt(&nonce, payload)
+            .map_err(|_| SecretsError::Corrupted)?;
+        self.entries
+            .insert(name, [nonce.as_slice(), &ciphertext].concat());
+        Ok(())
+    }
+
+    fn write(&self) -> Result<(), SecretsError> {
+        let mut text = to_hex(&self.salt);
+        for (name, sealed) in &self.entries {
+            let _ = write!(text, "\n{} {}", to_hex(name.as_bytes()), to_hex(sealed));
+        }
+        fs::write(file_path(), text)?;
+        Ok(())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
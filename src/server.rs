@@ -0,0 +1,246 @@
+//! HTTP API over the `database` functions and the `service` commands, so that scripts and other
+//! devices can read and add tasks while the GUI runs.
+//!
+//! Setting `TODOS_API_TOKEN` requires every request to send it as `Authorization: Bearer`, which
+//! is needed to serve on other addresses than the loopback ones. Without it, only local scripts
+//! are answered: requests from web pages, which send an `Origin`, and requests naming another
+//! host, like a rebound DNS name, are refused. The bodies are read as JSON only, so a page can't
+//! send them as a form.
+//!
+//! `POST /inbox` is a quick capture endpoint for automation tools like Zapier or iOS Shortcuts,
+//! enabled by setting `TODOS_INBOX_TOKEN` to the token they send as `Authorization: Bearer`.
+
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+
+use axum::extract::{Path, Query, Request};
+use axum::http::header::{AUTHORIZATION, HOST, ORIGIN as ORIGIN_HEADER};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::{Next, from_fn};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use sqlx::Error as SqlxError;
+
+use crate::core::{ServerError, StatusCounts};
+use crate::database::{TaskOrder, TaskQuery, count_by_status, get_task, get_tasks};
+use crate::notify::{Category, notify};
+use crate::repository::Database;
+use crate::service::{Command, execute, execute_one};
+use crate::{Priority, Status, Task};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:3000";
+const API_TOKEN_VAR: &str = "TODOS_API_TOKEN";
+const INBOX_TOKEN_VAR: &str = "TODOS_INBOX_TOKEN";
+const ORIGIN: &str = "server";
+
+/// A `ServerError` answered with the matching HTTP status.
+struct ApiError(ServerError);
+
+impl From<ServerError> for ApiError {
+    fn from(error: ServerError) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            ServerError::Database(SqlxError::RowNotFound) => StatusCode::NOT_FOUND,
+            ServerError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ServerError::Timeout | ServerError::ConnectionLost(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            ServerError::ReadOnly => StatusCode::FORBIDDEN,
+        };
+        (status, self.0.to_string()).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct ListParams {
+    status: Option<Status>,
+    search: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateBody {
+    description: String,
+}
+
+/// Body of `POST /inbox`. Tasks have no due date yet, so a `due` field is ignored.
+#[derive(Deserialize)]
+struct InboxBody {
+    text: String,
+    #[serde(default)]
+    priority: Priority,
+}
+
+#[derive(Deserialize)]
+struct UpdateBody {
+    description: String,
+    status: Status,
+    priority: Priority,
+}
+
+async fn list(Query(params): Query<ListParams>) -> Result<Json<Vec<Task>>, ApiError> {
+    let query = TaskQuery {
+        statuses: params.status.map(|status| vec![status]),
+        search: params.search,
+        order: Some(TaskOrder::StatusFirst),
+        ..Default::default()
+    };
+    Ok(Json(get_tasks(query).await?))
+}
+
+async fn create(Json(body): Json<CreateBody>) -> Result<(StatusCode, Json<Task>), ApiError> {
+    let command = Command::CreateTask {
+        description: body.description,
+        priority: Priority::default(),
+    };
+    let task = execute_one::<Database>(ORIGIN, command).await?;
+    tokio::spawn(notify(
+        Category::ExternalTask,
+        "Task added".to_string(),
+        task.description.to_string(),
+    ));
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+async fn inbox(Json(body): Json<InboxBody>) -> Result<(StatusCode, Json<Task>), Response> {
+    let text = body.text.trim().to_string();
+    if text.is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "the text is empty").into_response());
+    }
+    let command = Command::CreateTask {
+        description: text,
+        priority: body.priority,
+    };
+    let task = execute_one::<Database>(ORIGIN, command)
+        .await
+        .map_err(|error| ApiError(error).into_response())?;
+    tokio::spawn(notify(
+        Category::ExternalTask,
+        "Task added".to_string(),
+        task.description.to_string(),
+    ));
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+async fn read(Path(id): Path<i64>) -> Result<Json<Task>, ApiError> {
+    Ok(Json(get_task(id).await?))
+}
+
+async fn update(Path(id): Path<i64>, Json(body): Json<UpdateBody>) -> Result<Json<Task>, ApiError> {
+    let command = Command::UpdateTask {
+        id,
+        description: body.description,
+        status: body.status,
+        priority: body.priority,
+    };
+    let task = execute_one::<Database>(ORIGIN, command).await?;
+    Ok(Json(task))
+}
+
+async fn delete(Path(id): Path<i64>) -> Result<StatusCode, ApiError> {
+    execute::<Database>(ORIGIN, Command::DeleteTask(id)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn counts() -> Result<Json<StatusCounts>, ApiError> {
+    Ok(Json(count_by_status().await?))
+}
+
+/// The token set in `var`, if any.
+fn token(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|token| !token.is_empty())
+}
+
+/// The token sent as `Authorization: Bearer`.
+fn bearer(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Compares the tokens in a time that doesn't depend on where they differ, so that a client can't
+/// guess a token byte by byte.
+fn tokens_match(sent: &str, token: &str) -> bool {
+    sent.len() == token.len()
+        && sent
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Whether the request names a loopback host, as local scripts do.
+fn is_local(headers: &HeaderMap) -> bool {
+    let Some(host) = headers.get(HOST).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|byte| byte.is_ascii_digit()) => host,
+        _ => host,
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Answers the requests sending `TODOS_API_TOKEN`, or the local ones if it isn't set.
+async fn require_api_token(request: Request, next: Next) -> Response {
+    let headers = request.headers();
+    match token(API_TOKEN_VAR) {
+        Some(token) => {
+            if !bearer(headers).is_some_and(|sent| tokens_match(sent, &token)) {
+                return (StatusCode::UNAUTHORIZED, "invalid API token").into_response();
+            }
+        }
+        None => {
+            if headers.contains_key(ORIGIN_HEADER) || !is_local(headers) {
+                return (StatusCode::FORBIDDEN, "only local scripts can use the API")
+                    .into_response();
+            }
+        }
+    }
+    next.run(request).await
+}
+
+/// Answers the requests sending `TODOS_INBOX_TOKEN`, the inbox being disabled if it isn't set.
+async fn require_inbox_token(request: Request, next: Next) -> Response {
+    let Some(token) = token(INBOX_TOKEN_VAR) else {
+        return (StatusCode::NOT_FOUND, "the inbox is disabled").into_response();
+    };
+    if !bearer(request.headers()).is_some_and(|sent| tokens_match(sent, &token)) {
+        return (StatusCode::UNAUTHORIZED, "invalid inbox token").into_response();
+    }
+    next.run(request).await
+}
+
+/// Every route is behind a token check, the inbox having its own token so that the automation
+/// tools can't use the rest of the API.
+pub fn router() -> Router {
+    let inbox = Router::new()
+        .route("/inbox", post(inbox))
+        .route_layer(from_fn(require_inbox_token));
+    Router::new()
+        .route("/tasks", get(list).post(create))
+        .route("/tasks/{id}", get(read).put(update).delete(delete))
+        .route("/counts", get(counts))
+        .route_layer(from_fn(require_api_token))
+        .merge(inbox)
+}
+
+/// Serves the API until the process exits. Without `TODOS_API_TOKEN`, only the loopback addresses
+/// can be served.
+pub async fn serve(addr: SocketAddr) -> std::io::Result<()> {
+    if !addr.ip().is_loopback() && token(API_TOKEN_VAR).is_none() {
+        return Err(std::io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!("serving on {addr} requires setting {API_TOKEN_VAR}"),
+        ));
+    }
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router()).await
+}
@@ -0,0 +1,270 @@
+//! Application service between the frontends and the storage. The frontends send a `Command` to
+//! `execute`, and whatever reacts to the changes of the tasks subscribes to the resulting `Event`s
+//! instead of wrapping the storage calls: the task list, the audit log, the webhooks and the
+//! synchronization.
+//!
+//! The changes made by other processes, like `todos-cli` or a second window, are published by
+//! `watch` once found in the database.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use sqlx::Error as SqlxError;
+use tokio::sync::RwLock;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::broadcast::{self, Receiver, Sender};
+use tracing::{info, warn};
+
+use crate::core::ServerError;
+use crate::database::TaskWatcher;
+use crate::repository::TaskRepository;
+use crate::{Priority, Status, Task};
+
+/// Origin of the events published by `watch`.
+pub const EXTERNAL: &str = "another process";
+/// Number of events kept for the slow subscribers, which miss the older ones past it.
+const CAPACITY: usize = 4096;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+static EVENTS: LazyLock<Sender<(&'static str, Event)>> =
+    LazyLock::new(|| broadcast::channel(CAPACITY).0);
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+/// Held shared by the commands from their write to the publication of their events, and
+/// exclusively by `watch` while it reads the changes, so that it never finds a change of a command
+/// before its events.
+static WRITES: LazyLock<RwLock<()>> = LazyLock::new(|| RwLock::new(()));
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    CreateTask {
+        description: String,
+        priority: Priority,
+    },
+    /// Creates the tasks at the end of the user order, in a single transaction.
+    CreateTasks(Vec<(String, Priority)>),
+    UpdateTask {
+        id: i64,
+        description: String,
+        status: Status,
+        priority: Priority,
+    },
+    UpdateTasks(Vec<(i64, String, Status, Priority)>),
+    ChangeStatus {
+        ids: Vec<i64>,
+        status: Status,
+    },
+    /// Stores the order chosen by the user, the ids being sorted in the new order.
+    Reorder(Vec<i64>),
+    /// Sets the value of a custom field of the task, removing it when `None`.
+    SetFieldValue {
+        task_id: i64,
+        field_id: i64,
+        value: Option<String>,
+    },
+    DeleteTask(i64),
+    DeleteTasks(Vec<i64>),
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    TaskCreated(Task),
+    TaskUpdated(Task),
+    TaskDeleted(i64),
+}
+
+impl Event {
+    /// Id of the task concerned.
+    pub fn id(&self) -> i64 {
+        match self {
+            Event::TaskCreated(task) | Event::TaskUpdated(task) => task.id,
+            Event::TaskDeleted(id) => *id,
+        }
+    }
+
+    /// The task created or updated, `None` for a deletion.
+    pub fn into_task(self) -> Option<Task> {
+        match self {
+            Event::TaskCreated(task) | Event::TaskUpdated(task) => Some(task),
+            Event::TaskDeleted(_) => None,
+        }
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::TaskCreated(task) => write!(f, "created task {}: {}", task.id, task.description),
+            Event::TaskUpdated(task) => write!(
+                f,
+                "updated task {}: {} ({}, {})",
+                task.id, task.description, task.status, task.priority
+            ),
+            Event::TaskDeleted(id) => write!(f, "deleted task {id}"),
+        }
+    }
+}
+
+/// Receives the events published from now on, with the origin of their command like `"server"`.
+pub fn subscribe() -> Receiver<(&'static str, Event)> {
+    EVENTS.subscribe()
+}
+
+fn publish(origin: &'static str, event: Event) {
+    // Sending only fails when nothing is subscribed.
+    let _ = EVENTS.send((origin, event));
+}
+
+/// Makes `execute` refuse the commands with `ServerError::ReadOnly`, or accept them again, whichever
+/// frontend of the process sends them.
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Runs the command on the repository `R`, then publishes the resulting events and returns them.
+pub async fn execute<R: TaskRepository>(
+    origin: &'static str,
+    command: Command,
+) -> Result<Vec<Event>, ServerError> {
+    if is_read_only() {
+        return Err(ServerError::ReadOnly);
+    }
+    let _writing = WRITES.read().await;
+    let events = match command {
+        Command::CreateTask {
+            description,
+            priority,
+        } => {
+            let task = R::create(description, priority).await?;
+            vec![Event::TaskCreated(task)]
+        }
+        Command::CreateTasks(new_tasks) => R::create_many(new_tasks)
+            .await?
+            .into_iter()
+            .map(Event::TaskCreated)
+            .collect(),
+        Command::UpdateTask {
+            id,
+            description,
+            status,
+            priority,
+        } => vec![Event::TaskUpdated(
+            R::update(id, description, status, priority).await?,
+        )],
+        Command::UpdateTasks(updates) => R::update_many(updates)
+            .await?
+            .into_iter()
+            .map(Event::TaskUpdated)
+            .collect(),
+        Command::ChangeStatus { ids, status } => R::set_status_many(ids, status)
+            .await?
+            .into_iter()
+            .map(Event::TaskUpdated)
+            .collect(),
+        Command::Reorder(ids) => R::reorder(ids)
+            .await?
+            .into_iter()
+            .map(Event::TaskUpdated)
+            .collect(),
+        Command::SetFieldValue {
+            task_id,
+            field_id,
+            value,
+        } => vec![Event::TaskUpdated(
+            R::set_field(task_id, field_id, value).await?,
+        )],
+        Command::DeleteTask(id) => vec![Event::TaskDeleted(R::delete(id).await?)],
+        Command::DeleteTasks(ids) => R::delete_many(ids)
+            .await?
+            .into_iter()
+            .map(Event::TaskDeleted)
+            .collect(),
+    };
+    for event in &events {
+        publish(origin, event.clone());
+    }
+    Ok(events)
+}
+
+/// Runs a `CreateTask` or `UpdateTask` command like `execute`, and returns the task. Fails with
+/// `RowNotFound` if the command changed no task.
+pub async fn execute_one<R: TaskRepository>(
+    origin: &'static str,
+    command: Command,
+) -> Result<Task, ServerError> {
+    let events = execute::<R>(origin, command).await?;
+    let task = events.into_iter().find_map(Event::into_task);
+    task.ok_or(ServerError::Database(SqlxError::RowNotFound))
+}
+
+/// Publishes the changes made by other processes. The changes published by `execute` aren't
+/// published again. A failed read is logged and tried again on the next poll, so that a lost
+/// connection doesn't stop it once the pool is rebuilt.
+pub async fn watch() {
+    let mut published = subscribe();
+    let mut watcher = loop {
+        match TaskWatcher::new().await {
+            Ok(watcher) => break watcher,
+            Err(error) => {
+                warn!(%error, "can't follow the changes of other processes yet");
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    };
+    let mut ids = watcher.ids().collect::<HashSet<_>>();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let _writes = WRITES.write().await;
+        loop {
+            match published.try_recv() {
+                Ok((_, Event::TaskCreated(task) | Event::TaskUpdated(task))) => {
+                    ids.insert(task.id);
+                    watcher.mark_known(&task);
+                }
+                Ok((_, Event::TaskDeleted(id))) => {
+                    ids.remove(&id);
+                    watcher.forget(id);
+                }
+                // The missed changes are published again, which is better than not at all.
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        let (changed, deleted) = match watcher.changes().await {
+            Ok(changes) => changes,
+            Err(error) => {
+                warn!(%error, "can't read the changes of other processes");
+                continue;
+            }
+        };
+        for task in changed {
+            if ids.insert(task.id) {
+                publish(EXTERNAL, Event::TaskCreated(task));
+            } else {
+                publish(EXTERNAL, Event::TaskUpdated(task));
+            }
+        }
+        for id in deleted {
+            ids.remove(&id);
+            publish(EXTERNAL, Event::TaskDeleted(id));
+        }
+    }
+}
+
+/// Logs every event with its origin, as a trail of who changed what.
+pub async fn audit() {
+    let mut events = subscribe();
+    loop {
+        match events.recv().await {
+            Ok((origin, event)) => info!(target: "todos::audit", origin, "{event}"),
+            Err(RecvError::Lagged(skipped)) => warn!(skipped, "the audit log missed events"),
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Spell checking of the task descriptions with Hunspell dictionaries, off until a dictionary is
+//! chosen in the settings.
+//!
+//! A dictionary is a pair of `<locale>.aff` and `<locale>.dic` files, like `en_US.aff`, looked for
+//! in `TODOS_DICTIONARIES` and then in the directories where the systems install them.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::{env, fs};
+
+use spellbook::Dictionary;
+use thiserror::Error;
+use tokio::task::spawn_blocking;
+
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+
+const LOCALE_SETTING: &str = "spellcheck.locale";
+/// Suggestions shown for each misspelled word.
+const MAX_SUGGESTIONS: usize = 3;
+const SYSTEM_DIRECTORIES: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell/dicts",
+    "/usr/share/myspell",
+    "/Library/Spelling",
+];
+
+/// The dictionary last loaded, with its locale, since parsing one takes a while.
+static LOADED: Mutex<Option<(String, Arc<SpellChecker>)>> = Mutex::new(None);
+
+#[derive(Debug, Error)]
+pub enum SpellcheckError {
+    #[error("no dictionary for {0}")]
+    NotFound(String),
+    #[error("can't read the dictionary: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid dictionary: {0}")]
+    Invalid(String),
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+/// Directories searched for the dictionaries, in order.
+pub fn dictionary_directories() -> Vec<PathBuf> {
+    let custom = env::var_os("TODOS_DICTIONARIES").map(PathBuf::from);
+    custom
+        .into_iter()
+        .chain(SYSTEM_DIRECTORIES.iter().map(PathBuf::from))
+        .collect()
+}
+
+/// Locales of the dictionaries found, sorted.
+pub fn available_locales() -> Vec<String> {
+    let mut locales = dictionary_directories()
+        .into_iter()
+        .filter_map(|directory| fs::read_dir(&directory).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let locale = path.file_stem()?.to_str()?.to_string();
+            let has_affixes = path.with_extension("aff").is_file();
+            (path.extension()? == "dic" && has_affixes).then_some(locale)
+        })
+        .collect::<Vec<_>>();
+    locales.sort();
+    locales.dedup();
+    locales
+}
+
+/// Locale of the dictionary checking the descriptions, `None` when off.
+pub async fn locale() -> Result<Option<String>, ServerError> {
+    Ok(get_setting(LOCALE_SETTING)
+        .await?
+        .filter(|locale| !locale.is_empty()))
+}
+
+pub async fn set_locale(locale: Option<String>) -> Result<(), ServerError> {
+    set_setting(LOCALE_SETTING, locale.unwrap_or_default()).await
+}
+
+/// Words of `text` with their byte offset, the apostrophes inside the words being kept, like in
+/// "don't".
+pub fn words(text: &str) -> Vec<(usize, &str)> {
+    let is_apostrophe = |c: char| c == '\'' || c == '’';
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match start {
+            None if c.is_alphabetic() => start = Some(index),
+            Some(word_start) if !c.is_alphabetic() && !is_apostrophe(c) => {
+                let word = text[word_start..index].trim_end_matches(is_apostrophe);
+                words.push((word_start, word));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    words
+}
+
+/// `text` with the word at `start` replaced by `replacement`.
+pub fn replace_word(text: &str, start: usize, word: &str, replacement: &str) -> String {
+    let mut text = text.to_string();
+    text.replace_range(start..start + word.len(), replacement);
+    text
+}
+
+/// A word of the description not found in the dictionary.
+#[derive(Clone, Debug)]
+pub struct Misspelling {
+    /// Byte offset of the word in the description.
+    pub start: usize,
+    pub word: String,
+    pub suggestions: Vec<String>,
+}
+
+pub struct SpellChecker {
+    dictionary: Dictionary,
+}
+
+impl std::fmt::Debug for SpellChecker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpellChecker").finish_non_exhaustive()
+    }
+}
+
+impl SpellChecker {
+    /// Loads the dictionary of `locale` from the first directory having it.
+    pub fn load(locale: &str) -> Result<Self, SpellcheckError> {
+        let directory = dictionary_directories()
+            .into_iter()
+            .find(|directory| directory.join(format!("{locale}.dic")).is_file())
+            .ok_or_else(|| SpellcheckError::NotFound(locale.to_string()))?;
+        let affixes = fs::read_to_string(directory.join(format!("{locale}.aff")))?;
+        let words = fs::read_to_string(directory.join(format!("{locale}.dic")))?;
+        let dictionary = Dictionary::new(&affixes, &words)
+            .map_err(|error| SpellcheckError::Invalid(error.to_string()))?;
+        Ok(Self { dictionary })
+    }
+
+    /// The misspelled words of `text`, in order.
+    pub fn check(&self, text: &str) -> Vec<Misspelling> {
+        words(text)
+            .into_iter()
+            .filter(|(_, word)| !self.dictionary.check(word))
+            .map(|(start, word)| {
+                let mut suggestions = Vec::new();
+                self.dictionary.suggest(word, &mut suggestions);
+                suggestions.truncate(MAX_SUGGESTIONS);
+                Misspelling {
+                    start,
+                    word: word.to_string(),
+                    suggestions,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The misspelled words of `text` with the dictionary chosen in the settings, none when off.
+pub async fn check(text: String) -> Result<Vec<Misspelling>, SpellcheckError> {
+    let Some(locale) = locale().await? else {
+        return Ok(Vec::new());
+    };
+    let loaded = LOADED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|(loaded, _)| *loaded == locale)
+        .map(|(_, checker)| checker.clone());
+    let checker = match loaded {
+        Some(checker) => checker,
+        None => {
+            let load_locale = locale.clone();
+            let checker = spawn_blocking(move || SpellChecker::load(&load_locale))
+                .await
+                .map_err(|error| SpellcheckError::Io(std::io::Error::other(error)))??;
+            let checker = Arc::new(checker);
+            *LOADED.lock().unwrap() = Some((locale, checker.clone()));
+            checker
+        }
+    };
+    Ok(checker.check(&text))
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "caldav")]
+pub mod caldav;
+#[cfg(feature = "google-tasks")]
+pub mod google_tasks;
+
+/// What a synchronization changed.
+#[derive(Default, Clone, Debug)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub deleted_locally: usize,
+    pub deleted_remotely: usize,
+    /// Tasks changed on both sides, the server version was kept.
+    pub conflicts: Vec<i64>,
+}
+
+impl SyncReport {
+    pub fn changed_locally(&self) -> bool {
+        self.pulled > 0 || self.deleted_locally > 0
+    }
+}
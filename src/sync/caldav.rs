@@ -0,0 +1,606 @@
+//! Two-way synchronization of the tasks with the VTODOs of a CalDAV collection (Nextcloud,
+//! Fastmail...).
+//!
+//! Each synchronized task is linked to a VTODO by a `SyncEntry` remembering the ETag of the VTODO
+//! and the `updated_at` of the task at the last synchronization, so that changes on both sides can
+//! be told apart. When both sides changed, the server wins and the task is reported as a conflict.
+//! Tasks have no due dates yet, so `DUE` is left untouched.
+
+use std::env;
+
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Client, Method, StatusCode, Url};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::core::ServerError;
+use crate::database::{
+    SyncEntry, TaskQuery, create_task, delete_sync_entry, delete_task, get_sync_entries, get_tasks,
+    set_sync_entry, update_task,
+};
+use crate::secrets;
+use crate::sync::SyncReport;
+use crate::{Priority, Status, Task};
+
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data/>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VTODO"/>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#;
+
+const SECRETS_SERVICE: &str = "my-todos-caldav";
+
+#[derive(Clone, Debug)]
+pub struct CalDavConfig {
+    /// URL of the collection holding the VTODOs, ending with a `/`.
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl CalDavConfig {
+    /// Reads `TODOS_CALDAV_URL`, `TODOS_CALDAV_USERNAME` and `TODOS_CALDAV_PASSWORD`, the
+    /// password saved with `save_password` being used if the latter isn't set. Returns `None` if
+    /// the URL isn't set.
+    pub fn from_env() -> Option<Self> {
+        let mut url = env::var("TODOS_CALDAV_URL").ok()?;
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        let username = env::var("TODOS_CALDAV_USERNAME").unwrap_or_default();
+        let password = env::var("TODOS_CALDAV_PASSWORD").unwrap_or_else(|_| {
+            secrets::get(SECRETS_SERVICE, &username)
+                .inspect_err(|error| warn!(%error, "can't read the CalDAV password"))
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+        });
+        Some(Self {
+            url,
+            username,
+            password,
+        })
+    }
+
+    /// Saves the password of `username` in the keychain, see `secrets`.
+    pub fn save_password(username: &str, password: &str) -> Result<(), secrets::SecretsError> {
+        secrets::set(SECRETS_SERVICE, username, password)
+    }
+
+    /// Deletes the password of `username` from the keychain.
+    pub fn forget_password(username: &str) -> Result<(), secrets::SecretsError> {
+        secrets::delete(SECRETS_SERVICE, username)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("couldn't reach the CalDAV server: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("invalid CalDAV URL: {0}")]
+    Url(#[from] url::ParseError),
+    #[error("the CalDAV server answered {0}")]
+    Status(StatusCode),
+    #[error("invalid CalDAV response: {0}")]
+    InvalidResponse(String),
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+/// The fields of a VTODO that are mapped to a task.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Vtodo {
+    pub uid: String,
+    pub summary: String,
+    pub status: Status,
+    pub priority: Priority,
+}
+
+impl Vtodo {
+    pub fn from_task(task: &Task, uid: String) -> Self {
+        Self {
+            uid,
+            summary: task.description.to_string(),
+            status: task.status,
+            priority: task.priority,
+        }
+    }
+
+    /// Serializes the VTODO into an iCalendar object, `dtstamp` being a UTC date-time like
+    /// `20261015T120000Z`.
+    pub fn to_ical(&self, dtstamp: &str) -> String {
+        let status = match self.status {
+            Status::ToDo => "NEEDS-ACTION",
+            Status::InProgress => "IN-PROCESS",
+            Status::Done => "COMPLETED",
+        };
+        let priority = match self.priority {
+            Priority::High => 1,
+            Priority::Medium => 5,
+            Priority::Low => 9,
+        };
+        [
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//my-todos//todos//EN".to_string(),
+            "BEGIN:VTODO".to_string(),
+            format!("UID:{}", escape(&self.uid)),
+            format!("DTSTAMP:{dtstamp}"),
+            format!("SUMMARY:{}", escape(&self.summary)),
+            format!("STATUS:{status}"),
+            format!("PRIORITY:{priority}"),
+            "END:VTODO".to_string(),
+            "END:VCALENDAR".to_string(),
+        ]
+        .iter()
+        .map(|line| fold(line))
+        .collect()
+    }
+
+    /// Reads the first VTODO of an iCalendar object.
+    pub fn parse(ical: &str) -> Option<Self> {
+        let mut in_vtodo = false;
+        let mut uid = None;
+        let mut summary = String::new();
+        let mut status = Status::ToDo;
+        let mut priority = Priority::Low;
+        for line in unfold(ical) {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let name = name.split(';').next().unwrap_or(name);
+            match (in_vtodo, name) {
+                (false, "BEGIN") if value == "VTODO" => in_vtodo = true,
+                (true, "END") if value == "VTODO" => break,
+                (true, "UID") => uid = Some(unescape(value)),
+                (true, "SUMMARY") => summary = unescape(value),
+                (true, "STATUS") => {
+                    status = match value {
+                        "COMPLETED" | "CANCELLED" => Status::Done,
+                        "IN-PROCESS" => Status::InProgress,
+                        _ => Status::ToDo,
+                    }
+                }
+                (true, "PRIORITY") => {
+                    priority = match value.trim().parse::<u8>() {
+                        Ok(1..=4) => Priority::High,
+                        Ok(5) => Priority::Medium,
+                        _ => Priority::Low,
+                    }
+                }
+                _ => (),
+            }
+        }
+        Some(Self {
+            uid: uid?,
+            summary,
+            status,
+            priority,
+        })
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => (),
+        }
+    }
+    unescaped
+}
+
+/// Splits the line into lines of at most 75 octets, as required by iCalendar.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn unfold(ical: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ical.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Turns an SQLite timestamp (`2026-10-15 12:00:00`, UTC) into an iCalendar UTC date-time.
+fn ical_timestamp(updated_at: &str) -> String {
+    let digits = updated_at
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    match digits.len() {
+        14 => format!("{}T{}Z", &digits[..8], &digits[8..]),
+        _ => "19700101T000000Z".to_string(),
+    }
+}
+
+/// Contents of the elements named `name`, whatever their namespace prefix, the CDATA sections
+/// being skipped. This is enough for the multistatus responses of CalDAV servers.
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut contents = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(cdata) = rest.strip_prefix("![CDATA[") {
+            let Some(end) = cdata.find("]]>") else {
+                break;
+            };
+            rest = &cdata[end + 3..];
+            continue;
+        }
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..tag_end];
+        let tag_name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        if tag.starts_with('/') || tag_name.rsplit(':').next() != Some(name) {
+            continue;
+        }
+        rest = &rest[tag_end + 1..];
+        if tag.ends_with('/') {
+            contents.push("");
+            continue;
+        }
+        let close = format!("</{tag_name}>");
+        let Some(end) = rest.find(&close) else {
+            break;
+        };
+        contents.push(&rest[..end]);
+        rest = &rest[end + close.len()..];
+    }
+    contents
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// Text of an element, its CDATA sections taken as they are and the rest unescaped.
+fn xml_text(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("<![CDATA[") {
+        text.push_str(&xml_unescape(&rest[..start]));
+        rest = &rest[start + "<![CDATA[".len()..];
+        let end = rest.find("]]>").unwrap_or(rest.len());
+        text.push_str(&rest[..end]);
+        rest = rest.get(end + 3..).unwrap_or_default();
+    }
+    text.push_str(&xml_unescape(rest));
+    text
+}
+
+/// A VTODO of the collection.
+#[derive(Clone, Debug)]
+pub struct RemoteTodo {
+    /// Absolute URL of the VTODO.
+    pub href: String,
+    pub etag: String,
+    pub vtodo: Vtodo,
+}
+
+/// Reads the VTODOs of the multistatus answer to `CALENDAR_QUERY`, their hrefs made absolute
+/// with `base`. An entry that can't be read fails the whole listing, since the VTODOs missing
+/// from it would be taken as deleted.
+pub fn parse_multistatus(xml: &str, base: &Url) -> Result<Vec<RemoteTodo>, SyncError> {
+    let invalid = |reason: String| SyncError::InvalidResponse(reason);
+    elements(xml, "response")
+        .into_iter()
+        .map(|response| {
+            if elements(response, "status")
+                .iter()
+                .any(|status| status.contains(" 507 "))
+            {
+                return Err(invalid("the server truncated the listing".to_string()));
+            }
+            let href = elements(response, "href")
+                .first()
+                .map(|href| xml_text(href).trim().to_string())
+                .filter(|href| !href.is_empty())
+                .ok_or_else(|| invalid("an entry has no href".to_string()))?;
+            let href = base.join(&href)?.to_string();
+            let etag = elements(response, "getetag")
+                .first()
+                .map(|etag| xml_text(etag).trim().to_string())
+                .filter(|etag| !etag.is_empty())
+                .ok_or_else(|| invalid(format!("{href} has no ETag")))?;
+            let ical = elements(response, "calendar-data")
+                .first()
+                .map(|ical| xml_text(ical))
+                .ok_or_else(|| invalid(format!("{href} has no calendar data")))?;
+            let vtodo =
+                Vtodo::parse(&ical).ok_or_else(|| invalid(format!("{href} isn't a VTODO")))?;
+            Ok(RemoteTodo { href, etag, vtodo })
+        })
+        .collect()
+}
+
+/// The synchronized fields of the task, telling apart the changes made in the second of the last
+/// synchronization, which `updated_at` can't.
+fn synced_content(task: &Task) -> String {
+    format!(
+        "{}\n{}\n{}",
+        task.status as i32, task.priority as i32, task.description
+    )
+}
+
+/// Whether the task changed since it was last synchronized. The entries saved before the content
+/// was can only be compared by `updated_at`.
+fn changed_since(task: &Task, entry: &SyncEntry) -> bool {
+    task.updated_at != entry.synced_updated_at
+        || (!entry.synced_content.is_empty() && synced_content(task) != entry.synced_content)
+}
+
+struct CalDavClient {
+    client: Client,
+    config: CalDavConfig,
+    base: Url,
+}
+
+impl CalDavClient {
+    fn new(config: CalDavConfig) -> Result<Self, SyncError> {
+        let base = Url::parse(&config.url)?;
+        Ok(Self {
+            client: Client::new(),
+            config,
+            base,
+        })
+    }
+
+    /// Absolute URL of the href, which may be relative to the collection.
+    fn url(&self, href: &str) -> Result<String, SyncError> {
+        Ok(self.base.join(href)?.to_string())
+    }
+
+    fn request(&self, method: Method, href: &str) -> Result<reqwest::RequestBuilder, SyncError> {
+        let url = self.base.join(href)?;
+        Ok(self
+            .client
+            .request(method, url)
+            .basic_auth(&self.config.username, Some(&self.config.password)))
+    }
+
+    async fn list(&self) -> Result<Vec<RemoteTodo>, SyncError> {
+        let response = self
+            .request(Method::from_bytes(b"REPORT").unwrap(), "")?
+            .header("Depth", "1")
+            .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+            .body(CALENDAR_QUERY)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(SyncError::Status(response.status()));
+        }
+        let body = response.text().await?;
+        parse_multistatus(&body, &self.base)
+    }
+
+    /// Whether the VTODO is still on the server, for the ones missing from the listing.
+    async fn exists(&self, href: &str) -> Result<bool, SyncError> {
+        let response = self.request(Method::HEAD, href)?.send().await?;
+        match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::GONE => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => Err(SyncError::Status(status)),
+        }
+    }
+
+    /// Stores the VTODO, only if its ETag is still `etag`, or if it doesn't exist yet when `etag`
+    /// is `None`. Returns the new ETag, empty if the server didn't send it, or `None` if the
+    /// VTODO changed in the meantime.
+    async fn put(
+        &self,
+        href: &str,
+        vtodo: &Vtodo,
+        dtstamp: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<String>, SyncError> {
+        let request = self
+            .request(Method::PUT, href)?
+            .header(CONTENT_TYPE, "text/calendar; charset=utf-8")
+            .body(vtodo.to_ical(dtstamp));
+        let request = match etag {
+            Some(etag) => request.header(IF_MATCH, etag),
+            None => request.header(IF_NONE_MATCH, "*"),
+        };
+        let response = request.send().await?;
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => Ok(None),
+            status if status.is_success() => Ok(Some(
+                response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string(),
+            )),
+            status => Err(SyncError::Status(status)),
+        }
+    }
+
+    /// Deletes the VTODO if its ETag is still `etag`. Returns `false` if it changed.
+    async fn delete(&self, href: &str, etag: &str) -> Result<bool, SyncError> {
+        let response = self
+            .request(Method::DELETE, href)?
+            .header(IF_MATCH, etag)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => Ok(false),
+            status if status.is_success() || status == StatusCode::NOT_FOUND => Ok(true),
+            status => Err(SyncError::Status(status)),
+        }
+    }
+}
+
+/// Applies the VTODO to the task, or creates a task if there is none.
+async fn pull(task_id: Option<i64>, remote: &RemoteTodo) -> Result<SyncEntry, ServerError> {
+    let task_id = match task_id {
+        Some(task_id) => task_id,
+        None => create_task(remote.vtodo.summary.clone()).await?.id,
+    };
+    let vtodo = &remote.vtodo;
+    let task = update_task(task_id, vtodo.summary.clone(), vtodo.status, vtodo.priority).await?;
+    Ok(SyncEntry {
+        task_id,
+        href: remote.href.clone(),
+        uid: vtodo.uid.clone(),
+        etag: remote.etag.clone(),
+        synced_content: synced_content(&task),
+        synced_updated_at: task.updated_at,
+    })
+}
+
+/// Synchronizes all the tasks with the collection.
+pub async fn sync(config: CalDavConfig) -> Result<SyncReport, SyncError> {
+    let client = CalDavClient::new(config)?;
+    let mut remote = client.list().await?;
+    let mut tasks = get_tasks(TaskQuery::default()).await?;
+    let mut report = SyncReport::default();
+
+    for entry in get_sync_entries().await? {
+        let task_index = tasks.iter().position(|task| task.id == entry.task_id);
+        let task = task_index.map(|index| tasks.remove(index));
+        let href = client.url(&entry.href)?;
+        let remote_index = remote.iter().position(|todo| todo.href == href);
+        let remote_todo = remote_index.map(|index| remote.remove(index));
+        match (task, remote_todo) {
+            (None, None) => delete_sync_entry(entry.task_id).await?,
+            (None, Some(remote_todo)) => {
+                // Deleted locally, the VTODO is only deleted if it didn't change since.
+                if !client.delete(&remote_todo.href, &entry.etag).await? {
+                    set_sync_entry(pull(None, &remote_todo).await?).await?;
+                    report.conflicts.push(entry.task_id);
+                } else {
+                    report.deleted_remotely += 1;
+                }
+                delete_sync_entry(entry.task_id).await?;
+            }
+            (Some(task), None) => {
+                if client.exists(&href).await? {
+                    // Missing from a partial listing only, the task is synchronized next time.
+                    warn!(%href, "the VTODO is missing from the listing");
+                } else if !changed_since(&task, &entry) {
+                    delete_task(task.id).await?;
+                    delete_sync_entry(task.id).await?;
+                    report.deleted_locally += 1;
+                } else {
+                    // Deleted remotely but changed locally, the task is pushed again.
+                    tasks.push(task);
+                    delete_sync_entry(entry.task_id).await?;
+                }
+            }
+            (Some(task), Some(remote_todo)) => {
+                let local_changed = changed_since(&task, &entry);
+                let remote_changed = remote_todo.etag != entry.etag;
+                match (local_changed, remote_changed) {
+                    (false, false) => (),
+                    (true, false) => {
+                        let vtodo = Vtodo::from_task(&task, entry.uid.clone());
+                        let dtstamp = ical_timestamp(&task.updated_at);
+                        let etag = client
+                            .put(&entry.href, &vtodo, &dtstamp, Some(&entry.etag))
+                            .await?;
+                        match etag {
+                            Some(etag) => {
+                                set_sync_entry(SyncEntry {
+                                    href,
+                                    etag,
+                                    synced_content: synced_content(&task),
+                                    synced_updated_at: task.updated_at,
+                                    ..entry
+                                })
+                                .await?;
+                                report.pushed += 1;
+                            }
+                            None => {
+                                // Changed on the server in the meantime, it is pulled on the
+                                // next synchronization.
+                                report.conflicts.push(task.id);
+                            }
+                        }
+                    }
+                    (false, true) => {
+                        set_sync_entry(pull(Some(task.id), &remote_todo).await?).await?;
+                        report.pulled += 1;
+                    }
+                    (true, true) => {
+                        set_sync_entry(pull(Some(task.id), &remote_todo).await?).await?;
+                        report.conflicts.push(task.id);
+                    }
+                }
+            }
+        }
+    }
+
+    // Tasks that were never synchronized.
+    for task in tasks {
+        let uid = format!("todos-{}@my-todos", task.id);
+        let href = format!("todos-{}.ics", task.id);
+        let vtodo = Vtodo::from_task(&task, uid.clone());
+        let dtstamp = ical_timestamp(&task.updated_at);
+        if let Some(etag) = client.put(&href, &vtodo, &dtstamp, None).await? {
+            set_sync_entry(SyncEntry {
+                task_id: task.id,
+                href: client.url(&href)?,
+                uid,
+                etag,
+                synced_content: synced_content(&task),
+                synced_updated_at: task.updated_at,
+            })
+            .await?;
+            report.pushed += 1;
+        }
+    }
+
+    // VTODOs that were never synchronized.
+    for remote_todo in remote {
+        set_sync_entry(pull(None, &remote_todo).await?).await?;
+        report.pulled += 1;
+    }
+    Ok(report)
+}
@@ -0,0 +1,447 @@
+//! Two-way synchronization of the tasks with Google Tasks lists.
+//!
+//! Access is granted once with OAuth: `authorize_url` gives the consent page, whose code is
+//! exchanged by `authorize` for a refresh token kept with `secrets`. The lists to synchronize are
+//! also kept in the settings, see `set_lists`, tasks created locally being pushed to the first one.
+//!
+//! Like `sync::caldav`, each synchronized task is linked to a Google task by a `GoogleTasksEntry`
+//! remembering its ETag and the `updated_at` of the task, and the server wins conflicts. Google
+//! Tasks only knows if a task is completed and has no priorities, so a task in progress stays in
+//! progress until it is completed, and priorities are kept locally. Tasks have no due dates yet,
+//! so `due` is left untouched.
+
+use std::env;
+
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::ServerError;
+use crate::database::{
+    GoogleTasksEntry, TaskQuery, create_task, delete_google_tasks_entry, delete_task,
+    get_google_tasks_entries, get_setting, get_task, get_tasks, set_google_tasks_entry,
+    set_setting, update_task,
+};
+use crate::secrets::{self, SecretsError};
+use crate::sync::SyncReport;
+use crate::{Status, Task};
+
+const AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const API_URL: &str = "https://tasks.googleapis.com/tasks/v1";
+const SCOPE: &str = "https://www.googleapis.com/auth/tasks";
+
+const SECRETS_SERVICE: &str = "my-todos-google-tasks";
+/// Where the refresh token was kept before `secrets`, moved out by `refresh_token`.
+const REFRESH_TOKEN_SETTING: &str = "google_tasks.refresh_token";
+const LISTS_SETTING: &str = "google_tasks.lists";
+/// The list Google Tasks creates for every account.
+const DEFAULT_LIST: &str = "@default";
+
+/// OAuth client of the application, created in the Google Cloud console as a desktop app.
+#[derive(Clone, Debug)]
+pub struct GoogleTasksConfig {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+impl GoogleTasksConfig {
+    /// Reads `TODOS_GOOGLE_CLIENT_ID` and `TODOS_GOOGLE_CLIENT_SECRET`. Returns `None` if the
+    /// client id isn't set.
+    pub fn from_env() -> Option<Self> {
+        Some(Self {
+            client_id: env::var("TODOS_GOOGLE_CLIENT_ID").ok()?,
+            client_secret: env::var("TODOS_GOOGLE_CLIENT_SECRET").unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("couldn't reach Google Tasks: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Google Tasks answered {0}")]
+    Status(StatusCode),
+    #[error("Google Tasks access wasn't granted yet")]
+    NotAuthorized,
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error(transparent)]
+    Server(#[from] ServerError),
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TaskList {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RemoteTask {
+    id: String,
+    etag: String,
+    #[serde(default)]
+    title: String,
+    status: String,
+    #[serde(default)]
+    deleted: bool,
+}
+
+#[derive(Serialize)]
+struct TaskBody<'a> {
+    title: &'a str,
+    status: &'static str,
+}
+
+impl<'a> TaskBody<'a> {
+    fn from_task(task: &'a Task) -> Self {
+        let status = match task.status {
+            Status::Done => "completed",
+            Status::ToDo | Status::InProgress => "needsAction",
+        };
+        Self {
+            title: &task.description,
+            status,
+        }
+    }
+}
+
+/// URL of the consent page, which redirects to `redirect_uri` with a `code` to give to
+/// `authorize`.
+pub fn authorize_url(config: &GoogleTasksConfig, redirect_uri: &str) -> String {
+    Url::parse_with_params(
+        AUTH_URL,
+        [
+            ("client_id", config.client_id.as_str()),
+            ("redirect_uri", redirect_uri),
+            ("response_type", "code"),
+            ("scope", SCOPE),
+            ("access_type", "offline"),
+            ("prompt", "consent"),
+        ],
+    )
+    .expect("the authorization URL is valid")
+    .to_string()
+}
+
+/// Exchanges the code given by the consent page for a refresh token, kept with `secrets`.
+pub async fn authorize(
+    config: &GoogleTasksConfig,
+    code: &str,
+    redirect_uri: &str,
+) -> Result<(), SyncError> {
+    let response = Client::new()
+        .post(TOKEN_URL)
+        .form(&[
+            ("client_id", config.client_id.as_str()),
+            ("client_secret", config.client_secret.as_str()),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("grant_type", "authorization_code"),
+        ])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(SyncError::Status(response.status()));
+    }
+    let token = response.json::<TokenResponse>().await?;
+    let refresh_token = token.refresh_token.ok_or(SyncError::NotAuthorized)?;
+    secrets::set(SECRETS_SERVICE, &config.client_id, &refresh_token)?;
+    Ok(())
+}
+
+/// Deletes the refresh token of the client from the keychain, access having to be granted again.
+pub fn forget(config: &GoogleTasksConfig) -> Result<(), SecretsError> {
+    secrets::delete(SECRETS_SERVICE, &config.client_id)
+}
+
+/// The refresh token of the client, moving the one kept in the settings by the previous versions
+/// to `secrets`.
+async fn refresh_token(config: &GoogleTasksConfig) -> Result<String, SyncError> {
+    if let Some(refresh_token) = secrets::get(SECRETS_SERVICE, &config.client_id)? {
+        return Ok(refresh_token);
+    }
+    let refresh_token = get_setting(REFRESH_TOKEN_SETTING)
+        .await?
+        .filter(|refresh_token| !refresh_token.is_empty())
+        .ok_or(SyncError::NotAuthorized)?;
+    secrets::set(SECRETS_SERVICE, &config.client_id, &refresh_token)?;
+    set_setting(REFRESH_TOKEN_SETTING, String::new()).await?;
+    Ok(refresh_token)
+}
+
+/// Ids of the lists to synchronize, only the default list if none were chosen.
+pub async fn lists() -> Result<Vec<String>, ServerError> {
+    let lists = get_setting(LISTS_SETTING)
+        .await?
+        .map(|lists| {
+            lists
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|lists| !lists.is_empty())
+        .unwrap_or_else(|| vec![DEFAULT_LIST.to_string()]);
+    Ok(lists)
+}
+
+/// Chooses the lists to synchronize, tasks created locally are pushed to the first one.
+pub async fn set_lists(ids: Vec<String>) -> Result<(), ServerError> {
+    set_setting(LISTS_SETTING, ids.join(",")).await
+}
+
+struct GoogleTasksClient {
+    client: Client,
+    access_token: String,
+}
+
+impl GoogleTasksClient {
+    /// Gets an access token with the refresh token kept with `secrets`.
+    async fn connect(config: &GoogleTasksConfig) -> Result<Self, SyncError> {
+        let refresh_token = refresh_token(config).await?;
+        let client = Client::new();
+        let response = client
+            .post(TOKEN_URL)
+            .form(&[
+                ("client_id", config.client_id.as_str()),
+                ("client_secret", config.client_secret.as_str()),
+                ("refresh_token", refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+            ])
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED => Err(SyncError::NotAuthorized),
+            status if !status.is_success() => Err(SyncError::Status(status)),
+            _ => {
+                let token = response.json::<TokenResponse>().await?;
+                Ok(Self {
+                    client,
+                    access_token: token.access_token,
+                })
+            }
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, SyncError> {
+        let response = request.bearer_auth(&self.access_token).send().await?;
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            StatusCode::UNAUTHORIZED => Err(SyncError::NotAuthorized),
+            status => Err(SyncError::Status(status)),
+        }
+    }
+
+    /// Fetches all the pages of a collection.
+    async fn pages<T>(&self, url: &str, query: &[(&str, &str)]) -> Result<Vec<T>, SyncError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let mut items = Vec::new();
+        let mut page_token = None;
+        loop {
+            let mut request = self
+                .client
+                .get(url)
+                .query(query)
+                .query(&[("maxResults", "100")]);
+            if let Some(page_token) = &page_token {
+                request = request.query(&[("pageToken", page_token)]);
+            }
+            let page = self.send(request).await?.json::<Page<T>>().await?;
+            items.extend(page.items);
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    async fn lists(&self) -> Result<Vec<TaskList>, SyncError> {
+        self.pages(&format!("{API_URL}/users/@me/lists"), &[]).await
+    }
+
+    async fn tasks(&self, list_id: &str) -> Result<Vec<RemoteTask>, SyncError> {
+        let url = format!("{API_URL}/lists/{list_id}/tasks");
+        let query = [("showCompleted", "true"), ("showHidden", "true")];
+        let mut tasks = self.pages::<RemoteTask>(&url, &query).await?;
+        tasks.retain(|task| !task.deleted);
+        Ok(tasks)
+    }
+
+    async fn insert(&self, list_id: &str, task: &Task) -> Result<RemoteTask, SyncError> {
+        let request = self
+            .client
+            .post(format!("{API_URL}/lists/{list_id}/tasks"))
+            .json(&TaskBody::from_task(task));
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    async fn patch(
+        &self,
+        list_id: &str,
+        remote_id: &str,
+        task: &Task,
+    ) -> Result<RemoteTask, SyncError> {
+        let request = self
+            .client
+            .patch(format!("{API_URL}/lists/{list_id}/tasks/{remote_id}"))
+            .json(&TaskBody::from_task(task));
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    async fn delete(&self, list_id: &str, remote_id: &str) -> Result<(), SyncError> {
+        let request = self
+            .client
+            .delete(format!("{API_URL}/lists/{list_id}/tasks/{remote_id}"));
+        self.send(request).await?;
+        Ok(())
+    }
+}
+
+/// Lists of the account, to choose the ones to synchronize.
+pub async fn available_lists(config: &GoogleTasksConfig) -> Result<Vec<TaskList>, SyncError> {
+    GoogleTasksClient::connect(config).await?.lists().await
+}
+
+/// Applies the Google task to the task, or creates a task if there is none.
+async fn pull(
+    task_id: Option<i64>,
+    list_id: &str,
+    remote: &RemoteTask,
+) -> Result<GoogleTasksEntry, ServerError> {
+    let task = match task_id {
+        Some(task_id) => get_task(task_id).await?,
+        None => create_task(remote.title.clone()).await?,
+    };
+    let status = match (remote.status.as_str(), task.status) {
+        ("completed", _) => Status::Done,
+        (_, Status::InProgress) => Status::InProgress,
+        _ => Status::ToDo,
+    };
+    let task = update_task(task.id, remote.title.clone(), status, task.priority).await?;
+    Ok(GoogleTasksEntry {
+        task_id: task.id,
+        list_id: list_id.to_string(),
+        remote_id: remote.id.clone(),
+        etag: remote.etag.clone(),
+        synced_updated_at: task.updated_at,
+    })
+}
+
+/// Synchronizes all the tasks with the chosen lists.
+pub async fn sync(config: GoogleTasksConfig) -> Result<SyncReport, SyncError> {
+    let client = GoogleTasksClient::connect(&config).await?;
+    let lists = lists().await?;
+    let entries = get_google_tasks_entries().await?;
+    let mut tasks = get_tasks(TaskQuery::default()).await?;
+    let mut report = SyncReport::default();
+
+    // Tasks of the lists that aren't synchronized anymore are left alone.
+    tasks.retain(|task| {
+        !entries
+            .iter()
+            .any(|entry| entry.task_id == task.id && !lists.contains(&entry.list_id))
+    });
+
+    for list_id in &lists {
+        let mut remote = client.tasks(list_id).await?;
+        for entry in entries.iter().filter(|entry| &entry.list_id == list_id) {
+            let task_index = tasks.iter().position(|task| task.id == entry.task_id);
+            let task = task_index.map(|index| tasks.remove(index));
+            let remote_index = remote.iter().position(|todo| todo.id == entry.remote_id);
+            let remote_task = remote_index.map(|index| remote.remove(index));
+            match (task, remote_task) {
+                (None, None) => delete_google_tasks_entry(entry.task_id).await?,
+                (None, Some(remote_task)) => {
+                    // Deleted locally, the Google task is only deleted if it didn't change since.
+                    if remote_task.etag == entry.etag {
+                        client.delete(list_id, &remote_task.id).await?;
+                        report.deleted_remotely += 1;
+                    } else {
+                        set_google_tasks_entry(pull(None, list_id, &remote_task).await?).await?;
+                        report.conflicts.push(entry.task_id);
+                    }
+                    delete_google_tasks_entry(entry.task_id).await?;
+                }
+                (Some(task), None) => {
+                    delete_google_tasks_entry(task.id).await?;
+                    if task.updated_at == entry.synced_updated_at {
+                        delete_task(task.id).await?;
+                        report.deleted_locally += 1;
+                    } else {
+                        // Deleted remotely but changed locally, the task is pushed again.
+                        tasks.push(task);
+                    }
+                }
+                (Some(task), Some(remote_task)) => {
+                    let local_changed = task.updated_at != entry.synced_updated_at;
+                    let remote_changed = remote_task.etag != entry.etag;
+                    match (local_changed, remote_changed) {
+                        (false, false) => (),
+                        (true, false) => {
+                            let remote_task = client.patch(list_id, &remote_task.id, &task).await?;
+                            set_google_tasks_entry(GoogleTasksEntry {
+                                etag: remote_task.etag,
+                                synced_updated_at: task.updated_at,
+                                ..entry.clone()
+                            })
+                            .await?;
+                            report.pushed += 1;
+                        }
+                        (false, true) => {
+                            let entry = pull(Some(task.id), list_id, &remote_task).await?;
+                            set_google_tasks_entry(entry).await?;
+                            report.pulled += 1;
+                        }
+                        (true, true) => {
+                            let entry = pull(Some(task.id), list_id, &remote_task).await?;
+                            set_google_tasks_entry(entry).await?;
+                            report.conflicts.push(task.id);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Google tasks that were never synchronized.
+        for remote_task in remote {
+            set_google_tasks_entry(pull(None, list_id, &remote_task).await?).await?;
+            report.pulled += 1;
+        }
+    }
+
+    // Tasks that were never synchronized.
+    let Some(list_id) = lists.first() else {
+        return Ok(report);
+    };
+    for task in tasks {
+        let remote_task = client.insert(list_id, &task).await?;
+        set_google_tasks_entry(GoogleTasksEntry {
+            task_id: task.id,
+            list_id: list_id.clone(),
+            remote_id: remote_task.id,
+            etag: remote_task.etag,
+            synced_updated_at: task.updated_at,
+        })
+        .await?;
+        report.pushed += 1;
+    }
+    Ok(report)
+}
@@ -0,0 +1,154 @@
+//! Anonymous usage telemetry, off until the user opts in from the settings. The reports sent to the
+//! configured endpoint hold the version and platform of the app, how many tasks each frontend or
+//! integration created, updated and deleted, and where the app last crashed. They never hold the
+//! tasks, the settings or anything identifying the user.
+//!
+//! `run` counts the events of the `service` and sends a report every `REPORT_INTERVAL`, the counts
+//! starting over once sent. `report` returns the next report, shown before opting in.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::{Value, json};
+use tokio::sync::broadcast::error::TryRecvError;
+use tracing::warn;
+
+use crate::core::ServerError;
+use crate::crash;
+use crate::database::{get_setting, set_setting};
+use crate::service::{Event, subscribe};
+
+const ENABLED_SETTING: &str = "telemetry.enabled";
+const ENDPOINT_SETTING: &str = "telemetry.endpoint";
+/// First line of the crash report already sent, so that a crash is reported once.
+const CRASH_SENT_SETTING: &str = "telemetry.crash_sent";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const REPORT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of events by origin and kind, like `"task list: created"`, since the last report.
+static COUNTS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+pub async fn is_enabled() -> Result<bool, ServerError> {
+    Ok(get_setting(ENABLED_SETTING).await?.as_deref() == Some("true"))
+}
+
+pub async fn set_enabled(enabled: bool) -> Result<(), ServerError> {
+    set_setting(ENABLED_SETTING, enabled.to_string()).await
+}
+
+/// URL receiving the reports, nothing being sent without it.
+pub async fn endpoint() -> Result<Option<String>, ServerError> {
+    Ok(get_setting(ENDPOINT_SETTING)
+        .await?
+        .filter(|endpoint| !endpoint.is_empty()))
+}
+
+pub async fn set_endpoint(endpoint: String) -> Result<(), ServerError> {
+    set_setting(ENDPOINT_SETTING, endpoint).await
+}
+
+/// Counts an event of the service, see `COUNTS`.
+pub fn record(origin: &str, event: &Event) {
+    let kind = match event {
+        Event::TaskCreated(_) => "created",
+        Event::TaskUpdated(_) => "updated",
+        Event::TaskDeleted(_) => "deleted",
+    };
+    *COUNTS
+        .lock()
+        .unwrap()
+        .entry(format!("{origin}: {kind}"))
+        .or_default() += 1;
+}
+
+/// Where the app panicked, like `src/app.rs:120:5`, without the message which may quote a task.
+pub fn crash_signature(report: &str) -> Option<String> {
+    let location = report
+        .lines()
+        .find_map(|line| line.split_once("panicked at "))?
+        .1;
+    Some(location.trim_end_matches(':').to_string())
+}
+
+/// The crash report not sent yet with its first line, which holds the time of the crash.
+async fn unsent_crash() -> Result<Option<(String, String)>, ServerError> {
+    let Some(report) = crash::last_report() else {
+        return Ok(None);
+    };
+    let first_line = report.lines().next().unwrap_or_default().to_string();
+    if get_setting(CRASH_SENT_SETTING).await?.as_deref() == Some(first_line.as_str()) {
+        return Ok(None);
+    }
+    Ok(Some((first_line, report)))
+}
+
+/// Exactly what the next report sends.
+pub async fn report() -> Result<Value, ServerError> {
+    let crash = unsent_crash()
+        .await?
+        .and_then(|(_, report)| crash_signature(&report));
+    Ok(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "usage": *COUNTS.lock().unwrap(),
+        "crash": crash,
+    }))
+}
+
+/// Sends the report if the user opted in, then starts the counts over.
+async fn send_report(client: &Client) -> Result<(), ServerError> {
+    let (true, Some(endpoint)) = (is_enabled().await?, endpoint().await?) else {
+        return Ok(());
+    };
+    let crash = unsent_crash().await?;
+    let report = report().await?;
+    let error = match client.post(&endpoint).json(&report).send().await {
+        Ok(response) if response.status().is_success() => {
+            // The events counted while sending are kept for the next report.
+            let mut counts = COUNTS.lock().unwrap();
+            for (key, sent) in report["usage"].as_object().into_iter().flatten() {
+                if let Some(count) = counts.get_mut(key) {
+                    *count -= sent.as_u64().unwrap_or_default().min(*count);
+                }
+            }
+            counts.retain(|_, count| *count > 0);
+            drop(counts);
+            if let Some((first_line, _)) = crash {
+                set_setting(CRASH_SENT_SETTING, first_line).await?;
+            }
+            return Ok(());
+        }
+        Ok(response) => format!("answered {}", response.status()),
+        Err(error) => error.to_string(),
+    };
+    warn!(%endpoint, %error, "can't send the telemetry report");
+    Ok(())
+}
+
+/// Counts the events and sends the reports until the database can't be read.
+pub async fn run() -> Result<(), ServerError> {
+    let client = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let mut events = subscribe();
+    let mut next_report = tokio::time::Instant::now();
+    loop {
+        loop {
+            match events.try_recv() {
+                Ok((origin, event)) => record(origin, &event),
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        if tokio::time::Instant::now() >= next_report {
+            send_report(&client).await?;
+            next_report += REPORT_INTERVAL;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
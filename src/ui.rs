@@ -1,4 +1,42 @@
+#[cfg(feature = "llm")]
+pub mod breakdown;
 pub mod component;
+pub mod connection;
+pub mod crash;
+pub mod custom_fields;
+pub mod diagnostics;
+pub mod direction;
+pub mod enter_key;
+pub mod erase;
+pub mod focus;
+pub mod habit;
+#[cfg(feature = "lock")]
+pub mod lock;
+pub mod logs;
 pub mod pending;
+pub mod picker;
+pub mod profile;
+pub mod read_only;
+pub mod review;
+#[cfg(feature = "scripting")]
+pub mod scripts;
+pub mod session;
+pub mod settings;
+pub mod skeleton;
+#[cfg(feature = "spellcheck")]
+pub mod spelling;
+pub mod start_view;
+#[cfg(feature = "llm")]
+pub mod suggestion;
+#[cfg(feature = "caldav")]
+pub mod sync;
 pub mod task_list;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
 pub mod theme;
+pub mod toast;
+#[cfg(feature = "voice")]
+pub mod voice;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
+pub mod xp;
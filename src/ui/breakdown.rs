@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{FlexExt, button, flex_col, flex_row, label, spinner, text_button, worker};
+
+use crate::llm::{LlmConfig, LlmError, break_down};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+/// "Break down" action of the selected task, proposing subtasks from the model configured by
+/// `TODOS_LLM_URL`. Nothing is shown if no model is configured.
+///
+/// Accepted subtasks are collected until `take_accepted`, so that the task list creates them.
+#[derive(Debug)]
+pub struct TaskBreakdown {
+    config: Option<LlmConfig>,
+    /// The task being broken down, with its description.
+    task: Option<(i64, Arc<str>)>,
+    loading: bool,
+    proposals: Vec<String>,
+    accepted: Vec<String>,
+    error: Option<String>,
+    sender: Option<UnboundedSender<(i64, Arc<str>)>>,
+}
+
+impl Default for TaskBreakdown {
+    fn default() -> Self {
+        Self {
+            config: LlmConfig::from_env(),
+            task: None,
+            loading: false,
+            proposals: Vec::new(),
+            accepted: Vec::new(),
+            error: None,
+            sender: None,
+        }
+    }
+}
+
+impl TaskBreakdown {
+    /// Follows the selected task, dropping the proposals made for another one.
+    pub fn set_task(&mut self, task: Option<(i64, Arc<str>)>) {
+        if self.task.as_ref().map(|(id, _)| *id) != task.as_ref().map(|(id, _)| *id) {
+            self.proposals.clear();
+            self.error = None;
+            self.loading = false;
+        }
+        self.task = task;
+    }
+
+    /// Returns the subtasks accepted since the last call.
+    pub fn take_accepted(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.accepted)
+    }
+
+    fn request(&mut self) {
+        if let (Some(sender), Some(task)) = (&self.sender, &self.task) {
+            self.loading = sender.send(task.clone()).is_ok();
+            self.error = None;
+        }
+    }
+
+    fn accept(&mut self, index: usize) {
+        if index < self.proposals.len() {
+            self.accepted.push(self.proposals.remove(index));
+        }
+    }
+
+    fn reject(&mut self, index: usize) {
+        if index < self.proposals.len() {
+            self.proposals.remove(index);
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let config = self.config.clone();
+        let content = (self.config.is_some() && self.task.is_some()).then(|| {
+            let action = if self.loading {
+                Either::A(button(spinner(), |_| ()))
+            } else {
+                Either::B(text_button("Break down", |state: &mut Self| {
+                    state.request()
+                }))
+            };
+            let proposals = self
+                .proposals
+                .iter()
+                .enumerate()
+                .map(|(index, proposal)| {
+                    flex_row((
+                        label(proposal.clone()).flex(1.),
+                        text_button("Accept", move |state: &mut Self| state.accept(index)),
+                        text_button("Reject", move |state: &mut Self| state.reject(index)),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            let hint = (!self.proposals.is_empty())
+                .then(|| label("Accepted subtasks are added as tasks").color(MUTED_TEXT_COLOR));
+            let error = self
+                .error
+                .clone()
+                .map(|error| label(error).color(DANGER_COLOR));
+            flex_col((flex_row((action, error)), flex_col(proposals), hint))
+        });
+        fork(
+            flex_col(content),
+            worker(
+                move |proxy, mut rx: UnboundedReceiver<(i64, Arc<str>)>| {
+                    let config = config.clone();
+                    async move {
+                        let Some(config) = config else {
+                            return;
+                        };
+                        while let Some((id, description)) = rx.recv().await {
+                            let result = break_down(&config, &description).await;
+                            let _ = proxy.message((id, result));
+                        }
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, (id, result): (i64, Result<Vec<String>, LlmError>)| {
+                    // The answer for a task that isn't selected anymore is dropped.
+                    if state.task.as_ref().map(|(task_id, _)| *task_id) != Some(id) {
+                        return;
+                    }
+                    state.loading = false;
+                    match result {
+                        Ok(proposals) => state.proposals = proposals,
+                        Err(error) => state.error = Some(error.to_string()),
+                    }
+                },
+            ),
+        )
+    }
+}
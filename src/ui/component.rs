@@ -1,6 +1,8 @@
+pub mod draggable;
 pub mod error;
 pub mod form;
 pub mod list;
+pub mod shortcut;
 
 pub use error::ErrorView;
 pub use form::Form;
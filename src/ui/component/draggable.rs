@@ -0,0 +1,203 @@
+//! Row of a list moved by dragging it, like the tasks in user order.
+
+use xilem::core::{Arg, MessageCtx, MessageResult, Mut, View, ViewArgument, ViewMarker};
+use xilem::masonry::accesskit::{Node, Role};
+use xilem::masonry::core::{
+    AccessCtx, ChildrenIds, EventCtx, LayoutCtx, MeasureCtx, NewWidget, PaintCtx,
+    PointerButtonEvent, PointerEvent, PointerUpdate, PropertiesMut, PropertiesRef, RegisterCtx,
+    Widget, WidgetMut, WidgetPod,
+};
+use xilem::masonry::kurbo::{Axis, Point, Size};
+use xilem::masonry::layout::LenReq;
+use xilem::masonry::vello::Scene;
+use xilem::{Pod, ViewCtx, WidgetView};
+
+/// Sent when a `Draggable` is dropped, with the number of rows it was dragged by, negative when
+/// dragged up.
+#[derive(Debug)]
+pub struct Dropped(pub isize);
+
+/// Widget laid out like its child, dragged vertically by a press that its child doesn't handle.
+pub struct Draggable {
+    child: WidgetPod<dyn Widget>,
+    /// Vertical position of the press, while dragged.
+    start: Option<f64>,
+    offset: f64,
+}
+
+impl Draggable {
+    pub fn new(child: NewWidget<impl Widget + ?Sized>) -> Self {
+        Self {
+            child: child.erased().to_pod(),
+            start: None,
+            offset: 0.,
+        }
+    }
+
+    pub fn child_mut<'t>(this: &'t mut WidgetMut<'_, Self>) -> WidgetMut<'t, dyn Widget> {
+        this.ctx.get_mut(&mut this.widget.child)
+    }
+}
+
+impl Widget for Draggable {
+    type Action = Dropped;
+
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &PointerEvent,
+    ) {
+        match event {
+            PointerEvent::Down(PointerButtonEvent { state, .. }) => {
+                ctx.capture_pointer();
+                self.start = Some(ctx.local_position(state.position).y);
+                self.offset = 0.;
+            }
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                if let Some(start) = self.start {
+                    // The child follows the pointer, the rows around stay in place until the drop.
+                    self.offset = ctx.local_position(current.position).y - start;
+                    ctx.request_layout();
+                }
+            }
+            PointerEvent::Up(..) => {
+                if self.start.take().is_some() {
+                    // The rows are assumed to be as tall as this one.
+                    let rows = (self.offset / ctx.size().height).round() as isize;
+                    if rows != 0 {
+                        ctx.submit_action::<Self::Action>(Dropped(rows));
+                    }
+                    self.offset = 0.;
+                    ctx.request_layout();
+                }
+            }
+            PointerEvent::Cancel(..) => {
+                self.start = None;
+                self.offset = 0.;
+                ctx.request_layout();
+            }
+            _ => {}
+        }
+    }
+
+    fn register_children(&mut self, ctx: &mut RegisterCtx<'_>) {
+        ctx.register_child(&mut self.child);
+    }
+
+    fn measure(
+        &mut self,
+        ctx: &mut MeasureCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        axis: Axis,
+        len_req: LenReq,
+        cross_length: Option<f64>,
+    ) -> f64 {
+        ctx.compute_length(&mut self.child, len_req, axis, cross_length)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx<'_>, _props: &PropertiesRef<'_>, size: Size) {
+        ctx.run_layout(&mut self.child, size);
+        ctx.place_child(&mut self.child, Point::new(0., self.offset));
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, _scene: &mut Scene) {}
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _node: &mut Node,
+    ) {
+    }
+
+    fn children_ids(&self) -> ChildrenIds {
+        ChildrenIds::from_slice(&[self.child.id()])
+    }
+}
+
+/// Calls `callback` with the number of rows `child` was dragged by, negative when dragged up.
+pub fn draggable<State, Action, V, F>(child: V, callback: F) -> DraggableView<V, F>
+where
+    State: ViewArgument,
+    V: WidgetView<State, Action>,
+    F: Fn(Arg<'_, State>, isize) -> Action + Send + Sync + 'static,
+{
+    DraggableView { child, callback }
+}
+
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct DraggableView<V, F> {
+    child: V,
+    callback: F,
+}
+
+impl<V, F> ViewMarker for DraggableView<V, F> {}
+
+impl<State, Action, V, F> View<State, Action, ViewCtx> for DraggableView<V, F>
+where
+    State: ViewArgument,
+    Action: 'static,
+    V: WidgetView<State, Action>,
+    F: Fn(Arg<'_, State>, isize) -> Action + Send + Sync + 'static,
+{
+    type Element = Pod<Draggable>;
+    type ViewState = V::ViewState;
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        app_state: Arg<'_, State>,
+    ) -> (Self::Element, Self::ViewState) {
+        let (child, child_state) = self.child.build(ctx, app_state);
+        let widget = Draggable::new(child.new_widget);
+        let pod = ctx.with_action_widget(|ctx| ctx.create_pod(widget));
+        (pod, child_state)
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        view_state: &mut Self::ViewState,
+        ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        app_state: Arg<'_, State>,
+    ) {
+        let mut child = Draggable::child_mut(&mut element);
+        self.child
+            .rebuild(&prev.child, view_state, ctx, child.downcast(), app_state);
+    }
+
+    fn teardown(
+        &self,
+        view_state: &mut Self::ViewState,
+        ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+    ) {
+        let mut child = Draggable::child_mut(&mut element);
+        self.child.teardown(view_state, ctx, child.downcast());
+        ctx.teardown_action_source(element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        message: &mut MessageCtx,
+        mut element: Mut<'_, Self::Element>,
+        app_state: Arg<'_, State>,
+    ) -> MessageResult<Action> {
+        if message.remaining_path().is_empty() {
+            return match message.take_message::<Dropped>() {
+                Some(dropped) => MessageResult::Action((self.callback)(app_state, dropped.0)),
+                None => MessageResult::Stale,
+            };
+        }
+        let mut child = Draggable::child_mut(&mut element);
+        self.child
+            .message(view_state, message, child.downcast(), app_state)
+    }
+}
@@ -24,6 +24,25 @@ where
     fn reset(&mut self) {
         *self = Self::default();
     }
+    /// Splits a validated output into several outputs, for example when multiple lines are pasted
+    /// at once. By default, the output is kept as is.
+    fn split(output: Self::Output) -> Vec<Self::Output> {
+        vec![output]
+    }
+    /// Text typed and not submitted yet, recovered if the app crashes. By default, nothing is
+    /// recovered.
+    fn draft(&self) -> Option<String> {
+        None
+    }
+    /// Text filling the form again with an output that wasn't saved, recovered like `Self::draft`.
+    fn output_draft(output: &Self::Output) -> Option<String> {
+        let _ = output;
+        None
+    }
+    /// Fills the form with the drafts recovered after a crash, one per line.
+    fn restore_draft(&mut self, draft: String) {
+        let _ = draft;
+    }
     /// This function should call `Self::validate`, split the result to store the error in `Self::last_error` and return the output.
     fn submit(&mut self) -> Option<Self::Output> {
         match self.validate() {
@@ -1,45 +1,120 @@
+pub mod cache;
+#[cfg(debug_assertions)]
+pub mod chaos;
 pub mod filter;
+pub mod grouper;
+pub mod search;
 pub mod sorter;
 pub mod storage;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tracing::{debug, warn};
 use uuid::Uuid;
-use xilem::WidgetView;
 use xilem::core::one_of::Either;
-use xilem::core::{Edit, MessageProxy, Read, fork, lens, map_action, map_state};
+use xilem::core::{Edit, Read, fork, lens, map_action, map_state, memoize};
+use xilem::masonry::core::keyboard::{Key, NamedKey};
 use xilem::masonry::theme::BASIC_WIDGET_HEIGHT;
 use xilem::style::Style;
 use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
-use xilem::view::{flex_col, spinner, worker};
+use xilem::tokio::task::{AbortHandle, JoinHandle};
+use xilem::tokio::time::error::Elapsed;
+use xilem::tokio::time::timeout;
+use xilem::view::{FlexExt, flex_col, flex_row, label, progress_bar, spinner, text_button, worker};
+use xilem::{AnyWidgetView, WidgetView};
 
+use crate::ui::component::draggable::draggable;
 use crate::ui::component::form::Submit;
 pub use crate::ui::component::list::filter::ListFilter;
+pub use crate::ui::component::list::grouper::ListGrouper;
 pub use crate::ui::component::list::sorter::ListSorter;
 pub use crate::ui::component::list::storage::ListStorage;
-use crate::ui::component::list::storage::Retryable;
+use crate::ui::component::list::storage::{Progress, RetryPolicy, Retryable};
+use crate::ui::component::shortcut::shortcut;
 use crate::ui::component::{ErrorView, Form};
 use crate::ui::pending::Pending;
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+/// Requests pending for longer are considered lost, see `AsyncList::with_leak_timeout`.
+const DEFAULT_LEAK_TIMEOUT: Duration = Duration::from_secs(120);
+/// How often the pending requests are checked for leaks while there are some.
+const LEAK_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 
 pub trait ListItem
 where
     Self: Clone + std::fmt::Debug + Send + 'static,
 {
-    type Id: PartialEq + Copy + std::fmt::Debug + Send + Sync;
+    type Id: Eq + Hash + Copy + std::fmt::Debug + Send + Sync + 'static;
     type CreateForm: Form<Output: Clone + Send>;
     type UpdateForm: Form<Output: Clone + Send> + From<Self>;
     type Filter: ListFilter<Item = Self>;
     type Sorter: ListSorter<Item = Self>;
+    type Grouper: ListGrouper<Item = Self>;
+    /// State that can be changed for many items at once, like the status of a task. Items without
+    /// such a state can use `()`.
+    type Status: Clone + Send;
+    /// Actions specific to the items, like duplicating a task, sent with `ItemAction::Custom`.
+    /// Items without such actions can use `()`.
+    type Action: Send;
 
     fn id(&self) -> Self::Id;
+    /// Timestamp of the last modification of the item, used to only fetch what changed when the
+    /// list is refreshed. Timestamps must be ordered when compared as strings.
+    fn updated_at(&self) -> Option<&str> {
+        None
+    }
+    /// `selected` is `None` when the list doesn't support selection.
     fn view(
         &self,
         pending_item_operation: PendingItemOperation,
+        selected: Option<bool>,
     ) -> impl WidgetView<Read<Self>, ItemAction<Self>> + use<Self>;
+    /// View of the item while the list is read-only, see `AsyncList::set_read_only`. Defaults to
+    /// `view`, the actions changing the item being ignored.
+    fn read_only_view(&self) -> impl WidgetView<Read<Self>, ItemAction<Self>> + use<Self> {
+        self.view(PendingItemOperation::None, None)
+    }
+    /// Returns the item as it will be once updated, so that the update can be shown before the
+    /// storage confirms it. Items that can't predict the result of an update should return `None`.
+    fn optimistic_update(
+        &self,
+        update_output: &<Self::UpdateForm as Form>::Output,
+    ) -> Option<Self> {
+        let _ = update_output;
+        None
+    }
+    /// Returns the update setting the status of the item, used to change the status of many items
+    /// at once when the storage doesn't do it directly.
+    fn with_status(&self, status: Self::Status) -> Option<<Self::UpdateForm as Form>::Output> {
+        let _ = status;
+        None
+    }
+    /// Returns the request performing the custom action on the item, if any.
+    fn custom_action(&self, action: Self::Action) -> Option<ListRequest<Self>> {
+        let _ = action;
+        None
+    }
     fn pending_view(
         create_output: &<Self::CreateForm as Form>::Output,
     ) -> impl WidgetView<Read<<Self::CreateForm as Form>::Output>> + use<Self> {
         let _ = create_output;
         spinner().height(BASIC_WIDGET_HEIGHT)
     }
+    /// Placeholder of an item that couldn't be created, shown next to the Retry and Discard
+    /// buttons.
+    fn failed_view(
+        create_output: &<Self::CreateForm as Form>::Output,
+    ) -> impl WidgetView<Read<<Self::CreateForm as Form>::Output>> + use<Self> {
+        let _ = create_output;
+        label("Couldn't create the item")
+    }
 }
 
 pub enum ItemAction<T>
@@ -50,24 +125,176 @@ where
     Edit,
     Update(<T::UpdateForm as Form>::Output),
     Delete,
+    /// Cancels the pending update or deletion of the item.
+    Cancel,
+    /// Toggles the selection of the item.
+    Select,
+    /// Action specific to the items, see `ListItem::custom_action`.
+    Custom(T::Action),
 }
 
-#[derive(Default)]
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub enum SelectionMode {
+    #[default]
+    None,
+    Single,
+    Multi,
+}
+
+/// Interval at which the items are fetched again, the current ones staying shown meanwhile, so
+/// that a list backed by a shared storage stays current.
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub enum AutoRefresh {
+    #[default]
+    Off,
+    HalfMinute,
+    FiveMinutes,
+}
+
+impl fmt::Display for AutoRefresh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AutoRefresh::Off => write!(f, "Off"),
+            AutoRefresh::HalfMinute => write!(f, "30 s"),
+            AutoRefresh::FiveMinutes => write!(f, "5 min"),
+        }
+    }
+}
+
+impl AutoRefresh {
+    pub fn interval(&self) -> Option<Duration> {
+        match self {
+            AutoRefresh::Off => None,
+            AutoRefresh::HalfMinute => Some(Duration::from_secs(30)),
+            AutoRefresh::FiveMinutes => Some(Duration::from_secs(300)),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AutoRefresh::Off => AutoRefresh::HalfMinute,
+            AutoRefresh::HalfMinute => AutoRefresh::FiveMinutes,
+            AutoRefresh::FiveMinutes => AutoRefresh::Off,
+        }
+    }
+
+    fn save(&self) -> String {
+        match self {
+            AutoRefresh::Off => "off",
+            AutoRefresh::HalfMinute => "30s",
+            AutoRefresh::FiveMinutes => "5min",
+        }
+        .to_string()
+    }
+
+    fn load(saved: &str) -> Option<Self> {
+        match saved {
+            "off" => Some(AutoRefresh::Off),
+            "30s" => Some(AutoRefresh::HalfMinute),
+            "5min" => Some(AutoRefresh::FiveMinutes),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
 pub enum PendingItemOperation {
     #[default]
     None,
     PendingUpdate,
     PendingDelete,
+    /// The update is already shown but the storage hasn't confirmed it yet.
+    Syncing,
+    /// The last optimistic update failed and was rolled back.
+    Failed,
+}
+
+pub struct ListQuery<T>
+where
+    T: ListItem,
+{
+    pub filter: Option<T::Filter>,
+    pub sorter: Option<T::Sorter>,
+}
+
+impl<T> Clone for ListQuery<T>
+where
+    T: ListItem,
+{
+    fn clone(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            sorter: self.sorter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListDelta<T>
+where
+    T: ListItem,
+{
+    pub changed: Vec<T>,
+    pub deleted: Vec<T::Id>,
+}
+
+impl<T> PartialEq for ListQuery<T>
+where
+    T: ListItem,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.filter == other.filter && self.sorter == other.sorter
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListPage<T> {
+    pub items: Vec<T>,
+    /// Number of items matching the query, across all pages.
+    pub total: usize,
+}
+
+/// How far the running operation is, e.g. "Created 340 / 2000".
+#[derive(Clone, Debug)]
+pub struct ListProgress {
+    pub action: &'static str,
+    pub done: usize,
+    pub total: usize,
+}
+
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct ListSettings {
+    pub filter: Option<String>,
+    pub sorter: Option<String>,
+    pub grouper: Option<String>,
+    pub auto_refresh: Option<String>,
+    /// Filter the list starts with instead of the last used one, saved like `filter`. Only read by
+    /// the list, the storage saving it on its own.
+    pub default_filter: Option<String>,
+    /// Sorter the list starts with instead of the last used one, saved like `sorter`.
+    pub default_sorter: Option<String>,
 }
 
 pub enum ListRequest<T>
 where
     T: ListItem,
 {
-    FetchAll,
+    LoadSettings,
+    SaveSettings(ListSettings),
+    FetchAll(ListQuery<T>),
+    /// Fetches all the items page by page.
+    FetchPages(ListQuery<T>, usize),
+    FetchSince(String),
+    FetchSummary,
     Create(<T::CreateForm as Form>::Output),
+    CreateMany(Vec<<T::CreateForm as Form>::Output>),
     Update(T::Id, <T::UpdateForm as Form>::Output),
     Delete(T::Id),
+    UpdateMany(Vec<(T::Id, <T::UpdateForm as Form>::Output)>),
+    DeleteMany(Vec<T::Id>),
+    SetStatusMany(Vec<T>, T::Status),
+    /// Stores the order chosen by the user, the ids being sorted in the new order.
+    Reorder(Vec<T::Id>),
 }
 
 impl<T> Clone for ListRequest<T>
@@ -76,10 +303,115 @@ where
 {
     fn clone(&self) -> Self {
         match self {
-            Self::FetchAll => Self::FetchAll,
+            Self::LoadSettings => Self::LoadSettings,
+            Self::SaveSettings(arg0) => Self::SaveSettings(arg0.clone()),
+            Self::FetchAll(arg0) => Self::FetchAll(arg0.clone()),
+            Self::FetchPages(arg0, arg1) => Self::FetchPages(arg0.clone(), *arg1),
+            Self::FetchSince(arg0) => Self::FetchSince(arg0.clone()),
+            Self::FetchSummary => Self::FetchSummary,
             Self::Create(arg0) => Self::Create(arg0.clone()),
+            Self::CreateMany(arg0) => Self::CreateMany(arg0.clone()),
             Self::Update(arg0, arg1) => Self::Update(*arg0, arg1.clone()),
             Self::Delete(arg0) => Self::Delete(*arg0),
+            Self::UpdateMany(arg0) => Self::UpdateMany(arg0.clone()),
+            Self::DeleteMany(arg0) => Self::DeleteMany(arg0.clone()),
+            Self::SetStatusMany(arg0, arg1) => Self::SetStatusMany(arg0.clone(), arg1.clone()),
+            Self::Reorder(arg0) => Self::Reorder(arg0.clone()),
+        }
+    }
+}
+
+/// Fails with `Elapsed` if the storage takes longer than `timeout` to respond.
+async fn with_timeout<R, E>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<R, E>>,
+) -> Result<R, E>
+where
+    E: From<Elapsed>,
+{
+    match timeout {
+        Some(duration) => xilem::tokio::time::timeout(duration, future)
+            .await
+            .unwrap_or_else(|elapsed| Err(elapsed.into())),
+        None => future.await,
+    }
+}
+
+type Emit<T, S> = Arc<dyn Fn(Pending<ListMessage<T, S>>) + Send + Sync>;
+
+type SlotView<T, S> = Box<dyn Fn() -> Box<AnyWidgetView<Edit<AsyncList<T, S>>>> + Send + Sync>;
+
+type ErrorSlotView<T, S> = Box<
+    dyn Fn(
+            Option<&ListRequest<T>>,
+            &<S as ListStorage>::Error,
+        ) -> Box<AnyWidgetView<Edit<AsyncList<T, S>>>>
+        + Send
+        + Sync,
+>;
+
+enum WorkerCommand<T>
+where
+    T: ListItem,
+{
+    Run(Pending<ListRequest<T>>),
+    Cancel(Uuid),
+}
+
+/// Request run by the worker of the view once the one before it finished.
+struct RunningRequest {
+    request_id: Uuid,
+    abort_handle: AbortHandle,
+    /// Set once the previous request finished and this one started.
+    started: Arc<AtomicBool>,
+    /// Set by a cancellation, the request being skipped if it hasn't started. A waiting task
+    /// isn't aborted, the next one would stop waiting for the previous one too.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl RunningRequest {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if self.started.load(Ordering::SeqCst) {
+            self.abort_handle.abort();
+        }
+    }
+}
+
+impl<T> ListRequest<T>
+where
+    T: ListItem,
+{
+    /// Past participle of the operation, used to report its progress.
+    pub fn progress_action(&self) -> &'static str {
+        match self {
+            Self::FetchAll(_) | Self::FetchPages(..) | Self::FetchSince(_) => "Loaded",
+            Self::Create(_) | Self::CreateMany(_) => "Created",
+            Self::Update(..) | Self::UpdateMany(_) | Self::SetStatusMany(..) | Self::Reorder(_) => {
+                "Updated"
+            }
+            Self::Delete(_) | Self::DeleteMany(_) => "Deleted",
+            Self::LoadSettings | Self::SaveSettings(_) | Self::FetchSummary => "Processed",
+        }
+    }
+
+    /// Short description of the operation, shown when it fails.
+    pub fn description(&self) -> String {
+        match self {
+            Self::LoadSettings => "load the settings".to_string(),
+            Self::SaveSettings(_) => "save the settings".to_string(),
+            Self::FetchAll(_) | Self::FetchPages(..) | Self::FetchSince(_) => {
+                "fetch the items".to_string()
+            }
+            Self::FetchSummary => "fetch the summary".to_string(),
+            Self::Create(_) => "create the item".to_string(),
+            Self::CreateMany(create_outputs) => format!("create {} items", create_outputs.len()),
+            Self::Update(id, _) => format!("update item {id:?}"),
+            Self::Delete(id) => format!("delete item {id:?}"),
+            Self::UpdateMany(updates) => format!("update {} items", updates.len()),
+            Self::DeleteMany(ids) => format!("delete {} items", ids.len()),
+            Self::SetStatusMany(items, _) => format!("update {} items", items.len()),
+            Self::Reorder(_) => "reorder the items".to_string(),
         }
     }
 }
@@ -90,13 +422,46 @@ where
     T: ListItem,
     S: ListStorage,
 {
+    LoadedSettings(ListSettings),
+    SavedSettings,
     FetchedAll(Vec<T>),
+    /// A page of items starting at `offset`. The request is only resolved by the last page.
+    FetchedPage {
+        page: ListPage<T>,
+        offset: usize,
+        last: bool,
+    },
+    FetchedSince(ListDelta<T>),
+    FetchedSummary(S::Summary),
     Created(T),
+    CreatedMany(Vec<T>),
     Updated(T::Id, T),
     Deleted(T::Id),
+    UpdatedMany(Vec<T>),
+    DeletedMany(Vec<T::Id>),
+    /// Number of items processed so far out of the total. The request isn't resolved yet.
+    Progress(usize, usize),
     Error(S::Error),
 }
 
+/// What the view of an item is built from. The copy of the item is compared by identity, as it is
+/// replaced when the item changes.
+struct ItemViewData<T> {
+    item: Arc<T>,
+    pending_item_operation: PendingItemOperation,
+    selected: Option<bool>,
+    read_only: bool,
+}
+
+impl<T> PartialEq for ItemViewData<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.item, &other.item)
+            && self.pending_item_operation == other.pending_item_operation
+            && self.selected == other.selected
+            && self.read_only == other.read_only
+    }
+}
+
 #[derive(Default)]
 pub struct AsyncList<T, S>
 where
@@ -106,13 +471,61 @@ where
     create_form: T::CreateForm,
     update_form: T::UpdateForm,
     filter: Option<T::Filter>,
+    /// Filter replacing the saved one once the settings are loaded, see `set_initial_filter`.
+    initial_filter: Option<T::Filter>,
+    fetched_filter: Option<T::Filter>,
     sorter: Option<T::Sorter>,
+    /// Sorter replacing the saved one once the settings are loaded, see `set_initial_sorter`.
+    initial_sorter: Option<T::Sorter>,
+    /// Whether the settings were loaded once, their default filter and sorter only being used
+    /// the first time, see `ListSettings::default_filter`.
+    started: bool,
+    grouper: Option<T::Grouper>,
     editing: Option<T::Id>,
     items: Vec<T>,
-    processed_items: Vec<(T, f32)>,
-    sender: Option<UnboundedSender<Pending<ListRequest<T>>>>,
+    /// Indices in `items` of the shown items, in the shown order, with their filter score.
+    processed_items: Vec<(usize, f32)>,
+    /// Copies of the shown items that didn't change since their views were built. An item is
+    /// dropped from here when it changes, so that only its view is rebuilt.
+    shown_items: HashMap<T::Id, Arc<T>>,
+    sender: Option<UnboundedSender<WorkerCommand<T>>>,
     pending_requests: Vec<Pending<ListRequest<T>>>,
+    optimistic: bool,
+    snapshots: Vec<(Uuid, T)>,
+    failed: Vec<T::Id>,
+    retry_policy: RetryPolicy,
+    selection_mode: SelectionMode,
+    selection: Vec<T::Id>,
+    saved_settings: ListSettings,
+    page_size: Option<usize>,
+    timeout: Option<Duration>,
+    progress: Option<ListProgress>,
+    auto_refresh: AutoRefresh,
+    /// Sends the auto-refresh interval to the timer of the view.
+    auto_refresh_sender: Option<UnboundedSender<Option<Duration>>>,
+    /// Requests pending for longer are failed with a timeout, `None` keeping them forever.
+    leak_timeout: Option<Duration>,
+    /// Tells the leak checker of the view whether requests are pending.
+    leak_sender: Option<UnboundedSender<bool>>,
+    /// Hides the create form and ignores the actions changing the items.
+    read_only: bool,
+    /// Pages fetched in the background while the current items are still shown.
+    revalidating: Option<Vec<T>>,
+    /// The operation that caused the last error, so that it can be retried.
+    failed_request: Option<Pending<ListRequest<T>>>,
+    /// Creations that failed, shown in place of their items until retried or discarded.
+    failed_creates: Vec<Pending<ListRequest<T>>>,
+    /// Updates and deletions that failed, shown on their items until retried or dismissed.
+    item_errors: Vec<(T::Id, Pending<ListRequest<T>>)>,
+    /// Item whose error details are shown.
+    expanded_item_error: Option<T::Id>,
+    /// Shown instead of the items while they are first fetched.
+    loading_view: Option<SlotView<T, S>>,
+    /// Shown instead of the items when none match.
+    empty_view: Option<SlotView<T, S>>,
+    error_view: Option<ErrorSlotView<T, S>>,
     storage: S,
+    summary: S::Summary,
 }
 
 impl<T> ItemAction<T>
@@ -123,6 +536,13 @@ where
     where
         S: ListStorage<Item = T>,
     {
+        let changes_item = !matches!(
+            self,
+            ItemAction::None | ItemAction::Cancel | ItemAction::Select
+        );
+        if state.read_only && changes_item {
+            return;
+        }
         match self {
             ItemAction::None => (),
             ItemAction::Edit => {
@@ -132,11 +552,29 @@ where
                 }
             }
             ItemAction::Update(update_output) => {
-                state.send_request(ListRequest::Update(id, update_output));
+                state.send_update(id, update_output);
             }
             ItemAction::Delete => {
+                state.item_errors.retain(|(error_id, _)| *error_id != id);
                 state.send_request(ListRequest::Delete(id));
             }
+            ItemAction::Cancel => {
+                state.cancel(id);
+            }
+            ItemAction::Select => {
+                state.toggle_selection(id);
+            }
+            ItemAction::Custom(action) => {
+                match state.get(id).and_then(|item| item.custom_action(action)) {
+                    Some(ListRequest::Update(id, update_output)) => {
+                        state.send_update(id, update_output)
+                    }
+                    Some(request) => {
+                        state.send_request(request);
+                    }
+                    None => (),
+                }
+            }
         }
     }
 }
@@ -145,41 +583,137 @@ impl<T> Pending<ListRequest<T>>
 where
     T: ListItem,
 {
-    async fn handle<S>(self, proxy: &MessageProxy<Pending<ListMessage<T, S>>>)
+    /// Runs the request and sends its messages with `emit`, the last one resolving the request.
+    async fn handle<S>(self, emit: Emit<T, S>, timeout: Option<Duration>)
     where
         S: ListStorage<Item = T>,
     {
+        let request_id = self.request_id;
+        let progress = {
+            let emit = emit.clone();
+            Progress::new(move |done, total| {
+                emit(Pending::from((
+                    request_id,
+                    ListMessage::Progress(done, total),
+                )));
+            })
+        };
         let pending_message = self.map(async |list_request| match list_request {
-            ListRequest::FetchAll => {
-                let result = S::fetch_all().await;
+            ListRequest::LoadSettings => {
+                let result = with_timeout(timeout, S::load_settings()).await;
+                match result {
+                    Ok(settings) => ListMessage::LoadedSettings(settings),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
+            ListRequest::SaveSettings(settings) => {
+                let result = with_timeout(timeout, S::save_settings(settings)).await;
+                match result {
+                    Ok(()) => ListMessage::SavedSettings,
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
+            ListRequest::FetchAll(query) => {
+                let result = with_timeout(timeout, S::fetch_all(query)).await;
                 match result {
                     Ok(items) => ListMessage::FetchedAll(items),
                     Err(error) => ListMessage::Error(error),
                 }
             }
+            ListRequest::FetchPages(query, page_size) => {
+                let mut offset = 0;
+                loop {
+                    let result =
+                        with_timeout(timeout, S::fetch_page(query.clone(), offset, page_size))
+                            .await;
+                    match result {
+                        Ok(page) => {
+                            let loaded = offset + page.items.len();
+                            let last = page.items.is_empty() || loaded >= page.total;
+                            let message = ListMessage::FetchedPage { page, offset, last };
+                            if last {
+                                break message;
+                            }
+                            emit(Pending::from((request_id, message)));
+                            offset = loaded;
+                        }
+                        Err(error) => break ListMessage::Error(error),
+                    }
+                }
+            }
+            ListRequest::FetchSince(since) => {
+                let result = with_timeout(timeout, S::fetch_since(since)).await;
+                match result {
+                    Ok(delta) => ListMessage::FetchedSince(delta),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
+            ListRequest::FetchSummary => {
+                let result = with_timeout(timeout, S::fetch_summary()).await;
+                match result {
+                    Ok(summary) => ListMessage::FetchedSummary(summary),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
             ListRequest::Create(create_output) => {
-                let result = S::create(create_output).await;
+                let result = with_timeout(timeout, S::create(create_output)).await;
                 match result {
                     Ok(item) => ListMessage::Created(item),
                     Err(error) => ListMessage::Error(error),
                 }
             }
+            ListRequest::CreateMany(create_outputs) => {
+                let result = with_timeout(timeout, S::create_many(create_outputs, progress)).await;
+                match result {
+                    Ok(items) => ListMessage::CreatedMany(items),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
             ListRequest::Update(id, update_output) => {
-                let result = S::update(id, update_output).await;
+                let result = with_timeout(timeout, S::update(id, update_output)).await;
                 match result {
                     Ok(item) => ListMessage::Updated(id, item),
                     Err(error) => ListMessage::Error(error),
                 }
             }
             ListRequest::Delete(id) => {
-                let result = S::delete(id).await;
+                let result = with_timeout(timeout, S::delete(id)).await;
                 match result {
                     Ok(id) => ListMessage::Deleted(id),
                     Err(error) => ListMessage::Error(error),
                 }
             }
+            ListRequest::UpdateMany(updates) => {
+                let result = with_timeout(timeout, S::update_many(updates, progress)).await;
+                match result {
+                    Ok(items) => ListMessage::UpdatedMany(items),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
+            ListRequest::DeleteMany(ids) => {
+                let result = with_timeout(timeout, S::delete_many(ids, progress)).await;
+                match result {
+                    Ok(ids) => ListMessage::DeletedMany(ids),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
+            ListRequest::SetStatusMany(items, status) => {
+                let result =
+                    with_timeout(timeout, S::set_status_many(items, status, progress)).await;
+                match result {
+                    Ok(items) => ListMessage::UpdatedMany(items),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
+            ListRequest::Reorder(ids) => {
+                let result = with_timeout(timeout, S::reorder(ids)).await;
+                match result {
+                    Ok(items) => ListMessage::UpdatedMany(items),
+                    Err(error) => ListMessage::Error(error),
+                }
+            }
         });
-        let _ = proxy.message(pending_message.await);
+        emit(pending_message.await);
     }
 }
 
@@ -190,10 +724,80 @@ where
 {
     fn handle(self, state: &mut AsyncList<T, S>) {
         match self.data {
-            ListMessage::FetchedAll(items) => state.items = items,
+            ListMessage::LoadedSettings(settings) => {
+                state.load_settings(settings);
+                state.resolve_pending_request(self.request_id);
+                state.fetch_all();
+                return;
+            }
+            ListMessage::SavedSettings => {
+                state.resolve_pending_request(self.request_id);
+                return;
+            }
+            ListMessage::FetchedAll(items) => state.merge_items(items),
+            ListMessage::FetchedPage { page, offset, last } => {
+                // Pages replacing items already shown are kept aside until the last one arrives.
+                match (offset, &mut state.revalidating) {
+                    (0, _) if state.items.is_empty() => state.items = page.items,
+                    (0, revalidating) => *revalidating = Some(page.items),
+                    (_, Some(revalidating)) => revalidating.extend(page.items),
+                    (_, None) => state.items.extend(page.items),
+                }
+                if !last {
+                    let loaded = match &state.revalidating {
+                        Some(revalidating) => revalidating.len(),
+                        None => state.items.len(),
+                    };
+                    state.progress = Some(ListProgress {
+                        action: "Loaded",
+                        done: loaded,
+                        total: page.total,
+                    });
+                    return;
+                }
+                if let Some(items) = state.revalidating.take() {
+                    state.merge_items(items);
+                }
+                state.progress = None;
+            }
+            ListMessage::Progress(done, total) => {
+                let action = state
+                    .pending_requests
+                    .iter()
+                    .find(|pending_request| pending_request.request_id == self.request_id)
+                    .map(|pending_request| pending_request.data.progress_action());
+                if let Some(action) = action {
+                    state.progress = Some(ListProgress {
+                        action,
+                        done,
+                        total,
+                    });
+                }
+                return;
+            }
+            ListMessage::FetchedSince(delta) => {
+                for item in delta.changed {
+                    if let Some(existing) = state.get_mut(item.id()) {
+                        *existing = item;
+                    } else {
+                        state.items.push(item);
+                    }
+                }
+                for id in delta.deleted {
+                    state.remove(id);
+                }
+            }
+            ListMessage::FetchedSummary(summary) => {
+                state.summary = summary;
+                state.resolve_pending_request(self.request_id);
+                return;
+            }
             ListMessage::Created(item) => {
                 state.items.push(item);
             }
+            ListMessage::CreatedMany(items) => {
+                state.items.extend(items);
+            }
             ListMessage::Updated(id, new_item) => {
                 if let Some(item) = state.get_mut(id) {
                     *item = new_item;
@@ -202,18 +806,72 @@ where
             ListMessage::Deleted(id) => {
                 state.remove(id);
             }
+            ListMessage::UpdatedMany(new_items) => {
+                for new_item in new_items {
+                    if let Some(item) = state.get_mut(new_item.id()) {
+                        *item = new_item;
+                    }
+                }
+            }
+            ListMessage::DeletedMany(ids) => {
+                for id in ids {
+                    state.remove(id);
+                }
+            }
             ListMessage::Error(error) => {
-                if error.should_retry() {
-                    state.retry_request(self.request_id);
-                } else {
-                    state.resolve_pending_request(self.request_id);
+                let attempt = self.attempt() + 1;
+                // A creation that may have been written would be created twice by a retry.
+                let creates = state.pending_requests.iter().any(|pending_request| {
+                    pending_request.request_id == self.request_id
+                        && matches!(
+                            pending_request.data,
+                            ListRequest::Create(_) | ListRequest::CreateMany(_)
+                        )
+                });
+                let repeatable = !(creates && error.may_have_applied());
+                if error.should_retry() && repeatable && attempt <= state.retry_policy.max_attempts
+                {
+                    debug!(?error, attempt, "retrying a list request");
+                    state.retry_request(self.request_id, attempt);
+                    return;
                 }
+                warn!(?error, "a list request failed");
+                state.rollback(self.request_id);
+                state.progress = None;
+                state.revalidating = None;
+                let failed_request = state.resolve_pending_request(self.request_id);
+                match failed_request
+                    .as_ref()
+                    .map(|failed_request| &failed_request.data)
+                {
+                    Some(ListRequest::LoadSettings) => state.fetch_all(),
+                    // The items were already moved, fetching them again restores their order.
+                    Some(ListRequest::Reorder(_)) => state.fetch_all(),
+                    Some(ListRequest::Create(_) | ListRequest::CreateMany(_)) => {
+                        state.failed_creates.extend(failed_request.clone());
+                    }
+                    Some(ListRequest::Update(id, _) | ListRequest::Delete(id)) => {
+                        let id = *id;
+                        state.item_errors.retain(|(error_id, _)| *error_id != id);
+                        state.item_errors.extend(
+                            failed_request
+                                .clone()
+                                .map(|failed_request| (id, failed_request)),
+                        );
+                    }
+                    _ => {}
+                }
+                state.failed_request = failed_request;
                 *state.storage.last_error() = Some(error);
                 return;
             }
         }
+        state
+            .snapshots
+            .retain(|(request_id, _)| *request_id != self.request_id);
         state.resolve_pending_request(self.request_id);
-        *state.storage.last_error() = None;
+        state.progress = None;
+        state.send_request(ListRequest::FetchSummary);
     }
 }
 
@@ -222,21 +880,262 @@ where
     T: ListItem,
     S: ListStorage<Item = T>,
 {
-    pub fn new(filter: bool, sorter: bool) -> Self {
+    pub fn new(filter: bool, sorter: bool, grouper: bool) -> Self {
         Self {
             create_form: T::CreateForm::default(),
             update_form: T::UpdateForm::default(),
             filter: filter.then_some(T::Filter::default()),
+            initial_filter: None,
+            fetched_filter: None,
             sorter: sorter.then_some(T::Sorter::default()),
+            initial_sorter: None,
+            started: false,
+            grouper: grouper.then_some(T::Grouper::default()),
             editing: None,
             items: Vec::new(),
             processed_items: Vec::new(),
+            shown_items: HashMap::new(),
             pending_requests: Vec::new(),
+            optimistic: false,
+            snapshots: Vec::new(),
+            failed: Vec::new(),
+            retry_policy: RetryPolicy::default(),
+            selection_mode: SelectionMode::None,
+            selection: Vec::new(),
+            saved_settings: ListSettings::default(),
+            page_size: None,
+            timeout: None,
+            progress: None,
+            auto_refresh: AutoRefresh::Off,
+            auto_refresh_sender: None,
+            leak_timeout: Some(DEFAULT_LEAK_TIMEOUT),
+            leak_sender: None,
+            read_only: false,
+            revalidating: None,
+            failed_request: None,
+            failed_creates: Vec::new(),
+            item_errors: Vec::new(),
+            expanded_item_error: None,
+            loading_view: None,
+            empty_view: None,
+            error_view: None,
             sender: None,
             storage: S::default(),
+            summary: S::Summary::default(),
+        }
+    }
+
+    /// Applies updates locally right away instead of waiting for the storage, rolling them back if
+    /// the storage fails. Requires `ListItem::optimistic_update` to be implemented.
+    pub fn with_optimistic_updates(mut self) -> Self {
+        self.optimistic = true;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Fetches the items in pages of `page_size` items, showing them as soon as they arrive.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Makes the storage operations fail if they take longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails the requests still pending after `leak_timeout`, in case their response was lost, so
+    /// that their items don't stay pending forever. Two minutes by default.
+    pub fn with_leak_timeout(mut self, leak_timeout: Duration) -> Self {
+        self.leak_timeout = Some(leak_timeout);
+        self
+    }
+
+    /// Replaces the spinner shown while the items are first fetched.
+    pub fn with_loading_view<V>(mut self, view: impl Fn() -> V + Send + Sync + 'static) -> Self
+    where
+        V: WidgetView<Edit<Self>>,
+    {
+        self.loading_view = Some(Box::new(move || view().boxed()));
+        self
+    }
+
+    /// Replaces the message shown when no item matches.
+    pub fn with_empty_view<V>(mut self, view: impl Fn() -> V + Send + Sync + 'static) -> Self
+    where
+        V: WidgetView<Edit<Self>>,
+    {
+        self.empty_view = Some(Box::new(move || view().boxed()));
+        self
+    }
+
+    /// Replaces the error banner, `view` gets the failed operation, if known, and the error. Use
+    /// `retry_failed_request` and `dismiss_error` in its actions.
+    pub fn with_error_view<V>(
+        mut self,
+        view: impl Fn(Option<&ListRequest<T>>, &S::Error) -> V + Send + Sync + 'static,
+    ) -> Self
+    where
+        V: WidgetView<Edit<Self>>,
+    {
+        self.error_view = Some(Box::new(move |failed_request, error| {
+            view(failed_request, error).boxed()
+        }));
+        self
+    }
+
+    pub fn with_selection(mut self, selection_mode: SelectionMode) -> Self {
+        self.selection_mode = selection_mode;
+        self
+    }
+
+    /// Starts with `filter` instead of the saved one, like a filter chosen on the command line.
+    /// It is then saved like any other choice of the user.
+    pub fn set_initial_filter(&mut self, filter: T::Filter) {
+        self.initial_filter = Some(filter);
+    }
+
+    /// Starts with `sorter` instead of the saved one, like `set_initial_filter`.
+    pub fn set_initial_sorter(&mut self, sorter: T::Sorter) {
+        self.initial_sorter = Some(sorter);
+    }
+
+    pub fn selection(&self) -> &[T::Id] {
+        &self.selection
+    }
+
+    pub fn selected_items(&self) -> impl Iterator<Item = &T> {
+        self.items
+            .iter()
+            .filter(|item| self.selection.contains(&item.id()))
+    }
+
+    /// The selected item when exactly one is selected, as currently shown, including the
+    /// optimistic updates still in flight.
+    pub fn selected(&self) -> Option<&T> {
+        match self.selection.as_slice() {
+            [id] => self.get(*id),
+            _ => None,
+        }
+    }
+
+    pub fn is_selected(&self, id: T::Id) -> bool {
+        self.selection.contains(&id)
+    }
+
+    pub fn select(&mut self, id: T::Id) {
+        match self.selection_mode {
+            SelectionMode::None => (),
+            SelectionMode::Single => self.selection = vec![id],
+            SelectionMode::Multi => {
+                if !self.is_selected(id) {
+                    self.selection.push(id);
+                }
+            }
+        }
+    }
+
+    pub fn deselect(&mut self, id: T::Id) {
+        self.selection.retain(|selected_id| *selected_id != id);
+    }
+
+    pub fn toggle_selection(&mut self, id: T::Id) {
+        if self.is_selected(id) {
+            self.deselect(id);
+        } else {
+            self.select(id);
         }
     }
 
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+    }
+
+    pub fn update_many(&mut self, updates: Vec<(T::Id, <T::UpdateForm as Form>::Output)>) {
+        if !updates.is_empty() {
+            self.send_request(ListRequest::UpdateMany(updates));
+        }
+    }
+
+    pub fn delete_many(&mut self, ids: Vec<T::Id>) {
+        if !ids.is_empty() {
+            self.send_request(ListRequest::DeleteMany(ids));
+        }
+    }
+
+    pub fn set_status_many(&mut self, ids: &[T::Id], status: T::Status) {
+        let items = ids
+            .iter()
+            .filter_map(|id| self.get(*id).cloned())
+            .collect::<Vec<_>>();
+        if !items.is_empty() {
+            self.send_request(ListRequest::SetStatusMany(items, status));
+        }
+    }
+
+    /// Whether the items are shown in an order chosen by the user, see `ListSorter::user_order`.
+    pub fn user_order(&self) -> bool {
+        self.sorter
+            .as_ref()
+            .is_some_and(|sorter| sorter.enabled() && sorter.user_order())
+    }
+
+    /// Moves the item to `new_index` among the shown items. The item is moved right away and the
+    /// new order is then stored.
+    pub fn reorder(&mut self, id: T::Id, new_index: usize) {
+        // The shown items may be filtered, so the item is moved next to the one it replaces.
+        let target_id = self
+            .processed_items
+            .get(new_index)
+            .and_then(|(index, _)| self.items.get(*index))
+            .map(ListItem::id);
+        if let Some(target_id) = target_id {
+            self.move_to(id, target_id);
+        }
+    }
+
+    /// Moves the item to the place of `target_id`, the items in between shifting by one, like
+    /// `reorder` without depending on the shown items, which may have changed since.
+    pub fn move_to(&mut self, id: T::Id, target_id: T::Id) {
+        let Some(sorter) = self.sorter.as_ref().filter(|_| self.user_order()) else {
+            return;
+        };
+        let mut items = self.items.clone();
+        items.sort_by(|a, b| sorter.sort(a, b, 0., 0.));
+        let index = items.iter().position(|item| item.id() == id);
+        let target_index = items.iter().position(|item| item.id() == target_id);
+        let (Some(index), Some(target_index)) = (index, target_index) else {
+            return;
+        };
+        if index == target_index {
+            return;
+        }
+        sorter.reorder(&mut items, index, target_index);
+        // Only the items between the two places move, so only these are sent to be stored.
+        let moved = &items[index.min(target_index)..=index.max(target_index)];
+        let ids = moved.iter().map(ListItem::id).collect();
+        for item in moved.iter().cloned() {
+            if let Some(existing) = self.get_mut(item.id()) {
+                *existing = item;
+            }
+        }
+        self.send_request(ListRequest::Reorder(ids));
+    }
+
+    /// Progress of the running operation, if it reports any.
+    pub fn progress(&self) -> Option<&ListProgress> {
+        self.progress.as_ref()
+    }
+
+    pub fn summary(&self) -> &S::Summary {
+        &self.summary
+    }
+
     fn filter(&self, item: &T) -> (bool, f32) {
         self.filter
             .as_ref()
@@ -244,50 +1143,489 @@ where
             .unwrap_or((true, 0.))
     }
 
-    fn pending_item_operation(&self, id: T::Id) -> PendingItemOperation {
+    fn load_settings(&mut self, settings: ListSettings) {
+        // Once started, the list keeps the filter and the sorter in use.
+        let starting = !std::mem::replace(&mut self.started, true);
+        let default_filter = settings.default_filter.as_ref().filter(|_| starting);
+        let filter = default_filter.or(settings.filter.as_ref());
+        if let (Some(filter), Some(saved)) = (&mut self.filter, filter) {
+            filter.load(saved);
+        }
+        let default_sorter = settings.default_sorter.as_ref().filter(|_| starting);
+        let sorter = default_sorter.or(settings.sorter.as_ref());
+        if let (Some(sorter), Some(saved)) = (&mut self.sorter, sorter) {
+            sorter.load(saved);
+        }
+        if let (Some(grouper), Some(saved)) = (&mut self.grouper, &settings.grouper) {
+            grouper.load(saved);
+        }
+        if let Some(auto_refresh) = settings.auto_refresh.as_deref().and_then(AutoRefresh::load) {
+            self.set_auto_refresh(auto_refresh);
+        }
+        if let (Some(filter), Some(initial)) = (&mut self.filter, self.initial_filter.take()) {
+            *filter = initial;
+        }
+        if let (Some(sorter), Some(initial)) = (&mut self.sorter, self.initial_sorter.take()) {
+            *sorter = initial;
+        }
+        self.saved_settings = settings;
+    }
+
+    fn save_settings(&mut self) {
+        let settings = ListSettings {
+            filter: self.filter.as_ref().and_then(ListFilter::save),
+            sorter: self.sorter.as_ref().and_then(ListSorter::save),
+            grouper: self.grouper.as_ref().and_then(ListGrouper::save),
+            auto_refresh: Some(self.auto_refresh.save()),
+            // Chosen elsewhere and not saved by the list, kept as loaded.
+            default_filter: self.saved_settings.default_filter.clone(),
+            default_sorter: self.saved_settings.default_sorter.clone(),
+        };
+        if settings != self.saved_settings {
+            self.saved_settings = settings.clone();
+            self.send_request(ListRequest::SaveSettings(settings));
+        }
+    }
+
+    fn query(&self) -> ListQuery<T> {
+        ListQuery {
+            filter: self.filter.clone(),
+            sorter: self.sorter.clone(),
+        }
+    }
+
+    /// Replaces the items with the fetched ones in place, so that the list doesn't flicker. Items
+    /// with an optimistic update in flight keep their local version.
+    fn merge_items(&mut self, fetched: Vec<T>) {
+        let mut unchanged = HashMap::new();
+        self.items = fetched
+            .into_iter()
+            .map(|item| {
+                let id = item.id();
+                let syncing = self
+                    .snapshots
+                    .iter()
+                    .any(|(_, snapshot)| snapshot.id() == id);
+                let item = match self.get(id) {
+                    Some(local) if syncing => local.clone(),
+                    _ => item,
+                };
+                // Items with the same timestamp didn't change, so their views are kept.
+                if let Some(shown) = self.shown_items.get(&id)
+                    && (syncing
+                        || item
+                            .updated_at()
+                            .is_some_and(|updated_at| shown.updated_at() == Some(updated_at)))
+                {
+                    unchanged.insert(id, shown.clone());
+                }
+                item
+            })
+            .collect();
+        self.shown_items = unchanged;
+        // The ids of the items deleted elsewhere would be selected again if reused.
+        let items = &self.items;
+        self.selection
+            .retain(|id| items.iter().any(|item| item.id() == *id));
+    }
+
+    fn fetch_all(&mut self) {
+        self.fetched_filter = self.filter.clone();
+        match self.page_size {
+            Some(page_size) => {
+                self.send_request(ListRequest::FetchPages(self.query(), page_size));
+            }
+            None => {
+                self.send_request(ListRequest::FetchAll(self.query()));
+            }
+        }
+    }
+
+    /// Fetches all the items again, skipping any cache, e.g. after the storage was modified
+    /// externally.
+    pub fn reload(&mut self) {
+        if self.is_refreshing() {
+            return;
+        }
+        S::invalidate();
+        self.fetch_all();
+    }
+
+    pub fn auto_refresh(&self) -> AutoRefresh {
+        self.auto_refresh
+    }
+
+    /// Reloads the items at the interval of `auto_refresh` while the list is shown.
+    pub fn set_auto_refresh(&mut self, auto_refresh: AutoRefresh) {
+        self.auto_refresh = auto_refresh;
+        if let Some(sender) = &self.auto_refresh_sender {
+            let _ = sender.send(auto_refresh.interval());
+        }
+    }
+
+    /// Whether items are being fetched.
+    pub fn is_refreshing(&self) -> bool {
+        self.pending_requests.iter().any(|pending_request| {
+            matches!(
+                pending_request.data,
+                ListRequest::FetchAll(_) | ListRequest::FetchPages(..) | ListRequest::FetchSince(_)
+            )
+        })
+    }
+
+    /// Fetches the items changed since the last fetch, or all of them if the items don't provide
+    /// `ListItem::updated_at`.
+    pub fn refresh(&mut self) {
+        let since = self
+            .items
+            .iter()
+            .filter_map(T::updated_at)
+            .max()
+            .map(str::to_owned);
+        match since {
+            Some(since) => {
+                self.send_request(ListRequest::FetchSince(since));
+            }
+            None => self.fetch_all(),
+        }
+    }
+
+    pub fn pending_item_operation(&self, id: T::Id) -> PendingItemOperation {
         self.pending_requests
             .iter()
             .find_map(|pending_request| match pending_request {
                 Pending {
+                    request_id,
                     data: ListRequest::Update(pending_id, _),
                     ..
-                } if *pending_id == id => Some(PendingItemOperation::PendingUpdate),
+                } if *pending_id == id => {
+                    if self
+                        .snapshots
+                        .iter()
+                        .any(|(snapshot_id, _)| snapshot_id == request_id)
+                    {
+                        Some(PendingItemOperation::Syncing)
+                    } else {
+                        Some(PendingItemOperation::PendingUpdate)
+                    }
+                }
                 Pending {
                     data: ListRequest::Delete(pending_id),
                     ..
                 } if *pending_id == id => Some(PendingItemOperation::PendingDelete),
+                Pending {
+                    data: ListRequest::UpdateMany(updates),
+                    ..
+                } if updates.iter().any(|(pending_id, _)| *pending_id == id) => {
+                    Some(PendingItemOperation::PendingUpdate)
+                }
+                Pending {
+                    data: ListRequest::SetStatusMany(items, _),
+                    ..
+                } if items.iter().any(|item| item.id() == id) => {
+                    Some(PendingItemOperation::PendingUpdate)
+                }
+                Pending {
+                    data: ListRequest::DeleteMany(ids),
+                    ..
+                } if ids.contains(&id) => Some(PendingItemOperation::PendingDelete),
                 _ => None,
             })
+            .or_else(|| {
+                (self.failed.contains(&id) || self.item_error(id).is_some())
+                    .then_some(PendingItemOperation::Failed)
+            })
             .unwrap_or_default()
     }
 
-    fn send_request(&mut self, request: ListRequest<T>) {
-        if let Some(sender) = &self.sender {
-            let pending_request = Pending::new(request.clone());
+    fn send_request(&mut self, request: ListRequest<T>) -> Option<Uuid> {
+        let sender = self.sender.as_ref()?;
+        let pending_request = Pending::new(request.clone());
+        let request_id = pending_request.request_id;
+        if self.pending_requests.is_empty()
+            && let Some(leak_sender) = &self.leak_sender
+        {
+            let _ = leak_sender.send(true);
+        }
+        self.pending_requests
+            .push(Pending::from((request_id, request)));
+        let _ = sender.send(WorkerCommand::Run(pending_request));
+        Some(request_id)
+    }
+
+    fn send_update(&mut self, id: T::Id, update_output: <T::UpdateForm as Form>::Output) {
+        let optimistic_item = self
+            .get(id)
+            .filter(|_| self.optimistic)
+            .and_then(|item| item.optimistic_update(&update_output));
+        let request_id = self.send_request(ListRequest::Update(id, update_output));
+        if let (Some(request_id), Some(optimistic_item)) = (request_id, optimistic_item)
+            && let Some(item) = self.get_mut(id)
+        {
+            let item = std::mem::replace(item, optimistic_item);
+            self.snapshots.push((request_id, item));
+        }
+        self.failed.retain(|failed_id| *failed_id != id);
+        self.item_errors.retain(|(error_id, _)| *error_id != id);
+    }
+
+    /// Restores the item updated optimistically by the request, if any.
+    fn restore_snapshot(&mut self, request_id: Uuid) -> Option<T::Id> {
+        let index = self
+            .snapshots
+            .iter()
+            .position(|(snapshot_id, _)| *snapshot_id == request_id)?;
+        let (_, snapshot) = self.snapshots.remove(index);
+        let id = snapshot.id();
+        let item = self.get_mut(id)?;
+        *item = snapshot;
+        Some(id)
+    }
+
+    fn rollback(&mut self, request_id: Uuid) {
+        if let Some(id) = self.restore_snapshot(request_id) {
+            self.failed.push(id);
+        }
+    }
+
+    /// Aborts the pending update or deletion of the item and reverts it to its previous state.
+    /// The list is then refreshed in case the storage already applied the operation.
+    fn cancel(&mut self, id: T::Id) {
+        let request_id =
             self.pending_requests
-                .push(Pending::from((pending_request.request_id, request)));
-            let _ = sender.send(pending_request);
+                .iter()
+                .find_map(|pending_request| match pending_request.data {
+                    ListRequest::Update(pending_id, _) | ListRequest::Delete(pending_id)
+                        if pending_id == id =>
+                    {
+                        Some(pending_request.request_id)
+                    }
+                    _ => None,
+                });
+        if let (Some(sender), Some(request_id)) = (&self.sender, request_id) {
+            let _ = sender.send(WorkerCommand::Cancel(request_id));
+            self.restore_snapshot(request_id);
+            self.resolve_pending_request(request_id);
+            self.refresh();
         }
     }
 
-    fn retry_request(&mut self, request_id: Uuid) {
+    fn retry_request(&mut self, request_id: Uuid, attempt: u32) {
         let pending_request = self
             .pending_requests
             .iter()
             .find(|pending_request| pending_request.request_id == request_id);
         if let (Some(sender), Some(pending_request)) = (&self.sender, pending_request) {
-            let _ = sender.send(pending_request.clone().with_delay(0.5));
+            let delay = self.retry_policy.delay(attempt);
+            let _ = sender.send(WorkerCommand::Run(
+                pending_request
+                    .clone()
+                    .with_attempt(attempt)
+                    .with_delay(delay.as_secs_f32()),
+            ));
         }
     }
 
-    fn resolve_pending_request(&mut self, request_id: Uuid) {
-        if let Some(index) = self
+    /// Sends the operation that caused the last error again.
+    pub fn retry_failed_request(&mut self) {
+        let Some(failed_request) = self.failed_request.take() else {
+            return;
+        };
+        *self.storage.last_error() = None;
+        self.failed_creates
+            .retain(|failed_create| failed_create.request_id != failed_request.request_id);
+        self.item_errors
+            .retain(|(_, item_error)| item_error.request_id != failed_request.request_id);
+        match failed_request.data {
+            ListRequest::FetchAll(_) | ListRequest::FetchPages(..) => self.fetch_all(),
+            ListRequest::Update(id, update_output) => self.send_update(id, update_output),
+            failed_request => {
+                self.send_request(failed_request);
+            }
+        }
+    }
+
+    pub fn dismiss_error(&mut self) {
+        self.failed_request = None;
+        *self.storage.last_error() = None;
+    }
+
+    /// Dismisses the shared error if it was caused by the request.
+    fn dismiss_error_of(&mut self, request_id: Uuid) {
+        if self
+            .failed_request
+            .as_ref()
+            .is_some_and(|failed_request| failed_request.request_id == request_id)
+        {
+            self.dismiss_error();
+        }
+    }
+
+    fn take_failed_create(&mut self, request_id: Uuid) -> Option<ListRequest<T>> {
+        let index = self
+            .failed_creates
+            .iter()
+            .position(|failed_create| failed_create.request_id == request_id)?;
+        self.dismiss_error_of(request_id);
+        Some(self.failed_creates.remove(index).data)
+    }
+
+    /// The text typed in the create form and the creations not saved yet, to recover them if the
+    /// app crashes.
+    pub fn unsaved_drafts(&self) -> Vec<String> {
+        let creations = self
+            .pending_requests
+            .iter()
+            .chain(&self.failed_creates)
+            .flat_map(|pending| match &pending.data {
+                ListRequest::Create(output) => vec![output],
+                ListRequest::CreateMany(outputs) => outputs.iter().collect(),
+                _ => Vec::new(),
+            })
+            .filter_map(T::CreateForm::output_draft);
+        self.create_form
+            .draft()
+            .into_iter()
+            .chain(creations)
+            .collect()
+    }
+
+    /// Puts the drafts recovered after a crash back in the create form, to be submitted again.
+    pub fn restore_drafts(&mut self, drafts: Vec<String>) {
+        if !drafts.is_empty() {
+            self.create_form.restore_draft(drafts.join("\n"));
+        }
+    }
+
+    /// Sends the failed creation again.
+    pub fn retry_create(&mut self, request_id: Uuid) {
+        if let Some(request) = self.take_failed_create(request_id) {
+            self.send_request(request);
+        }
+    }
+
+    /// Forgets the failed creation.
+    pub fn discard_create(&mut self, request_id: Uuid) {
+        self.take_failed_create(request_id);
+    }
+
+    /// The failed update or deletion of the item, if any.
+    pub fn item_error(&self, id: T::Id) -> Option<&ListRequest<T>> {
+        self.item_errors
+            .iter()
+            .find_map(|(error_id, item_error)| (*error_id == id).then_some(&item_error.data))
+    }
+
+    fn take_item_error(&mut self, id: T::Id) -> Option<ListRequest<T>> {
+        let index = self
+            .item_errors
+            .iter()
+            .position(|(error_id, _)| *error_id == id)?;
+        let (_, item_error) = self.item_errors.remove(index);
+        self.dismiss_error_of(item_error.request_id);
+        if self.expanded_item_error == Some(id) {
+            self.expanded_item_error = None;
+        }
+        Some(item_error.data)
+    }
+
+    /// Sends the failed update or deletion of the item again.
+    pub fn retry_item(&mut self, id: T::Id) {
+        match self.take_item_error(id) {
+            Some(ListRequest::Update(id, update_output)) => self.send_update(id, update_output),
+            Some(request) => {
+                self.send_request(request);
+            }
+            None => (),
+        }
+    }
+
+    pub fn dismiss_item_error(&mut self, id: T::Id) {
+        self.take_item_error(id);
+        self.failed.retain(|failed_id| *failed_id != id);
+    }
+
+    fn toggle_item_error(&mut self, id: T::Id) {
+        self.expanded_item_error = match self.expanded_item_error {
+            Some(expanded_id) if expanded_id == id => None,
+            _ => Some(id),
+        };
+    }
+
+    fn resolve_pending_request(&mut self, request_id: Uuid) -> Option<Pending<ListRequest<T>>> {
+        let index = self
             .pending_requests
             .iter()
             .enumerate()
-            .find_map(|(i, pending)| (request_id == pending.request_id).then_some(i))
+            .find_map(|(i, pending)| (request_id == pending.request_id).then_some(i))?;
+        Some(self.pending_requests.remove(index))
+    }
+
+    /// The requests in flight with their description and age, the oldest first.
+    pub fn pending_operations(&self) -> Vec<(String, Duration)> {
+        self.pending_requests
+            .iter()
+            .map(|pending_request| (pending_request.data.description(), pending_request.age()))
+            .collect()
+    }
+
+    /// Fails the oldest request pending for longer than the leak timeout, as if it timed out
+    /// without being retried, and aborts it in case it is still running. Called by the leak
+    /// checker of the view, which provides the timeout error.
+    fn expire_leaked_request(&mut self, elapsed: Elapsed) {
+        let leaked = self.leak_timeout.and_then(|leak_timeout| {
+            self.pending_requests
+                .iter()
+                .find(|pending_request| pending_request.age() > leak_timeout)
+        });
+        if let Some(leaked) = leaked {
+            let request_id = leaked.request_id;
+            warn!(
+                age = ?leaked.age(),
+                "the request to {} never completed",
+                leaked.data.description()
+            );
+            if let Some(sender) = &self.sender {
+                let _ = sender.send(WorkerCommand::Cancel(request_id));
+            }
+            Pending::from((request_id, ListMessage::<T, S>::Error(elapsed.into())))
+                .with_attempt(self.retry_policy.max_attempts)
+                .handle(self);
+        }
+        if self.pending_requests.is_empty()
+            && let Some(leak_sender) = &self.leak_sender
         {
-            self.pending_requests.remove(index);
+            let _ = leak_sender.send(false);
+        }
+    }
+
+    /// Hides the create form and the actions changing the items, for showing the list without
+    /// letting it be changed. The storage should refuse the writes as well.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        if read_only {
+            self.editing = None;
+        }
+        self.read_only = read_only;
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Whether the worker started. Requests made before are dropped.
+    pub fn is_running(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    /// Creates an item, like submitting the create form.
+    pub fn create(&mut self, create_output: <T::CreateForm as Form>::Output) {
+        self.send_request(ListRequest::Create(create_output));
+    }
+
+    /// Creates all the items with a single request, showing one pending placeholder per item.
+    pub fn create_many(&mut self, create_outputs: Vec<<T::CreateForm as Form>::Output>) {
+        if !create_outputs.is_empty() {
+            self.send_request(ListRequest::CreateMany(create_outputs));
         }
     }
 
@@ -295,7 +1633,9 @@ where
         self.items.iter().find(|item| item.id() == id)
     }
 
+    /// Items are only changed through here, so that their views are rebuilt.
     fn get_mut(&mut self, id: T::Id) -> Option<&mut T> {
+        self.shown_items.remove(&id);
         self.items.iter_mut().find(|item| item.id() == id)
     }
 
@@ -308,6 +1648,10 @@ where
         {
             self.items.remove(index);
         }
+        self.shown_items.remove(&id);
+        self.failed.retain(|failed_id| *failed_id != id);
+        self.item_errors.retain(|(error_id, _)| *error_id != id);
+        self.deselect(id);
     }
 
     fn handle_create_submit(&mut self, submit: Submit) {
@@ -318,7 +1662,12 @@ where
             }
             Submit::Yes => {
                 if let Some(output) = self.create_form.submit() {
-                    self.send_request(ListRequest::Create(output));
+                    let mut outputs = T::CreateForm::split(output);
+                    if outputs.len() == 1 {
+                        self.send_request(ListRequest::Create(outputs.remove(0)));
+                    } else {
+                        self.create_many(outputs);
+                    }
                 }
             }
         }
@@ -334,7 +1683,7 @@ where
             Submit::Yes => {
                 if let Some(output) = self.update_form.submit() {
                     self.editing = None;
-                    self.send_request(ListRequest::Update(id, output));
+                    self.send_update(id, output);
                 }
             }
         }
@@ -343,9 +1692,11 @@ where
     fn item_view(
         editing: bool,
         pending_item_operation: PendingItemOperation,
-        id: T::Id,
-        item: &T,
+        selected: Option<bool>,
+        read_only: bool,
+        item: Arc<T>,
     ) -> impl WidgetView<Edit<Self>> + use<T, S> {
+        let id = item.id();
         if editing {
             Either::A(map_action(
                 lens(
@@ -357,37 +1708,168 @@ where
                 },
             ))
         } else {
-            Either::B(map_action(
-                map_state(
-                    item.view(pending_item_operation),
-                    move |state: &mut Self, ()| state.get(id).unwrap(),
-                ),
-                move |state: &mut Self, action| {
-                    action.handle(state, id);
-                },
-            ))
+            let data = ItemViewData {
+                item,
+                pending_item_operation,
+                selected,
+                read_only,
+            };
+            Either::B(memoize(data, |data: &ItemViewData<T>| {
+                let id = data.item.id();
+                let item_view = if data.read_only {
+                    Either::A(data.item.read_only_view())
+                } else {
+                    Either::B(data.item.view(data.pending_item_operation, data.selected))
+                };
+                map_action(
+                    map_state(item_view, move |state: &mut Self, ()| {
+                        state.get(id).unwrap()
+                    }),
+                    move |state: &mut Self, action| {
+                        action.handle(state, id);
+                    },
+                )
+            }))
         }
     }
 
+    /// Badge shown next to an item whose update or deletion failed, and the details shown when
+    /// it is clicked.
+    fn item_error_view(
+        &self,
+        id: T::Id,
+    ) -> Option<(
+        impl WidgetView<Edit<Self>> + use<T, S>,
+        Option<impl WidgetView<Edit<Self>> + use<T, S>>,
+    )> {
+        let item_error = self.item_error(id)?;
+        let badge = text_button("!", move |state: &mut Self| {
+            state.toggle_item_error(id);
+        })
+        .background_color(DANGER_COLOR);
+        let details = (self.expanded_item_error == Some(id)).then(|| {
+            flex_row((
+                label(format!("Couldn't {}", item_error.description())).color(DANGER_COLOR),
+                text_button("Retry", move |state: &mut Self| {
+                    state.retry_item(id);
+                }),
+                text_button("Dismiss", move |state: &mut Self| {
+                    state.dismiss_item_error(id);
+                }),
+            ))
+        });
+        Some((badge, details))
+    }
+
     fn process_items(&mut self) -> impl Iterator<Item = impl WidgetView<Edit<Self>> + use<T, S>> {
+        // Only indices are kept, so that the items aren't cloned on every rebuild.
         self.processed_items = self
             .items
             .iter()
-            .cloned()
-            .filter_map(|item| match self.filter(&item) {
-                (filter, score) if filter => Some((item, score)),
+            .enumerate()
+            .filter_map(|(index, item)| match self.filter(item) {
+                (filter, score) if filter => Some((index, score)),
                 _ => None,
             })
             .collect::<Vec<_>>();
+        let items = &self.items;
         if let Some(sorter) = &self.sorter {
             self.processed_items
-                .sort_by(|(a, sa), (b, sb)| sorter.sort(a, b, *sa, *sb));
+                .sort_by(|(a, sa), (b, sb)| sorter.sort(&items[*a], &items[*b], *sa, *sb));
+        }
+        let grouper = self.grouper.as_ref().filter(|grouper| grouper.enabled());
+        // The sort is stable, so items keep the order of the sorter inside of their group.
+        if let Some(grouper) = grouper {
+            self.processed_items
+                .sort_by(|(a, _), (b, _)| grouper.key(&items[*a]).cmp(&grouper.key(&items[*b])));
+        }
+        for (index, _) in &self.processed_items {
+            let item = &self.items[*index];
+            self.shown_items
+                .entry(item.id())
+                .or_insert_with(|| Arc::new(item.clone()));
         }
-        self.processed_items.iter().map(|(item, _)| {
-            let id = item.id();
-            let editing = self.editing == Some(id);
-            let pending_item_operation = self.pending_item_operation(id);
-            Self::item_view(editing, pending_item_operation, id, item)
+        let this = &*self;
+        let groups = match grouper {
+            Some(grouper) => this
+                .processed_items
+                .chunk_by(|(a, _), (b, _)| {
+                    grouper.key(&this.items[*a]) == grouper.key(&this.items[*b])
+                })
+                .collect::<Vec<_>>(),
+            None => vec![this.processed_items.as_slice()],
+        };
+        let user_order = this.user_order();
+        let groups = groups.into_iter().scan(0, |offset, group| {
+            let start = *offset;
+            *offset += group.len();
+            Some((start, group))
+        });
+        groups.flat_map(move |(start, group)| {
+            let header = grouper.zip(group.first()).map(|(grouper, (first, _))| {
+                let items = group
+                    .iter()
+                    .map(|(index, _)| &this.items[*index])
+                    .collect::<Vec<_>>();
+                Either::A(label(
+                    grouper.header(&grouper.key(&this.items[*first]), &items),
+                ))
+            });
+            let items = group.iter().enumerate().map(move |(i, (item_index, _))| {
+                let item = &this.items[*item_index];
+                let id = item.id();
+                let index = start + i;
+                let editing = this.editing == Some(id);
+                let pending_item_operation = this.pending_item_operation(id);
+                let selected =
+                    (this.selection_mode != SelectionMode::None).then(|| this.is_selected(id));
+                let shown_item = Arc::clone(&this.shown_items[&id]);
+                let item_view = Self::item_view(
+                    editing,
+                    pending_item_operation,
+                    selected,
+                    this.read_only,
+                    shown_item,
+                );
+                let (error_badge, error_details) = this.item_error_view(id).unzip();
+                let movable = user_order && !this.read_only;
+                let move_buttons = movable.then(|| {
+                    // The neighbors are captured by id, the shown items may change before a click.
+                    let neighbor = |index: Option<usize>| {
+                        index
+                            .and_then(|index| this.processed_items.get(index))
+                            .map(|(item_index, _)| this.items[*item_index].id())
+                    };
+                    let previous = neighbor(index.checked_sub(1));
+                    let next = neighbor(Some(index + 1));
+                    flex_col((
+                        text_button("↑", move |state: &mut Self| {
+                            if let Some(previous) = previous {
+                                state.move_to(id, previous);
+                            }
+                        })
+                        .disabled(previous.is_none()),
+                        text_button("↓", move |state: &mut Self| {
+                            if let Some(next) = next {
+                                state.move_to(id, next);
+                            }
+                        })
+                        .disabled(next.is_none()),
+                    ))
+                });
+                let row = flex_col((
+                    flex_row((move_buttons, item_view.flex(1.), error_badge)),
+                    error_details.flatten(),
+                ));
+                if !movable {
+                    return Either::B(Either::A(row));
+                }
+                Either::B(Either::B(draggable(row, move |state: &mut Self, rows| {
+                    let last = state.processed_items.len().saturating_sub(1);
+                    state.reorder(id, index.saturating_add_signed(rows).min(last));
+                })))
+            });
+            header.into_iter().chain(items)
         })
     }
 
@@ -397,63 +1879,322 @@ where
         self.pending_requests
             .iter()
             .enumerate()
-            .filter_map(|(i, pending_request)| {
-                matches!(pending_request.data, ListRequest::Create(_)).then_some(lens(
-                    T::pending_view,
-                    move |state: &mut Self, ()| match &state.pending_requests.get(i).unwrap().data {
-                        ListRequest::Create(create_output) => create_output,
-                        _ => unreachable!(),
-                    },
+            .flat_map(|(i, pending_request)| {
+                let count = match &pending_request.data {
+                    ListRequest::Create(_) => 1,
+                    ListRequest::CreateMany(create_outputs) => create_outputs.len(),
+                    _ => 0,
+                };
+                (0..count).map(move |j| {
+                    lens(T::pending_view, move |state: &mut Self, ()| {
+                        match &state.pending_requests.get(i).unwrap().data {
+                            ListRequest::Create(create_output) => create_output,
+                            ListRequest::CreateMany(create_outputs) => &create_outputs[j],
+                            _ => unreachable!(),
+                        }
+                    })
+                })
+            })
+    }
+
+    fn process_failed_items(
+        &self,
+    ) -> impl Iterator<Item = impl WidgetView<Edit<Self>> + use<T, S>> {
+        self.failed_creates
+            .iter()
+            .enumerate()
+            .map(|(i, failed_create)| {
+                let request_id = failed_create.request_id;
+                let count = match &failed_create.data {
+                    ListRequest::CreateMany(create_outputs) => create_outputs.len(),
+                    _ => 1,
+                };
+                let items = (0..count)
+                    .map(move |j| {
+                        lens(T::failed_view, move |state: &mut Self, ()| {
+                            match &state.failed_creates.get(i).unwrap().data {
+                                ListRequest::Create(create_output) => create_output,
+                                ListRequest::CreateMany(create_outputs) => &create_outputs[j],
+                                _ => unreachable!(),
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                flex_row((
+                    flex_col(items).flex(1.),
+                    text_button("Retry", move |state: &mut Self| {
+                        state.retry_create(request_id);
+                    }),
+                    text_button("Discard", move |state: &mut Self| {
+                        state.discard_create(request_id);
+                    }),
                 ))
             })
     }
 
     pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<T, S> {
-        let create_line = map_action(
-            lens(
-                <T::CreateForm as Form>::view,
-                move |state: &mut Self, ()| &mut state.create_form,
-            ),
-            |state: &mut Self, submit| {
-                state.handle_create_submit(submit);
+        let create_line = (!self.read_only).then(|| {
+            map_action(
+                lens(
+                    <T::CreateForm as Form>::view,
+                    move |state: &mut Self, ()| &mut state.create_form,
+                ),
+                |state: &mut Self, submit| {
+                    state.handle_create_submit(submit);
+                },
+            )
+        });
+        let refresh_button = if self.is_refreshing() {
+            Either::A(flex_row((
+                spinner()
+                    .width(BASIC_WIDGET_HEIGHT)
+                    .height(BASIC_WIDGET_HEIGHT),
+                label("Refreshing…"),
+            )))
+        } else {
+            Either::B(flex_row(text_button("Refresh (F5)", |state: &mut Self| {
+                state.reload();
+            })))
+        };
+        let auto_refresh_button = text_button(
+            format!("Auto-refresh: {}", self.auto_refresh),
+            |state: &mut Self| {
+                state.set_auto_refresh(state.auto_refresh.next());
+                state.save_settings();
             },
         );
+        let refresh_line = flex_row((refresh_button, auto_refresh_button));
         let filter_line = self.filter.as_mut().map(|filter| {
-            map_state(filter.view(), move |state: &mut Self, ()| {
-                state.filter.as_mut().unwrap()
-            })
+            map_action(
+                map_state(filter.view(), move |state: &mut Self, ()| {
+                    state.filter.as_mut().unwrap()
+                }),
+                |state: &mut Self, ()| {
+                    if state.filter != state.fetched_filter {
+                        state.fetch_all();
+                    }
+                    state.save_settings();
+                },
+            )
         });
         let sorter_line = self.sorter.as_mut().map(|sorter| {
-            map_state(sorter.view(), move |state: &mut Self, ()| {
-                state.sorter.as_mut().unwrap()
-            })
+            map_action(
+                map_state(sorter.view(), move |state: &mut Self, ()| {
+                    state.sorter.as_mut().unwrap()
+                }),
+                |state: &mut Self, ()| {
+                    state.save_settings();
+                },
+            )
+        });
+        let grouper_line = self.grouper.as_mut().map(|grouper| {
+            map_action(
+                map_state(grouper.view(), move |state: &mut Self, ()| {
+                    state.grouper.as_mut().unwrap()
+                }),
+                |state: &mut Self, ()| {
+                    state.save_settings();
+                },
+            )
+        });
+        let progress = self.progress.as_ref().map(|progress| {
+            flex_row((
+                progress_bar(Some(progress.done as f64 / progress.total.max(1) as f64)).flex(1.),
+                label(format!(
+                    "{} {} / {}",
+                    progress.action, progress.done, progress.total
+                )),
+            ))
         });
         let items = self.process_items().collect::<Vec<_>>();
         let pending_items = self.process_pending_items().collect::<Vec<_>>();
-        fork(
-            flex_col((create_line, filter_line, sorter_line, items, pending_items)),
+        let failed_items = self.process_failed_items().collect::<Vec<_>>();
+        let placeholder = if !items.is_empty() || !pending_items.is_empty() {
+            None
+        } else if self.is_refreshing() {
+            Some(Either::A(match &self.loading_view {
+                Some(loading_view) => Either::A(loading_view()),
+                None => Either::B(spinner().height(BASIC_WIDGET_HEIGHT)),
+            }))
+        } else {
+            Some(Either::B(match &self.empty_view {
+                Some(empty_view) => Either::A(empty_view()),
+                None => Either::B(label("Nothing here yet").color(MUTED_TEXT_COLOR)),
+            }))
+        };
+        let timeout = self.timeout;
+        let content = fork(
+            shortcut(
+                Key::Named(NamedKey::F5),
+                flex_col((
+                    create_line,
+                    refresh_line,
+                    filter_line,
+                    sorter_line,
+                    grouper_line,
+                    placeholder,
+                    items,
+                    progress,
+                    pending_items,
+                    failed_items,
+                )),
+                |state: &mut Self| state.reload(),
+            ),
             worker(
-                |proxy, mut rx: UnboundedReceiver<Pending<ListRequest<T>>>| async move {
-                    while let Some(pending_request) = rx.recv().await {
-                        pending_request.handle(&proxy).await;
+                move |proxy, mut rx: UnboundedReceiver<WorkerCommand<T>>| async move {
+                    // Requests run one after the other, each task waiting for the previous one,
+                    // so that the running one can be aborted without blocking the others.
+                    let mut previous: Option<JoinHandle<()>> = None;
+                    let mut running: Vec<RunningRequest> = Vec::new();
+                    while let Some(command) = rx.recv().await {
+                        running.retain(|request| !request.abort_handle.is_finished());
+                        match command {
+                            WorkerCommand::Run(pending_request) => {
+                                let request_id = pending_request.request_id;
+                                let proxy = proxy.clone();
+                                let emit: Emit<T, S> = Arc::new(move |pending_message| {
+                                    let _ = proxy.message(pending_message);
+                                });
+                                let started = Arc::new(AtomicBool::new(false));
+                                let cancelled = Arc::new(AtomicBool::new(false));
+                                let previous_task = previous.take();
+                                let task = xilem::tokio::spawn({
+                                    let started = started.clone();
+                                    let cancelled = cancelled.clone();
+                                    async move {
+                                        if let Some(previous_task) = previous_task {
+                                            let _ = previous_task.await;
+                                        }
+                                        started.store(true, Ordering::SeqCst);
+                                        if !cancelled.load(Ordering::SeqCst) {
+                                            pending_request.handle(emit, timeout).await;
+                                        }
+                                    }
+                                });
+                                running.push(RunningRequest {
+                                    request_id,
+                                    abort_handle: task.abort_handle(),
+                                    started,
+                                    cancelled,
+                                });
+                                previous = Some(task);
+                            }
+                            WorkerCommand::Cancel(request_id) => {
+                                running
+                                    .iter()
+                                    .filter(|request| request.request_id == request_id)
+                                    .for_each(RunningRequest::cancel);
+                            }
+                        }
                     }
                 },
                 |state: &mut Self, sender| {
                     state.sender = Some(sender);
-                    state.send_request(ListRequest::FetchAll);
+                    state.send_request(ListRequest::LoadSettings);
                 },
                 |state: &mut Self, pending_message: Pending<ListMessage<T, S>>| {
                     pending_message.handle(state);
                 },
             ),
+        );
+        let content = fork(
+            content,
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Option<Duration>>| async move {
+                    let mut interval = None;
+                    loop {
+                        let received = match interval {
+                            Some(interval) => match timeout(interval, rx.recv()).await {
+                                Ok(received) => received,
+                                Err(_) => {
+                                    if proxy.message(()).is_err() {
+                                        break;
+                                    }
+                                    continue;
+                                }
+                            },
+                            None => rx.recv().await,
+                        };
+                        match received {
+                            Some(new_interval) => interval = new_interval,
+                            None => break,
+                        }
+                    }
+                },
+                |state: &mut Self, sender: UnboundedSender<Option<Duration>>| {
+                    let _ = sender.send(state.auto_refresh.interval());
+                    state.auto_refresh_sender = Some(sender);
+                },
+                |state: &mut Self, ()| {
+                    state.reload();
+                },
+            ),
+        );
+        fork(
+            content,
+            worker(
+                |proxy, mut rx: UnboundedReceiver<bool>| async move {
+                    // Checks every `LEAK_CHECK_INTERVAL` while requests are pending, the timeout
+                    // giving the error of the requests that leaked.
+                    let mut pending = false;
+                    loop {
+                        let received = if pending {
+                            match timeout(LEAK_CHECK_INTERVAL, rx.recv()).await {
+                                Ok(received) => received,
+                                Err(elapsed) => {
+                                    if proxy.message(elapsed).is_err() {
+                                        break;
+                                    }
+                                    continue;
+                                }
+                            }
+                        } else {
+                            rx.recv().await
+                        };
+                        match received {
+                            Some(new_pending) => pending = new_pending,
+                            None => break,
+                        }
+                    }
+                },
+                |state: &mut Self, sender: UnboundedSender<bool>| {
+                    let _ = sender.send(!state.pending_requests.is_empty());
+                    state.leak_sender = Some(sender);
+                },
+                |state: &mut Self, elapsed: Elapsed| {
+                    state.expire_leaked_request(elapsed);
+                },
+            ),
         )
     }
 
     pub fn error_view(&mut self) -> Option<impl WidgetView<Edit<Self>> + use<T, S>> {
-        self.storage.last_error().as_ref().map(|error| {
-            map_state(error.view(), move |state: &mut Self, ()| {
-                state.storage.last_error().as_ref().unwrap()
+        let failed_request = self
+            .failed_request
+            .as_ref()
+            .map(|failed_request| &failed_request.data);
+        let error = self.storage.last_error().as_ref()?;
+        if let Some(error_view) = &self.error_view {
+            return Some(Either::A(error_view(failed_request, error)));
+        }
+        let description = failed_request
+            .map(|failed_request| label(format!("Couldn't {}:", failed_request.description())));
+        let retry_button = failed_request.map(|_| {
+            text_button("Retry", |state: &mut Self| {
+                state.retry_failed_request();
             })
-        })
+        });
+        let error = map_state(error.view(), move |state: &mut Self, ()| {
+            state.storage.last_error().as_ref().unwrap()
+        });
+        let dismiss_button = text_button("Dismiss", |state: &mut Self| {
+            state.dismiss_error();
+        });
+        Some(Either::B(flex_row((
+            description,
+            error,
+            retry_button,
+            dismiss_button,
+        ))))
     }
 }
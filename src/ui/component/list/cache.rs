@@ -0,0 +1,297 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::ui::component::Form;
+use crate::ui::component::list::storage::{ListStorage, Progress};
+use crate::ui::component::list::{ListDelta, ListItem, ListPage, ListQuery, ListSettings};
+
+static CACHES: LazyLock<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+struct Cache<T>
+where
+    T: ListItem,
+{
+    items: HashMap<T::Id, T>,
+    /// Ids of the items returned for each query, in order.
+    queries: Vec<(ListQuery<T>, Vec<T::Id>, Instant)>,
+    /// Query being fetched page by page, cached once its last page arrives.
+    partial_query: Option<(ListQuery<T>, Vec<T::Id>)>,
+}
+
+impl<T> Default for Cache<T>
+where
+    T: ListItem,
+{
+    fn default() -> Self {
+        Self {
+            items: HashMap::new(),
+            queries: Vec::new(),
+            partial_query: None,
+        }
+    }
+}
+
+impl<T> Cache<T>
+where
+    T: ListItem,
+{
+    fn get(&self, query: &ListQuery<T>, ttl: Duration) -> Option<Vec<T>> {
+        let (_, ids, _) = self.queries.iter().find(|(cached_query, _, fetched_at)| {
+            cached_query == query && fetched_at.elapsed() < ttl
+        })?;
+        ids.iter().map(|id| self.items.get(id).cloned()).collect()
+    }
+
+    fn insert_items(&mut self, items: &[T]) {
+        self.items
+            .extend(items.iter().map(|item| (item.id(), item.clone())));
+    }
+
+    fn insert_query(&mut self, query: ListQuery<T>, items: &[T]) {
+        self.insert_items(items);
+        self.queries
+            .retain(|(cached_query, _, _)| *cached_query != query);
+        let ids = items.iter().map(ListItem::id).collect();
+        self.queries.push((query, ids, Instant::now()));
+    }
+
+    fn insert_page(&mut self, query: ListQuery<T>, offset: usize, page: &ListPage<T>) {
+        self.insert_items(&page.items);
+        let ids = page.items.iter().map(ListItem::id);
+        if offset == 0 {
+            self.partial_query = Some((query, ids.collect()));
+        } else if let Some((partial_query, partial_ids)) = &mut self.partial_query
+            && *partial_query == query
+        {
+            partial_ids.extend(ids);
+        } else {
+            return;
+        }
+        if let Some((query, ids)) = self
+            .partial_query
+            .take_if(|(_, ids)| ids.len() >= page.total)
+        {
+            self.queries
+                .retain(|(cached_query, _, _)| *cached_query != query);
+            self.queries.push((query, ids, Instant::now()));
+        }
+    }
+
+    fn remove(&mut self, id: T::Id) {
+        self.items.remove(&id);
+    }
+
+    /// Query results are dropped on every mutation since they may not match anymore, even when it
+    /// failed since some of the items may have been changed before the error.
+    fn invalidate(&mut self) {
+        self.queries.clear();
+        self.partial_query = None;
+    }
+}
+
+/// Wraps a storage to keep the fetched items in memory for `TTL_SECS` seconds, so that switching
+/// back to a query already fetched doesn't hit the storage again.
+#[derive(Default, Debug)]
+pub struct CachedStorage<S, const TTL_SECS: u64 = 30> {
+    storage: S,
+}
+
+impl<S, const TTL_SECS: u64> CachedStorage<S, TTL_SECS>
+where
+    S: ListStorage,
+{
+    const TTL: Duration = Duration::from_secs(TTL_SECS);
+
+    /// Each storage type has its own cache, so the lists of different storages never see each
+    /// other's items.
+    fn cache<R>(f: impl FnOnce(&mut Cache<S::Item>) -> R) -> R {
+        let mut caches = CACHES.lock().unwrap();
+        let cache = caches
+            .entry(TypeId::of::<Self>())
+            .or_insert_with(|| Box::new(Cache::<S::Item>::default()));
+        f(cache.downcast_mut().unwrap())
+    }
+}
+
+impl<S, const TTL_SECS: u64> ListStorage for CachedStorage<S, TTL_SECS>
+where
+    S: ListStorage,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+    type Summary = S::Summary;
+
+    fn last_error(&mut self) -> &mut Option<S::Error> {
+        self.storage.last_error()
+    }
+
+    async fn fetch_all(query: ListQuery<S::Item>) -> Result<Vec<S::Item>, S::Error> {
+        if let Some(items) = Self::cache(|cache| cache.get(&query, Self::TTL)) {
+            return Ok(items);
+        }
+        let items = S::fetch_all(query.clone()).await?;
+        Self::cache(|cache| cache.insert_query(query, &items));
+        Ok(items)
+    }
+
+    async fn fetch_page(
+        query: ListQuery<S::Item>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ListPage<S::Item>, S::Error> {
+        if offset == 0
+            && let Some(items) = Self::cache(|cache| cache.get(&query, Self::TTL))
+        {
+            let total = items.len();
+            return Ok(ListPage { items, total });
+        }
+        let page = S::fetch_page(query.clone(), offset, limit).await?;
+        Self::cache(|cache| cache.insert_page(query, offset, &page));
+        Ok(page)
+    }
+
+    async fn fetch_since(since: String) -> Result<ListDelta<S::Item>, S::Error> {
+        let delta = S::fetch_since(since).await?;
+        Self::cache(|cache| {
+            if !delta.changed.is_empty() || !delta.deleted.is_empty() {
+                cache.invalidate();
+            }
+            cache.insert_items(&delta.changed);
+            for id in &delta.deleted {
+                cache.remove(*id);
+            }
+        });
+        Ok(delta)
+    }
+
+    async fn fetch_summary() -> Result<S::Summary, S::Error> {
+        S::fetch_summary().await
+    }
+
+    async fn create(
+        create_output: <<S::Item as ListItem>::CreateForm as Form>::Output,
+    ) -> Result<S::Item, S::Error> {
+        let result = S::create(create_output).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(item) = &result {
+                cache.insert_items(std::slice::from_ref(item));
+            }
+        });
+        result
+    }
+
+    async fn create_many(
+        create_outputs: Vec<<<S::Item as ListItem>::CreateForm as Form>::Output>,
+        progress: Progress,
+    ) -> Result<Vec<S::Item>, S::Error> {
+        let result = S::create_many(create_outputs, progress).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(items) = &result {
+                cache.insert_items(items);
+            }
+        });
+        result
+    }
+
+    async fn update(
+        id: <S::Item as ListItem>::Id,
+        update_output: <<S::Item as ListItem>::UpdateForm as Form>::Output,
+    ) -> Result<S::Item, S::Error> {
+        let result = S::update(id, update_output).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(item) = &result {
+                cache.insert_items(std::slice::from_ref(item));
+            }
+        });
+        result
+    }
+
+    async fn delete(id: <S::Item as ListItem>::Id) -> Result<<S::Item as ListItem>::Id, S::Error> {
+        let result = S::delete(id).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(id) = &result {
+                cache.remove(*id);
+            }
+        });
+        result
+    }
+
+    async fn update_many(
+        updates: Vec<(
+            <S::Item as ListItem>::Id,
+            <<S::Item as ListItem>::UpdateForm as Form>::Output,
+        )>,
+        progress: Progress,
+    ) -> Result<Vec<S::Item>, S::Error> {
+        let result = S::update_many(updates, progress).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(items) = &result {
+                cache.insert_items(items);
+            }
+        });
+        result
+    }
+
+    async fn delete_many(
+        ids: Vec<<S::Item as ListItem>::Id>,
+        progress: Progress,
+    ) -> Result<Vec<<S::Item as ListItem>::Id>, S::Error> {
+        let result = S::delete_many(ids, progress).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(ids) = &result {
+                for id in ids {
+                    cache.remove(*id);
+                }
+            }
+        });
+        result
+    }
+
+    async fn set_status_many(
+        items: Vec<S::Item>,
+        status: <S::Item as ListItem>::Status,
+        progress: Progress,
+    ) -> Result<Vec<S::Item>, S::Error> {
+        let result = S::set_status_many(items, status, progress).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(items) = &result {
+                cache.insert_items(items);
+            }
+        });
+        result
+    }
+
+    async fn reorder(ids: Vec<<S::Item as ListItem>::Id>) -> Result<Vec<S::Item>, S::Error> {
+        let result = S::reorder(ids).await;
+        Self::cache(|cache| {
+            cache.invalidate();
+            if let Ok(items) = &result {
+                cache.insert_items(items);
+            }
+        });
+        result
+    }
+
+    async fn load_settings() -> Result<ListSettings, S::Error> {
+        S::load_settings().await
+    }
+
+    async fn save_settings(settings: ListSettings) -> Result<(), S::Error> {
+        S::save_settings(settings).await
+    }
+
+    fn invalidate() {
+        Self::cache(Cache::invalidate);
+        S::invalidate();
+    }
+}
@@ -0,0 +1,225 @@
+//! Storage wrapper injecting failures, timeouts and delays at random, so that the retries, the
+//! rollback of optimistic updates and the error banner can be tried without breaking the database.
+//! Only compiled in debug builds.
+//!
+//! Configured with `set_chaos` or the `TODOS_CHAOS` environment variable, like
+//! `TODOS_CHAOS=failures=0.2,timeouts=0.1,delay_ms=2000`. Nothing is injected by default.
+
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+use tracing::{debug, warn};
+use xilem::tokio::time::error::Elapsed;
+use xilem::tokio::time::{sleep, timeout};
+
+use crate::core::ServerError;
+use crate::ui::component::Form;
+use crate::ui::component::list::storage::{ListStorage, Progress};
+use crate::ui::component::list::{ListDelta, ListItem, ListPage, ListQuery, ListSettings};
+
+static CHAOS: LazyLock<RwLock<Chaos>> = LazyLock::new(|| RwLock::new(Chaos::from_env()));
+
+/// What `ChaosStorage` injects in each operation.
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub struct Chaos {
+    /// Probability, between `0.0` and `1.0`, that an operation fails without being retried.
+    pub failures: f32,
+    /// Probability that an operation times out, which the list retries.
+    pub timeouts: f32,
+    /// Operations are delayed by up to this duration.
+    pub max_delay: Duration,
+}
+
+impl Chaos {
+    /// Reads `TODOS_CHAOS`, ignoring the invalid settings.
+    fn from_env() -> Self {
+        std::env::var("TODOS_CHAOS")
+            .map(|chaos| Self::parse(&chaos))
+            .unwrap_or_default()
+    }
+
+    /// Parses comma-separated settings like `failures=0.2,timeouts=0.1,delay_ms=2000`.
+    pub fn parse(settings: &str) -> Self {
+        let mut chaos = Self::default();
+        for setting in settings.split(',').filter(|setting| !setting.is_empty()) {
+            let parsed = match setting.split_once('=') {
+                Some(("failures", value)) => value.parse().map(|value| chaos.failures = value).ok(),
+                Some(("timeouts", value)) => value.parse().map(|value| chaos.timeouts = value).ok(),
+                Some(("delay_ms", value)) => value
+                    .parse()
+                    .map(|value| chaos.max_delay = Duration::from_millis(value))
+                    .ok(),
+                _ => None,
+            };
+            if parsed.is_none() {
+                warn!("ignored the invalid chaos setting {setting}");
+            }
+        }
+        chaos
+    }
+}
+
+/// Replaces what `ChaosStorage` injects from now on.
+pub fn set_chaos(chaos: Chaos) {
+    *CHAOS.write().unwrap() = chaos;
+}
+
+/// Errors that `ChaosStorage` can make up.
+pub trait Injected {
+    /// A failure that isn't retried.
+    fn injected() -> Self;
+}
+
+impl Injected for ServerError {
+    fn injected() -> Self {
+        ServerError::Database(sqlx::Error::Protocol("injected failure".to_string()))
+    }
+}
+
+/// Between `0.0` and `1.0`.
+fn random() -> f32 {
+    RandomState::new().hash_one(0) as f32 / u64::MAX as f32
+}
+
+/// Waits for the random delay, then fails if the dice say so.
+async fn inject<E>(operation: &str) -> Result<(), E>
+where
+    E: Injected + From<Elapsed>,
+{
+    let chaos = *CHAOS.read().unwrap();
+    if !chaos.max_delay.is_zero() {
+        sleep(chaos.max_delay.mul_f32(random())).await;
+    }
+    if random() < chaos.failures {
+        debug!("injected a failure in {operation}");
+        return Err(E::injected());
+    }
+    if random() < chaos.timeouts {
+        debug!("injected a timeout in {operation}");
+        // `Elapsed` can only be made by a timeout.
+        let elapsed = timeout(Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        return Err(elapsed.into());
+    }
+    Ok(())
+}
+
+/// Wraps a storage to make its operations fail, time out or slow down at random, following
+/// `set_chaos`. The settings of the list are passed through, so that the list still loads.
+#[derive(Default, Debug)]
+pub struct ChaosStorage<S> {
+    storage: S,
+}
+
+impl<S> ListStorage for ChaosStorage<S>
+where
+    S: ListStorage,
+    S::Error: Injected,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+    type Summary = S::Summary;
+
+    fn last_error(&mut self) -> &mut Option<S::Error> {
+        self.storage.last_error()
+    }
+
+    async fn fetch_all(query: ListQuery<S::Item>) -> Result<Vec<S::Item>, S::Error> {
+        inject::<S::Error>("fetch_all").await?;
+        S::fetch_all(query).await
+    }
+
+    async fn fetch_page(
+        query: ListQuery<S::Item>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ListPage<S::Item>, S::Error> {
+        inject::<S::Error>("fetch_page").await?;
+        S::fetch_page(query, offset, limit).await
+    }
+
+    async fn fetch_since(since: String) -> Result<ListDelta<S::Item>, S::Error> {
+        inject::<S::Error>("fetch_since").await?;
+        S::fetch_since(since).await
+    }
+
+    async fn fetch_summary() -> Result<S::Summary, S::Error> {
+        inject::<S::Error>("fetch_summary").await?;
+        S::fetch_summary().await
+    }
+
+    async fn create(
+        create_output: <<S::Item as ListItem>::CreateForm as Form>::Output,
+    ) -> Result<S::Item, S::Error> {
+        inject::<S::Error>("create").await?;
+        S::create(create_output).await
+    }
+
+    async fn create_many(
+        create_outputs: Vec<<<S::Item as ListItem>::CreateForm as Form>::Output>,
+        progress: Progress,
+    ) -> Result<Vec<S::Item>, S::Error> {
+        inject::<S::Error>("create_many").await?;
+        S::create_many(create_outputs, progress).await
+    }
+
+    async fn update(
+        id: <S::Item as ListItem>::Id,
+        update_output: <<S::Item as ListItem>::UpdateForm as Form>::Output,
+    ) -> Result<S::Item, S::Error> {
+        inject::<S::Error>("update").await?;
+        S::update(id, update_output).await
+    }
+
+    async fn delete(id: <S::Item as ListItem>::Id) -> Result<<S::Item as ListItem>::Id, S::Error> {
+        inject::<S::Error>("delete").await?;
+        S::delete(id).await
+    }
+
+    async fn update_many(
+        updates: Vec<(
+            <S::Item as ListItem>::Id,
+            <<S::Item as ListItem>::UpdateForm as Form>::Output,
+        )>,
+        progress: Progress,
+    ) -> Result<Vec<S::Item>, S::Error> {
+        inject::<S::Error>("update_many").await?;
+        S::update_many(updates, progress).await
+    }
+
+    async fn delete_many(
+        ids: Vec<<S::Item as ListItem>::Id>,
+        progress: Progress,
+    ) -> Result<Vec<<S::Item as ListItem>::Id>, S::Error> {
+        inject::<S::Error>("delete_many").await?;
+        S::delete_many(ids, progress).await
+    }
+
+    async fn set_status_many(
+        items: Vec<S::Item>,
+        status: <S::Item as ListItem>::Status,
+        progress: Progress,
+    ) -> Result<Vec<S::Item>, S::Error> {
+        inject::<S::Error>("set_status_many").await?;
+        S::set_status_many(items, status, progress).await
+    }
+
+    async fn reorder(ids: Vec<<S::Item as ListItem>::Id>) -> Result<Vec<S::Item>, S::Error> {
+        inject::<S::Error>("reorder").await?;
+        S::reorder(ids).await
+    }
+
+    async fn load_settings() -> Result<ListSettings, S::Error> {
+        S::load_settings().await
+    }
+
+    async fn save_settings(settings: ListSettings) -> Result<(), S::Error> {
+        S::save_settings(settings).await
+    }
+
+    fn invalidate() {
+        S::invalidate();
+    }
+}
@@ -1,12 +1,12 @@
 use xilem::WidgetView;
-use xilem::core::Edit;
-use xilem::view::flex_row;
+use xilem::core::{Edit, map_state};
+use xilem::view::{FlexExt, flex_row};
 
 use crate::ui::component::list::ListItem;
 
 pub trait ListFilter
 where
-    Self: Default + Sized + 'static,
+    Self: Default + Clone + PartialEq + Send + Sized + 'static,
 {
     type Item;
 
@@ -16,11 +16,36 @@ where
     /// be between `0.0` and `1.0`. To disable filtering completely, please return `(true, 0.0)`
     /// so that `ListSorter` can ignore the `score` value when sorting.
     fn filter(&self, item: &Self::Item) -> (bool, f32);
+    /// Whether the filter currently restricts the items. Disabled filters match every item, and
+    /// `AnyOf` skips them.
+    fn enabled(&self) -> bool {
+        true
+    }
+    /// Serializes the state of the filter so that it can be restored on the next launch. Filters
+    /// returning `None` aren't persisted.
+    fn save(&self) -> Option<String> {
+        None
+    }
+    fn load(&mut self, saved: &str) {
+        let _ = saved;
+    }
 }
 
 #[derive(Default)]
 pub struct NoFilter<T>(std::marker::PhantomData<T>);
 
+impl<T> Clone for NoFilter<T> {
+    fn clone(&self) -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<T> PartialEq for NoFilter<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 impl<T> ListFilter for NoFilter<T>
 where
     T: ListItem + Default,
@@ -34,4 +59,123 @@ where
     fn filter(&self, _item: &Self::Item) -> (bool, f32) {
         (true, 0.)
     }
+
+    fn enabled(&self) -> bool {
+        false
+    }
+}
+
+/// Keeps the items matching both filters. The score is the sum of both scores, clamped to `1.0`.
+///
+/// Nest combinators to combine more than two filters, e.g. `AllOf<AllOf<A, B>, C>`.
+#[derive(Default, PartialEq, Clone)]
+pub struct AllOf<A, B>(pub A, pub B);
+
+/// Keeps the items matching at least one of the enabled filters, all the items when none is. The
+/// score is the best score among the matching filters.
+#[derive(Default, PartialEq, Clone)]
+pub struct AnyOf<A, B>(pub A, pub B);
+
+/// Saves both filters on two lines. Splitting on the last line break allows nesting combinators
+/// on the left side.
+fn combined_save(first: Option<String>, second: Option<String>) -> Option<String> {
+    if first.is_none() && second.is_none() {
+        return None;
+    }
+    Some(format!(
+        "{}\n{}",
+        first.unwrap_or_default(),
+        second.unwrap_or_default()
+    ))
+}
+
+fn combined_load<A, B>(first: &mut A, second: &mut B, saved: &str)
+where
+    A: ListFilter,
+    B: ListFilter,
+{
+    if let Some((first_saved, second_saved)) = saved.rsplit_once('\n') {
+        first.load(first_saved);
+        second.load(second_saved);
+    }
+}
+
+impl<A, B> ListFilter for AllOf<A, B>
+where
+    A: ListFilter,
+    B: ListFilter<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<A, B> {
+        let first = map_state(self.0.view(), |state: &mut Self, ()| &mut state.0);
+        let second = map_state(self.1.view(), |state: &mut Self, ()| &mut state.1);
+        flex_row((first.flex(1.), second))
+    }
+
+    fn filter(&self, item: &Self::Item) -> (bool, f32) {
+        let (first_filter, first_score) = self.0.filter(item);
+        let (second_filter, second_score) = self.1.filter(item);
+        (
+            first_filter && second_filter,
+            (first_score + second_score).min(1.),
+        )
+    }
+
+    fn enabled(&self) -> bool {
+        self.0.enabled() || self.1.enabled()
+    }
+
+    fn save(&self) -> Option<String> {
+        combined_save(self.0.save(), self.1.save())
+    }
+
+    fn load(&mut self, saved: &str) {
+        combined_load(&mut self.0, &mut self.1, saved);
+    }
+}
+
+impl<A, B> ListFilter for AnyOf<A, B>
+where
+    A: ListFilter,
+    B: ListFilter<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<A, B> {
+        let first = map_state(self.0.view(), |state: &mut Self, ()| &mut state.0);
+        let second = map_state(self.1.view(), |state: &mut Self, ()| &mut state.1);
+        flex_row((first.flex(1.), second))
+    }
+
+    fn filter(&self, item: &Self::Item) -> (bool, f32) {
+        // A disabled filter matches every item, which would make the others useless.
+        match (self.0.enabled(), self.1.enabled()) {
+            (false, false) => return (true, 0.),
+            (true, false) => return self.0.filter(item),
+            (false, true) => return self.1.filter(item),
+            (true, true) => (),
+        }
+        let (first_filter, first_score) = self.0.filter(item);
+        let (second_filter, second_score) = self.1.filter(item);
+        let score = match (first_filter, second_filter) {
+            (true, true) => first_score.max(second_score),
+            (true, false) => first_score,
+            (false, true) => second_score,
+            (false, false) => 0.,
+        };
+        (first_filter || second_filter, score)
+    }
+
+    fn enabled(&self) -> bool {
+        self.0.enabled() || self.1.enabled()
+    }
+
+    fn save(&self) -> Option<String> {
+        combined_save(self.0.save(), self.1.save())
+    }
+
+    fn load(&mut self, saved: &str) {
+        combined_load(&mut self.0, &mut self.1, saved);
+    }
 }
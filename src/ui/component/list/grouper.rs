@@ -0,0 +1,65 @@
+use xilem::WidgetView;
+use xilem::core::Edit;
+use xilem::view::flex_row;
+
+use crate::ui::component::list::ListItem;
+
+pub trait ListGrouper
+where
+    Self: Default + Clone + PartialEq + Send + Sized + 'static,
+{
+    type Item;
+    /// Groups are shown in the order of their keys.
+    type Key: Ord;
+
+    fn enabled(&self) -> bool;
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<Self>;
+    fn key(&self, item: &Self::Item) -> Self::Key;
+    /// Text shown above the group, usually its name followed by aggregates computed on `items`.
+    fn header(&self, key: &Self::Key, items: &[&Self::Item]) -> String;
+    /// Serializes the state of the grouper so that it can be restored on the next launch.
+    /// Groupers returning `None` aren't persisted.
+    fn save(&self) -> Option<String> {
+        None
+    }
+    fn load(&mut self, saved: &str) {
+        let _ = saved;
+    }
+}
+
+#[derive(Default)]
+pub struct NoGrouper<T>(std::marker::PhantomData<T>);
+
+impl<T> Clone for NoGrouper<T> {
+    fn clone(&self) -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<T> PartialEq for NoGrouper<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> ListGrouper for NoGrouper<T>
+where
+    T: ListItem + Default,
+{
+    type Item = T;
+    type Key = ();
+
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<T> {
+        flex_row(())
+    }
+
+    fn key(&self, _item: &Self::Item) -> Self::Key {}
+
+    fn header(&self, _key: &Self::Key, _items: &[&Self::Item]) -> String {
+        String::new()
+    }
+}
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::timeout;
+use xilem::view::{text_input, worker};
+
+use crate::ui::component::list::ListFilter;
+
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
+
+pub trait Searchable {
+    fn search_text(&self) -> &str;
+}
+
+/// Returns how well `query` matches `text`, between `0.0` and `1.0`, or `None` if `text` doesn't
+/// contain `query`. Matches at the start of `text` and matches covering more of `text` score higher.
+pub fn match_score(query: &str, text: &str) -> Option<f32> {
+    let query = query.to_lowercase();
+    let text = text.to_lowercase();
+    let position = text.find(&query)?;
+    let coverage = query.chars().count() as f32 / text.chars().count().max(1) as f32;
+    let start_bonus = if position == 0 { 0.5 } else { 0. };
+    Some(start_bonus + 0.5 * coverage)
+}
+
+/// Text filter whose query is only updated once the user stops typing, so that the list isn't
+/// filtered (and possibly fetched again) on every keystroke.
+pub struct SearchFilter<T> {
+    input: String,
+    query: String,
+    sender: Option<UnboundedSender<String>>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for SearchFilter<T> {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            query: String::new(),
+            sender: None,
+            item: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for SearchFilter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            input: self.input.clone(),
+            query: self.query.clone(),
+            sender: self.sender.clone(),
+            item: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for SearchFilter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query
+    }
+}
+
+impl<T> SearchFilter<T> {
+    pub fn query(&self) -> Option<&str> {
+        (!self.query.is_empty()).then_some(&self.query)
+    }
+}
+
+impl<T> ListFilter for SearchFilter<T>
+where
+    T: Searchable + 'static,
+{
+    type Item = T;
+
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<T> {
+        fork(
+            text_input(self.input.clone(), |state: &mut Self, input| {
+                if let Some(sender) = &state.sender {
+                    let _ = sender.send(input.clone());
+                }
+                state.input = input;
+            })
+            .placeholder("Search"),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<String>| async move {
+                    while let Some(mut input) = rx.recv().await {
+                        while let Ok(Some(next_input)) = timeout(DEBOUNCE_DELAY, rx.recv()).await {
+                            input = next_input;
+                        }
+                        let _ = proxy.message(input.trim().to_string());
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, query: String| {
+                    state.query = query;
+                },
+            ),
+        )
+    }
+
+    fn enabled(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    fn filter(&self, item: &T) -> (bool, f32) {
+        if self.query.is_empty() {
+            return (true, 0.);
+        }
+        match match_score(&self.query, item.search_text()) {
+            Some(score) => (true, score),
+            None => (false, 0.),
+        }
+    }
+}
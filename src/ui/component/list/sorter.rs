@@ -8,18 +8,47 @@ use crate::ui::component::list::ListItem;
 
 pub trait ListSorter
 where
-    Self: Default + Sized + 'static,
+    Self: Default + Clone + PartialEq + Send + Sized + 'static,
 {
     type Item;
 
     fn enabled(&self) -> bool;
     fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<Self>;
     fn sort(&self, a: &Self::Item, b: &Self::Item, score_a: f32, score_b: f32) -> Ordering;
+    /// Serializes the state of the sorter so that it can be restored on the next launch. Sorters
+    /// returning `None` aren't persisted.
+    fn save(&self) -> Option<String> {
+        None
+    }
+    fn load(&mut self, saved: &str) {
+        let _ = saved;
+    }
+    /// Whether the items are sorted in an order chosen by the user, who can then move them.
+    fn user_order(&self) -> bool {
+        false
+    }
+    /// Moves the item at `index` to `new_index` by changing the sort keys of `items`, which are
+    /// sorted by this sorter. Only called in user order.
+    fn reorder(&self, items: &mut [Self::Item], index: usize, new_index: usize) {
+        let _ = (items, index, new_index);
+    }
 }
 
 #[derive(Default)]
 pub struct NoSorter<T>(std::marker::PhantomData<T>);
 
+impl<T> Clone for NoSorter<T> {
+    fn clone(&self) -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<T> PartialEq for NoSorter<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 impl<T> ListSorter for NoSorter<T>
 where
     T: ListItem + Default,
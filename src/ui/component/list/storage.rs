@@ -1,4 +1,10 @@
-use crate::ui::component::list::ListItem;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::Arc;
+use std::time::Duration;
+
+use xilem::tokio::time::error::Elapsed;
+
+use crate::ui::component::list::{ListDelta, ListItem, ListPage, ListQuery, ListSettings};
 use crate::ui::component::{ErrorView, Form};
 
 pub trait ListStorage
@@ -6,14 +12,63 @@ where
     Self: Default + std::fmt::Debug + 'static,
 {
     type Item: ListItem;
-    type Error: ErrorView + Retryable + std::fmt::Debug + Send;
+    /// Errors must be convertible from `Elapsed` so that operations can time out.
+    type Error: ErrorView + Retryable + From<Elapsed> + std::fmt::Debug + Send;
+    /// Aggregated data about the whole storage (counts, totals...) that is cheap to compute
+    /// without fetching every item.
+    type Summary: Default + Clone + std::fmt::Debug + Send;
 
     fn last_error(&mut self) -> &mut Option<Self::Error>;
 
-    fn fetch_all() -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>> + Send;
+    /// The query can be used to filter and sort the items directly in the storage instead of
+    /// fetching everything. Items are still filtered and sorted by the list afterward.
+    fn fetch_all(
+        query: ListQuery<Self::Item>,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>> + Send;
+    /// Fetches `limit` items starting at `offset`, so that the first items can be shown while the
+    /// others are loading. By default, all the items are fetched as a single page.
+    fn fetch_page(
+        query: ListQuery<Self::Item>,
+        offset: usize,
+        limit: usize,
+    ) -> impl Future<Output = Result<ListPage<Self::Item>, Self::Error>> + Send {
+        let _ = limit;
+        async move {
+            if offset > 0 {
+                return Ok(ListPage {
+                    items: Vec::new(),
+                    total: offset,
+                });
+            }
+            let items = Self::fetch_all(query).await?;
+            let total = items.len();
+            Ok(ListPage { items, total })
+        }
+    }
+    /// Returns the items changed or deleted since the `since` timestamp, see `ListItem::updated_at`.
+    fn fetch_since(
+        since: String,
+    ) -> impl Future<Output = Result<ListDelta<Self::Item>, Self::Error>> + Send;
+    fn fetch_summary() -> impl Future<Output = Result<Self::Summary, Self::Error>> + Send;
     fn create(
         create_form: <<Self::Item as ListItem>::CreateForm as Form>::Output,
     ) -> impl Future<Output = Result<Self::Item, Self::Error>> + Send;
+    /// Creates all the items at once. The default implementation calls `Self::create` for each
+    /// item, storages that support batch inserts should override it.
+    fn create_many(
+        create_outputs: Vec<<<Self::Item as ListItem>::CreateForm as Form>::Output>,
+        progress: Progress,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>> + Send {
+        async move {
+            let total = create_outputs.len();
+            let mut items = Vec::with_capacity(total);
+            for create_output in create_outputs {
+                items.push(Self::create(create_output).await?);
+                progress.report(items.len(), total);
+            }
+            Ok(items)
+        }
+    }
     fn update(
         id: <Self::Item as ListItem>::Id,
         update_form: <<Self::Item as ListItem>::UpdateForm as Form>::Output,
@@ -21,8 +76,145 @@ where
     fn delete(
         id: <Self::Item as ListItem>::Id,
     ) -> impl Future<Output = Result<<Self::Item as ListItem>::Id, Self::Error>> + Send;
+    /// Updates all the items at once. The default implementation calls `Self::update` for each
+    /// item, storages that support batch updates should override it.
+    fn update_many(
+        updates: Vec<(
+            <Self::Item as ListItem>::Id,
+            <<Self::Item as ListItem>::UpdateForm as Form>::Output,
+        )>,
+        progress: Progress,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>> + Send {
+        async move {
+            let total = updates.len();
+            let mut items = Vec::with_capacity(total);
+            for (id, update_output) in updates {
+                items.push(Self::update(id, update_output).await?);
+                progress.report(items.len(), total);
+            }
+            Ok(items)
+        }
+    }
+    /// Deletes all the items at once. The default implementation calls `Self::delete` for each
+    /// item, storages that support batch deletes should override it.
+    fn delete_many(
+        ids: Vec<<Self::Item as ListItem>::Id>,
+        progress: Progress,
+    ) -> impl Future<Output = Result<Vec<<Self::Item as ListItem>::Id>, Self::Error>> + Send {
+        async move {
+            let total = ids.len();
+            let mut deleted = Vec::with_capacity(total);
+            for id in ids {
+                deleted.push(Self::delete(id).await?);
+                progress.report(deleted.len(), total);
+            }
+            Ok(deleted)
+        }
+    }
+    /// Sets the status of all the items at once. The default implementation goes through
+    /// `Self::update_many` with the updates returned by `ListItem::with_status`.
+    fn set_status_many(
+        items: Vec<Self::Item>,
+        status: <Self::Item as ListItem>::Status,
+        progress: Progress,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>> + Send {
+        let updates = items
+            .iter()
+            .filter_map(|item| Some((item.id(), item.with_status(status.clone())?)))
+            .collect();
+        Self::update_many(updates, progress)
+    }
+
+    /// Stores the order chosen by the user, `ids` being sorted in the new order. The items take
+    /// the places they had among themselves, the other items don't move. Returns the items whose
+    /// sort keys changed. By default, the order isn't persisted.
+    fn reorder(
+        ids: Vec<<Self::Item as ListItem>::Id>,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, Self::Error>> + Send {
+        let _ = ids;
+        async { Ok(Vec::new()) }
+    }
+
+    /// Loads the persisted state of the list. By default, nothing is persisted.
+    fn load_settings() -> impl Future<Output = Result<ListSettings, Self::Error>> + Send {
+        async { Ok(ListSettings::default()) }
+    }
+    fn save_settings(
+        settings: ListSettings,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        let _ = settings;
+        async { Ok(()) }
+    }
+    /// Forgets anything kept in memory, so that the next fetch hits the underlying storage.
+    fn invalidate() {}
+}
+
+/// Reports how many items a long operation processed so far, so that the list can show something
+/// like "Created 340 / 2000" instead of a bare spinner.
+#[derive(Clone, Default)]
+pub struct Progress {
+    report: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
+}
+
+impl Progress {
+    pub fn new(report: impl Fn(usize, usize) + Send + Sync + 'static) -> Self {
+        Self {
+            report: Some(Arc::new(report)),
+        }
+    }
+
+    pub fn report(&self, done: usize, total: usize) {
+        if let Some(report) = &self.report {
+            report(done, total);
+        }
+    }
 }
 
 pub trait Retryable {
     fn should_retry(&self) -> bool;
+    /// Whether the request may have been applied despite the error, like a timeout dropping it
+    /// once written. The creations aren't retried then, since they could be applied twice.
+    fn may_have_applied(&self) -> bool {
+        false
+    }
+}
+
+/// How many times and how often failed requests are retried when their error is `Retryable`.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Fraction of the delay that is randomized, between `0.0` and `1.0`.
+    pub jitter: f32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the delay to wait before the given attempt, starting at `1` for the first retry.
+    /// The delay doubles with each attempt up to `max_delay`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        let jitter = self.jitter.clamp(0., 1.);
+        let random = RandomState::new().hash_one(attempt) as f32 / u64::MAX as f32;
+        delay.mul_f32(1. + jitter * (2. * random - 1.))
+    }
 }
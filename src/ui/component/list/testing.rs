@@ -0,0 +1,424 @@
+//! Storage with scripted responses and a harness running the requests of an `AsyncList` without a
+//! database or a window, so that its pending, optimistic and error behavior can be tested.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use thiserror::Error;
+use uuid::Uuid;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+use xilem::tokio::time::error::Elapsed;
+
+use crate::core::{Priority, Status, Task};
+use crate::ui::component::Form;
+#[cfg(debug_assertions)]
+use crate::ui::component::list::chaos::Injected;
+use crate::ui::component::list::storage::{ListStorage, Retryable};
+use crate::ui::component::list::{
+    AsyncList, Emit, ListDelta, ListItem, ListQuery, ListRequest, PendingItemOperation,
+    WorkerCommand,
+};
+
+/// Items that `MockStorage` knows how to create and update.
+pub trait MockItem
+where
+    Self: ListItem,
+{
+    fn create(id: usize, create_output: <Self::CreateForm as Form>::Output) -> Self;
+    fn update(&self, update_output: <Self::UpdateForm as Form>::Output) -> Self;
+}
+
+impl MockItem for Task {
+    fn create(id: usize, (description, priority): (String, Priority)) -> Self {
+        Task {
+            id: id as i64,
+            description: description.into(),
+            priority,
+            ..Default::default()
+        }
+    }
+
+    fn update(&self, (description, status, priority): (String, Status, Priority)) -> Self {
+        Task {
+            description: description.into(),
+            status,
+            priority,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Error, Clone, Debug)]
+pub enum MockError {
+    #[error("scripted failure")]
+    Failure,
+    /// Failure that the list retries.
+    #[error("scripted transient failure")]
+    Transient,
+    #[error("the operation timed out")]
+    Timeout,
+}
+
+impl From<Elapsed> for MockError {
+    fn from(_: Elapsed) -> Self {
+        MockError::Timeout
+    }
+}
+
+impl Retryable for MockError {
+    fn should_retry(&self) -> bool {
+        matches!(self, MockError::Transient | MockError::Timeout)
+    }
+
+    fn may_have_applied(&self) -> bool {
+        matches!(self, MockError::Timeout)
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Injected for MockError {
+    fn injected() -> Self {
+        MockError::Failure
+    }
+}
+
+struct MockState<T> {
+    items: Vec<T>,
+    next_id: usize,
+    failures: VecDeque<MockError>,
+    /// Whether the next operation times out once applied.
+    late: bool,
+    delay: Duration,
+    calls: Vec<&'static str>,
+}
+
+impl<T> Default for MockState<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            next_id: 0,
+            failures: VecDeque::new(),
+            late: false,
+            delay: Duration::ZERO,
+            calls: Vec::new(),
+        }
+    }
+}
+
+thread_local! {
+    static MOCKS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// Storage keeping its items in memory. Its state is per thread, so tests running in parallel
+/// don't interfere, and requests must be run on the same thread with `ListHarness`.
+#[derive(Debug)]
+pub struct MockStorage<T> {
+    last_error: Option<MockError>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for MockStorage<T> {
+    fn default() -> Self {
+        Self {
+            last_error: None,
+            item: PhantomData,
+        }
+    }
+}
+
+impl<T> MockStorage<T>
+where
+    T: MockItem,
+{
+    fn with<R>(f: impl FnOnce(&mut MockState<T>) -> R) -> R {
+        MOCKS.with_borrow_mut(|mocks| {
+            let state = mocks
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| Box::new(MockState::<T>::default()));
+            f(state.downcast_mut().unwrap())
+        })
+    }
+
+    /// Replaces the stored items.
+    pub fn set_items(items: Vec<T>) {
+        Self::with(|state| {
+            state.next_id = items.len();
+            state.items = items;
+        });
+    }
+
+    pub fn items() -> Vec<T> {
+        Self::with(|state| state.items.clone())
+    }
+
+    /// Makes the next operation fail with `error`. Failures are consumed in order.
+    pub fn fail_next(error: MockError) {
+        Self::with(|state| state.failures.push_back(error));
+    }
+
+    /// Makes the next operation fail with `MockError::Timeout` once applied, like a storage
+    /// answering after the timeout of the list.
+    pub fn time_out_after_next() {
+        Self::with(|state| state.late = true);
+    }
+
+    /// Delay added to every operation.
+    pub fn set_delay(delay: Duration) {
+        Self::with(|state| state.delay = delay);
+    }
+
+    /// Names of the operations called so far, e.g. `"fetch_all"`.
+    pub fn calls() -> Vec<&'static str> {
+        Self::with(|state| state.calls.clone())
+    }
+
+    fn call<R>(name: &'static str, f: impl FnOnce(&mut MockState<T>) -> R) -> Result<R, MockError> {
+        let (delay, failure, late) = Self::with(|state| {
+            state.calls.push(name);
+            let failure = state.failures.pop_front();
+            (state.delay, failure, std::mem::take(&mut state.late))
+        });
+        std::thread::sleep(delay);
+        match failure {
+            Some(error) => Err(error),
+            None if late => {
+                Self::with(f);
+                Err(MockError::Timeout)
+            }
+            None => Ok(Self::with(f)),
+        }
+    }
+}
+
+impl<T> ListStorage for MockStorage<T>
+where
+    T: MockItem,
+{
+    type Item = T;
+    type Error = MockError;
+    type Summary = usize;
+
+    fn last_error(&mut self) -> &mut Option<MockError> {
+        &mut self.last_error
+    }
+
+    async fn fetch_all(_query: ListQuery<T>) -> Result<Vec<T>, MockError> {
+        Self::call("fetch_all", |state| state.items.clone())
+    }
+
+    async fn fetch_since(_since: String) -> Result<ListDelta<T>, MockError> {
+        Self::call("fetch_since", |state| ListDelta {
+            changed: state.items.clone(),
+            deleted: Vec::new(),
+        })
+    }
+
+    async fn fetch_summary() -> Result<usize, MockError> {
+        Self::call("fetch_summary", |state| state.items.len())
+    }
+
+    async fn create(create_output: <T::CreateForm as Form>::Output) -> Result<T, MockError> {
+        Self::call("create", |state| {
+            state.next_id += 1;
+            let item = T::create(state.next_id, create_output);
+            state.items.push(item.clone());
+            item
+        })
+    }
+
+    async fn update(
+        id: T::Id,
+        update_output: <T::UpdateForm as Form>::Output,
+    ) -> Result<T, MockError> {
+        Self::call("update", |state| {
+            let item = state.items.iter_mut().find(|item| item.id() == id)?;
+            *item = item.update(update_output);
+            Some(item.clone())
+        })?
+        .ok_or(MockError::Failure)
+    }
+
+    async fn delete(id: T::Id) -> Result<T::Id, MockError> {
+        Self::call("delete", |state| {
+            state.items.retain(|item| item.id() != id);
+            id
+        })
+    }
+}
+
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
+
+/// Runs the requests of an `AsyncList` one by one on the current thread, in place of the worker
+/// started by `AsyncList::view`.
+pub struct ListHarness<T>
+where
+    T: MockItem,
+{
+    list: AsyncList<T, MockStorage<T>>,
+    receiver: UnboundedReceiver<WorkerCommand<T>>,
+}
+
+impl<T> ListHarness<T>
+where
+    T: MockItem,
+{
+    /// Starts the list like its worker would, the initial requests are queued but not run yet.
+    pub fn new(mut list: AsyncList<T, MockStorage<T>>) -> Self {
+        let (sender, receiver) = unbounded_channel();
+        list.sender = Some(sender);
+        list.send_request(ListRequest::LoadSettings);
+        Self { list, receiver }
+    }
+
+    pub fn list(&self) -> &AsyncList<T, MockStorage<T>> {
+        &self.list
+    }
+
+    pub fn list_mut(&mut self) -> &mut AsyncList<T, MockStorage<T>> {
+        &mut self.list
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.list.items
+    }
+
+    pub fn create(&mut self, create_output: <T::CreateForm as Form>::Output) {
+        self.list.send_request(ListRequest::Create(create_output));
+    }
+
+    pub fn update(&mut self, id: T::Id, update_output: <T::UpdateForm as Form>::Output) {
+        self.list.send_update(id, update_output);
+    }
+
+    pub fn delete(&mut self, id: T::Id) {
+        self.list.send_request(ListRequest::Delete(id));
+    }
+
+    pub fn cancel(&mut self, id: T::Id) {
+        self.list.cancel(id);
+    }
+
+    /// Request ids of the creations shown as failed.
+    pub fn failed_creates(&self) -> Vec<Uuid> {
+        self.list
+            .failed_creates
+            .iter()
+            .map(|failed_create| failed_create.request_id)
+            .collect()
+    }
+
+    /// Runs the next queued request and handles its messages. Returns `false` if no request was
+    /// queued.
+    pub fn run_next(&mut self) -> bool {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(WorkerCommand::Run(pending_request)) => {
+                    let request_id = pending_request.request_id;
+                    // Requests cancelled after being queued are skipped like aborted tasks.
+                    if !self
+                        .list
+                        .pending_requests
+                        .iter()
+                        .any(|pending_request| pending_request.request_id == request_id)
+                    {
+                        continue;
+                    }
+                    let messages = Arc::new(Mutex::new(Vec::new()));
+                    let emit: Emit<T, MockStorage<T>> = {
+                        let messages = messages.clone();
+                        Arc::new(move |pending_message| {
+                            messages.lock().unwrap().push(pending_message);
+                        })
+                    };
+                    block_on(pending_request.handle::<MockStorage<T>>(emit, None));
+                    let messages = std::mem::take(&mut *messages.lock().unwrap());
+                    for pending_message in messages {
+                        pending_message.handle(&mut self.list);
+                    }
+                    return true;
+                }
+                Ok(WorkerCommand::Cancel(_)) => continue,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    /// Runs requests until none are queued, including the ones sent while handling messages.
+    pub fn run_all(&mut self) {
+        while self.run_next() {}
+    }
+
+    /// Checks the pending requests for leaks like the view does periodically, see
+    /// `AsyncList::with_leak_timeout`.
+    pub fn check_leaks(&mut self) {
+        // Timeout errors can only be made by a Tokio timer.
+        let runtime = xilem::tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let elapsed = runtime
+            .block_on(xilem::tokio::time::timeout(
+                Duration::ZERO,
+                std::future::pending::<()>(),
+            ))
+            .unwrap_err();
+        self.list.expire_leaked_request(elapsed);
+    }
+
+    pub fn assert_items(&self, ids: &[T::Id]) {
+        let item_ids = self.list.items.iter().map(ListItem::id).collect::<Vec<_>>();
+        assert_eq!(item_ids, ids, "unexpected items");
+    }
+
+    pub fn assert_pending(&self, id: T::Id, pending_item_operation: PendingItemOperation) {
+        assert_eq!(
+            self.list.pending_item_operation(id),
+            pending_item_operation,
+            "unexpected pending operation for item {id:?}"
+        );
+    }
+
+    pub fn assert_pending_requests(&self, count: usize) {
+        assert_eq!(
+            self.list.pending_requests.len(),
+            count,
+            "unexpected number of pending requests"
+        );
+    }
+
+    pub fn assert_error(&mut self) {
+        assert!(
+            self.list.storage.last_error().is_some(),
+            "expected an error to be shown"
+        );
+    }
+
+    pub fn assert_no_error(&mut self) {
+        let last_error = self.list.storage.last_error();
+        assert!(last_error.is_none(), "unexpected error {last_error:?}");
+    }
+}
+
+impl ListHarness<Task> {
+    pub fn task(&self, id: i64) -> Option<&Task> {
+        self.list.get(id)
+    }
+}
@@ -0,0 +1,187 @@
+//! Keyboard shortcut around a view, like F5 refreshing an `AsyncList`.
+
+use xilem::core::{Arg, MessageCtx, MessageResult, Mut, View, ViewArgument, ViewMarker};
+use xilem::masonry::accesskit::{Node, Role};
+use xilem::masonry::core::keyboard::{Key, KeyState};
+use xilem::masonry::core::{
+    AccessCtx, ChildrenIds, EventCtx, LayoutCtx, MeasureCtx, NewWidget, PaintCtx, PropertiesMut,
+    PropertiesRef, RegisterCtx, TextEvent, Widget, WidgetMut, WidgetPod,
+};
+use xilem::masonry::kurbo::{Axis, Point, Size};
+use xilem::masonry::layout::LenReq;
+use xilem::masonry::vello::Scene;
+use xilem::{Pod, ViewCtx, WidgetView};
+
+/// Sent when the key of a `Shortcut` is pressed.
+#[derive(Debug)]
+pub struct ShortcutPressed;
+
+/// Widget laid out like its child, reacting to a key pressed while the focus is inside it.
+pub struct Shortcut {
+    key: Key,
+    child: WidgetPod<dyn Widget>,
+}
+
+impl Shortcut {
+    pub fn new(key: Key, child: NewWidget<impl Widget + ?Sized>) -> Self {
+        Self {
+            key,
+            child: child.erased().to_pod(),
+        }
+    }
+
+    pub fn set_key(this: &mut WidgetMut<'_, Self>, key: Key) {
+        this.widget.key = key;
+    }
+
+    pub fn child_mut<'t>(this: &'t mut WidgetMut<'_, Self>) -> WidgetMut<'t, dyn Widget> {
+        this.ctx.get_mut(&mut this.widget.child)
+    }
+}
+
+impl Widget for Shortcut {
+    type Action = ShortcutPressed;
+
+    fn on_text_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &TextEvent,
+    ) {
+        if let TextEvent::Keyboard(event) = event
+            && event.state == KeyState::Down
+            && event.key == self.key
+        {
+            ctx.submit_action::<Self::Action>(ShortcutPressed);
+            ctx.set_handled();
+        }
+    }
+
+    fn register_children(&mut self, ctx: &mut RegisterCtx<'_>) {
+        ctx.register_child(&mut self.child);
+    }
+
+    fn measure(
+        &mut self,
+        ctx: &mut MeasureCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        axis: Axis,
+        len_req: LenReq,
+        cross_length: Option<f64>,
+    ) -> f64 {
+        ctx.compute_length(&mut self.child, len_req, axis, cross_length)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx<'_>, _props: &PropertiesRef<'_>, size: Size) {
+        ctx.run_layout(&mut self.child, size);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, _scene: &mut Scene) {}
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _node: &mut Node,
+    ) {
+    }
+
+    fn children_ids(&self) -> ChildrenIds {
+        ChildrenIds::from_slice(&[self.child.id()])
+    }
+}
+
+/// Calls `callback` when `key` is pressed while the focus is inside `child`.
+pub fn shortcut<State, Action, V, F>(key: Key, child: V, callback: F) -> ShortcutView<V, F>
+where
+    State: ViewArgument,
+    V: WidgetView<State, Action>,
+    F: Fn(Arg<'_, State>) -> Action + Send + Sync + 'static,
+{
+    ShortcutView {
+        key,
+        child,
+        callback,
+    }
+}
+
+#[must_use = "View values do nothing unless provided to Xilem."]
+pub struct ShortcutView<V, F> {
+    key: Key,
+    child: V,
+    callback: F,
+}
+
+impl<V, F> ViewMarker for ShortcutView<V, F> {}
+
+impl<State, Action, V, F> View<State, Action, ViewCtx> for ShortcutView<V, F>
+where
+    State: ViewArgument,
+    Action: 'static,
+    V: WidgetView<State, Action>,
+    F: Fn(Arg<'_, State>) -> Action + Send + Sync + 'static,
+{
+    type Element = Pod<Shortcut>;
+    type ViewState = V::ViewState;
+
+    fn build(
+        &self,
+        ctx: &mut ViewCtx,
+        app_state: Arg<'_, State>,
+    ) -> (Self::Element, Self::ViewState) {
+        let (child, child_state) = self.child.build(ctx, app_state);
+        let widget = Shortcut::new(self.key.clone(), child.new_widget);
+        let pod = ctx.with_action_widget(|ctx| ctx.create_pod(widget));
+        (pod, child_state)
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        view_state: &mut Self::ViewState,
+        ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        app_state: Arg<'_, State>,
+    ) {
+        if self.key != prev.key {
+            Shortcut::set_key(&mut element, self.key.clone());
+        }
+        let mut child = Shortcut::child_mut(&mut element);
+        self.child
+            .rebuild(&prev.child, view_state, ctx, child.downcast(), app_state);
+    }
+
+    fn teardown(
+        &self,
+        view_state: &mut Self::ViewState,
+        ctx: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+    ) {
+        let mut child = Shortcut::child_mut(&mut element);
+        self.child.teardown(view_state, ctx, child.downcast());
+        ctx.teardown_action_source(element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        message: &mut MessageCtx,
+        mut element: Mut<'_, Self::Element>,
+        app_state: Arg<'_, State>,
+    ) -> MessageResult<Action> {
+        if message.remaining_path().is_empty() {
+            return match message.take_message::<ShortcutPressed>() {
+                Some(_) => MessageResult::Action((self.callback)(app_state)),
+                None => MessageResult::Stale,
+            };
+        }
+        let mut child = Shortcut::child_mut(&mut element);
+        self.child
+            .message(view_state, message, child.downcast(), app_state)
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::timeout;
+use xilem::view::{FlexExt, button, flex_col, flex_row, prose, spinner, text_button, worker};
+
+use crate::core::ServerError;
+use crate::database::{ping, reconnect};
+use crate::ui::theme::{DANGER_COLOR, SURFACE_COLOR};
+
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Regularly pings the database and rebuilds the pool when the connection is broken. A banner
+/// with a reconnect button is shown if reconnecting automatically didn't help.
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    last_error: Option<ServerError>,
+    reconnecting: bool,
+    sender: Option<UnboundedSender<()>>,
+}
+
+impl ConnectionHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.last_error.is_none()
+    }
+
+    fn reconnect(&mut self) {
+        if let Some(sender) = &self.sender {
+            self.reconnecting = sender.send(()).is_ok();
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let banner = self.last_error.as_ref().map(|error| {
+            let reconnect_button = if self.reconnecting {
+                Either::A(button(spinner(), |_| ()))
+            } else {
+                Either::B(text_button("Reconnect", |state: &mut Self| {
+                    state.reconnect()
+                }))
+            };
+            flex_row(
+                flex_row((
+                    prose(format!("The database is unreachable: {error}"))
+                        .text_color(DANGER_COLOR)
+                        .flex(1.),
+                    reconnect_button,
+                ))
+                .padding(5.)
+                .corner_radius(10.)
+                .background_color(SURFACE_COLOR)
+                .border(DANGER_COLOR, 1.)
+                .flex(1.),
+            )
+            .padding(15.)
+        });
+        fork(
+            flex_col(banner),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<()>| async move {
+                    loop {
+                        match timeout(PING_INTERVAL, rx.recv()).await {
+                            Ok(Some(())) => reconnect(),
+                            Ok(None) => break,
+                            Err(_) => (),
+                        }
+                        let mut result = ping().await;
+                        if result.is_err() {
+                            reconnect();
+                            result = ping().await;
+                        }
+                        let _ = proxy.message(result.err());
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, error: Option<ServerError>| {
+                    state.last_error = error;
+                    state.reconnecting = false;
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use xilem::WidgetView;
+use xilem::core::Edit;
+use xilem::style::Style;
+use xilem::view::{FlexExt, flex_col, flex_row, prose, text_button};
+
+use crate::crash::{dismiss, last_report, report_path};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR, SURFACE_COLOR};
+
+/// Banner offering to show the report of the last crash, until it is dismissed.
+#[derive(Debug)]
+pub struct CrashBanner {
+    report: Option<String>,
+    /// Number of drafts put back in the create form.
+    recovered: usize,
+    expanded: bool,
+    #[cfg(not(target_os = "android"))]
+    copied: bool,
+}
+
+impl Default for CrashBanner {
+    fn default() -> Self {
+        Self {
+            report: last_report(),
+            recovered: 0,
+            expanded: false,
+            #[cfg(not(target_os = "android"))]
+            copied: false,
+        }
+    }
+}
+
+impl CrashBanner {
+    pub fn set_recovered(&mut self, recovered: usize) {
+        self.recovered = recovered;
+    }
+
+    fn dismiss(&mut self) {
+        dismiss();
+        self.report = None;
+    }
+
+    /// Puts the report in the clipboard, to paste it in a bug report.
+    #[cfg(not(target_os = "android"))]
+    fn copy(&mut self) {
+        let Some(report) = self.report.clone() else {
+            return;
+        };
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(report)) {
+            Ok(()) => self.copied = true,
+            Err(error) => tracing::warn!(%error, "can't copy the crash report"),
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let banner = self.report.clone().map(|report| {
+            let show_button = text_button(
+                if self.expanded {
+                    "Hide the report"
+                } else {
+                    "Show the report"
+                },
+                |state: &mut Self| state.expanded = !state.expanded,
+            );
+            #[cfg(not(target_os = "android"))]
+            let copy_button = text_button(
+                if self.copied { "Copied" } else { "Copy" },
+                |state: &mut Self| state.copy(),
+            );
+            #[cfg(target_os = "android")]
+            let copy_button = ();
+            let dismiss_button = text_button("Dismiss", |state: &mut Self| state.dismiss());
+            let location = format!("The report is kept in {}", report_path().display());
+            let message = match self.recovered {
+                0 => "The app crashed last time".to_string(),
+                recovered => format!(
+                    "The app crashed last time, {recovered} unsaved tasks were put back in the \
+                     form, check that they weren't already added"
+                ),
+            };
+            let details = self
+                .expanded
+                .then(|| flex_col((prose(location).text_color(MUTED_TEXT_COLOR), prose(report))));
+            flex_row(
+                flex_col((
+                    flex_row((
+                        prose(message).text_color(DANGER_COLOR).flex(1.),
+                        show_button,
+                        copy_button,
+                        dismiss_button,
+                    )),
+                    details,
+                ))
+                .padding(5.)
+                .corner_radius(10.)
+                .background_color(SURFACE_COLOR)
+                .border(DANGER_COLOR, 1.)
+                .flex(1.),
+            )
+            .padding(15.)
+        });
+        flex_col(banner)
+    }
+}
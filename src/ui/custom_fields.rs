@@ -0,0 +1,359 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{
+    FlexExt, checkbox, flex_col, flex_row, label, prose, text_button, text_input, worker,
+};
+
+use crate::core::{CustomField, FieldKind, ServerError};
+use crate::database::{
+    create_custom_field, delete_custom_field, get_custom_fields, get_field_values,
+};
+use crate::repository::Database;
+use crate::service::{Command, execute};
+use crate::ui::task_list::set_custom_fields;
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const DESCRIPTION: &str = "Fields every task can have a value of, set with the details of the \
+    selected task. The list and the CLI filter and sort the tasks by their values, and the exports \
+    have a column per field.";
+/// Origin of the commands sent by the details of the selected task.
+const ORIGIN: &str = "custom fields";
+
+#[derive(Debug)]
+enum SettingsRequest {
+    Create(String, FieldKind, String),
+    Delete(i64),
+}
+
+/// Settings section defining the custom fields of the tasks, see `CustomField`.
+#[derive(Debug, Default)]
+pub struct CustomFieldSettings {
+    /// `None` until loaded.
+    fields: Option<Vec<CustomField>>,
+    name: String,
+    kind: FieldKind,
+    /// Choices of a `FieldKind::Choice` field, separated by commas.
+    choices: String,
+    error: Option<String>,
+    sender: Option<UnboundedSender<SettingsRequest>>,
+}
+
+impl CustomFieldSettings {
+    fn send(&self, request: SettingsRequest) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+
+    fn add(&mut self) {
+        let name = self.name.trim().to_string();
+        let choices = self
+            .choices
+            .split(',')
+            .map(str::trim)
+            .filter(|choice| !choice.is_empty())
+            .collect::<Vec<_>>();
+        if name.is_empty() {
+            self.error = Some("A field needs a name".to_string());
+            return;
+        }
+        if self.kind == FieldKind::Choice && choices.is_empty() {
+            self.error = Some("A choice field needs choices".to_string());
+            return;
+        }
+        let options = match self.kind {
+            FieldKind::Choice => choices.join("\n"),
+            _ => String::new(),
+        };
+        self.send(SettingsRequest::Create(name, self.kind, options));
+        self.name.clear();
+        self.choices.clear();
+        self.error = None;
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let fields = match &self.fields {
+            None => Either::A(label("Loading…").color(MUTED_TEXT_COLOR)),
+            Some(fields) if fields.is_empty() => {
+                Either::A(label("No custom fields yet").color(MUTED_TEXT_COLOR))
+            }
+            Some(fields) => Either::B(flex_col(
+                fields
+                    .iter()
+                    .map(|field| {
+                        let id = field.id;
+                        let kind = match field.kind {
+                            FieldKind::Choice => {
+                                format!(
+                                    "Choice of {}",
+                                    field.choices().collect::<Vec<_>>().join(", ")
+                                )
+                            }
+                            kind => kind.to_string(),
+                        };
+                        flex_row((
+                            label(field.name.clone()),
+                            label(kind).color(MUTED_TEXT_COLOR).flex(1.),
+                            text_button("Remove", move |state: &mut Self| {
+                                state.send(SettingsRequest::Delete(id));
+                            }),
+                        ))
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        };
+        let name = text_input(self.name.clone(), |state: &mut Self, name| {
+            state.name = name;
+        })
+        .on_enter(|state: &mut Self, _| state.add())
+        .placeholder("Field name");
+        let kind = text_button(self.kind.to_string(), |state: &mut Self| {
+            state.kind = state.kind.next();
+        });
+        let choices = (self.kind == FieldKind::Choice).then(|| {
+            text_input(self.choices.clone(), |state: &mut Self, choices| {
+                state.choices = choices;
+            })
+            .on_enter(|state: &mut Self, _| state.add())
+            .placeholder("Choices, separated by commas")
+            .flex(1.)
+        });
+        let add_button = text_button("Add field", |state: &mut Self| state.add());
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Custom fields"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                fields,
+                flex_row((name.flex(1.), kind, choices, add_button)),
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<SettingsRequest>| async move {
+                    let _ = proxy.message(get_custom_fields().await);
+                    while let Some(request) = rx.recv().await {
+                        let result = async {
+                            match request {
+                                SettingsRequest::Create(name, kind, options) => {
+                                    create_custom_field(&name, kind, &options).await?;
+                                }
+                                SettingsRequest::Delete(id) => delete_custom_field(id).await?,
+                            }
+                            get_custom_fields().await
+                        };
+                        let _ = proxy.message(result.await);
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Vec<CustomField>, ServerError>| match result {
+                    Ok(fields) => {
+                        set_custom_fields(fields.clone());
+                        state.fields = Some(fields);
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
+
+/// A custom field with the value of the selected task.
+#[derive(Clone, Debug)]
+struct Entry {
+    field: CustomField,
+    value: Option<String>,
+    /// Value being typed, see `CustomField::parse_value`.
+    input: String,
+}
+
+#[derive(Debug)]
+enum PanelRequest {
+    Load(i64),
+    Set(i64, i64, Option<String>),
+}
+
+/// Values of the custom fields of the selected task, shown with the details. The choice fields
+/// are picked from their choices, the other values are checked against their kind once saved.
+/// The values are set through the service, so the change is logged and followed by the task list
+/// like the changes of the other frontends.
+#[derive(Debug, Default)]
+pub struct CustomFieldPanel {
+    task: Option<i64>,
+    entries: Vec<Entry>,
+    read_only: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<PanelRequest>>,
+}
+
+impl CustomFieldPanel {
+    /// Follows the selected task, loading its values.
+    pub fn set_task(&mut self, task: Option<i64>) {
+        if self.task == task {
+            return;
+        }
+        self.task = task;
+        self.entries.clear();
+        self.error = None;
+        if let Some(id) = task {
+            self.send(PanelRequest::Load(id));
+        }
+    }
+
+    /// Shows the values without changing them, see `AsyncList::set_read_only`.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    fn send(&self, request: PanelRequest) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+
+    fn set(&mut self, index: usize, value: Option<String>) {
+        let (Some(task), Some(entry)) = (self.task, self.entries.get(index)) else {
+            return;
+        };
+        let value = match value
+            .map(|value| entry.field.parse_value(&value))
+            .transpose()
+        {
+            Ok(value) => value,
+            Err(error) => {
+                self.error = Some(error.to_string());
+                return;
+            }
+        };
+        self.error = None;
+        self.send(PanelRequest::Set(task, entry.field.id, value));
+    }
+
+    fn entry_view(&self, index: usize, entry: &Entry) -> impl WidgetView<Edit<Self>> + use<> {
+        let name = label(entry.field.name.clone());
+        if self.read_only {
+            let value = entry.value.clone().unwrap_or_else(|| "—".to_string());
+            return Either::A(flex_row((name, label(value).color(MUTED_TEXT_COLOR))));
+        }
+        if entry.field.kind == FieldKind::Choice {
+            let choices = entry
+                .field
+                .choices()
+                .map(|choice| {
+                    let checked = entry.value.as_deref() == Some(choice);
+                    let choice = choice.to_string();
+                    checkbox(choice.clone(), checked, move |state: &mut Self, checked| {
+                        state.set(index, checked.then(|| choice.clone()));
+                    })
+                })
+                .collect::<Vec<_>>();
+            return Either::B(Either::A(flex_row((name, flex_row(choices)))));
+        }
+        let placeholder = match entry.field.kind {
+            FieldKind::Number => "Number",
+            FieldKind::Date => "2026-10-15",
+            _ => "Text",
+        };
+        let input = text_input(entry.input.clone(), move |state: &mut Self, input| {
+            if let Some(entry) = state.entries.get_mut(index) {
+                entry.input = input;
+            }
+        })
+        .on_enter(move |state: &mut Self, input| state.set(index, Some(input)))
+        .placeholder(placeholder);
+        let save =
+            (entry.value.as_ref() != Some(&entry.input) && !entry.input.is_empty()).then(|| {
+                text_button("Save", move |state: &mut Self| {
+                    if let Some(entry) = state.entries.get(index) {
+                        state.set(index, Some(entry.input.clone()));
+                    }
+                })
+            });
+        let clear = entry
+            .value
+            .is_some()
+            .then(|| text_button("Clear", move |state: &mut Self| state.set(index, None)));
+        Either::B(Either::B(flex_row((name, input.flex(1.), save, clear))))
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let entries = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| self.entry_view(index, entry))
+            .collect::<Vec<_>>();
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((flex_col(entries), error)),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<PanelRequest>| async move {
+                    while let Some(request) = rx.recv().await {
+                        let id = match &request {
+                            PanelRequest::Load(id) | PanelRequest::Set(id, _, _) => *id,
+                        };
+                        let result = async {
+                            if let PanelRequest::Set(task_id, field_id, value) = request {
+                                let command = Command::SetFieldValue {
+                                    task_id,
+                                    field_id,
+                                    value,
+                                };
+                                execute::<Database>(ORIGIN, command).await?;
+                            }
+                            let values = get_field_values(Some(id)).await?;
+                            let entries = get_custom_fields()
+                                .await?
+                                .into_iter()
+                                .map(|field| {
+                                    let value = values
+                                        .iter()
+                                        .find(|value| value.field_id == field.id)
+                                        .map(|value| value.value.clone());
+                                    let input = value.clone().unwrap_or_default();
+                                    Entry {
+                                        field,
+                                        value,
+                                        input,
+                                    }
+                                })
+                                .collect();
+                            Ok::<_, ServerError>(entries)
+                        };
+                        let _ = proxy.message((id, result.await));
+                    }
+                },
+                |state: &mut Self, sender: UnboundedSender<PanelRequest>| {
+                    // The task may be selected before the worker starts.
+                    if let Some(id) = state.task {
+                        let _ = sender.send(PanelRequest::Load(id));
+                    }
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, (id, result): (i64, Result<Vec<Entry>, ServerError>)| {
+                    // The answer for a task that isn't selected anymore is dropped.
+                    if state.task != Some(id) {
+                        return;
+                    }
+                    match result {
+                        Ok(entries) => {
+                            state.entries = entries;
+                            state.error = None;
+                        }
+                        Err(error) => state.error = Some(error.to_string()),
+                    }
+                },
+            ),
+        )
+    }
+}
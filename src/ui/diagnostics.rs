@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::UnboundedReceiver;
+use xilem::tokio::time::sleep;
+use xilem::view::{flex_col, label, prose, worker};
+
+use crate::database::metrics::{BUCKETS, OperationMetrics, snapshot};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+fn format_latency(latency: Duration) -> String {
+    format!("{:.1} ms", latency.as_secs_f64() * 1000.)
+}
+
+fn format_percentile(metrics: &OperationMetrics, fraction: f64) -> String {
+    match metrics.percentile(fraction) {
+        Some(bound) => format!("≤ {}", format_latency(bound)),
+        None => format!("> {}", format_latency(BUCKETS[BUCKETS.len() - 1])),
+    }
+}
+
+/// Page with the latency and the errors of every database operation since the start, refreshed
+/// while it is shown, and the operations of the task list still pending.
+#[derive(Debug, Default)]
+pub struct DiagnosticsPanel {
+    operations: Vec<(&'static str, OperationMetrics)>,
+    pending: Vec<(String, Duration)>,
+}
+
+impl DiagnosticsPanel {
+    /// Replaces the pending operations of the task list with their age, see
+    /// `AsyncList::pending_operations`.
+    pub fn set_pending(&mut self, pending: Vec<(String, Duration)>) {
+        self.pending = pending;
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let pending = (!self.pending.is_empty()).then(|| {
+            let operations = self
+                .pending
+                .iter()
+                .map(|(description, age)| {
+                    prose(format!("{description} · for {:.1} s", age.as_secs_f64()))
+                        .text_color(MUTED_TEXT_COLOR)
+                })
+                .collect::<Vec<_>>();
+            flex_col((label("Pending operations"), flex_col(operations)))
+        });
+        let placeholder = self
+            .operations
+            .is_empty()
+            .then(|| prose("No database operation yet").text_color(MUTED_TEXT_COLOR));
+        let operations = self
+            .operations
+            .iter()
+            .map(|(name, metrics)| {
+                let errors = (metrics.errors > 0)
+                    .then(|| label(format!("{} errors", metrics.errors)).color(DANGER_COLOR));
+                flex_col((
+                    label(*name),
+                    prose(format!(
+                        "{} calls · mean {} · p50 {} · p95 {} · max {}",
+                        metrics.calls,
+                        format_latency(metrics.mean()),
+                        format_percentile(metrics, 0.5),
+                        format_percentile(metrics, 0.95),
+                        format_latency(metrics.max),
+                    ))
+                    .text_color(MUTED_TEXT_COLOR),
+                    errors,
+                ))
+            })
+            .collect::<Vec<_>>();
+        fork(
+            flex_col((
+                label("Diagnostics"),
+                pending,
+                placeholder,
+                flex_col(operations),
+            )),
+            worker(
+                |proxy, _rx: UnboundedReceiver<()>| async move {
+                    while proxy.message(snapshot()).is_ok() {
+                        sleep(REFRESH_INTERVAL).await;
+                    }
+                },
+                |_: &mut Self, _| {},
+                |state: &mut Self, operations: Vec<(&'static str, OperationMetrics)>| {
+                    state.operations = operations;
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,79 @@
+use std::env;
+use std::sync::LazyLock;
+
+use xilem::view::MainAxisAlignment;
+
+/// Languages written from right to left, by ISO 639 code.
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "syr", "ug", "ur", "yi",
+];
+
+/// Whether the locale of the app is written from right to left, read once from the environment.
+static RTL: LazyLock<bool> = LazyLock::new(|| {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|locale| !locale.is_empty())
+        .is_some_and(|locale| is_rtl_locale(&locale))
+});
+
+/// Whether `locale`, like `ar_EG.UTF-8` or `he-IL`, is written from right to left.
+pub fn is_rtl_locale(locale: &str) -> bool {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    RTL_LANGUAGES.contains(&language.as_str())
+}
+
+/// Whether the rows are mirrored, see `mirrored_row`.
+pub fn is_rtl() -> bool {
+    *RTL
+}
+
+/// Alignment at the start of the rows, on the right when mirrored.
+pub fn start() -> MainAxisAlignment {
+    if is_rtl() {
+        MainAxisAlignment::End
+    } else {
+        MainAxisAlignment::Start
+    }
+}
+
+/// Alignment at the end of the rows, on the left when mirrored.
+pub fn end() -> MainAxisAlignment {
+    if is_rtl() {
+        MainAxisAlignment::Start
+    } else {
+        MainAxisAlignment::End
+    }
+}
+
+/// `flex_row` of the items, in the reverse order when the locale is written from right to left,
+/// followed by the methods called on the row, like
+/// `mirrored_row!([status, description.flex(1.), delete].padding(5.))`.
+macro_rules! mirrored_row {
+    (@reversed [$($reversed:expr),*]) => {
+        ($($reversed,)*)
+    };
+    (@reversed [$($reversed:expr),*] $first:expr $(, $rest:expr)*) => {
+        $crate::ui::direction::mirrored_row!(@reversed [$first $(, $reversed)*] $($rest),*)
+    };
+    ([$($item:expr),+ $(,)?] $(.$method:ident($($arg:expr),* $(,)?))*) => {
+        if $crate::ui::direction::is_rtl() {
+            ::xilem::core::one_of::Either::A(
+                ::xilem::view::flex_row(
+                    $crate::ui::direction::mirrored_row!(@reversed [] $($item),+),
+                )
+                $(.$method($($arg),*))*,
+            )
+        } else {
+            ::xilem::core::one_of::Either::B(
+                ::xilem::view::flex_row(($($item,)+))$(.$method($($arg),*))*,
+            )
+        }
+    };
+}
+
+pub(crate) use mirrored_row;
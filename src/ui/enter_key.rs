@@ -0,0 +1,90 @@
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{checkbox, flex_col, label, prose, worker};
+
+use crate::core::ServerError;
+use crate::ui::task_list::{enter_submits, set_enter_submits};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const DESCRIPTION: &str = "With an input method, like for Chinese, Japanese or Korean, Enter may \
+    confirm the composed text. Submitting with the buttons only keeps Enter from adding or saving \
+    the task at the same time.";
+const CHOICES: [(&str, bool); 2] = [
+    ("Enter submits the task", true),
+    ("Enter only confirms the text, the buttons submit", false),
+];
+
+/// Settings section choosing whether Enter submits the forms of the task list, see
+/// `task_list::enter_submits`.
+#[derive(Debug)]
+pub struct EnterKeySettings {
+    enter_submits: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<bool>>,
+}
+
+impl Default for EnterKeySettings {
+    fn default() -> Self {
+        Self {
+            // Loaded by the task list when it starts.
+            enter_submits: enter_submits(),
+            error: None,
+            sender: None,
+        }
+    }
+}
+
+impl EnterKeySettings {
+    fn set(&mut self, enter_submits: bool) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(enter_submits);
+        }
+        self.enter_submits = enter_submits;
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let choices = CHOICES
+            .into_iter()
+            .map(|(name, choice)| {
+                checkbox(
+                    name,
+                    self.enter_submits == choice,
+                    move |state: &mut Self, _| state.set(choice),
+                )
+            })
+            .collect::<Vec<_>>();
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Enter key"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                flex_col(choices),
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<bool>| async move {
+                    while let Some(enter_submits) = rx.recv().await {
+                        let result = set_enter_submits(enter_submits).await;
+                        let _ = proxy.message(result.map(|()| enter_submits));
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                    state.enter_submits = enter_submits();
+                },
+                |state: &mut Self, result: Result<bool, ServerError>| match result {
+                    Ok(enter_submits) => {
+                        state.enter_submits = enter_submits;
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
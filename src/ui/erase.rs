@@ -0,0 +1,115 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{button, flex_col, flex_row, label, prose, spinner, text_button, worker};
+
+use crate::erase::erase_all;
+use crate::profile;
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const DESCRIPTION: &str = "Deletes the tasks, the settings, the backups, the unsaved drafts, the \
+    logs and the saved credentials of this profile, then restarts the app as on its first run.";
+/// The other files being shared with the default profile, only the database of the others is
+/// erased, see `erase_all`.
+const PROFILE_DESCRIPTION: &str = "Deletes the tasks and the settings of this profile, then \
+    restarts the app as on its first run. The backups, the logs and the saved credentials are \
+    shared with the default profile and only erased from it.";
+const WARNING: &str = "Everything will be overwritten then deleted, this can't be undone.";
+
+#[derive(Default, PartialEq, Clone, Debug)]
+enum EraseState {
+    #[default]
+    Idle,
+    Confirming,
+    Erasing,
+    Failed(String),
+    Erased,
+}
+
+/// Settings section erasing all the data after a confirmation, see `erase`.
+#[derive(Debug, Default)]
+pub struct EraseData {
+    state: EraseState,
+    sender: Option<UnboundedSender<()>>,
+}
+
+impl EraseData {
+    /// Whether everything was erased, the app having to restart.
+    pub fn is_erased(&self) -> bool {
+        self.state == EraseState::Erased
+    }
+
+    fn erase(&mut self) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(());
+            self.state = EraseState::Erasing;
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let error = match &self.state {
+            EraseState::Failed(error) => {
+                Some(label(format!("Couldn't erase everything: {error}")).color(DANGER_COLOR))
+            }
+            _ => None,
+        };
+        let action = match &self.state {
+            EraseState::Confirming => Either::A(flex_col((
+                prose(WARNING).text_color(DANGER_COLOR),
+                flex_row((
+                    button(
+                        label("Erase everything").color(DANGER_COLOR),
+                        |state: &mut Self| {
+                            state.erase();
+                        },
+                    ),
+                    text_button("Cancel", |state: &mut Self| state.state = EraseState::Idle),
+                )),
+            ))),
+            EraseState::Erasing | EraseState::Erased => Either::B(Either::A(flex_row((
+                button(spinner(), |_| ()),
+                label("Erasing…"),
+            )))),
+            EraseState::Idle | EraseState::Failed(_) => Either::B(Either::B(text_button(
+                "Erase all my data…",
+                |state: &mut Self| state.state = EraseState::Confirming,
+            ))),
+        };
+        fork(
+            flex_col((
+                label("Erase all my data"),
+                prose(if profile::is_default() {
+                    DESCRIPTION
+                } else {
+                    PROFILE_DESCRIPTION
+                })
+                .text_color(MUTED_TEXT_COLOR),
+                error,
+                action,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<()>| async move {
+                    while rx.recv().await.is_some() {
+                        // Spawned, so that leaving the settings doesn't stop it halfway.
+                        let result = match xilem::tokio::spawn(erase_all()).await {
+                            Ok(result) => result.map_err(|error| error.to_string()),
+                            Err(error) => Err(error.to_string()),
+                        };
+                        let _ = proxy.message(result);
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<(), String>| {
+                    state.state = match result {
+                        Ok(()) => EraseState::Erased,
+                        Err(error) => EraseState::Failed(error),
+                    };
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::timeout;
+use xilem::view::{FlexExt, flex_row, label, prose, text_button, worker};
+
+use crate::core::{ServerError, Status, Task};
+use crate::database::{TaskQuery, get_setting, get_tasks, local_date, set_setting};
+use crate::ui::picker::{pick, random};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR, SURFACE_BORDER_COLOR, SURFACE_COLOR};
+
+const TASK_SETTING: &str = "focus.task";
+const DATE_SETTING: &str = "focus.date";
+/// Interval at which the day is checked, so that a new focus is chosen in the morning.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The task to focus on: the highest priority, then the one untouched for the longest, tasks
+/// having no deadlines.
+pub fn most_important(tasks: &[Task]) -> Option<&Task> {
+    tasks.iter().min_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.updated_at.cmp(&b.updated_at))
+            .then(a.id.cmp(&b.id))
+    })
+}
+
+/// The focus of today, chosen again once the day changed or the task isn't active anymore, or
+/// another task picked at random if `shuffle`.
+async fn focus(shuffle: bool) -> Result<Option<Task>, ServerError> {
+    let today = local_date().await?;
+    let query = TaskQuery {
+        statuses: Some(vec![Status::ToDo, Status::InProgress]),
+        ..Default::default()
+    };
+    let tasks = get_tasks(query).await?;
+    let saved = get_setting(TASK_SETTING).await?;
+    let saved_date = get_setting(DATE_SETTING).await?;
+    let current = tasks
+        .iter()
+        .find(|task| saved.as_deref() == Some(task.id.to_string().as_str()));
+    let focus = match current {
+        Some(task) if shuffle => {
+            let others = tasks
+                .iter()
+                .filter(|other| other.id != task.id)
+                .cloned()
+                .collect::<Vec<_>>();
+            pick(&others, random()).unwrap_or(task).clone()
+        }
+        Some(task) if saved_date.as_ref() == Some(&today) => {
+            return Ok(Some(task.clone()));
+        }
+        _ => match most_important(&tasks) {
+            Some(task) => task.clone(),
+            None => return Ok(None),
+        },
+    };
+    set_setting(TASK_SETTING, focus.id.to_string()).await?;
+    set_setting(DATE_SETTING, today).await?;
+    Ok(Some(focus))
+}
+
+#[derive(Debug)]
+enum Request {
+    Refresh,
+    Shuffle,
+}
+
+/// Suggested task of the day pinned above the list, see `most_important`. It is kept for the day
+/// unless shuffled, and chosen again once done or deleted.
+#[derive(Debug, Default)]
+pub struct FocusTask {
+    task: Option<Task>,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Request>>,
+}
+
+impl FocusTask {
+    /// Checks the focus again, like after a change of the tasks.
+    pub fn refresh(&mut self) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Request::Refresh);
+        }
+    }
+
+    fn shuffle(&mut self) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Request::Shuffle);
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let task = self.task.as_ref().map(|task| {
+            flex_row((
+                label("Today's focus").color(MUTED_TEXT_COLOR),
+                prose(task.description.clone()).flex(1.),
+                text_button("Shuffle", |state: &mut Self| state.shuffle()),
+            ))
+            .padding(10.)
+            .corner_radius(10.)
+            .background_color(SURFACE_COLOR)
+            .border(SURFACE_BORDER_COLOR, 1.)
+        });
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_row((task, error)),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Request>| async move {
+                    let mut request = Request::Refresh;
+                    loop {
+                        let _ = proxy.message(focus(matches!(request, Request::Shuffle)).await);
+                        request = match timeout(CHECK_INTERVAL, rx.recv()).await {
+                            Ok(None) => break,
+                            Ok(Some(request)) => request,
+                            Err(_) => Request::Refresh,
+                        };
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Option<Task>, ServerError>| match result {
+                    Ok(task) => {
+                        state.task = task;
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,245 @@
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::masonry::layout::AsUnit;
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{checkbox, flex_col, flex_row, label, sized_box, text_button, worker};
+
+use crate::core::ServerError;
+use crate::database::{get_habit, local_date, set_habit, set_habit_done};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR, SUCCESS_COLOR, SURFACE_BORDER_COLOR};
+
+/// Weeks shown by the calendar, the current one last.
+const CALENDAR_WEEKS: i64 = 4;
+
+/// Number of days since 1970-01-01 of `day`, like `2026-10-15`.
+pub fn day_number(day: &str) -> Option<i64> {
+    let mut parts = day.splitn(3, '-').map(str::parse::<i64>);
+    let (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    // See http://howardhinnant.github.io/date_algorithms.html.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Days in a row the habit was done until `today`, or until yesterday while today is still to do.
+pub fn streak(days: &[String], today: &str) -> u32 {
+    let Some(today) = day_number(today) else {
+        return 0;
+    };
+    let mut done = days
+        .iter()
+        .filter_map(|day| day_number(day))
+        .collect::<Vec<_>>();
+    done.sort_unstable_by(|a, b| b.cmp(a));
+    let mut expected = if done.first() == Some(&today) {
+        today
+    } else {
+        today - 1
+    };
+    let mut streak = 0;
+    for day in done {
+        if day == expected {
+            streak += 1;
+            expected -= 1;
+        } else if day < expected {
+            break;
+        }
+    }
+    streak
+}
+
+/// Whether the habit was done on each day of the last `CALENDAR_WEEKS` weeks, from Monday, `None`
+/// for the days after `today`.
+pub fn calendar(days: &[String], today: &str) -> Vec<[Option<bool>; 7]> {
+    let Some(today) = day_number(today) else {
+        return Vec::new();
+    };
+    let done = days
+        .iter()
+        .filter_map(|day| day_number(day))
+        .collect::<Vec<_>>();
+    // 1970-01-01 was a Thursday.
+    let monday = today - (today + 3).rem_euclid(7);
+    (0..CALENDAR_WEEKS)
+        .rev()
+        .map(|week| {
+            let start = monday - 7 * week;
+            std::array::from_fn(|weekday| {
+                let day = start + weekday as i64;
+                (day <= today).then(|| done.contains(&day))
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct Habit {
+    /// Days it was done, the most recent first.
+    days: Vec<String>,
+    today: String,
+}
+
+#[derive(Debug)]
+enum Request {
+    Load(i64),
+    SetHabit(i64, bool),
+    SetDone(i64, String, bool),
+}
+
+/// Habit mode of the selected task, shown with the details. A habit is done once a day, each day
+/// being recorded instead of changing the status of the task, with its streak and a calendar of
+/// the last weeks.
+#[derive(Debug, Default)]
+pub struct HabitPanel {
+    task: Option<i64>,
+    /// The habit of `task`, `None` if it isn't one or until loaded.
+    habit: Option<Habit>,
+    read_only: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Request>>,
+}
+
+impl HabitPanel {
+    /// Follows the selected task, loading its habit.
+    pub fn set_task(&mut self, task: Option<i64>) {
+        if self.task == task {
+            return;
+        }
+        self.task = task;
+        self.habit = None;
+        self.error = None;
+        if let Some(id) = task {
+            self.send(Request::Load(id));
+        }
+    }
+
+    /// Hides the switch and the button changing the habit, see `AsyncList::set_read_only`.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    fn send(&self, request: Request) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+
+    fn habit_view(&self, id: i64, habit: &Habit) -> impl WidgetView<Edit<Self>> + use<> {
+        let done_today = habit.days.first() == Some(&habit.today);
+        let streak = streak(&habit.days, &habit.today);
+        let toggle = (!self.read_only).then(|| {
+            let today = habit.today.clone();
+            let name = if done_today {
+                "Not done today"
+            } else {
+                "Done today"
+            };
+            text_button(name, move |state: &mut Self| {
+                state.send(Request::SetDone(id, today.clone(), !done_today));
+            })
+        });
+        let weeks = calendar(&habit.days, &habit.today)
+            .into_iter()
+            .map(|week| {
+                let days = week.map(|done| {
+                    let color = match done {
+                        Some(true) => SUCCESS_COLOR,
+                        Some(false) => SURFACE_BORDER_COLOR,
+                        None => SURFACE_BORDER_COLOR.with_alpha(0.3),
+                    };
+                    sized_box(flex_row(()).corner_radius(3.).background_color(color))
+                        .width(14.px())
+                        .height(14.px())
+                });
+                flex_row(days.to_vec()).gap(3.px())
+            })
+            .collect::<Vec<_>>();
+        flex_col((
+            flex_row((
+                label(format!(
+                    "{streak} day{} streak",
+                    if streak == 1 { "" } else { "s" }
+                )),
+                toggle,
+            )),
+            flex_col(weeks).gap(3.px()),
+        ))
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let content = self.task.map(|id| {
+            let switch = (!self.read_only).then(|| {
+                checkbox(
+                    "Habit, done every day",
+                    self.habit.is_some(),
+                    move |state: &mut Self, checked| state.send(Request::SetHabit(id, checked)),
+                )
+            });
+            let badge = (self.read_only && self.habit.is_some())
+                .then(|| label("Habit").color(MUTED_TEXT_COLOR));
+            let habit = self.habit.as_ref().map(|habit| self.habit_view(id, habit));
+            flex_col((switch, badge, habit))
+        });
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((content, error)),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Request>| async move {
+                    while let Some(request) = rx.recv().await {
+                        let id = match &request {
+                            Request::Load(id)
+                            | Request::SetHabit(id, _)
+                            | Request::SetDone(id, _, _) => *id,
+                        };
+                        let result = async {
+                            match request {
+                                Request::Load(_) => (),
+                                Request::SetHabit(id, habit) => set_habit(id, habit).await?,
+                                Request::SetDone(id, day, done) => {
+                                    set_habit_done(id, &day, done).await?;
+                                }
+                            }
+                            let Some(days) = get_habit(id).await? else {
+                                return Ok(None);
+                            };
+                            let today = local_date().await?;
+                            Ok::<_, ServerError>(Some(Habit { days, today }))
+                        };
+                        let _ = proxy.message((id, result.await));
+                    }
+                },
+                |state: &mut Self, sender: UnboundedSender<Request>| {
+                    // The task may be selected before the worker starts.
+                    if let Some(id) = state.task {
+                        let _ = sender.send(Request::Load(id));
+                    }
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, (id, result): (i64, Result<Option<Habit>, ServerError>)| {
+                    // The answer for a task that isn't selected anymore is dropped.
+                    if state.task != Some(id) {
+                        return;
+                    }
+                    match result {
+                        Ok(habit) => {
+                            state.habit = habit;
+                            state.error = None;
+                        }
+                        Err(error) => state.error = Some(error.to_string()),
+                    }
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::task::spawn_blocking;
+use xilem::tokio::time::{sleep, sleep_until, timeout};
+use xilem::view::{MainAxisAlignment, flex_col, flex_row, label, text_button, worker};
+
+use crate::lock::{Lock, load, retry_delay, set_failures};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR, SURFACE_COLOR};
+
+/// How often the idle time is checked.
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum LockState {
+    /// The lock isn't loaded yet, so the tasks are hidden.
+    Loading,
+    Locked,
+    Unlocked,
+}
+
+#[derive(Debug)]
+enum LockMessage {
+    Loaded(Option<Lock>),
+    CheckIdle,
+    /// Answer to a PIN, with the time to wait before the next one if it was wrong.
+    Checked(Result<(), Duration>),
+}
+
+/// Keypad asking for the PIN at startup and after being idle, see `lock`. Only the changes made
+/// from the window count as activity, see `touch`. The PINs are checked by the worker, since
+/// Argon2 takes a while, and the wrong ones make the next ones wait, see `retry_delay`.
+#[derive(Debug)]
+pub struct LockScreen {
+    state: LockState,
+    lock: Option<Lock>,
+    pin: String,
+    /// Whether the worker is checking a PIN.
+    checking: bool,
+    /// The last PIN was wrong, the next one being checked after this delay.
+    wrong_pin: Option<Duration>,
+    last_activity: Instant,
+    sender: Option<UnboundedSender<String>>,
+}
+
+impl Default for LockScreen {
+    fn default() -> Self {
+        Self {
+            state: LockState::Loading,
+            lock: None,
+            pin: String::new(),
+            checking: false,
+            wrong_pin: None,
+            last_activity: Instant::now(),
+            sender: None,
+        }
+    }
+}
+
+impl LockScreen {
+    /// Whether the tasks must be hidden.
+    pub fn is_locked(&self) -> bool {
+        self.state != LockState::Unlocked
+    }
+
+    /// Postpones the idle lock, when the user changed something.
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    fn unlock(&mut self) {
+        let Some(sender) = self.sender.as_ref().filter(|_| !self.checking) else {
+            return;
+        };
+        if sender.send(std::mem::take(&mut self.pin)).is_ok() {
+            self.checking = true;
+        }
+    }
+
+    fn handle(&mut self, message: LockMessage) {
+        match message {
+            LockMessage::Loaded(lock) => {
+                self.state = if lock.is_some() {
+                    LockState::Locked
+                } else {
+                    LockState::Unlocked
+                };
+                self.lock = lock;
+            }
+            LockMessage::Checked(result) => {
+                self.checking = false;
+                match result {
+                    Ok(()) => {
+                        self.state = LockState::Unlocked;
+                        self.wrong_pin = None;
+                        self.touch();
+                    }
+                    Err(delay) => self.wrong_pin = Some(delay),
+                }
+            }
+            LockMessage::CheckIdle => {
+                let idle_timeout = self.lock.as_ref().and_then(|lock| lock.idle_timeout);
+                if self.state == LockState::Unlocked
+                    && idle_timeout.is_some_and(|timeout| self.last_activity.elapsed() >= timeout)
+                {
+                    self.state = LockState::Locked;
+                }
+            }
+        }
+    }
+
+    fn keypad(&self) -> impl WidgetView<Edit<Self>> + use<> {
+        let digit = |digit: char| {
+            text_button(digit.to_string(), move |state: &mut Self| {
+                state.pin.push(digit);
+                state.wrong_pin = None;
+            })
+        };
+        let row = |digits: [char; 3]| {
+            flex_row(digits.map(digit).to_vec()).main_axis_alignment(MainAxisAlignment::Center)
+        };
+        let erase_button = text_button("Erase", |state: &mut Self| {
+            state.pin.pop();
+        });
+        let unlock_button =
+            text_button("Unlock", |state: &mut Self| state.unlock()).disabled(self.checking);
+        let message = match self.wrong_pin {
+            _ if self.checking => label("Checking…").color(MUTED_TEXT_COLOR),
+            Some(Duration::ZERO) => label("Wrong PIN").color(DANGER_COLOR),
+            Some(delay) => label(format!(
+                "Wrong PIN, the next one is checked after {} s",
+                delay.as_secs()
+            ))
+            .color(DANGER_COLOR),
+            None => label("Enter the PIN to show the tasks").color(MUTED_TEXT_COLOR),
+        };
+        flex_row(
+            flex_col((
+                label("Locked"),
+                message,
+                label("•".repeat(self.pin.chars().count())),
+                row(['1', '2', '3']),
+                row(['4', '5', '6']),
+                row(['7', '8', '9']),
+                flex_row((erase_button, digit('0'), unlock_button))
+                    .main_axis_alignment(MainAxisAlignment::Center),
+            ))
+            .padding(15.)
+            .corner_radius(10.)
+            .background_color(SURFACE_COLOR),
+        )
+        .main_axis_alignment(MainAxisAlignment::Center)
+        .padding(15.)
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let keypad = match self.state {
+            LockState::Locked => Either::A(self.keypad()),
+            LockState::Loading | LockState::Unlocked => Either::B(flex_col(())),
+        };
+        fork(
+            keypad,
+            worker(
+                |proxy, mut rx: UnboundedReceiver<String>| async move {
+                    // The tasks stay hidden until the lock can be read.
+                    let lock = loop {
+                        match load().await {
+                            Ok(lock) => break lock,
+                            Err(error) => warn!(%error, "can't read the lock"),
+                        }
+                        sleep(IDLE_CHECK_INTERVAL).await;
+                    };
+                    if proxy.message(LockMessage::Loaded(lock.clone())).is_err() {
+                        return;
+                    }
+                    let Some(lock) = lock else {
+                        return;
+                    };
+                    let mut failures = lock.failures;
+                    let mut next_attempt = Instant::now() + retry_delay(failures);
+                    loop {
+                        let message = match timeout(IDLE_CHECK_INTERVAL, rx.recv()).await {
+                            Err(_) => LockMessage::CheckIdle,
+                            Ok(None) => break,
+                            Ok(Some(pin)) => {
+                                sleep_until(next_attempt.into()).await;
+                                let lock = lock.clone();
+                                let verified = spawn_blocking(move || lock.verify(&pin))
+                                    .await
+                                    .unwrap_or(false);
+                                failures = if verified { 0 } else { failures + 1 };
+                                if let Err(error) = set_failures(failures).await {
+                                    warn!(%error, "can't store the wrong PINs");
+                                }
+                                if verified {
+                                    LockMessage::Checked(Ok(()))
+                                } else {
+                                    let delay = retry_delay(failures);
+                                    next_attempt = Instant::now() + delay;
+                                    LockMessage::Checked(Err(delay))
+                                }
+                            }
+                        };
+                        if proxy.message(message).is_err() {
+                            break;
+                        }
+                    }
+                },
+                |state: &mut Self, sender| state.sender = Some(sender),
+                |state: &mut Self, message: LockMessage| state.handle(message),
+            ),
+        )
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::UnboundedReceiver;
+use xilem::tokio::time::sleep;
+use xilem::view::{flex_col, label, prose, worker};
+
+use crate::logging::{log_dir, recent};
+use crate::ui::theme::MUTED_TEXT_COLOR;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Page with the last lines logged, refreshed while it is shown. `--verbose` includes the
+/// database operations.
+#[derive(Debug, Default)]
+pub struct LogViewer {
+    lines: Vec<String>,
+}
+
+impl LogViewer {
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let location = format!("Older logs are in {}", log_dir().display());
+        let lines = if self.lines.is_empty() {
+            vec![prose("Nothing logged yet").text_color(MUTED_TEXT_COLOR)]
+        } else {
+            self.lines.iter().map(|line| prose(line.clone())).collect()
+        };
+        fork(
+            flex_col((
+                label("Logs"),
+                label(location).color(MUTED_TEXT_COLOR),
+                flex_col(lines),
+            )),
+            worker(
+                |proxy, _rx: UnboundedReceiver<()>| async move {
+                    while proxy.message(recent()).is_ok() {
+                        sleep(REFRESH_INTERVAL).await;
+                    }
+                },
+                |_: &mut Self, _| {},
+                |state: &mut Self, lines: Vec<String>| {
+                    state.lines = lines;
+                },
+            ),
+        )
+    }
+}
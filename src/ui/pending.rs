@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use uuid::Uuid;
 
@@ -7,6 +7,8 @@ pub struct Pending<T> {
     pub request_id: Uuid,
     pub data: T,
     delay: f32,
+    attempt: u32,
+    created_at: Instant,
 }
 
 impl<T> Pending<T> {
@@ -15,6 +17,8 @@ impl<T> Pending<T> {
             request_id: Uuid::new_v4(),
             data,
             delay: 0.,
+            attempt: 0,
+            created_at: Instant::now(),
         }
     }
 
@@ -23,16 +27,36 @@ impl<T> Pending<T> {
         self
     }
 
+    pub fn with_attempt(mut self, attempt: u32) -> Self {
+        self.attempt = attempt;
+        self
+    }
+
+    /// Number of times this request was retried.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Time since the request was first made, retries included.
+    pub fn age(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+
     pub async fn map<U, F>(self, f: F) -> Pending<U>
     where
         F: AsyncFnOnce(T) -> U,
     {
+        // Without delay, the future doesn't need a Tokio timer, like in `ListHarness`.
+        if self.delay > 0. {
+            xilem::tokio::time::sleep(Duration::from_secs_f32(self.delay)).await;
+        }
         let data = f(self.data).await;
-        std::thread::sleep(Duration::from_secs_f32(self.delay));
         Pending {
             request_id: self.request_id,
             data,
             delay: self.delay,
+            attempt: self.attempt,
+            created_at: self.created_at,
         }
     }
 }
@@ -43,6 +67,8 @@ impl<T> From<(Uuid, T)> for Pending<T> {
             request_id,
             data,
             delay: 0.,
+            attempt: 0,
+            created_at: Instant::now(),
         }
     }
 }
@@ -0,0 +1,174 @@
+use std::hash::{BuildHasher, RandomState};
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{flex_row, label, text_button, worker};
+
+use crate::core::{Priority, ServerError, Status, Task};
+use crate::database::{TaskQuery, get_task, get_tasks};
+use crate::repository::Database;
+use crate::service::{Command, execute_one};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+/// Origin of the commands sent by the picker.
+const ORIGIN: &str = "picker";
+
+fn priority_weight(priority: Priority) -> f32 {
+    match priority {
+        Priority::High => 4.,
+        Priority::Medium => 2.,
+        _ => 1.,
+    }
+}
+
+/// Picks one of `tasks` from `random`, between `0.0` and `1.0`. Tasks are weighted by priority,
+/// and the ones untouched for longer weigh up to twice more, tasks having no due dates.
+pub fn pick(tasks: &[Task], random: f32) -> Option<&Task> {
+    let mut by_age = tasks.iter().collect::<Vec<_>>();
+    by_age.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)));
+    let oldest = by_age.len().saturating_sub(1).max(1) as f32;
+    let weights = by_age
+        .iter()
+        .enumerate()
+        .map(|(age, task)| priority_weight(task.priority) * (1. + age as f32 / oldest))
+        .collect::<Vec<_>>();
+    let mut target = random.clamp(0., 1.) * weights.iter().sum::<f32>();
+    for (task, weight) in by_age.iter().zip(weights) {
+        if target < weight {
+            return Some(task);
+        }
+        target -= weight;
+    }
+    by_age.last().copied()
+}
+
+/// Between `0.0` and `1.0`.
+pub(crate) fn random() -> f32 {
+    RandomState::new().hash_one(0) as f32 / u64::MAX as f32
+}
+
+#[derive(Debug)]
+enum Request {
+    Pick,
+    Start(i64),
+}
+
+#[derive(Debug)]
+enum Response {
+    Picked(Option<Task>),
+    Started(Task),
+}
+
+/// Button picking an active task at random for the moments nothing stands out, see `pick`. The
+/// picked task is selected in the list, and can be started from here.
+#[derive(Debug, Default)]
+pub struct TaskPicker {
+    picked: Option<Task>,
+    /// Whether there was no active task to pick.
+    nothing_to_do: bool,
+    /// Picked task not selected in the list yet, see `take_picked`.
+    to_select: Option<i64>,
+    read_only: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Request>>,
+}
+
+impl TaskPicker {
+    /// Hides the button starting the picked task, see `AsyncList::set_read_only`.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// The task just picked, to be selected in the list.
+    pub fn take_picked(&mut self) -> Option<i64> {
+        self.to_select.take()
+    }
+
+    fn send(&self, request: Request) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let picked = self.picked.as_ref().map(|task| {
+            let start = (task.status == Status::ToDo && !self.read_only).then(|| {
+                let id = task.id;
+                text_button("Start it", move |state: &mut Self| {
+                    state.send(Request::Start(id));
+                })
+            });
+            flex_row((
+                label(format!("Picked “{}”", task.description)),
+                start,
+                text_button("Dismiss", |state: &mut Self| state.picked = None),
+            ))
+        });
+        let nothing_to_do = self
+            .nothing_to_do
+            .then(|| label("Nothing left to do").color(MUTED_TEXT_COLOR));
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_row((
+                text_button("Pick something for me", |state: &mut Self| {
+                    state.send(Request::Pick);
+                }),
+                picked,
+                nothing_to_do,
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Request>| async move {
+                    while let Some(request) = rx.recv().await {
+                        let response = match request {
+                            Request::Pick => {
+                                let query = TaskQuery {
+                                    statuses: Some(vec![Status::ToDo, Status::InProgress]),
+                                    ..Default::default()
+                                };
+                                get_tasks(query)
+                                    .await
+                                    .map(|tasks| Response::Picked(pick(&tasks, random()).cloned()))
+                            }
+                            Request::Start(id) => start(id).await.map(Response::Started),
+                        };
+                        let _ = proxy.message(response);
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Response, ServerError>| match result {
+                    Ok(Response::Picked(task)) => {
+                        state.to_select = task.as_ref().map(|task| task.id);
+                        state.nothing_to_do = task.is_none();
+                        state.picked = task;
+                        state.error = None;
+                    }
+                    Ok(Response::Started(task)) => {
+                        state.picked = Some(task);
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
+
+/// Sets the task in progress, reading it again so that the changes made since the pick are kept.
+async fn start(id: i64) -> Result<Task, ServerError> {
+    let task = get_task(id).await?;
+    let command = Command::UpdateTask {
+        id,
+        description: task.description.to_string(),
+        status: Status::InProgress,
+        priority: task.priority,
+    };
+    execute_one::<Database>(ORIGIN, command).await
+}
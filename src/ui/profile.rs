@@ -0,0 +1,113 @@
+use std::process::Command;
+
+use xilem::core::Edit;
+use xilem::masonry::layout::AsUnit;
+use xilem::masonry::theme::default_property_set;
+use xilem::style::Style;
+use xilem::view::{FlexExt, flex_col, flex_row, label, text_button, text_input};
+use xilem::winit::error::EventLoopError;
+use xilem::{EventLoopBuilder, WidgetView, WindowId, WindowView, Xilem, window};
+
+use crate::profile::{self, DEFAULT_PROFILE};
+use crate::ui::theme::{BACKGROUND_COLOR, DANGER_COLOR, apply_theme};
+
+/// Window asking which profile to open, shown at startup once profiles were created. The app is
+/// launched again with `--profile`, so that no database is opened before the choice.
+pub struct ProfilePicker {
+    running: bool,
+    window_id: WindowId,
+    profiles: Vec<String>,
+    new_profile: String,
+    error: Option<String>,
+    /// Arguments of the app, passed again with the chosen profile.
+    args: Vec<String>,
+}
+
+impl ProfilePicker {
+    pub fn new(args: Vec<String>) -> Self {
+        Self {
+            running: true,
+            window_id: WindowId::next(),
+            profiles: profile::list(),
+            new_profile: String::new(),
+            error: None,
+            args,
+        }
+    }
+
+    fn open(&mut self, name: String) {
+        if let Err(error) = profile::validate(&name) {
+            self.error = Some(error.to_string());
+            return;
+        }
+        let launched = std::env::current_exe().and_then(|exe| {
+            Command::new(exe)
+                .args(&self.args)
+                .args(["--profile", &name])
+                .spawn()
+        });
+        match launched {
+            Ok(_) => self.running = false,
+            Err(error) => self.error = Some(format!("Couldn't open the profile: {error}")),
+        }
+    }
+
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let profiles = std::iter::once(DEFAULT_PROFILE.to_string())
+            .chain(self.profiles.iter().cloned())
+            .map(|name| {
+                text_button(name.clone(), move |state: &mut Self| {
+                    state.open(name.clone());
+                })
+            })
+            .collect::<Vec<_>>();
+        let input = text_input(self.new_profile.clone(), |state: &mut Self, name| {
+            state.new_profile = name;
+        })
+        .on_enter(|state: &mut Self, name| state.open(name.trim().to_string()))
+        .placeholder("New profile");
+        let create_button = text_button("Create", |state: &mut Self| {
+            state.open(state.new_profile.trim().to_string());
+        });
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        flex_col((
+            label("Open the tasks of"),
+            flex_col(profiles),
+            flex_row((input.flex(1.), create_button)),
+            error,
+        ))
+        .gap(10.px())
+        .padding(15.)
+    }
+
+    fn logic(&mut self) -> impl Iterator<Item = WindowView<ProfilePicker>> + use<> {
+        std::iter::once(
+            window(self.window_id, "Todos", self.view())
+                .with_options(|options| {
+                    options.on_close(|state: &mut ProfilePicker| state.running = false)
+                })
+                .with_base_color(BACKGROUND_COLOR),
+        )
+    }
+}
+
+impl xilem::AppState for ProfilePicker {
+    fn keep_running(&self) -> bool {
+        self.running
+    }
+}
+
+/// Shows the picker until a profile is opened or the window is closed.
+pub fn run_picker(
+    event_loop: EventLoopBuilder,
+    picker: ProfilePicker,
+) -> Result<(), EventLoopError> {
+    let mut def_props = default_property_set();
+    apply_theme(&mut def_props);
+
+    let app = Xilem::new(picker, ProfilePicker::logic).with_default_properties(def_props);
+    app.run_in(event_loop)
+}
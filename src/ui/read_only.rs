@@ -0,0 +1,99 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{checkbox, flex_col, flex_row, label, prose, worker};
+
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const READ_ONLY_SETTING: &str = "app.read_only";
+const DESCRIPTION: &str = "Hides the create form and the buttons changing the tasks, and refuses \
+    their changes, for showing the list on a shared screen.";
+
+/// Read-only mode of the task list, saved in the settings or forced with `--read-only`.
+#[derive(Debug, Default)]
+pub struct ReadOnlyMode {
+    /// `None` until loaded.
+    saved: Option<bool>,
+    /// Set by `--read-only` for this session, whatever the setting.
+    forced: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<bool>>,
+}
+
+impl ReadOnlyMode {
+    pub fn force(&mut self) {
+        self.forced = true;
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.forced || self.saved == Some(true)
+    }
+
+    fn set(&mut self, read_only: bool) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(read_only);
+        }
+        self.saved = Some(read_only);
+    }
+
+    /// Section of the settings page switching the mode.
+    pub fn settings_view(&self) -> impl WidgetView<Edit<Self>> + use<> {
+        let switch = if self.forced {
+            Either::A(label(
+                "Read-only for this session, started with --read-only",
+            ))
+        } else {
+            Either::B(checkbox(
+                "Read-only",
+                self.saved == Some(true),
+                |state: &mut Self, checked| state.set(checked),
+            ))
+        };
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        flex_col((
+            label("Read-only mode"),
+            prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+            switch,
+            error,
+        ))
+    }
+
+    /// Badge shown while read-only, loading and saving the setting in the background.
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let badge = self
+            .is_read_only()
+            .then(|| label("Read-only").color(MUTED_TEXT_COLOR));
+        fork(
+            flex_row(badge),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<bool>| async move {
+                    let saved = get_setting(READ_ONLY_SETTING)
+                        .await
+                        .map(|saved| saved.as_deref() == Some("true"));
+                    let _ = proxy.message(saved);
+                    while let Some(read_only) = rx.recv().await {
+                        let result = set_setting(READ_ONLY_SETTING, read_only.to_string()).await;
+                        let _ = proxy.message(result.map(|()| read_only));
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<bool, ServerError>| match result {
+                    Ok(read_only) => {
+                        state.saved = Some(read_only);
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,235 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{
+    FlexExt, button, flex_col, flex_row, label, prose, text_button, text_input, worker,
+};
+
+use crate::core::{Priority, ServerError, Task};
+use crate::database::{get_setting, get_stale_tasks, set_setting};
+use crate::repository::Database;
+use crate::service::{Command, execute};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR, ThemeColor};
+
+const DAYS_SETTING: &str = "review.stale_days";
+const DEFAULT_DAYS: u32 = 7;
+/// Origin of the commands sent by the review.
+const ORIGIN: &str = "review";
+const DESCRIPTION: &str = "Goes through the tasks not done and untouched for a while, one at a \
+    time, to keep the list honest.";
+
+/// What to do with a stale task.
+#[derive(Clone, Copy, Debug)]
+enum Decision {
+    /// Saves the task unchanged, so that it isn't stale anymore.
+    Keep,
+    /// Lowers the priority by one level.
+    Deprioritize,
+    Delete,
+}
+
+#[derive(Debug)]
+enum Request {
+    LoadDays,
+    Start(u32),
+    Decide(Task, Decision),
+}
+
+#[derive(Debug)]
+enum Response {
+    Days(u32),
+    Started(Vec<Task>),
+    Decided,
+}
+
+fn lower(priority: Priority) -> Priority {
+    match priority {
+        Priority::High => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+async fn decide(task: Task, decision: Decision) -> Result<(), ServerError> {
+    let command = match decision {
+        Decision::Keep | Decision::Deprioritize => Command::UpdateTask {
+            id: task.id,
+            description: task.description.to_string(),
+            status: task.status,
+            priority: match decision {
+                Decision::Deprioritize => lower(task.priority),
+                _ => task.priority,
+            },
+        },
+        Decision::Delete => Command::DeleteTask(task.id),
+    };
+    execute::<Database>(ORIGIN, command).await?;
+    Ok(())
+}
+
+/// Page walking through the stale tasks, see `get_stale_tasks`. The decisions are sent to the
+/// service, so the task list follows them like the changes of the other frontends.
+#[derive(Debug, Default)]
+pub struct WeeklyReview {
+    days: String,
+    /// Tasks left to review, the first one being shown. `None` before starting.
+    queue: Option<Vec<Task>>,
+    /// Number of tasks of the review, for the progress.
+    total: usize,
+    /// Whether a decision is being saved.
+    saving: bool,
+    read_only: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Request>>,
+}
+
+impl WeeklyReview {
+    /// Hides the decisions changing the tasks, see `AsyncList::set_read_only`.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    fn send(&self, request: Request) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+
+    fn start(&mut self) {
+        match self.days.trim().parse::<u32>() {
+            Ok(days) if days > 0 => {
+                self.error = None;
+                self.send(Request::Start(days));
+            }
+            _ => self.error = Some("Enter a number of days above 0".to_string()),
+        }
+    }
+
+    fn decide(&mut self, decision: Decision) {
+        let Some(task) = self.queue.as_ref().and_then(|queue| queue.first()) else {
+            return;
+        };
+        self.send(Request::Decide(task.clone(), decision));
+        self.saving = true;
+    }
+
+    /// Moves on to the next task, leaving this one for the next review.
+    fn skip(&mut self) {
+        if let Some(queue) = &mut self.queue
+            && !queue.is_empty()
+        {
+            queue.remove(0);
+        }
+    }
+
+    fn task_view(&self, task: &Task) -> impl WidgetView<Edit<Self>> + use<> {
+        let done = self.total - self.queue.as_ref().map_or(0, Vec::len);
+        let progress = format!("Task {} of {}", done + 1, self.total);
+        let decisions = (!self.read_only && !self.saving).then(|| {
+            let deprioritize = (task.priority != Priority::Low).then(|| {
+                text_button("Deprioritize", |state: &mut Self| {
+                    state.decide(Decision::Deprioritize);
+                })
+            });
+            flex_row((
+                text_button("Keep", |state: &mut Self| state.decide(Decision::Keep)),
+                deprioritize,
+                button(label("Delete").color(DANGER_COLOR), |state: &mut Self| {
+                    state.decide(Decision::Delete);
+                }),
+            ))
+        });
+        flex_col((
+            label(progress).color(MUTED_TEXT_COLOR),
+            prose(task.description.clone()),
+            flex_row((
+                label(task.status.to_string()).color(task.status.color()),
+                label(format!("{} priority", task.priority)).color(task.priority.color()),
+            )),
+            label(format!("Untouched since {}", task.updated_at)).color(MUTED_TEXT_COLOR),
+            flex_row((
+                decisions,
+                text_button("Decide later", |state: &mut Self| state.skip()),
+            )),
+        ))
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let content = match &self.queue {
+            Some(queue) => match queue.first() {
+                Some(task) => Either::A(Either::A(self.task_view(task))),
+                None => Either::A(Either::B(flex_col((
+                    label(format!("Reviewed {} stale tasks", self.total)),
+                    text_button("Review again", |state: &mut Self| state.queue = None),
+                )))),
+            },
+            None => {
+                let input = text_input(self.days.clone(), |state: &mut Self, days| {
+                    state.days = days;
+                })
+                .on_enter(|state: &mut Self, _| state.start());
+                Either::B(flex_row((
+                    label("Untouched for"),
+                    input.flex(1.),
+                    label("days"),
+                    text_button("Start the review", |state: &mut Self| state.start()),
+                )))
+            }
+        };
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Weekly review"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                content,
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Request>| async move {
+                    while let Some(request) = rx.recv().await {
+                        let response = match request {
+                            Request::LoadDays => get_setting(DAYS_SETTING).await.map(|days| {
+                                let days = days.and_then(|days| days.parse().ok());
+                                Response::Days(days.unwrap_or(DEFAULT_DAYS))
+                            }),
+                            Request::Start(days) => {
+                                let started = async {
+                                    set_setting(DAYS_SETTING, days.to_string()).await?;
+                                    get_stale_tasks(days).await
+                                };
+                                started.await.map(Response::Started)
+                            }
+                            Request::Decide(task, decision) => {
+                                decide(task, decision).await.map(|()| Response::Decided)
+                            }
+                        };
+                        let _ = proxy.message(response);
+                    }
+                },
+                |state: &mut Self, sender: UnboundedSender<Request>| {
+                    let _ = sender.send(Request::LoadDays);
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Response, ServerError>| {
+                    state.saving = false;
+                    match result {
+                        Ok(Response::Days(days)) => state.days = days.to_string(),
+                        Ok(Response::Started(tasks)) => {
+                            state.total = tasks.len();
+                            state.queue = Some(tasks);
+                        }
+                        Ok(Response::Decided) => {
+                            state.error = None;
+                            state.skip();
+                        }
+                        Err(error) => state.error = Some(error.to_string()),
+                    }
+                },
+            ),
+        )
+    }
+}
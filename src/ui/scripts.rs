@@ -0,0 +1,77 @@
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{flex_col, flex_row, label, prose, text_button, text_input, worker};
+
+use crate::core::ServerError;
+use crate::scripting::{check, script, set_script};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const DESCRIPTION: &str = "Rhai script automating the tasks, like \
+    weekly(\"monday\", \"08:00\", || create_task(\"Water the plants\"));";
+
+/// Settings section to edit the script run by `scripting::run`.
+#[derive(Debug, Default)]
+pub struct ScriptsPanel {
+    source: String,
+    /// Whether `source` differs from the saved script.
+    edited: bool,
+    error: Option<String>,
+    sender: Option<UnboundedSender<String>>,
+}
+
+impl ScriptsPanel {
+    fn save(&mut self) {
+        if let Err(error) = check(&self.source) {
+            self.error = Some(error.to_string());
+            return;
+        }
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(self.source.clone());
+        }
+        self.error = None;
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let editor = text_input(self.source.clone(), |state: &mut Self, source| {
+            state.source = source;
+            state.edited = true;
+        })
+        .placeholder("// every(60, || print(tasks().len()));");
+        let status = match &self.error {
+            Some(error) => label(error.clone()).color(DANGER_COLOR),
+            None if self.edited => label("Not saved").color(MUTED_TEXT_COLOR),
+            None => label("Saved").color(MUTED_TEXT_COLOR),
+        };
+        let save_button = text_button("Save script", |state: &mut Self| state.save());
+        fork(
+            flex_col((
+                label("Scripts"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                editor,
+                flex_row((save_button, status)),
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<String>| async move {
+                    let _ = proxy.message(script().await.map(Option::unwrap_or_default));
+                    while let Some(source) = rx.recv().await {
+                        let _ = proxy.message(set_script(source.clone()).await.map(|()| source));
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<String, ServerError>| match result {
+                    Ok(source) => {
+                        state.edited = state.source != source && state.edited;
+                        if !state.edited {
+                            state.source = source;
+                        }
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,90 @@
+use tracing::warn;
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{flex_row, worker};
+
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+
+const PAGE_SETTING: &str = "session.page";
+const SELECTION_SETTING: &str = "session.selection";
+
+/// Where the app was left, the filter and the sorter being saved by the task list itself.
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct Session {
+    /// Name of the page shown instead of the tasks, empty for the tasks.
+    pub page: String,
+    /// Selected tasks, the details being shown when a single one is.
+    pub selection: Vec<i64>,
+}
+
+async fn load() -> Result<Session, ServerError> {
+    let page = get_setting(PAGE_SETTING).await?.unwrap_or_default();
+    let selection = get_setting(SELECTION_SETTING)
+        .await?
+        .map(|ids| ids.split(',').filter_map(|id| id.parse().ok()).collect())
+        .unwrap_or_default();
+    Ok(Session { page, selection })
+}
+
+async fn save(session: Session) -> Result<(), ServerError> {
+    set_setting(PAGE_SETTING, session.page).await?;
+    let ids = session.selection.iter().map(i64::to_string);
+    set_setting(SELECTION_SETTING, ids.collect::<Vec<_>>().join(",")).await
+}
+
+/// Saves the session while it changes, and loads the one of the last launch to restore it.
+#[derive(Debug, Default)]
+pub struct SessionRestore {
+    /// Session of the last launch, until taken.
+    loaded: Option<Session>,
+    /// `None` until loaded, nothing being saved before.
+    saved: Option<Session>,
+    sender: Option<UnboundedSender<Session>>,
+}
+
+impl SessionRestore {
+    /// The session of the last launch, once loaded.
+    pub fn take_loaded(&mut self) -> Option<Session> {
+        self.loaded.take()
+    }
+
+    /// Saves `session` if it changed since the last time.
+    pub fn save(&mut self, session: Session) {
+        if self.saved.as_ref().is_none_or(|saved| *saved == session) {
+            return;
+        }
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(session.clone());
+        }
+        self.saved = Some(session);
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        fork(
+            flex_row(()),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Session>| async move {
+                    let _ = proxy.message(load().await);
+                    while let Some(session) = rx.recv().await {
+                        if let Err(error) = save(session).await {
+                            warn!(%error, "can't save the session");
+                        }
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Session, ServerError>| {
+                    let session = result.unwrap_or_else(|error| {
+                        warn!(%error, "can't restore the session");
+                        Session::default()
+                    });
+                    state.saved = Some(session.clone());
+                    state.loaded = Some(session);
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,113 @@
+use xilem::WidgetView;
+use xilem::core::{Edit, map_state};
+use xilem::masonry::layout::AsUnit;
+use xilem::view::flex_col;
+
+use crate::ui::custom_fields::CustomFieldSettings;
+use crate::ui::enter_key::EnterKeySettings;
+use crate::ui::erase::EraseData;
+use crate::ui::read_only::ReadOnlyMode;
+#[cfg(feature = "scripting")]
+use crate::ui::scripts::ScriptsPanel;
+#[cfg(feature = "spellcheck")]
+use crate::ui::spelling::SpellcheckSettings;
+use crate::ui::start_view::StartViewSettings;
+#[cfg(feature = "telemetry")]
+use crate::ui::telemetry::TelemetrySettings;
+#[cfg(feature = "webhooks")]
+use crate::ui::webhooks::WebhookSettings;
+use crate::ui::xp::XpBar;
+
+/// Page with the settings of the app and of the optional features, shown instead of the tasks.
+#[derive(Debug, Default)]
+pub struct SettingsPage {
+    start_view: StartViewSettings,
+    enter_key: EnterKeySettings,
+    #[cfg(feature = "spellcheck")]
+    spelling: SpellcheckSettings,
+    read_only: ReadOnlyMode,
+    custom_fields: CustomFieldSettings,
+    xp: XpBar,
+    #[cfg(feature = "webhooks")]
+    webhooks: WebhookSettings,
+    #[cfg(feature = "scripting")]
+    scripts: ScriptsPanel,
+    #[cfg(feature = "telemetry")]
+    telemetry: TelemetrySettings,
+    erase: EraseData,
+}
+
+impl SettingsPage {
+    pub fn read_only(&self) -> &ReadOnlyMode {
+        &self.read_only
+    }
+
+    pub fn read_only_mut(&mut self) -> &mut ReadOnlyMode {
+        &mut self.read_only
+    }
+
+    pub fn xp_mut(&mut self) -> &mut XpBar {
+        &mut self.xp
+    }
+
+    /// Whether all the data was erased, the app having to restart.
+    pub fn is_erased(&self) -> bool {
+        self.erase.is_erased()
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let start_view = map_state(self.start_view.view(), |state: &mut Self, ()| {
+            &mut state.start_view
+        });
+        let enter_key = map_state(self.enter_key.view(), |state: &mut Self, ()| {
+            &mut state.enter_key
+        });
+        #[cfg(feature = "spellcheck")]
+        let spelling = map_state(self.spelling.view(), |state: &mut Self, ()| {
+            &mut state.spelling
+        });
+        #[cfg(not(feature = "spellcheck"))]
+        let spelling = ();
+        let read_only = map_state(self.read_only.settings_view(), |state: &mut Self, ()| {
+            &mut state.read_only
+        });
+        let custom_fields = map_state(self.custom_fields.view(), |state: &mut Self, ()| {
+            &mut state.custom_fields
+        });
+        let xp = map_state(self.xp.settings_view(), |state: &mut Self, ()| {
+            &mut state.xp
+        });
+        #[cfg(feature = "webhooks")]
+        let webhooks = map_state(self.webhooks.view(), |state: &mut Self, ()| {
+            &mut state.webhooks
+        });
+        #[cfg(not(feature = "webhooks"))]
+        let webhooks = ();
+        #[cfg(feature = "scripting")]
+        let scripts = map_state(self.scripts.view(), |state: &mut Self, ()| {
+            &mut state.scripts
+        });
+        #[cfg(not(feature = "scripting"))]
+        let scripts = ();
+        #[cfg(feature = "telemetry")]
+        let telemetry = map_state(self.telemetry.view(), |state: &mut Self, ()| {
+            &mut state.telemetry
+        });
+        #[cfg(not(feature = "telemetry"))]
+        let telemetry = ();
+        let erase = map_state(self.erase.view(), |state: &mut Self, ()| &mut state.erase);
+        flex_col((
+            start_view,
+            enter_key,
+            spelling,
+            read_only,
+            custom_fields,
+            xp,
+            webhooks,
+            scripts,
+            telemetry,
+            erase,
+        ))
+        .gap(30.px())
+    }
+}
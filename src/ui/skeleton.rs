@@ -0,0 +1,65 @@
+use std::f64::consts::TAU;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::masonry::layout::AsUnit;
+use xilem::masonry::theme::BASIC_WIDGET_HEIGHT;
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::UnboundedReceiver;
+use xilem::tokio::time::sleep;
+use xilem::view::{FlexExt, flex_col, flex_row, sized_box, worker};
+
+use crate::ui::theme::{SURFACE_BORDER_COLOR, SURFACE_COLOR};
+
+/// How often the shimmer moves.
+const FRAME_INTERVAL: Duration = Duration::from_millis(50);
+/// Time for the shimmer to go through all the rows.
+const SHIMMER_PERIOD: f64 = 1.5;
+
+static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// Placeholder rows shaped like the tasks, with a shimmer running down while they are shown, so
+/// that the window looks ready before the first fetch returns.
+pub fn skeleton<State>(rows: usize) -> impl WidgetView<Edit<State>> + use<State>
+where
+    State: 'static,
+{
+    let time = START.elapsed().as_secs_f64() / SHIMMER_PERIOD;
+    let rows = (0..rows)
+        .map(|row| {
+            let wave = ((time - row as f64 / 8.) * TAU).sin() * 0.5 + 0.5;
+            let color = SURFACE_BORDER_COLOR.map_lightness(|l| l * (1. + 0.6 * wave as f32));
+            let bar = move |width: f64| {
+                sized_box(flex_row(()).corner_radius(4.).background_color(color))
+                    .width(width.px())
+                    .height(BASIC_WIDGET_HEIGHT)
+            };
+            // Descriptions of different lengths.
+            let description = 200. + (row * 97 % 5) as f64 * 60.;
+            flex_row((
+                bar(80.),
+                flex_row(bar(description)).flex(1.),
+                bar(50.),
+                bar(60.),
+            ))
+            .padding(5.)
+            .corner_radius(10.)
+            .background_color(SURFACE_COLOR)
+        })
+        .collect::<Vec<_>>();
+    fork(
+        flex_col(rows),
+        worker(
+            |proxy, _rx: UnboundedReceiver<()>| async move {
+                // Each message rebuilds the view with the next frame.
+                while proxy.message(()).is_ok() {
+                    sleep(FRAME_INTERVAL).await;
+                }
+            },
+            |_: &mut State, _| {},
+            |_: &mut State, ()| {},
+        ),
+    )
+}
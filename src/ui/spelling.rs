@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::timeout;
+use xilem::view::{checkbox, flex_col, flex_row, label, prose, text_button, worker};
+
+use crate::core::ServerError;
+use crate::spellcheck::{
+    Misspelling, SpellcheckError, available_locales, check, locale, replace_word, set_locale,
+};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+/// The description is only checked once the user stops typing.
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
+/// Misspelled words shown at once, the first ones of the description.
+const MAX_SHOWN: usize = 3;
+const DESCRIPTION: &str = "Lists the misspelled words of the description being typed, with \
+    suggestions replacing them. Dictionaries are the .aff and .dic files of Hunspell, installed \
+    with the system or in the directory set by TODOS_DICTIONARIES.";
+
+/// Misspelled words of the description being typed, see `spellcheck`, shown under the input with
+/// the suggestions replacing them. The view returns the corrected description once a suggestion
+/// is chosen. Nothing is shown while no dictionary is chosen.
+#[derive(Debug, Default)]
+pub struct SpellingHints {
+    text: String,
+    misspellings: Vec<Misspelling>,
+    sender: Option<UnboundedSender<String>>,
+}
+
+impl SpellingHints {
+    /// Checks `text` once the user stops typing.
+    pub fn set_text(&mut self, text: &str) {
+        if self.text == text {
+            return;
+        }
+        self.text = text.to_string();
+        self.misspellings.clear();
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(self.text.clone());
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>, Option<String>> + use<> {
+        let hints = self
+            .misspellings
+            .iter()
+            .take(MAX_SHOWN)
+            .map(|misspelling| {
+                let suggestions = misspelling
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| {
+                        let corrected = replace_word(
+                            &self.text,
+                            misspelling.start,
+                            &misspelling.word,
+                            suggestion,
+                        );
+                        text_button(suggestion.clone(), move |_: &mut Self| {
+                            Some(corrected.clone())
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let none = misspelling
+                    .suggestions
+                    .is_empty()
+                    .then(|| label("No suggestions").color(MUTED_TEXT_COLOR));
+                flex_row((
+                    label(misspelling.word.clone()).color(DANGER_COLOR),
+                    flex_row(suggestions),
+                    none,
+                ))
+            })
+            .collect::<Vec<_>>();
+        fork(
+            flex_col(hints),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<String>| async move {
+                    while let Some(mut text) = rx.recv().await {
+                        while let Ok(Some(next)) = timeout(DEBOUNCE_DELAY, rx.recv()).await {
+                            text = next;
+                        }
+                        let result = check(text.clone()).await;
+                        let _ = proxy.message((text, result));
+                    }
+                },
+                |state: &mut Self, sender: UnboundedSender<String>| {
+                    // The description may be typed before the worker starts.
+                    if !state.text.is_empty() {
+                        let _ = sender.send(state.text.clone());
+                    }
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self,
+                 (text, result): (String, Result<Vec<Misspelling>, SpellcheckError>)| {
+                    // Words of a description since edited are dropped.
+                    if text == state.text {
+                        // A missing dictionary is reported by the settings.
+                        state.misspellings = result.unwrap_or_default();
+                    }
+                    None
+                },
+            ),
+        )
+    }
+}
+
+/// Settings section choosing the dictionary of the spell checking, or turning it off.
+#[derive(Debug, Default)]
+pub struct SpellcheckSettings {
+    /// `None` until loaded, `Some(None)` when off.
+    locale: Option<Option<String>>,
+    /// Locales of the installed dictionaries.
+    available: Vec<String>,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Option<String>>>,
+}
+
+impl SpellcheckSettings {
+    fn set(&mut self, locale: Option<String>) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(locale.clone());
+        }
+        self.locale = Some(locale);
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let choices = match &self.locale {
+            None => Either::A(label("Loading…").color(MUTED_TEXT_COLOR)),
+            Some(current) => {
+                let missing = current
+                    .as_ref()
+                    .filter(|locale| !self.available.contains(locale))
+                    .map(|locale| {
+                        label(format!("The dictionary {locale} isn't installed"))
+                            .color(DANGER_COLOR)
+                    });
+                let locales = [None]
+                    .into_iter()
+                    .chain(self.available.iter().cloned().map(Some))
+                    .map(|locale| {
+                        let name = locale.clone().unwrap_or_else(|| "Off".to_string());
+                        checkbox(name, *current == locale, move |state: &mut Self, _| {
+                            state.set(locale.clone())
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Either::B(flex_col((flex_col(locales), missing)))
+            }
+        };
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Spell checking"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                choices,
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Option<String>>| async move {
+                    let _ = proxy.message(locale().await);
+                    while let Some(locale) = rx.recv().await {
+                        let result = set_locale(locale.clone()).await;
+                        let _ = proxy.message(result.map(|()| locale));
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.available = available_locales();
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Option<String>, ServerError>| match result {
+                    Ok(locale) => {
+                        state.locale = Some(locale);
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
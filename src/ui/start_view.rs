@@ -0,0 +1,104 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{checkbox, flex_col, flex_row, label, prose, worker};
+
+use crate::core::ServerError;
+use crate::ui::task_list::{StatusFilter, TaskSorter, set_start_view, start_view};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const DESCRIPTION: &str = "The filter and the sorter of the list at launch. The last used ones are \
+    kept otherwise, and --filter or the configuration file still win.";
+const FILTERS: [(&str, Option<StatusFilter>); 4] = [
+    ("Last used", None),
+    ("All", Some(StatusFilter::All)),
+    ("Active", Some(StatusFilter::Active)),
+    ("Completed", Some(StatusFilter::Completed)),
+];
+const SORTERS: [(&str, Option<TaskSorter>); 4] = [
+    ("Last used", None),
+    ("Status first", Some(TaskSorter::StatusFirst)),
+    ("Priority first", Some(TaskSorter::PriorityFirst)),
+    ("Manual order", Some(TaskSorter::Manual)),
+];
+
+type StartView = (Option<StatusFilter>, Option<TaskSorter>);
+
+/// Settings section choosing what the task list starts with, see `task_list::start_view`.
+#[derive(Debug, Default)]
+pub struct StartViewSettings {
+    /// `None` until loaded.
+    start_view: Option<StartView>,
+    error: Option<String>,
+    sender: Option<UnboundedSender<StartView>>,
+}
+
+impl StartViewSettings {
+    fn set(&mut self, start_view: StartView) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(start_view);
+        }
+        self.start_view = Some(start_view);
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let choices = match self.start_view {
+            None => Either::A(label("Loading…").color(MUTED_TEXT_COLOR)),
+            Some((filter, sorter)) => {
+                let filters = FILTERS
+                    .into_iter()
+                    .map(|(name, choice)| {
+                        checkbox(name, filter == choice, move |state: &mut Self, _| {
+                            state.set((choice, sorter));
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let sorters = SORTERS
+                    .into_iter()
+                    .map(|(name, choice)| {
+                        checkbox(name, sorter == choice, move |state: &mut Self, _| {
+                            state.set((filter, choice));
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Either::B(flex_col((
+                    flex_row((label("Show"), flex_row(filters))),
+                    flex_row((label("Sort"), flex_row(sorters))),
+                )))
+            }
+        };
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Task list at launch"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                choices,
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<StartView>| async move {
+                    let _ = proxy.message(start_view().await);
+                    while let Some((filter, sorter)) = rx.recv().await {
+                        let result = set_start_view(filter, sorter).await;
+                        let _ = proxy.message(result.map(|()| (filter, sorter)));
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<StartView, ServerError>| match result {
+                    Ok(start_view) => {
+                        state.start_view = Some(start_view);
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use tracing::warn;
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::timeout;
+use xilem::view::{flex_row, label, text_button, worker};
+
+use crate::Priority;
+use crate::llm::{LlmConfig, LlmError, suggest_priority};
+use crate::ui::theme::{MUTED_TEXT_COLOR, ThemeColor};
+
+/// The model is only asked once the user stops typing.
+const DEBOUNCE_DELAY: Duration = Duration::from_secs(1);
+
+/// Priority suggested by the model configured with `TODOS_LLM_URL` for the description being
+/// typed, shown as a chip to accept or reject. Tasks have no tags or due dates yet, so only the
+/// priority is suggested. Nothing is shown if no model is configured.
+#[derive(Debug)]
+pub struct PrioritySuggestion {
+    config: Option<LlmConfig>,
+    description: String,
+    suggested: Option<Priority>,
+    accepted: Option<Priority>,
+    sender: Option<UnboundedSender<String>>,
+}
+
+impl Default for PrioritySuggestion {
+    fn default() -> Self {
+        Self {
+            config: LlmConfig::from_env(),
+            description: String::new(),
+            suggested: None,
+            accepted: None,
+            sender: None,
+        }
+    }
+}
+
+impl PrioritySuggestion {
+    /// Asks for a new suggestion, once the user stops typing.
+    pub fn set_description(&mut self, description: &str) {
+        self.description = description.trim().to_string();
+        self.suggested = None;
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(self.description.clone());
+        }
+    }
+
+    /// Returns the accepted priority, and forgets the suggestion for the next task.
+    pub fn take_accepted(&mut self) -> Option<Priority> {
+        self.description.clear();
+        self.suggested = None;
+        self.accepted.take()
+    }
+
+    fn accept(&mut self) {
+        self.accepted = self.suggested.take();
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let config = self.config.clone();
+        let chip = match (self.accepted, self.suggested) {
+            (Some(priority), _) => Some(flex_row((
+                label(format!("{priority} priority")).color(priority.text_color()),
+                text_button("Undo", |state: &mut Self| state.accepted = None),
+            ))),
+            (None, Some(priority)) => Some(flex_row((
+                label(format!("Suggested: {priority} priority")).color(MUTED_TEXT_COLOR),
+                text_button("Accept", |state: &mut Self| state.accept()),
+                text_button("Reject", |state: &mut Self| state.suggested = None),
+            ))),
+            (None, None) => None,
+        };
+        fork(
+            flex_row(chip),
+            worker(
+                move |proxy, mut rx: UnboundedReceiver<String>| {
+                    let config = config.clone();
+                    async move {
+                        let Some(config) = config else {
+                            return;
+                        };
+                        while let Some(mut description) = rx.recv().await {
+                            while let Ok(Some(next)) = timeout(DEBOUNCE_DELAY, rx.recv()).await {
+                                description = next;
+                            }
+                            if description.is_empty() {
+                                continue;
+                            }
+                            let result = suggest_priority(&config, &description).await;
+                            let _ = proxy.message((description, result));
+                        }
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, (description, result): (String, Result<Priority, LlmError>)| {
+                    // Suggestions for a description since edited are dropped.
+                    if description != state.description {
+                        return;
+                    }
+                    match result {
+                        Ok(priority) => state.suggested = Some(priority),
+                        Err(error) => warn!(%error, "couldn't suggest a priority"),
+                    }
+                },
+            ),
+        )
+    }
+}
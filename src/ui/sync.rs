@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::{sleep, timeout};
+use xilem::view::{button, flex_row, label, spinner, text_button, worker};
+
+use crate::notify::{Category, notify};
+use crate::sync::SyncReport;
+use crate::sync::caldav::{CalDavConfig, SyncError, sync};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(300);
+/// Delay before pushing the local changes, so that a burst of them is synchronized once.
+const PUSH_DELAY: Duration = Duration::from_secs(10);
+
+/// Synchronizes the tasks with the CalDAV server configured by `TODOS_CALDAV_URL` on startup,
+/// regularly, after local changes and with a button. Nothing is shown if no server is configured.
+#[derive(Debug)]
+pub struct CalDavSync {
+    config: Option<CalDavConfig>,
+    syncing: bool,
+    last_result: Option<Result<SyncReport, String>>,
+    /// Whether the last synchronization changed the tasks, so that the list must be reloaded.
+    changed: bool,
+    /// Sends the delay before the next synchronization.
+    sender: Option<UnboundedSender<Duration>>,
+}
+
+impl Default for CalDavSync {
+    fn default() -> Self {
+        let config = CalDavConfig::from_env();
+        Self {
+            syncing: config.is_some(),
+            config,
+            last_result: None,
+            changed: false,
+            sender: None,
+        }
+    }
+}
+
+impl CalDavSync {
+    fn sync(&mut self) {
+        if let Some(sender) = &self.sender {
+            self.syncing = sender.send(Duration::ZERO).is_ok();
+        }
+    }
+
+    /// Synchronizes soon, to push the changes made to the tasks.
+    pub fn push_local_changes(&mut self) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(PUSH_DELAY);
+        }
+    }
+
+    /// Returns whether the tasks changed since the last call.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let config = self.config.clone();
+        let status = match &self.last_result {
+            Some(Ok(report)) if !report.conflicts.is_empty() => Some(
+                label(format!(
+                    "Synced, {} conflicts kept from the server",
+                    report.conflicts.len()
+                ))
+                .color(MUTED_TEXT_COLOR),
+            ),
+            Some(Ok(_)) => Some(label("Synced").color(MUTED_TEXT_COLOR)),
+            Some(Err(error)) => Some(label(error.clone()).color(DANGER_COLOR)),
+            None => None,
+        };
+        let sync_button = if self.syncing {
+            Either::A(button(spinner(), |_| ()))
+        } else {
+            Either::B(text_button("Sync", |state: &mut Self| state.sync()))
+        };
+        let content = self
+            .config
+            .is_some()
+            .then(|| flex_row((status, sync_button)));
+        fork(
+            flex_row(content),
+            worker(
+                move |proxy, mut rx: UnboundedReceiver<Duration>| {
+                    let config = config.clone();
+                    async move {
+                        let Some(config) = config else {
+                            return;
+                        };
+                        loop {
+                            let result = sync(config.clone()).await;
+                            if let Err(error) = &result {
+                                let summary = "Synchronization failed".to_string();
+                                notify(Category::SyncFailure, summary, error.to_string()).await;
+                            }
+                            let _ = proxy.message(result);
+                            match timeout(SYNC_INTERVAL, rx.recv()).await {
+                                Ok(None) => break,
+                                Ok(Some(delay)) => {
+                                    sleep(delay).await;
+                                    // The requests received meanwhile are covered by this one.
+                                    while rx.try_recv().is_ok() {}
+                                }
+                                Err(_) => {}
+                            }
+                        }
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<SyncReport, SyncError>| {
+                    state.changed |= result.as_ref().is_ok_and(SyncReport::changed_locally);
+                    state.last_result = Some(result.map_err(|error| error.to_string()));
+                    state.syncing = false;
+                },
+            ),
+        )
+    }
+}
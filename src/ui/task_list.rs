@@ -1,9 +1,16 @@
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::sync::atomic::{self, AtomicBool};
 
 use thiserror::Error;
 use xilem::WidgetView;
 use xilem::core::one_of::Either;
 use xilem::core::{Edit, Read};
+#[cfg(any(feature = "llm", feature = "spellcheck", feature = "voice"))]
+use xilem::core::{map_action, map_state};
 use xilem::palette::css::BLACK;
 use xilem::style::Style;
 use xilem::view::{
@@ -11,16 +18,32 @@ use xilem::view::{
     text_button, text_input, zstack,
 };
 
-use crate::core::ServerError;
-use crate::database::{create_task, delete_task, get_tasks, update_task};
-use crate::ui::component::Form;
+use crate::core::{CustomField, FieldKind, ServerError, StatusCounts};
+use crate::database::{TaskOrder, TaskQuery, get_setting, set_setting};
+use crate::repository::{Database, TaskRepository};
+use crate::service::{Command, Event, execute, execute_one};
 use crate::ui::component::form::Submit;
+use crate::ui::component::list::filter::AllOf;
+use crate::ui::component::list::grouper::ListGrouper;
+use crate::ui::component::list::search::{SearchFilter, Searchable};
 use crate::ui::component::list::sorter::ListSorter;
-use crate::ui::component::list::storage::Retryable;
+use crate::ui::component::list::storage::{Progress, Retryable};
 use crate::ui::component::list::{
-    ItemAction, ListFilter, ListItem, ListStorage, PendingItemOperation,
+    ItemAction, ListDelta, ListFilter, ListItem, ListPage, ListQuery, ListRequest, ListSettings,
+    ListStorage, PendingItemOperation,
 };
-use crate::ui::theme::{DANGER_COLOR, SUCCESS_COLOR, SURFACE_BORDER_COLOR, SURFACE_COLOR};
+use crate::ui::component::{AsyncList, Form};
+use crate::ui::direction::{self, mirrored_row};
+#[cfg(feature = "spellcheck")]
+use crate::ui::spelling::SpellingHints;
+#[cfg(feature = "llm")]
+use crate::ui::suggestion::PrioritySuggestion;
+use crate::ui::theme::{
+    ACCENT_COLOR, DANGER_COLOR, MUTED_TEXT_COLOR, SUCCESS_COLOR, SURFACE_BORDER_COLOR,
+    SURFACE_COLOR, ThemeColor,
+};
+#[cfg(feature = "voice")]
+use crate::ui::voice::VoiceCapture;
 use crate::{Priority, Status, Task};
 
 #[derive(Debug, Error)]
@@ -33,10 +56,17 @@ pub enum TaskError {
 pub struct CreateTaskForm {
     description: String,
     last_error: Option<TaskError>,
+    #[cfg(feature = "llm")]
+    suggestion: PrioritySuggestion,
+    #[cfg(feature = "spellcheck")]
+    spelling: SpellingHints,
+    #[cfg(feature = "voice")]
+    voice: VoiceCapture,
 }
 
 impl Form for CreateTaskForm {
-    type Output = String;
+    /// The description, and the priority suggested by the model if accepted.
+    type Output = (String, Priority);
     type Error = TaskError;
 
     fn last_error(&mut self) -> &mut Option<TaskError> {
@@ -47,26 +77,108 @@ impl Form for CreateTaskForm {
         let description = text_input(
             self.description.clone(),
             |state: &mut CreateTaskForm, input| {
+                #[cfg(feature = "llm")]
+                state.suggestion.set_description(&input);
+                #[cfg(feature = "spellcheck")]
+                state.spelling.set_text(&input);
                 state.description = input;
                 Submit::No
             },
         )
-        .on_enter(|_, _| Submit::Yes)
+        .on_enter(|_, _| enter_submit())
         .placeholder("What needs to be done?");
         let add_button = text_button("Add task", |_| Submit::Yes);
+        #[cfg(feature = "voice")]
+        let voice = map_action(
+            map_state(self.voice.view(), |state: &mut Self, ()| &mut state.voice),
+            |state: &mut Self, transcript: Option<String>| {
+                if let Some(transcript) = transcript {
+                    #[cfg(feature = "llm")]
+                    state.suggestion.set_description(&transcript);
+                    #[cfg(feature = "spellcheck")]
+                    state.spelling.set_text(&transcript);
+                    state.description = transcript;
+                }
+                Submit::No
+            },
+        );
+        #[cfg(not(feature = "voice"))]
+        let voice = ();
+        #[cfg(feature = "llm")]
+        let suggestion = map_action(
+            map_state(self.suggestion.view(), |state: &mut Self, ()| {
+                &mut state.suggestion
+            }),
+            |_: &mut Self, ()| Submit::No,
+        );
+        #[cfg(not(feature = "llm"))]
+        let suggestion = ();
+        #[cfg(feature = "spellcheck")]
+        let spelling = map_action(
+            map_state(self.spelling.view(), |state: &mut Self, ()| {
+                &mut state.spelling
+            }),
+            |state: &mut Self, corrected: Option<String>| {
+                if let Some(corrected) = corrected {
+                    #[cfg(feature = "llm")]
+                    state.suggestion.set_description(&corrected);
+                    state.spelling.set_text(&corrected);
+                    state.description = corrected;
+                }
+                Submit::No
+            },
+        );
+        #[cfg(not(feature = "spellcheck"))]
+        let spelling = ();
         let error = self.error_view();
-        flex_col((flex_row((description.flex(1.), add_button)), error))
-            .padding(25.)
-            .corner_radius(15.)
-            .background_color(SURFACE_COLOR)
-            .border(SURFACE_BORDER_COLOR, 1.)
+        flex_col((
+            mirrored_row!([description.flex(1.), voice, add_button]),
+            spelling,
+            suggestion,
+            error,
+        ))
+        .padding(25.)
+        .corner_radius(15.)
+        .background_color(SURFACE_COLOR)
+        .border(SURFACE_BORDER_COLOR, 1.)
     }
 
-    fn validate(&mut self) -> Result<String, TaskError> {
+    fn validate(&mut self) -> Result<(String, Priority), TaskError> {
         if self.description.is_empty() {
             return Err(TaskError::EmptyDescription);
         }
-        Ok(std::mem::take(&mut self.description))
+        #[cfg(feature = "llm")]
+        let priority = self.suggestion.take_accepted().unwrap_or_default();
+        #[cfg(not(feature = "llm"))]
+        let priority = Priority::default();
+        #[cfg(feature = "spellcheck")]
+        self.spelling.set_text("");
+        Ok((std::mem::take(&mut self.description), priority))
+    }
+
+    fn draft(&self) -> Option<String> {
+        (!self.description.is_empty()).then(|| self.description.clone())
+    }
+
+    fn output_draft((description, _): &(String, Priority)) -> Option<String> {
+        Some(description.clone())
+    }
+
+    fn restore_draft(&mut self, draft: String) {
+        #[cfg(feature = "llm")]
+        self.suggestion.set_description(&draft);
+        #[cfg(feature = "spellcheck")]
+        self.spelling.set_text(&draft);
+        self.description = draft;
+    }
+
+    fn split((description, priority): (String, Priority)) -> Vec<(String, Priority)> {
+        description
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| (line.to_string(), priority))
+            .collect()
     }
 }
 
@@ -76,6 +188,8 @@ pub struct UpdateTaskForm {
     status: Status,
     priority: Priority,
     last_error: Option<TaskError>,
+    #[cfg(feature = "spellcheck")]
+    spelling: SpellingHints,
 }
 
 impl Form for UpdateTaskForm {
@@ -96,7 +210,24 @@ impl Form for UpdateTaskForm {
             state.description = input;
             Submit::No
         })
-        .on_enter(|_, _| Submit::Yes);
+        .on_enter(|_, _| enter_submit());
+        // Also checks the description the form was opened with.
+        #[cfg(feature = "spellcheck")]
+        self.spelling.set_text(&self.description);
+        #[cfg(feature = "spellcheck")]
+        let spelling = map_action(
+            map_state(self.spelling.view(), |state: &mut Self, ()| {
+                &mut state.spelling
+            }),
+            |state: &mut Self, corrected: Option<String>| {
+                if let Some(corrected) = corrected {
+                    state.description = corrected;
+                }
+                Submit::No
+            },
+        );
+        #[cfg(not(feature = "spellcheck"))]
+        let spelling = ();
         let priority = button(
             label(self.priority.to_string()).color(self.priority.text_color()),
             |state: &mut Self| {
@@ -108,13 +239,14 @@ impl Form for UpdateTaskForm {
         let cancel_button = text_button("Cancel", |_| Submit::Cancel);
         let error = self.error_view();
         flex_col((
-            flex_row((
+            mirrored_row!([
                 status,
                 description.flex(1.),
                 priority,
                 ok_button,
                 cancel_button,
-            )),
+            ]),
+            spelling,
             error,
         ))
         .padding(5.)
@@ -138,7 +270,7 @@ impl Form for UpdateTaskForm {
 impl From<Task> for UpdateTaskForm {
     fn from(value: Task) -> Self {
         Self {
-            description: value.description.clone(),
+            description: value.description.to_string(),
             status: value.status,
             priority: value.priority,
             ..Default::default()
@@ -146,29 +278,55 @@ impl From<Task> for UpdateTaskForm {
     }
 }
 
-#[derive(Default, PartialEq, Clone, Copy)]
-pub enum TaskFilter {
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub enum StatusFilter {
     All,
     #[default]
     Active,
     Completed,
 }
 
-impl ListFilter for TaskFilter {
+impl StatusFilter {
+    fn statuses(&self) -> Option<Vec<Status>> {
+        match self {
+            Self::All => None,
+            Self::Active => Some(vec![Status::ToDo, Status::InProgress]),
+            Self::Completed => Some(vec![Status::Done]),
+        }
+    }
+
+    /// Number of tasks the filter shows, from the counts of the last summary.
+    fn count(&self, counts: &StatusCounts) -> i64 {
+        match self {
+            Self::All => counts.total(),
+            Self::Active => counts.active(),
+            Self::Completed => counts.done,
+        }
+    }
+}
+
+impl ListFilter for StatusFilter {
     type Item = Task;
 
     fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
-        let filter_task = |label, filter| {
+        let counts = *STATUS_COUNTS.lock().unwrap();
+        let filter_task = |name: &str, filter: Self| {
+            let label = match &counts {
+                Some(counts) => format!("{name} ({})", filter.count(counts)),
+                None => name.to_string(),
+            };
             checkbox::<_, Edit<Self>, _>(label, *self == filter, move |state: &mut Self, _| {
                 *state = filter
             })
         };
-        flex_row((
-            filter_task("All", Self::All),
-            filter_task("Active", Self::Active),
-            filter_task("Completed", Self::Completed),
-        ))
-        .main_axis_alignment(MainAxisAlignment::End)
+        mirrored_row!(
+            [
+                filter_task("All", Self::All),
+                filter_task("Active", Self::Active),
+                filter_task("Completed", Self::Completed),
+            ]
+            .main_axis_alignment(direction::end())
+        )
     }
     fn filter(&self, task: &Task) -> (bool, f32) {
         let filter = match self {
@@ -178,13 +336,218 @@ impl ListFilter for TaskFilter {
         };
         (filter, 0.)
     }
+
+    fn enabled(&self) -> bool {
+        *self != Self::All
+    }
+
+    fn save(&self) -> Option<String> {
+        let saved = match self {
+            Self::All => "all",
+            Self::Active => "active",
+            Self::Completed => "completed",
+        };
+        Some(saved.to_string())
+    }
+
+    fn load(&mut self, saved: &str) {
+        if let Ok(filter) = saved.parse() {
+            *self = filter;
+        }
+    }
 }
 
-#[derive(Default)]
+impl FromStr for StatusFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            "active" => Ok(Self::Active),
+            "completed" => Ok(Self::Completed),
+            _ => Err(format!(
+                "unknown filter {s}, expected all, active or completed"
+            )),
+        }
+    }
+}
+
+/// Keeps the tasks with a value of a custom field, like `TaskQuery::field`. The value is applied
+/// with Enter, so that the list isn't fetched again on every keystroke.
+#[derive(Default, Clone, Debug)]
+pub struct FieldFilter {
+    field_id: Option<i64>,
+    input: String,
+    value: String,
+}
+
+impl PartialEq for FieldFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.field_id == other.field_id && self.value == other.value
+    }
+}
+
+impl FieldFilter {
+    /// The field and the value the tasks must have, the value as stored by
+    /// `CustomField::parse_value`.
+    fn field(&self) -> Option<(i64, String)> {
+        let field_id = self.field_id?;
+        if self.value.is_empty() {
+            return None;
+        }
+        let value = CUSTOM_FIELDS
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|field| field.id == field_id)
+            .and_then(|field| field.parse_value(&self.value).ok())
+            .unwrap_or_else(|| self.value.clone());
+        Some((field_id, value))
+    }
+}
+
+impl ListFilter for FieldFilter {
+    type Item = Task;
+
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let fields = CUSTOM_FIELDS.lock().unwrap().clone();
+        let field = self
+            .field_id
+            .map(|field_id| fields.iter().find(|field| field.id == field_id));
+        let name = match field {
+            None => "Any field".to_string(),
+            Some(None) => "Deleted field".to_string(),
+            Some(Some(field)) => field.name.clone(),
+        };
+        let placeholder = match field.flatten() {
+            Some(field) if field.kind == FieldKind::Choice => {
+                field.choices().collect::<Vec<_>>().join(", ")
+            }
+            Some(field) if field.kind == FieldKind::Number => "Number".to_string(),
+            Some(field) if field.kind == FieldKind::Date => "2026-10-15".to_string(),
+            _ => "Value".to_string(),
+        };
+        let button = text_button(name, |state: &mut Self| {
+            let fields = CUSTOM_FIELDS.lock().unwrap();
+            state.field_id = match state.field_id {
+                None => fields.first().map(|field| field.id),
+                Some(field_id) => fields
+                    .iter()
+                    .skip_while(|field| field.id != field_id)
+                    .nth(1)
+                    .map(|field| field.id),
+            };
+            state.input.clear();
+            state.value.clear();
+        });
+        let input = self.field_id.is_some().then(|| {
+            text_input(self.input.clone(), |state: &mut Self, input| {
+                state.input = input;
+            })
+            .on_enter(|state: &mut Self, input: String| {
+                state.value = input.trim().to_string();
+            })
+            .placeholder(placeholder)
+        });
+        flex_row((button, input)).main_axis_alignment(direction::end())
+    }
+
+    fn filter(&self, task: &Task) -> (bool, f32) {
+        let Some((field_id, value)) = self.field() else {
+            return (true, 0.);
+        };
+        let values = FIELD_VALUES.lock().unwrap();
+        (values.get(&(task.id, field_id)) == Some(&value), 0.)
+    }
+
+    fn enabled(&self) -> bool {
+        self.field().is_some()
+    }
+
+    fn save(&self) -> Option<String> {
+        self.field_id
+            .map(|field_id| format!("{field_id} {}", self.value))
+    }
+
+    fn load(&mut self, saved: &str) {
+        let Some((field_id, value)) = saved.split_once(' ') else {
+            *self = Self::default();
+            return;
+        };
+        self.field_id = field_id.parse().ok();
+        self.input = value.to_string();
+        self.value = value.to_string();
+    }
+}
+
+impl Searchable for Task {
+    fn search_text(&self) -> &str {
+        &self.description
+    }
+}
+
+pub type TaskFilter = AllOf<AllOf<SearchFilter<Task>, StatusFilter>, FieldFilter>;
+
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
 pub enum TaskSorter {
     #[default]
     StatusFirst,
     PriorityFirst,
+    /// Order chosen by the user.
+    Manual,
+    /// By the value of the custom field with the id, then status first.
+    Field(i64),
+}
+
+impl TaskSorter {
+    fn order(&self) -> TaskOrder {
+        match self {
+            TaskSorter::StatusFirst | TaskSorter::Field(_) => TaskOrder::StatusFirst,
+            TaskSorter::PriorityFirst => TaskOrder::PriorityFirst,
+            TaskSorter::Manual => TaskOrder::UserOrder,
+        }
+    }
+
+    fn field_order(&self) -> Option<i64> {
+        match self {
+            TaskSorter::Field(field_id) => Some(*field_id),
+            _ => None,
+        }
+    }
+}
+
+/// Orders by the value of the custom field, the tasks without one last, like
+/// `TaskQuery::field_order`.
+fn field_ordering(field_id: i64, a: &Task, b: &Task) -> Ordering {
+    let numeric = CUSTOM_FIELDS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|field| field.id == field_id && field.kind == FieldKind::Number);
+    let values = FIELD_VALUES.lock().unwrap();
+    let number = |value: &String| value.parse::<f64>().unwrap_or_default();
+    match (values.get(&(a.id, field_id)), values.get(&(b.id, field_id))) {
+        (Some(a), Some(b)) if numeric => number(a).total_cmp(&number(b)),
+        (a, b) => a.is_none().cmp(&b.is_none()).then(a.cmp(&b)),
+    }
+}
+
+impl FromStr for TaskSorter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "status_first" => Ok(TaskSorter::StatusFirst),
+            "priority_first" => Ok(TaskSorter::PriorityFirst),
+            "manual" => Ok(TaskSorter::Manual),
+            _ => match s.strip_prefix("field:").map(str::parse) {
+                Some(Ok(field_id)) => Ok(TaskSorter::Field(field_id)),
+                _ => Err(format!(
+                    "unknown sorter {s}, expected status_first, priority_first, manual or field:<id>"
+                )),
+            },
+        }
+    }
 }
 
 impl ListSorter for TaskSorter {
@@ -194,59 +557,455 @@ impl ListSorter for TaskSorter {
         true
     }
 
+    fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let label = match self {
+            TaskSorter::StatusFirst => "Status first".to_string(),
+            TaskSorter::PriorityFirst => "Priority first".to_string(),
+            TaskSorter::Manual => "Manual order".to_string(),
+            TaskSorter::Field(field_id) => {
+                let fields = CUSTOM_FIELDS.lock().unwrap();
+                match fields.iter().find(|field| field.id == *field_id) {
+                    Some(field) => format!("By {}", field.name),
+                    None => "By a deleted field".to_string(),
+                }
+            }
+        };
+        // The custom fields come after the manual order.
+        let button = text_button(label, |state: &mut Self| {
+            let fields = CUSTOM_FIELDS.lock().unwrap();
+            let next_field = |field_id: Option<i64>| {
+                let mut fields = fields.iter().map(|field| field.id);
+                let next = match field_id {
+                    Some(field_id) => fields.skip_while(|id| *id != field_id).nth(1),
+                    None => fields.next(),
+                };
+                next.map_or(TaskSorter::StatusFirst, TaskSorter::Field)
+            };
+            *state = match *state {
+                TaskSorter::StatusFirst => TaskSorter::PriorityFirst,
+                TaskSorter::PriorityFirst => TaskSorter::Manual,
+                TaskSorter::Manual => next_field(None),
+                TaskSorter::Field(field_id) => next_field(Some(field_id)),
+            };
+        });
+        flex_row(button).main_axis_alignment(direction::end())
+    }
+
+    fn sort(&self, a: &Self::Item, b: &Self::Item, score_a: f32, score_b: f32) -> Ordering {
+        let score_ordering = score_b.total_cmp(&score_a);
+        let status_ordering = (a.status as i32).cmp(&(b.status as i32));
+        let priority_ordering = (b.priority as i32).cmp(&(a.priority as i32));
+        let id_ordering = b.id.cmp(&a.id);
+        let ordering = match self {
+            TaskSorter::StatusFirst => status_ordering.then(priority_ordering),
+            TaskSorter::PriorityFirst => priority_ordering.then(status_ordering),
+            TaskSorter::Manual => a.position.cmp(&b.position),
+            TaskSorter::Field(field_id) => field_ordering(*field_id, a, b)
+                .then(status_ordering)
+                .then(priority_ordering),
+        };
+        score_ordering.then(ordering).then(id_ordering)
+    }
+
+    fn save(&self) -> Option<String> {
+        let saved = match self {
+            TaskSorter::StatusFirst => "status_first".to_string(),
+            TaskSorter::PriorityFirst => "priority_first".to_string(),
+            TaskSorter::Manual => "manual".to_string(),
+            TaskSorter::Field(field_id) => format!("field:{field_id}"),
+        };
+        Some(saved)
+    }
+
+    fn load(&mut self, saved: &str) {
+        if let Ok(sorter) = saved.parse() {
+            *self = sorter;
+        }
+    }
+
+    fn user_order(&self) -> bool {
+        *self == TaskSorter::Manual
+    }
+
+    /// Tasks swap their positions so that they keep their place relative to the tasks not shown.
+    fn reorder(&self, tasks: &mut [Task], index: usize, new_index: usize) {
+        let positions = tasks.iter().map(|task| task.position).collect::<Vec<_>>();
+        if index < new_index {
+            tasks[index..=new_index].rotate_left(1);
+        } else {
+            tasks[new_index..=index].rotate_right(1);
+        }
+        for (task, position) in tasks.iter_mut().zip(positions) {
+            task.position = position;
+        }
+    }
+}
+
+#[derive(Default, PartialEq, Clone, Copy)]
+pub enum TaskGrouper {
+    #[default]
+    None,
+    Status,
+    Priority,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskGroup {
+    Status(Status),
+    /// Reversed so that high priority tasks come first.
+    Priority(Reverse<Priority>),
+    None,
+}
+
+impl ListGrouper for TaskGrouper {
+    type Item = Task;
+    type Key = TaskGroup;
+
+    fn enabled(&self) -> bool {
+        *self != TaskGrouper::None
+    }
+
     fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
         let button = text_button(
             match self {
-                TaskSorter::StatusFirst => "Status first",
-                TaskSorter::PriorityFirst => "Priority first",
+                TaskGrouper::None => "No grouping",
+                TaskGrouper::Status => "Grouped by status",
+                TaskGrouper::Priority => "Grouped by priority",
             },
             |state: &mut Self| match state {
-                TaskSorter::StatusFirst => *state = TaskSorter::PriorityFirst,
-                TaskSorter::PriorityFirst => *state = TaskSorter::StatusFirst,
+                TaskGrouper::None => *state = TaskGrouper::Status,
+                TaskGrouper::Status => *state = TaskGrouper::Priority,
+                TaskGrouper::Priority => *state = TaskGrouper::None,
             },
         );
-        flex_row(button).main_axis_alignment(MainAxisAlignment::End)
+        flex_row(button).main_axis_alignment(direction::end())
     }
 
-    fn sort(&self, a: &Self::Item, b: &Self::Item, _score_a: f32, _score_b: f32) -> Ordering {
-        let status_ordering = (a.status as i32).cmp(&(b.status as i32));
-        let priority_ordering = (b.priority as i32).cmp(&(a.priority as i32));
-        let id_ordering = b.id.cmp(&a.id);
+    fn key(&self, task: &Task) -> TaskGroup {
         match self {
-            TaskSorter::StatusFirst => status_ordering.then(priority_ordering),
-            TaskSorter::PriorityFirst => priority_ordering.then(status_ordering),
+            TaskGrouper::None => TaskGroup::None,
+            TaskGrouper::Status => TaskGroup::Status(task.status),
+            TaskGrouper::Priority => TaskGroup::Priority(Reverse(task.priority)),
+        }
+    }
+
+    fn header(&self, key: &TaskGroup, tasks: &[&Task]) -> String {
+        let count = tasks.len();
+        match key {
+            TaskGroup::Status(status) => {
+                let high = tasks
+                    .iter()
+                    .filter(|task| task.priority == Priority::High)
+                    .count();
+                format!("{status} · {count} tasks · {high} high priority")
+            }
+            TaskGroup::Priority(Reverse(priority)) => {
+                let done = tasks
+                    .iter()
+                    .filter(|task| task.status == Status::Done)
+                    .count();
+                format!("{priority} · {count} tasks · {done} done")
+            }
+            TaskGroup::None => format!("{count} tasks"),
         }
-        .then(id_ordering)
+    }
+
+    fn save(&self) -> Option<String> {
+        let saved = match self {
+            TaskGrouper::None => "none",
+            TaskGrouper::Status => "status",
+            TaskGrouper::Priority => "priority",
+        };
+        Some(saved.to_string())
+    }
+
+    fn load(&mut self, saved: &str) {
+        match saved {
+            "none" => *self = TaskGrouper::None,
+            "status" => *self = TaskGrouper::Status,
+            "priority" => *self = TaskGrouper::Priority,
+            _ => (),
+        }
+    }
+}
+
+fn task_query(query: ListQuery<Task>) -> TaskQuery {
+    let filter = query.filter.as_ref();
+    TaskQuery {
+        statuses: filter.and_then(|AllOf(AllOf(_, status), _)| status.statuses()),
+        search: filter.and_then(|AllOf(AllOf(search, _), _)| search.query().map(str::to_owned)),
+        field: filter.and_then(|AllOf(_, field)| field.field()),
+        order: query.sorter.map(|sorter| sorter.order()),
+        field_order: query.sorter.and_then(|sorter| sorter.field_order()),
+        ..Default::default()
     }
 }
 
+/// Actions applied to all the selected tasks, shown while some tasks are selected.
+pub fn bulk_actions_view<S>(
+    task_list: &AsyncList<Task, S>,
+) -> Option<impl WidgetView<Edit<AsyncList<Task, S>>> + use<S>>
+where
+    S: ListStorage<Item = Task>,
+{
+    let count = task_list.selection().len();
+    let set_status = |status: Status| {
+        text_button(status.to_string(), move |state: &mut AsyncList<Task, S>| {
+            let selection = state.selection().to_vec();
+            state.set_status_many(&selection, status);
+        })
+        .background_color(status.color())
+    };
+    (count > 0).then(|| {
+        mirrored_row!(
+            [
+                label(format!("{count} selected")).flex(1.),
+                set_status(Status::ToDo),
+                set_status(Status::InProgress),
+                set_status(Status::Done),
+                text_button("Delete", |state: &mut AsyncList<Task, S>| {
+                    let selection = state.selection().to_vec();
+                    state.delete_many(selection);
+                })
+                .background_color(DANGER_COLOR),
+                text_button("Clear", |state: &mut AsyncList<Task, S>| {
+                    state.clear_selection();
+                }),
+            ]
+            .padding(5.)
+        )
+    })
+}
+
+/// Details of the selected task, shown while a single task is selected.
+pub fn task_detail_view<S>(
+    task_list: &AsyncList<Task, S>,
+) -> Option<impl WidgetView<Edit<AsyncList<Task, S>>> + use<S>>
+where
+    S: ListStorage<Item = Task>,
+{
+    let task = task_list.selected()?;
+    let sync_state = match task_list.pending_item_operation(task.id) {
+        PendingItemOperation::None => None,
+        PendingItemOperation::Failed => Some(label("Not saved").color(DANGER_COLOR)),
+        _ => Some(label("Syncing…").color(MUTED_TEXT_COLOR)),
+    };
+    Some(
+        flex_col((
+            mirrored_row!([label(format!("Task #{}", task.id)).flex(1.), sync_state]),
+            prose(task.description.clone()),
+            mirrored_row!(
+                [
+                    label(task.status.to_string()).color(task.status.color()),
+                    label(format!("{} priority", task.priority)).color(task.priority.color()),
+                ]
+                .main_axis_alignment(direction::start())
+            ),
+            label(format!("Updated at {}", task.updated_at)).color(MUTED_TEXT_COLOR),
+        ))
+        .padding(10.)
+        .corner_radius(10.)
+        .background_color(SURFACE_COLOR)
+        .border(SURFACE_BORDER_COLOR, 1.),
+    )
+}
+
+/// Number of tasks deleted by a single command, progress is reported after each.
+const BATCH_SIZE: usize = 100;
+/// Origin of the commands sent by the task list.
+pub(crate) const ORIGIN: &str = "task list";
+
+const FILTER_SETTING: &str = "task_list.filter";
+const SORTER_SETTING: &str = "task_list.sorter";
+const GROUPER_SETTING: &str = "task_list.grouper";
+const AUTO_REFRESH_SETTING: &str = "task_list.auto_refresh";
+const DEFAULT_FILTER_SETTING: &str = "task_list.default_filter";
+const DEFAULT_SORTER_SETTING: &str = "task_list.default_sorter";
+const ENTER_SUBMITS_SETTING: &str = "task_list.enter_submits";
+
+static ENTER_SUBMITS: AtomicBool = AtomicBool::new(true);
+/// Counts of the last summary, shown by the badges of `StatusFilter`, `None` until fetched.
+static STATUS_COUNTS: Mutex<Option<StatusCounts>> = Mutex::new(None);
+/// Custom fields of the last fetch, offered by `FieldFilter` and `TaskSorter`.
+static CUSTOM_FIELDS: Mutex<Vec<CustomField>> = Mutex::new(Vec::new());
+/// Values of the custom fields of the last fetch, by task id and field id, with which the changed
+/// tasks are filtered and sorted without fetching the list again.
+static FIELD_VALUES: Mutex<BTreeMap<(i64, i64), String>> = Mutex::new(BTreeMap::new());
+
+/// Whether Enter submits the forms of the list. Otherwise it only confirms the text, like the
+/// composition of an input method, and the forms are submitted with their buttons.
+pub fn enter_submits() -> bool {
+    ENTER_SUBMITS.load(atomic::Ordering::Relaxed)
+}
+
+/// Saves whether Enter submits the forms, read back by `TaskStorage` when the list starts.
+pub async fn set_enter_submits(enter_submits: bool) -> Result<(), ServerError> {
+    set_setting(ENTER_SUBMITS_SETTING, enter_submits.to_string()).await?;
+    ENTER_SUBMITS.store(enter_submits, atomic::Ordering::Relaxed);
+    Ok(())
+}
+
+/// Keeps the custom fields defined in the settings, the values of the deleted ones being dropped,
+/// so that the list doesn't wait for its next fetch from the start.
+pub fn set_custom_fields(fields: Vec<CustomField>) {
+    FIELD_VALUES
+        .lock()
+        .unwrap()
+        .retain(|(_, field_id), _| fields.iter().any(|field| field.id == *field_id));
+    *CUSTOM_FIELDS.lock().unwrap() = fields;
+}
+
+fn enter_submit() -> Submit {
+    if enter_submits() {
+        Submit::Yes
+    } else {
+        Submit::No
+    }
+}
+
+/// The filter and the sorter the list starts with, `None` for the last used one.
+pub async fn start_view() -> Result<(Option<StatusFilter>, Option<TaskSorter>), ServerError> {
+    let filter = get_setting(DEFAULT_FILTER_SETTING).await?;
+    // The status is on the second line, see `migrate_filter`.
+    let filter = filter.and_then(|saved| saved.lines().nth(1)?.parse().ok());
+    let sorter = get_setting(DEFAULT_SORTER_SETTING).await?;
+    let sorter = sorter.and_then(|saved| saved.parse().ok());
+    Ok((filter, sorter))
+}
+
+/// The saved `TaskFilter` in the layout of `AllOf`, the search, the status and the custom field
+/// on three lines. The filters saved before were only the status, like `active`, then the search
+/// and the status.
+fn migrate_filter(saved: String) -> String {
+    match saved.matches('\n').count() {
+        0 => format!("\n{saved}\n"),
+        1 => format!("{saved}\n"),
+        _ => saved,
+    }
+}
+
+/// Saves the filter and the sorter the list starts with, read back by `TaskStorage`.
+pub async fn set_start_view(
+    filter: Option<StatusFilter>,
+    sorter: Option<TaskSorter>,
+) -> Result<(), ServerError> {
+    let filter = filter.and_then(|filter| {
+        let filter: TaskFilter = AllOf(
+            AllOf(SearchFilter::default(), filter),
+            FieldFilter::default(),
+        );
+        filter.save()
+    });
+    let sorter = sorter.and_then(|sorter| sorter.save());
+    set_setting(DEFAULT_FILTER_SETTING, filter.unwrap_or_default()).await?;
+    set_setting(DEFAULT_SORTER_SETTING, sorter.unwrap_or_default()).await
+}
+
+/// Storage of the task list, sending commands to the service on the repository `R`. Writes fail
+/// while `service::set_read_only` is set, the settings of the list still being saved.
 #[derive(Debug, Default)]
-pub struct TaskStorage {
+pub struct TaskStorage<R = Database> {
     last_error: Option<ServerError>,
+    repository: PhantomData<R>,
+}
+
+/// Keeps the custom fields and their values for `FieldFilter` and `TaskSorter`. Loaded when the
+/// list is fetched from the start and when tasks changed, the values being set by task events.
+async fn load_fields<R: TaskRepository>() -> Result<(), ServerError> {
+    let fields = R::fetch_fields().await?;
+    let values = R::fetch_field_values().await?;
+    *CUSTOM_FIELDS.lock().unwrap() = fields;
+    *FIELD_VALUES.lock().unwrap() = values
+        .into_iter()
+        .map(|value| ((value.task_id, value.field_id), value.value))
+        .collect();
+    Ok(())
 }
 
 impl Retryable for ServerError {
     fn should_retry(&self) -> bool {
-        false
+        matches!(self, ServerError::Timeout | ServerError::ConnectionLost(_))
+    }
+
+    fn may_have_applied(&self) -> bool {
+        matches!(self, ServerError::Timeout)
     }
 }
 
-impl ListStorage for TaskStorage {
+impl<R> ListStorage for TaskStorage<R>
+where
+    R: TaskRepository + Default + std::fmt::Debug,
+{
     type Item = Task;
     type Error = ServerError;
+    type Summary = StatusCounts;
 
     fn last_error(&mut self) -> &mut Option<ServerError> {
         &mut self.last_error
     }
 
     #[inline(always)]
-    async fn fetch_all() -> Result<Vec<Task>, ServerError> {
-        get_tasks().await
+    async fn fetch_all(query: ListQuery<Task>) -> Result<Vec<Task>, ServerError> {
+        load_fields::<R>().await?;
+        R::fetch(task_query(query)).await
+    }
+
+    #[inline(always)]
+    async fn fetch_page(
+        query: ListQuery<Task>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ListPage<Task>, ServerError> {
+        if offset == 0 {
+            load_fields::<R>().await?;
+        }
+        let (items, total) = R::fetch_page(task_query(query), offset as i64, limit as i64).await?;
+        Ok(ListPage {
+            items,
+            total: total as usize,
+        })
     }
 
     #[inline(always)]
-    async fn create(description: String) -> Result<Task, ServerError> {
-        create_task(description).await
+    async fn fetch_since(since: String) -> Result<ListDelta<Task>, ServerError> {
+        let (changed, deleted) = R::fetch_since(since).await?;
+        if !changed.is_empty() || !deleted.is_empty() {
+            load_fields::<R>().await?;
+        }
+        Ok(ListDelta { changed, deleted })
+    }
+
+    #[inline(always)]
+    async fn fetch_summary() -> Result<StatusCounts, ServerError> {
+        let counts = R::count_by_status().await?;
+        *STATUS_COUNTS.lock().unwrap() = Some(counts);
+        Ok(counts)
+    }
+
+    #[inline(always)]
+    async fn create((description, priority): (String, Priority)) -> Result<Task, ServerError> {
+        let command = Command::CreateTask {
+            description,
+            priority,
+        };
+        execute_one::<R>(ORIGIN, command).await
+    }
+
+    #[inline(always)]
+    async fn create_many(
+        new_tasks: Vec<(String, Priority)>,
+        progress: Progress,
+    ) -> Result<Vec<Task>, ServerError> {
+        // A single command, creating all the tasks or none, so that retrying after an error can't
+        // create the first ones twice.
+        let total = new_tasks.len();
+        let events = execute::<R>(ORIGIN, Command::CreateTasks(new_tasks)).await?;
+        let tasks = events
+            .into_iter()
+            .filter_map(Event::into_task)
+            .collect::<Vec<_>>();
+        progress.report(tasks.len(), total);
+        Ok(tasks)
     }
 
     #[inline(always)]
@@ -254,68 +1013,235 @@ impl ListStorage for TaskStorage {
         id: i64,
         (desc, status, priority): (String, Status, Priority),
     ) -> Result<Task, ServerError> {
-        update_task(id, desc, status, priority).await
+        let command = Command::UpdateTask {
+            id,
+            description: desc,
+            status,
+            priority,
+        };
+        execute_one::<R>(ORIGIN, command).await
     }
 
     #[inline(always)]
     async fn delete(id: i64) -> Result<i64, ServerError> {
-        delete_task(id).await
+        execute::<R>(ORIGIN, Command::DeleteTask(id)).await?;
+        Ok(id)
+    }
+
+    #[inline(always)]
+    async fn update_many(
+        updates: Vec<(i64, (String, Status, Priority))>,
+        _progress: Progress,
+    ) -> Result<Vec<Task>, ServerError> {
+        let updates = updates
+            .into_iter()
+            .map(|(id, (desc, status, priority))| (id, desc, status, priority))
+            .collect();
+        let events = execute::<R>(ORIGIN, Command::UpdateTasks(updates)).await?;
+        Ok(events.into_iter().filter_map(Event::into_task).collect())
+    }
+
+    #[inline(always)]
+    async fn delete_many(ids: Vec<i64>, progress: Progress) -> Result<Vec<i64>, ServerError> {
+        let total = ids.len();
+        let mut deleted = Vec::with_capacity(total);
+        for batch in ids.chunks(BATCH_SIZE) {
+            let events = execute::<R>(ORIGIN, Command::DeleteTasks(batch.to_vec())).await?;
+            deleted.extend(events.iter().map(Event::id));
+            progress.report(deleted.len(), total);
+        }
+        Ok(deleted)
+    }
+
+    #[inline(always)]
+    async fn set_status_many(
+        tasks: Vec<Task>,
+        status: Status,
+        _progress: Progress,
+    ) -> Result<Vec<Task>, ServerError> {
+        let ids = tasks.iter().map(|task| task.id).collect();
+        let events = execute::<R>(ORIGIN, Command::ChangeStatus { ids, status }).await?;
+        Ok(events.into_iter().filter_map(Event::into_task).collect())
+    }
+
+    async fn reorder(ids: Vec<i64>) -> Result<Vec<Task>, ServerError> {
+        let events = execute::<R>(ORIGIN, Command::Reorder(ids)).await?;
+        Ok(events.into_iter().filter_map(Event::into_task).collect())
+    }
+
+    async fn load_settings() -> Result<ListSettings, ServerError> {
+        let enter_submits = get_setting(ENTER_SUBMITS_SETTING).await?;
+        ENTER_SUBMITS.store(
+            enter_submits.as_deref() != Some("false"),
+            atomic::Ordering::Relaxed,
+        );
+        Ok(ListSettings {
+            filter: get_setting(FILTER_SETTING).await?.map(migrate_filter),
+            sorter: get_setting(SORTER_SETTING).await?,
+            grouper: get_setting(GROUPER_SETTING).await?,
+            auto_refresh: get_setting(AUTO_REFRESH_SETTING).await?,
+            // Empty when the last used ones are kept.
+            default_filter: get_setting(DEFAULT_FILTER_SETTING)
+                .await?
+                .filter(|filter| !filter.is_empty())
+                .map(migrate_filter),
+            default_sorter: get_setting(DEFAULT_SORTER_SETTING)
+                .await?
+                .filter(|sorter| !sorter.is_empty()),
+        })
+    }
+
+    async fn save_settings(settings: ListSettings) -> Result<(), ServerError> {
+        if let Some(filter) = settings.filter {
+            set_setting(FILTER_SETTING, filter).await?;
+        }
+        if let Some(sorter) = settings.sorter {
+            set_setting(SORTER_SETTING, sorter).await?;
+        }
+        if let Some(grouper) = settings.grouper {
+            set_setting(GROUPER_SETTING, grouper).await?;
+        }
+        if let Some(auto_refresh) = settings.auto_refresh {
+            set_setting(AUTO_REFRESH_SETTING, auto_refresh).await?;
+        }
+        Ok(())
     }
 }
 
+pub enum TaskAction {
+    Duplicate,
+}
+
 impl ListItem for Task {
     type Id = i64;
     type CreateForm = CreateTaskForm;
     type UpdateForm = UpdateTaskForm;
     type Filter = TaskFilter;
     type Sorter = TaskSorter;
+    type Grouper = TaskGrouper;
+    type Status = Status;
+    type Action = TaskAction;
 
     fn id(&self) -> i64 {
         self.id
     }
 
+    fn updated_at(&self) -> Option<&str> {
+        Some(&self.updated_at)
+    }
+
+    fn optimistic_update(
+        &self,
+        (description, status, priority): &(String, Status, Priority),
+    ) -> Option<Self> {
+        Some(Self {
+            description: description.as_str().into(),
+            status: *status,
+            priority: *priority,
+            ..self.clone()
+        })
+    }
+
+    fn with_status(&self, status: Status) -> Option<(String, Status, Priority)> {
+        Some((self.description.to_string(), status, self.priority))
+    }
+
+    fn custom_action(&self, action: TaskAction) -> Option<ListRequest<Self>> {
+        match action {
+            TaskAction::Duplicate => Some(ListRequest::Create((
+                self.description.to_string(),
+                self.priority,
+            ))),
+        }
+    }
+
     fn view(
         &self,
         pending_item_operation: PendingItemOperation,
+        selected: Option<bool>,
     ) -> impl WidgetView<Read<Self>, ItemAction<Self>> + use<> {
+        let select = selected.map(|selected| {
+            checkbox::<_, Read<Self>, _>("", selected, |_: &Self, _| ItemAction::Select)
+        });
         let status = text_button(self.status.to_string(), |state: &Self| {
             ItemAction::Update((
-                state.description.clone(),
+                state.description.to_string(),
                 state.status.next(),
                 state.priority,
             ))
         })
         .background_color(self.status.color());
         let description = prose(self.description.clone());
+        let sync_state = match pending_item_operation {
+            PendingItemOperation::Syncing => Some(label("Syncing…").color(MUTED_TEXT_COLOR)),
+            PendingItemOperation::Failed => Some(label("Not saved").color(DANGER_COLOR)),
+            _ => None,
+        };
         let edit_button = if matches!(pending_item_operation, PendingItemOperation::PendingUpdate) {
-            Either::A(button(spinner(), |_| ItemAction::None))
+            Either::A(button(spinner(), |_| ItemAction::Cancel))
         } else {
             Either::B(text_button("Edit", |_| ItemAction::Edit))
         };
+        let duplicate_button =
+            text_button("Duplicate", |_| ItemAction::Custom(TaskAction::Duplicate));
         let delete_button = if matches!(pending_item_operation, PendingItemOperation::PendingDelete)
         {
-            Either::A(button(spinner().color(DANGER_COLOR), |_| ItemAction::None))
+            Either::A(button(spinner().color(DANGER_COLOR), |_| {
+                ItemAction::Cancel
+            }))
         } else {
             Either::B(button(label("Delete").color(DANGER_COLOR), |_| {
                 ItemAction::Delete
             }))
         };
-        flex_row((status, description.flex(1.), edit_button, delete_button))
+        let border_color = if selected == Some(true) {
+            ACCENT_COLOR
+        } else {
+            self.priority.color()
+        };
+        mirrored_row!(
+            [
+                select,
+                status,
+                description.flex(1.),
+                sync_state,
+                edit_button,
+                duplicate_button,
+                delete_button,
+            ]
+            .padding(5.)
+            .corner_radius(10.)
+            .background_color(SURFACE_COLOR)
+            .border(border_color, 1.)
+        )
+    }
+
+    fn read_only_view(&self) -> impl WidgetView<Read<Self>, ItemAction<Self>> + use<> {
+        mirrored_row!(
+            [
+                label(self.status.to_string()).color(self.status.color()),
+                prose(self.description.clone()).flex(1.),
+            ]
             .padding(5.)
             .corner_radius(10.)
             .background_color(SURFACE_COLOR)
             .border(self.priority.color(), 1.)
+        )
     }
 
-    fn pending_view(create_output: &String) -> impl WidgetView<Read<String>> + use<> {
+    fn pending_view(
+        (description, _): &(String, Priority),
+    ) -> impl WidgetView<Read<(String, Priority)>> + use<> {
         let status = text_button(Status::ToDo.to_string(), |_| {}).disabled(true);
-        let description = prose(create_output.clone());
+        let description = prose(description.clone());
         let edit_button = text_button("Edit", |_| {}).disabled(true);
         let delete_button = text_button("Delete", |_| {}).disabled(true);
-        let pending_layer = flex_row((status, description.flex(1.), edit_button, delete_button))
-            .padding(5.)
-            .corner_radius(10.)
-            .background_color(SURFACE_COLOR);
+        let pending_layer = mirrored_row!(
+            [status, description.flex(1.), edit_button, delete_button]
+                .padding(5.)
+                .corner_radius(10.)
+                .background_color(SURFACE_COLOR)
+        );
         let spinner_layer = flex_row(spinner())
             .main_axis_alignment(MainAxisAlignment::Center)
             .padding(5.)
@@ -323,4 +1249,19 @@ impl ListItem for Task {
             .background_color(BLACK.with_alpha(0.25));
         zstack((pending_layer, spinner_layer))
     }
+
+    fn failed_view(
+        (description, _): &(String, Priority),
+    ) -> impl WidgetView<Read<(String, Priority)>> + use<> {
+        let status = text_button(Status::ToDo.to_string(), |_| {}).disabled(true);
+        let description = prose(description.clone());
+        let failed_state = label("Not created").color(DANGER_COLOR);
+        mirrored_row!(
+            [status, description.flex(1.), failed_state]
+                .padding(5.)
+                .corner_radius(10.)
+                .background_color(SURFACE_COLOR)
+                .border(DANGER_COLOR, 1.)
+        )
+    }
 }
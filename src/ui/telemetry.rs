@@ -0,0 +1,149 @@
+use serde_json::Value;
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{
+    FlexExt, checkbox, flex_col, flex_row, label, prose, text_button, text_input, worker,
+};
+
+use crate::core::ServerError;
+use crate::telemetry::{endpoint, is_enabled, report, set_enabled, set_endpoint};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const DESCRIPTION: &str = "Sends every hour the version of the app, the number of tasks created, \
+    updated and deleted from each part of the app, and where it last crashed. Never the tasks \
+    themselves.";
+
+#[derive(Debug)]
+enum Request {
+    SetEnabled(bool),
+    SetEndpoint(String),
+    Preview,
+}
+
+#[derive(Debug)]
+enum Response {
+    Loaded { enabled: bool, endpoint: String },
+    Saved,
+    Preview(Value),
+}
+
+/// Settings section to opt in to `telemetry`, showing the next report before.
+#[derive(Debug, Default)]
+pub struct TelemetrySettings {
+    /// `None` until loaded.
+    enabled: Option<bool>,
+    endpoint: String,
+    /// The next report, as sent, once asked for.
+    preview: Option<String>,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Request>>,
+}
+
+impl TelemetrySettings {
+    fn send(&self, request: Request) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.send(Request::SetEnabled(enabled));
+        self.enabled = Some(enabled);
+    }
+
+    fn save_endpoint(&mut self) {
+        let endpoint = self.endpoint.trim().to_string();
+        let valid = endpoint.starts_with("http://") || endpoint.starts_with("https://");
+        if !endpoint.is_empty() && !valid {
+            self.error = Some("The endpoint starts with http:// or https://".to_string());
+            return;
+        }
+        self.error = None;
+        self.send(Request::SetEndpoint(endpoint));
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let switch = match self.enabled {
+            None => Either::A(label("Loading…").color(MUTED_TEXT_COLOR)),
+            Some(enabled) => Either::B(checkbox(
+                "Send anonymous usage statistics",
+                enabled,
+                |state: &mut Self, checked| state.set_enabled(checked),
+            )),
+        };
+        let input = text_input(self.endpoint.clone(), |state: &mut Self, endpoint| {
+            state.endpoint = endpoint;
+        })
+        .on_enter(|state: &mut Self, _| state.save_endpoint())
+        .placeholder("https://example.com/telemetry");
+        let save_button = text_button("Save endpoint", |state: &mut Self| state.save_endpoint());
+        let preview = match &self.preview {
+            None => Either::A(text_button(
+                "Show what would be sent",
+                |state: &mut Self| state.send(Request::Preview),
+            )),
+            Some(preview) => Either::B(flex_col((
+                prose(preview.clone()),
+                text_button("Hide", |state: &mut Self| state.preview = None),
+            ))),
+        };
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Usage statistics"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                switch,
+                flex_row((input.flex(1.), save_button)),
+                preview,
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Request>| async move {
+                    let loaded = async {
+                        Ok::<_, ServerError>(Response::Loaded {
+                            enabled: is_enabled().await?,
+                            endpoint: endpoint().await?.unwrap_or_default(),
+                        })
+                    };
+                    let _ = proxy.message(loaded.await);
+                    while let Some(request) = rx.recv().await {
+                        let response = match request {
+                            Request::SetEnabled(enabled) => {
+                                set_enabled(enabled).await.map(|()| Response::Saved)
+                            }
+                            Request::SetEndpoint(endpoint) => {
+                                set_endpoint(endpoint).await.map(|()| Response::Saved)
+                            }
+                            Request::Preview => report().await.map(Response::Preview),
+                        };
+                        let _ = proxy.message(response);
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Response, ServerError>| match result {
+                    Ok(Response::Loaded { enabled, endpoint }) => {
+                        state.enabled = Some(enabled);
+                        state.endpoint = endpoint;
+                    }
+                    Ok(Response::Saved) => state.error = None,
+                    Ok(Response::Preview(report)) => {
+                        state.preview = Some(
+                            serde_json::to_string_pretty(&report).unwrap_or_else(|error| {
+                                format!("Couldn't show the report: {error}")
+                            }),
+                        );
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
@@ -1,12 +1,21 @@
 use xilem::Color;
 use xilem::masonry::core::DefaultProperties;
+#[cfg(target_os = "android")]
+use xilem::masonry::widgets::Button;
 use xilem::masonry::widgets::TextInput;
+use xilem::palette::css::{DODGER_BLUE, GOLD, LIME_GREEN, ORANGE_RED, RED, WHITE};
 use xilem::style::Background;
+#[cfg(target_os = "android")]
+use xilem::style::Padding;
+
+use crate::core::{Priority, Status};
 
 pub const BACKGROUND_COLOR: Color = Color::from_rgb8(0x0a, 0x0a, 0x0a);
 pub const SURFACE_COLOR: Color = Color::from_rgb8(0x14, 0x14, 0x14);
 pub const SURFACE_BORDER_COLOR: Color = Color::from_rgb8(0x1e, 0x1e, 0x1e);
+pub const MUTED_TEXT_COLOR: Color = Color::from_rgb8(0x80, 0x80, 0x80);
 
+pub const ACCENT_COLOR: Color = Color::from_rgb8(0x37, 0x8c, 0xc8);
 pub const SUCCESS_COLOR: Color = Color::from_rgb8(0x37, 0xc8, 0x37);
 pub const DANGER_COLOR: Color = Color::from_rgb8(0xc8, 0x37, 0x37);
 
@@ -14,4 +23,49 @@ pub fn apply_theme(def_props: &mut DefaultProperties) {
     def_props.insert::<TextInput, Background>(Background::Color(
         SURFACE_COLOR.map_lightness(|l| l * 0.95),
     ));
+    // Bigger buttons, easier to hit with a finger.
+    #[cfg(target_os = "android")]
+    def_props.insert::<Button, Padding>(Padding::from_vh(12., 16.));
+}
+
+/// Colors of the task fields, which live with the theme so that `core` doesn't depend on the GUI.
+pub trait ThemeColor {
+    fn color(&self) -> Color;
+
+    /// Color of the text naming the value.
+    #[inline]
+    fn text_color(&self) -> Color {
+        self.color()
+    }
+}
+
+impl ThemeColor for Status {
+    #[inline]
+    fn color(&self) -> Color {
+        match self {
+            Status::ToDo => DODGER_BLUE,
+            Status::InProgress => ORANGE_RED,
+            Status::Done => LIME_GREEN,
+        }
+    }
+}
+
+impl ThemeColor for Priority {
+    #[inline]
+    fn color(&self) -> Color {
+        match self {
+            Priority::Low => SURFACE_BORDER_COLOR,
+            Priority::Medium => GOLD,
+            Priority::High => RED,
+        }
+    }
+
+    #[inline]
+    fn text_color(&self) -> Color {
+        match self {
+            Priority::Low => WHITE,
+            Priority::Medium => GOLD,
+            Priority::High => RED,
+        }
+    }
 }
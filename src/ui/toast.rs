@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::time::timeout;
+use xilem::view::{flex_row, label, worker};
+
+use crate::ui::theme::{SURFACE_BORDER_COLOR, SURFACE_COLOR};
+
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Short confirmation message, hidden after a few seconds.
+#[derive(Debug, Default)]
+pub struct Toast {
+    message: Option<String>,
+    sender: Option<UnboundedSender<()>>,
+}
+
+impl Toast {
+    pub fn show(&mut self, message: String) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(());
+        }
+        self.message = Some(message);
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let toast = self.message.clone().map(|message| {
+            flex_row(label(message))
+                .padding(8.)
+                .corner_radius(10.)
+                .background_color(SURFACE_COLOR)
+                .border(SURFACE_BORDER_COLOR, 1.)
+        });
+        fork(
+            flex_row(toast),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<()>| async move {
+                    while let Some(()) = rx.recv().await {
+                        // Showing another message restarts the delay.
+                        while let Ok(Some(())) = timeout(TOAST_DURATION, rx.recv()).await {}
+                        let _ = proxy.message(());
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, ()| {
+                    state.message = None;
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::tokio::task::spawn_blocking;
+use xilem::view::{button, flex_row, label, spinner, text_button, worker};
+
+use crate::ui::theme::DANGER_COLOR;
+use crate::voice::{VoiceError, model_path, record_until, transcribe};
+
+/// Microphone button recording a description, transcribed once the recording is stopped. Nothing
+/// is shown if no whisper model is configured with `TODOS_WHISPER_MODEL`.
+///
+/// The view emits the transcript.
+#[derive(Debug, Default)]
+pub struct VoiceCapture {
+    recording: bool,
+    transcribing: bool,
+    error: Option<String>,
+    /// Starts the recording with `true`, and stops it with `false`.
+    sender: Option<UnboundedSender<bool>>,
+}
+
+impl VoiceCapture {
+    fn toggle(&mut self) {
+        if let Some(sender) = &self.sender {
+            if sender.send(!self.recording).is_ok() {
+                self.transcribing = self.recording;
+                self.recording = !self.recording;
+                self.error = None;
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>, Option<String>> + use<> {
+        let model_path = model_path();
+        let content = model_path.is_some().then(|| {
+            let microphone = if self.transcribing {
+                Either::A(button(spinner(), |_| None))
+            } else {
+                let text = if self.recording { "Stop" } else { "🎤" };
+                Either::B(text_button(text, |state: &mut Self| {
+                    state.toggle();
+                    None
+                }))
+            };
+            let error = self
+                .error
+                .clone()
+                .map(|error| label(error).color(DANGER_COLOR));
+            flex_row((microphone, error))
+        });
+        fork(
+            flex_row(content),
+            worker(
+                move |proxy, mut rx: UnboundedReceiver<bool>| {
+                    let model_path = model_path.clone();
+                    async move {
+                        let Some(model_path) = model_path else {
+                            return;
+                        };
+                        while let Some(start) = rx.recv().await {
+                            if !start {
+                                continue;
+                            }
+                            let (stop, stopped) = std::sync::mpsc::channel();
+                            let recording = spawn_blocking(move || record_until(stopped));
+                            while let Some(true) = rx.recv().await {}
+                            let _ = stop.send(());
+                            let model_path = model_path.clone();
+                            let transcription = match recording.await {
+                                Ok(Ok(audio)) => {
+                                    spawn_blocking(move || transcribe(&model_path, &audio)).await
+                                }
+                                Ok(Err(error)) => Ok(Err(error)),
+                                Err(error) => Err(error),
+                            };
+                            let result = transcription
+                                .unwrap_or_else(|error| Err(VoiceError::Audio(error.to_string())));
+                            let _ = proxy.message(result);
+                        }
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<String, VoiceError>| {
+                    state.transcribing = false;
+                    match result {
+                        Ok(transcript) if !transcript.is_empty() => Some(transcript),
+                        Ok(_) => None,
+                        Err(error) => {
+                            state.error = Some(error.to_string());
+                            None
+                        }
+                    }
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,109 @@
+use xilem::WidgetView;
+use xilem::core::one_of::Either;
+use xilem::core::{Edit, fork};
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{FlexExt, flex_col, flex_row, label, prose, text_button, text_input, worker};
+
+use crate::core::ServerError;
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+use crate::webhooks::{set_webhooks, webhooks};
+
+const DESCRIPTION: &str =
+    "These URLs receive a JSON payload when a task is created, updated, completed or deleted.";
+
+/// Settings section to register the URLs called by `webhooks::dispatch`.
+#[derive(Debug, Default)]
+pub struct WebhookSettings {
+    /// `None` until loaded.
+    urls: Option<Vec<String>>,
+    input: String,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Vec<String>>>,
+}
+
+impl WebhookSettings {
+    fn save(&mut self, urls: Vec<String>) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(urls.clone());
+        }
+        self.urls = Some(urls);
+    }
+
+    fn add(&mut self) {
+        let url = self.input.trim().to_string();
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            self.error = Some("Webhook URLs start with http:// or https://".to_string());
+            return;
+        }
+        let mut urls = self.urls.clone().unwrap_or_default();
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+        self.input.clear();
+        self.error = None;
+        self.save(urls);
+    }
+
+    fn remove(&mut self, index: usize) {
+        let mut urls = self.urls.clone().unwrap_or_default();
+        if index < urls.len() {
+            urls.remove(index);
+            self.save(urls);
+        }
+    }
+
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let urls = match &self.urls {
+            None => Either::A(label("Loading…").color(MUTED_TEXT_COLOR)),
+            Some(urls) if urls.is_empty() => {
+                Either::A(label("No webhooks yet").color(MUTED_TEXT_COLOR))
+            }
+            Some(urls) => Either::B(flex_col(
+                urls.iter()
+                    .enumerate()
+                    .map(|(index, url)| {
+                        flex_row((
+                            label(url.clone()).flex(1.),
+                            text_button("Remove", move |state: &mut Self| state.remove(index)),
+                        ))
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        };
+        let input = text_input(self.input.clone(), |state: &mut Self, input| {
+            state.input = input;
+        })
+        .on_enter(|state: &mut Self, _| state.add())
+        .placeholder("https://example.com/hook");
+        let add_button = text_button("Add webhook", |state: &mut Self| state.add());
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        fork(
+            flex_col((
+                label("Webhooks"),
+                prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+                urls,
+                flex_row((input.flex(1.), add_button)),
+                error,
+            )),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Vec<String>>| async move {
+                    let _ = proxy.message(webhooks().await);
+                    while let Some(urls) = rx.recv().await {
+                        let _ = proxy.message(set_webhooks(urls.clone()).await.map(|()| urls));
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Vec<String>, ServerError>| match result {
+                    Ok(urls) => state.urls = Some(urls),
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
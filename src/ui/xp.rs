@@ -0,0 +1,158 @@
+use xilem::WidgetView;
+use xilem::core::{Edit, fork};
+use xilem::masonry::layout::AsUnit;
+use xilem::style::Style;
+use xilem::tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use xilem::view::{checkbox, flex_col, flex_row, label, progress_bar, prose, sized_box, worker};
+
+use crate::core::ServerError;
+use crate::database::{get_setting, set_setting};
+use crate::gamification::{
+    ACHIEVEMENTS, Progress, level, level_progress, points_for_level, update,
+};
+use crate::ui::theme::{DANGER_COLOR, MUTED_TEXT_COLOR};
+
+const ENABLED_SETTING: &str = "gamification.enabled";
+const DESCRIPTION: &str = "Shows the level and the points earned by completing tasks in the \
+    footer, and a message when an achievement is unlocked.";
+
+#[derive(Debug)]
+enum Request {
+    Refresh,
+    SetEnabled(bool),
+}
+
+#[derive(Debug)]
+enum Response {
+    Enabled(bool),
+    Progress(Progress),
+}
+
+/// XP bar of the footer with the level and the points, see `gamification`. It can be hidden
+/// entirely from the settings, no achievement being announced then.
+#[derive(Debug, Default)]
+pub struct XpBar {
+    /// `None` until loaded.
+    enabled: Option<bool>,
+    progress: Progress,
+    /// Names of the achievements unlocked and not announced yet, see `take_unlocked`.
+    unlocked: Vec<&'static str>,
+    error: Option<String>,
+    sender: Option<UnboundedSender<Request>>,
+}
+
+impl XpBar {
+    fn is_enabled(&self) -> bool {
+        self.enabled != Some(false)
+    }
+
+    /// Reads the points again, like after a change of the tasks.
+    pub fn refresh(&mut self) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Request::Refresh);
+        }
+    }
+
+    /// Names of the achievements just unlocked, to be announced.
+    pub fn take_unlocked(&mut self) -> Vec<&'static str> {
+        std::mem::take(&mut self.unlocked)
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Request::SetEnabled(enabled));
+        }
+        self.enabled = Some(enabled);
+    }
+
+    /// Section of the settings page switching the bar and listing the achievements.
+    pub fn settings_view(&self) -> impl WidgetView<Edit<Self>> + use<> {
+        let achievements = ACHIEVEMENTS
+            .iter()
+            .map(|achievement| {
+                let unlocked = self
+                    .progress
+                    .unlocked
+                    .iter()
+                    .any(|key| key == achievement.key);
+                let status = if unlocked { "Unlocked" } else { "Locked" };
+                flex_row((
+                    label(achievement.name),
+                    label(format!("{} · {status}", achievement.description))
+                        .color(MUTED_TEXT_COLOR),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let error = self
+            .error
+            .clone()
+            .map(|error| label(error).color(DANGER_COLOR));
+        flex_col((
+            label("Points and achievements"),
+            prose(DESCRIPTION).text_color(MUTED_TEXT_COLOR),
+            checkbox(
+                "Show the XP bar",
+                self.is_enabled(),
+                |state: &mut Self, checked| state.set_enabled(checked),
+            ),
+            achievements,
+            error,
+        ))
+    }
+
+    /// The bar, loading the setting and the points in the background.
+    pub fn view(&mut self) -> impl WidgetView<Edit<Self>> + use<> {
+        let points = self.progress.summary.points;
+        let level = level(points);
+        let bar = (self.enabled == Some(true)).then(|| {
+            flex_row((
+                label(format!("Level {level}")),
+                sized_box(progress_bar(Some(level_progress(points)))).width(120.px()),
+                label(format!("{points} / {} XP", points_for_level(level + 1)))
+                    .color(MUTED_TEXT_COLOR),
+            ))
+        });
+        fork(
+            flex_row(bar),
+            worker(
+                |proxy, mut rx: UnboundedReceiver<Request>| async move {
+                    let enabled = get_setting(ENABLED_SETTING)
+                        .await
+                        .map(|enabled| Response::Enabled(enabled.as_deref() != Some("false")));
+                    let _ = proxy.message(enabled);
+                    let mut request = Some(Request::Refresh);
+                    while let Some(next) = request {
+                        let response = match next {
+                            Request::Refresh => update().await.map(Response::Progress),
+                            Request::SetEnabled(enabled) => {
+                                set_setting(ENABLED_SETTING, enabled.to_string())
+                                    .await
+                                    .map(|()| Response::Enabled(enabled))
+                            }
+                        };
+                        let _ = proxy.message(response);
+                        request = rx.recv().await;
+                    }
+                },
+                |state: &mut Self, sender| {
+                    state.sender = Some(sender);
+                },
+                |state: &mut Self, result: Result<Response, ServerError>| match result {
+                    Ok(Response::Enabled(enabled)) => {
+                        state.enabled = Some(enabled);
+                        state.error = None;
+                    }
+                    Ok(Response::Progress(progress)) => {
+                        if state.is_enabled() {
+                            let names = progress.new.iter().map(|achievement| achievement.name);
+                            state.unlocked.extend(names);
+                        }
+                        state.progress = progress;
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error.to_string()),
+                },
+            ),
+        )
+    }
+}
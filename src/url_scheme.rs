@@ -0,0 +1,240 @@
+//! `todos://` URLs, like `todos://add?text=Buy%20milk` or `todos://open?id=4`, so that browser
+//! extensions and other applications can create or show tasks.
+//!
+//! Only one instance per database handles them: the first one listens on a socket of the user, in
+//! `$XDG_RUNTIME_DIR`, and instances launched later on the same database with a URL forward it
+//! there and exit, so that each profile has its own. Other platforms than Unix use a local port,
+//! which any user of the machine can reach.
+
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+#[cfg(not(unix))]
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::database::database_file;
+
+pub const SCHEME: &str = "todos";
+/// First of the local ports used by the instances, the port of a database being picked among
+/// the `PORT_RANGE` following it.
+#[cfg(not(unix))]
+const BASE_PORT: u16 = 47315;
+#[cfg(not(unix))]
+const PORT_RANGE: u64 = 1000;
+/// Time given to another instance to send its URL, so that a client sending nothing can't block
+/// the others.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+/// Longest URL read, the rest being dropped.
+const MAX_URL_LENGTH: u64 = 64 * 1024;
+
+/// Listener of the URLs forwarded by later instances.
+#[cfg(unix)]
+pub type Listener = UnixListener;
+#[cfg(not(unix))]
+pub type Listener = TcpListener;
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum UrlCommand {
+    /// Creates a task with this description.
+    Add(String),
+    /// Shows the task with this id.
+    Open(i64),
+}
+
+/// Decodes the `%XX` escapes, and `+` as a space like in HTML forms.
+fn percent_decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [input.next(), input.next()];
+                let decoded = match hex {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                    _ => None,
+                };
+                match decoded {
+                    Some(decoded) => bytes.push(decoded),
+                    None => {
+                        bytes.push(b'%');
+                        bytes.extend(hex.into_iter().flatten());
+                    }
+                }
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Escapes everything but the unreserved characters of RFC 3986.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+impl fmt::Display for UrlCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UrlCommand::Add(text) => write!(f, "{SCHEME}://add?text={}", percent_encode(text)),
+            UrlCommand::Open(id) => write!(f, "{SCHEME}://open?id={id}"),
+        }
+    }
+}
+
+impl FromStr for UrlCommand {
+    type Err = String;
+
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid {SCHEME}:// URL: {url}");
+        let rest = url
+            .strip_prefix(SCHEME)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(invalid)?;
+        let rest = rest.trim_start_matches('/');
+        let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| percent_decode(value))
+        };
+        match action.trim_end_matches('/') {
+            "add" => {
+                let text = param("text").filter(|text| !text.trim().is_empty());
+                Ok(UrlCommand::Add(text.ok_or_else(invalid)?))
+            }
+            "open" => {
+                let id = param("id").and_then(|id| id.parse().ok());
+                Ok(UrlCommand::Open(id.ok_or_else(invalid)?))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+pub enum Instance {
+    /// No other instance is running, URLs forwarded by later instances arrive on the listener.
+    Primary(Listener),
+    /// The URL was given to the running instance.
+    Forwarded,
+    /// Another instance is running, but there was no URL to forward.
+    Secondary,
+}
+
+/// Hash of the database opened, so that the instances of different profiles or `--db` files
+/// don't take the URLs of each other.
+fn database_key() -> u64 {
+    let path = database_file().map(|path| path.canonicalize().unwrap_or(path));
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Path of the socket of the database, in the runtime directory of the user or else in the
+/// temporary one.
+#[cfg(unix)]
+fn socket_path() -> PathBuf {
+    let key = database_key();
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join(format!("todos-{key:016x}.sock")),
+        None => {
+            let user = std::env::var("USER").unwrap_or_default();
+            std::env::temp_dir().join(format!("todos-{user}-{key:016x}.sock"))
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn addr() -> (&'static str, u16) {
+    let port = BASE_PORT + (database_key() % PORT_RANGE) as u16;
+    ("127.0.0.1", port)
+}
+
+#[cfg(unix)]
+fn bind() -> io::Result<Listener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = socket_path();
+    let listener = match UnixListener::bind(&path) {
+        // The socket of an instance that didn't exit cleanly is left behind, nobody answers on it.
+        Err(error)
+            if error.kind() == ErrorKind::AddrInUse
+                && UnixStream::connect(&path)
+                    .is_err_and(|error| error.kind() == ErrorKind::ConnectionRefused) =>
+        {
+            std::fs::remove_file(&path)?;
+            UnixListener::bind(&path)?
+        }
+        result => result?,
+    };
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+#[cfg(not(unix))]
+fn bind() -> io::Result<Listener> {
+    TcpListener::bind(addr())
+}
+
+#[cfg(unix)]
+fn connect() -> io::Result<UnixStream> {
+    UnixStream::connect(socket_path())
+}
+
+#[cfg(not(unix))]
+fn connect() -> io::Result<TcpStream> {
+    TcpStream::connect(addr())
+}
+
+/// Becomes the instance handling the URLs, or forwards `url` to the running one.
+pub fn claim_instance(url: Option<&str>) -> io::Result<Instance> {
+    match bind() {
+        Ok(listener) => Ok(Instance::Primary(listener)),
+        Err(error) if error.kind() == ErrorKind::AddrInUse => {
+            let Some(url) = url else {
+                return Ok(Instance::Secondary);
+            };
+            let mut stream = connect()?;
+            writeln!(stream, "{url}")?;
+            Ok(Instance::Forwarded)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Calls `on_url` with the URLs forwarded by other instances, on a thread of its own, until it
+/// returns `false`.
+pub fn listen(listener: Listener, on_url: impl Fn(String) -> bool + Send + 'static) {
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+                continue;
+            }
+            let mut url = String::new();
+            let mut reader = BufReader::new(stream.take(MAX_URL_LENGTH));
+            if reader.read_line(&mut url).is_ok() && !on_url(url.trim().to_string()) {
+                return;
+            }
+        }
+    });
+}
@@ -0,0 +1,134 @@
+//! Voice capture: `record_until` records the default microphone and `transcribe` turns the audio
+//! into text locally with whisper.cpp, using the model file of `TODOS_WHISPER_MODEL`, like
+//! `ggml-base.en.bin`.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{Sample, SampleFormat};
+use thiserror::Error;
+use tracing::warn;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// Sample rate expected by whisper.
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+#[derive(Debug, Error)]
+pub enum VoiceError {
+    #[error("no microphone was found")]
+    NoMicrophone,
+    #[error("couldn't record: {0}")]
+    Audio(String),
+    #[error("couldn't transcribe: {0}")]
+    Whisper(#[from] whisper_rs::WhisperError),
+}
+
+/// Mono audio at the sample rate expected by whisper.
+#[derive(Clone, Debug, Default)]
+pub struct Audio {
+    samples: Vec<f32>,
+}
+
+impl Audio {
+    /// Mixes the channels down to mono, and resamples them linearly to 16 kHz.
+    fn from_interleaved(samples: &[f32], channels: u16, sample_rate: u32) -> Self {
+        let mono = samples
+            .chunks(channels.max(1) as usize)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect::<Vec<_>>();
+        let ratio = sample_rate as f64 / WHISPER_SAMPLE_RATE as f64;
+        let length = (mono.len() as f64 / ratio) as usize;
+        let samples = (0..length)
+            .map(|index| {
+                let position = index as f64 * ratio;
+                let before = position as usize;
+                let after = (before + 1).min(mono.len() - 1);
+                let weight = (position - before as f64) as f32;
+                mono[before] * (1. - weight) + mono[after] * weight
+            })
+            .collect();
+        Self { samples }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+/// The whisper model of `TODOS_WHISPER_MODEL`, `None` if voice capture isn't configured.
+pub fn model_path() -> Option<PathBuf> {
+    std::env::var_os("TODOS_WHISPER_MODEL").map(PathBuf::from)
+}
+
+/// Records the default microphone until `stop` receives a message or is disconnected.
+///
+/// Blocks the current thread.
+pub fn record_until(stop: Receiver<()>) -> Result<Audio, VoiceError> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or(VoiceError::NoMicrophone)?;
+    let config = device
+        .default_input_config()
+        .map_err(|error| VoiceError::Audio(error.to_string()))?;
+    let channels = config.channels();
+    let sample_rate = config.sample_rate().0;
+    let samples = Arc::new(Mutex::new(Vec::<f32>::new()));
+    let on_error = |error| warn!(%error, "the recording failed");
+    let recorded = samples.clone();
+    let stream = match config.sample_format() {
+        SampleFormat::F32 => device.build_input_stream(
+            &config.into(),
+            move |data: &[f32], _| recorded.lock().unwrap().extend_from_slice(data),
+            on_error,
+            None,
+        ),
+        SampleFormat::I16 => device.build_input_stream(
+            &config.into(),
+            move |data: &[i16], _| {
+                let data = data.iter().map(|sample| sample.to_sample::<f32>());
+                recorded.lock().unwrap().extend(data);
+            },
+            on_error,
+            None,
+        ),
+        format => {
+            return Err(VoiceError::Audio(format!(
+                "unsupported sample format {format}"
+            )));
+        }
+    }
+    .map_err(|error| VoiceError::Audio(error.to_string()))?;
+    stream
+        .play()
+        .map_err(|error| VoiceError::Audio(error.to_string()))?;
+    let _ = stop.recv();
+    drop(stream);
+    let samples = samples.lock().unwrap();
+    Ok(Audio::from_interleaved(&samples, channels, sample_rate))
+}
+
+/// Transcribes the audio with the whisper model at `model_path`.
+///
+/// Blocks the current thread, for seconds with bigger models.
+pub fn transcribe(model_path: &Path, audio: &Audio) -> Result<String, VoiceError> {
+    if audio.is_empty() {
+        return Ok(String::new());
+    }
+    let context = WhisperContext::new_with_params(
+        &model_path.to_string_lossy(),
+        WhisperContextParameters::default(),
+    )?;
+    let mut state = context.create_state()?;
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    state.full(params, &audio.samples)?;
+    let mut transcript = String::new();
+    for segment in 0..state.full_n_segments()? {
+        transcript.push_str(&state.full_get_segment_text(segment)?);
+    }
+    Ok(transcript.trim().to_string())
+}
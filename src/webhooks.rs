@@ -0,0 +1,183 @@
+//! Webhooks called with a JSON payload when a task is created, updated, completed or deleted, by
+//! anyone, for automations like Home Assistant or Slack.
+//!
+//! `dispatch` follows the events of the `service` and queues a delivery per registered URL, each
+//! URL having its own queue delivered in order. Failed deliveries are retried with an increasing
+//! delay, the next ones of the URL waiting, and dropped after `MAX_ATTEMPTS`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::{Value, json};
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tracing::warn;
+
+use crate::core::ServerError;
+use crate::database::{TaskQuery, get_setting, get_tasks, set_setting};
+use crate::service::{self, subscribe};
+use crate::{Status, Task};
+
+const WEBHOOKS_SETTING: &str = "webhooks.urls";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_ATTEMPTS: u32 = 6;
+
+/// The registered webhook URLs.
+pub async fn webhooks() -> Result<Vec<String>, ServerError> {
+    let urls = get_setting(WEBHOOKS_SETTING)
+        .await?
+        .map(|urls| urls.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+    Ok(urls)
+}
+
+pub async fn set_webhooks(urls: Vec<String>) -> Result<(), ServerError> {
+    set_setting(WEBHOOKS_SETTING, urls.join("\n")).await
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Created(Task),
+    Updated(Task),
+    /// The task became done.
+    Completed(Task),
+    Deleted(i64),
+}
+
+impl Event {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Created(_) => "task.created",
+            Event::Updated(_) => "task.updated",
+            Event::Completed(_) => "task.completed",
+            Event::Deleted(_) => "task.deleted",
+        }
+    }
+
+    /// Body sent to the webhooks, like `{"event": "task.created", "task": {..}}`, or with the `id`
+    /// of the task for `task.deleted`.
+    pub fn payload(&self) -> Value {
+        match self {
+            Event::Created(task) | Event::Updated(task) | Event::Completed(task) => {
+                json!({ "event": self.name(), "task": task })
+            }
+            Event::Deleted(id) => json!({ "event": self.name(), "id": id }),
+        }
+    }
+}
+
+/// Turns the events of the service into webhook events.
+struct EventWatcher {
+    events: Receiver<(&'static str, service::Event)>,
+    /// Status of the known tasks, to tell completions from other updates.
+    statuses: HashMap<i64, Status>,
+}
+
+impl EventWatcher {
+    async fn new() -> Result<Self, ServerError> {
+        // Subscribed first, so that no change is missed while reading the tasks.
+        let events = subscribe();
+        let statuses = get_tasks(TaskQuery::default())
+            .await?
+            .into_iter()
+            .map(|task| (task.id, task.status))
+            .collect();
+        Ok(Self { events, statuses })
+    }
+
+    /// Returns the events published since the last call.
+    fn events(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        loop {
+            let event = match self.events.try_recv() {
+                Ok((_, event)) => event,
+                Err(TryRecvError::Lagged(skipped)) => {
+                    warn!(skipped, "the webhooks missed events");
+                    continue;
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            };
+            let event = match event {
+                service::Event::TaskCreated(task) => {
+                    self.statuses.insert(task.id, task.status);
+                    Event::Created(task)
+                }
+                service::Event::TaskUpdated(task) => {
+                    match self.statuses.insert(task.id, task.status) {
+                        Some(status) if status != Status::Done && task.status == Status::Done => {
+                            Event::Completed(task)
+                        }
+                        _ => Event::Updated(task),
+                    }
+                }
+                service::Event::TaskDeleted(id) => {
+                    self.statuses.remove(&id);
+                    Event::Deleted(id)
+                }
+            };
+            events.push(event);
+        }
+        events
+    }
+}
+
+/// Delivers the payloads to `url` in their order, each one being retried before the next is sent,
+/// until the sender is dropped.
+async fn deliver(client: Client, url: String, mut payloads: UnboundedReceiver<Value>) {
+    while let Some(payload) = payloads.recv().await {
+        let mut attempts = 0;
+        loop {
+            let response = client.post(&url).json(&payload).send().await;
+            let error = match response {
+                Ok(response) if response.status().is_success() => break,
+                Ok(response) => format!("answered {}", response.status()),
+                Err(error) => error.to_string(),
+            };
+            attempts += 1;
+            if attempts >= MAX_ATTEMPTS {
+                warn!(%url, %error, "a webhook dropped an event");
+                break;
+            }
+            tokio::time::sleep(FIRST_RETRY_DELAY * 2u32.pow(attempts - 1)).await;
+        }
+    }
+}
+
+/// Delivers the events to the registered webhooks, until the tasks can't be read when starting.
+pub async fn dispatch() -> Result<(), ServerError> {
+    let client = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let mut watcher = EventWatcher::new().await?;
+    let mut urls = Vec::new();
+    // A queue per URL, so that an endpoint that is down or slow doesn't delay the others.
+    let mut queues = HashMap::<String, UnboundedSender<Value>>::new();
+    loop {
+        let events = watcher.events();
+        if !events.is_empty() {
+            // The URLs read last are kept when the settings can't be read.
+            match webhooks().await {
+                Ok(read) => urls = read,
+                Err(error) => warn!(%error, "can't read the webhooks"),
+            }
+            // Dropping the queue of a removed URL ends its task after the deliveries left.
+            queues.retain(|url, _| urls.contains(url));
+            for event in events {
+                for url in &urls {
+                    let queue = queues.entry(url.clone()).or_insert_with(|| {
+                        let (sender, receiver) = unbounded_channel();
+                        tokio::spawn(deliver(client.clone(), url.clone(), receiver));
+                        sender
+                    });
+                    let _ = queue.send(event.payload());
+                }
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use todos::core::{Priority, Status, Task};
+use todos::ui::component::AsyncList;
+use todos::ui::component::list::storage::RetryPolicy;
+use todos::ui::component::list::testing::{ListHarness, MockError, MockStorage};
+use todos::ui::component::list::{PendingItemOperation, SelectionMode};
+
+fn task(id: i64, description: &str) -> Task {
+    Task {
+        id,
+        description: description.into(),
+        ..Default::default()
+    }
+}
+
+fn harness(list: AsyncList<Task, MockStorage<Task>>) -> ListHarness<Task> {
+    MockStorage::<Task>::set_items(vec![task(1, "First"), task(2, "Second")]);
+    let mut harness = ListHarness::new(list.with_retry_policy(RetryPolicy::never()));
+    harness.run_all();
+    harness
+}
+
+fn new_task(description: &str) -> (String, Priority) {
+    (description.to_string(), Priority::default())
+}
+
+fn update(description: &str) -> (String, Status, Priority) {
+    (description.to_string(), Status::Done, Priority::High)
+}
+
+#[test]
+fn fetches_items_on_start() {
+    let harness = harness(AsyncList::new(false, false, false));
+    harness.assert_items(&[1, 2]);
+    harness.assert_pending_requests(0);
+    assert!(MockStorage::<Task>::calls().contains(&"fetch_all"));
+}
+
+#[test]
+fn delete_is_pending_until_the_storage_confirms_it() {
+    let mut harness = harness(AsyncList::new(false, false, false));
+    harness.delete(1);
+    harness.assert_pending(1, PendingItemOperation::PendingDelete);
+    harness.assert_items(&[1, 2]);
+    harness.run_all();
+    harness.assert_items(&[2]);
+    harness.assert_pending(2, PendingItemOperation::None);
+}
+
+#[test]
+fn optimistic_update_is_shown_right_away() {
+    let mut harness = harness(AsyncList::new(false, false, false).with_optimistic_updates());
+    harness.update(1, update("Updated"));
+    assert_eq!(&*harness.task(1).unwrap().description, "Updated");
+    harness.assert_pending(1, PendingItemOperation::Syncing);
+    harness.run_all();
+    assert_eq!(&*harness.task(1).unwrap().description, "Updated");
+    harness.assert_pending(1, PendingItemOperation::None);
+    harness.assert_no_error();
+}
+
+#[test]
+fn failed_optimistic_update_is_rolled_back_and_can_be_retried() {
+    let mut harness = harness(AsyncList::new(false, false, false).with_optimistic_updates());
+    MockStorage::<Task>::fail_next(MockError::Failure);
+    harness.update(1, update("Updated"));
+    harness.run_all();
+    assert_eq!(&*harness.task(1).unwrap().description, "First");
+    harness.assert_pending(1, PendingItemOperation::Failed);
+    harness.assert_error();
+
+    harness.list_mut().retry_failed_request();
+    harness.run_all();
+    assert_eq!(&*harness.task(1).unwrap().description, "Updated");
+    harness.assert_pending(1, PendingItemOperation::None);
+    harness.assert_no_error();
+}
+
+#[test]
+fn transient_failures_are_retried() {
+    MockStorage::<Task>::set_items(vec![task(1, "First"), task(2, "Second")]);
+    let mut harness = ListHarness::new(AsyncList::new(false, false, false).with_retry_policy(
+        RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: 0.,
+        },
+    ));
+    harness.run_all();
+    MockStorage::<Task>::fail_next(MockError::Transient);
+    harness.create(new_task("Third"));
+    harness.run_all();
+    harness.assert_no_error();
+    harness.assert_items(&[1, 2, 3]);
+}
+
+#[test]
+fn create_applied_after_a_timeout_is_not_retried() {
+    MockStorage::<Task>::set_items(vec![task(1, "First"), task(2, "Second")]);
+    let mut harness = ListHarness::new(AsyncList::new(false, false, false).with_retry_policy(
+        RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: 0.,
+        },
+    ));
+    harness.run_all();
+    MockStorage::<Task>::time_out_after_next();
+    harness.create(new_task("Third"));
+    harness.run_all();
+    let creates = MockStorage::<Task>::calls()
+        .into_iter()
+        .filter(|call| *call == "create")
+        .count();
+    assert_eq!(creates, 1);
+    assert_eq!(MockStorage::<Task>::items().len(), 3);
+    assert_eq!(harness.failed_creates().len(), 1);
+    harness.assert_error();
+}
+
+#[test]
+fn dismissed_errors_are_cleared() {
+    let mut harness = harness(AsyncList::new(false, false, false));
+    MockStorage::<Task>::fail_next(MockError::Failure);
+    harness.create(new_task("Third"));
+    harness.run_all();
+    harness.assert_error();
+    harness.list_mut().dismiss_error();
+    harness.assert_no_error();
+    harness.assert_items(&[1, 2]);
+}
+
+#[test]
+fn reload_fetches_external_changes() {
+    let mut harness = harness(AsyncList::new(false, false, false));
+    MockStorage::<Task>::set_items(vec![task(2, "Second"), task(3, "Third")]);
+    harness.list_mut().reload();
+    assert!(harness.list().is_refreshing());
+    harness.run_all();
+    assert!(!harness.list().is_refreshing());
+    harness.assert_items(&[2, 3]);
+}
+
+#[test]
+fn failed_create_can_be_retried_or_discarded() {
+    let mut harness = harness(AsyncList::new(false, false, false));
+    MockStorage::<Task>::fail_next(MockError::Failure);
+    MockStorage::<Task>::fail_next(MockError::Failure);
+    harness.create(new_task("Third"));
+    harness.create(new_task("Fourth"));
+    harness.run_all();
+    let failed_creates = harness.failed_creates();
+    assert_eq!(failed_creates.len(), 2);
+
+    harness.list_mut().retry_create(failed_creates[0]);
+    harness.list_mut().discard_create(failed_creates[1]);
+    harness.run_all();
+    assert!(harness.failed_creates().is_empty());
+    harness.assert_items(&[1, 2, 3]);
+    harness.assert_no_error();
+}
+
+#[test]
+fn leaked_create_fails_after_the_leak_timeout() {
+    let mut harness =
+        harness(AsyncList::new(false, false, false).with_leak_timeout(Duration::ZERO));
+    harness.create(new_task("Lost"));
+    assert_eq!(harness.list().pending_operations().len(), 1);
+
+    harness.check_leaks();
+    harness.assert_pending_requests(0);
+    assert_eq!(harness.failed_creates().len(), 1);
+    harness.assert_error();
+    // The request was aborted.
+    harness.run_all();
+    harness.assert_items(&[1, 2]);
+}
+
+#[test]
+fn pending_requests_are_kept_within_the_leak_timeout() {
+    let mut harness = harness(AsyncList::new(false, false, false));
+    harness.create(new_task("Slow"));
+    harness.check_leaks();
+    harness.assert_pending_requests(1);
+    harness.run_all();
+    harness.assert_items(&[1, 2, 3]);
+}
+
+#[test]
+fn failed_delete_is_shown_on_the_item() {
+    let mut harness = harness(AsyncList::new(false, false, false));
+    MockStorage::<Task>::fail_next(MockError::Failure);
+    harness.delete(1);
+    harness.run_all();
+    assert!(harness.list().item_error(1).is_some());
+    harness.assert_pending(1, PendingItemOperation::Failed);
+    harness.assert_pending(2, PendingItemOperation::None);
+
+    harness.list_mut().retry_item(1);
+    harness.run_all();
+    assert!(harness.list().item_error(1).is_none());
+    harness.assert_items(&[2]);
+    harness.assert_no_error();
+}
+
+#[test]
+fn selection_drops_the_items_deleted_elsewhere_on_refetch() {
+    let mut harness =
+        harness(AsyncList::new(false, false, false).with_selection(SelectionMode::Multi));
+    harness.list_mut().select(1);
+    harness.list_mut().select(2);
+    MockStorage::<Task>::set_items(vec![task(2, "Second"), task(3, "Third")]);
+    harness.list_mut().reload();
+    harness.run_all();
+    assert_eq!(harness.list().selection(), [2]);
+
+    // A task created later with the id of the deleted one isn't selected.
+    MockStorage::<Task>::set_items(vec![task(1, "Reused"), task(2, "Second"), task(3, "Third")]);
+    harness.list_mut().reload();
+    harness.run_all();
+    assert_eq!(harness.list().selection(), [2]);
+}
@@ -0,0 +1,75 @@
+use reqwest::Url;
+use todos::core::{Priority, Status};
+use todos::sync::caldav::{SyncError, Vtodo, parse_multistatus};
+
+const ICAL: &str = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:a\r\nSUMMARY:Call <Bob> & Alice\r\n\
+                    END:VTODO\r\nEND:VCALENDAR\r\n";
+
+fn base() -> Url {
+    Url::parse("https://dav.example.com/calendars/me/tasks/").unwrap()
+}
+
+#[test]
+fn vtodo_round_trips_through_ical() {
+    let vtodo = Vtodo {
+        uid: "todos-1@my-todos".to_string(),
+        summary: "Buy milk, eggs; and a very long list of other things that needs folding"
+            .to_string(),
+        status: Status::InProgress,
+        priority: Priority::High,
+    };
+    let ical = vtodo.to_ical("20261015T120000Z");
+    assert!(ical.lines().all(|line| line.len() <= 75));
+    assert_eq!(Vtodo::parse(&ical), Some(vtodo));
+}
+
+#[test]
+fn vtodo_priorities_are_mapped_to_ranges() {
+    let ical = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:a\r\nSUMMARY:Call\\, maybe\r\n\
+                STATUS:COMPLETED\r\nPRIORITY:3\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+    let vtodo = Vtodo::parse(ical).unwrap();
+    assert_eq!(vtodo.summary, "Call, maybe");
+    assert_eq!(vtodo.status, Status::Done);
+    assert_eq!(vtodo.priority, Priority::High);
+}
+
+#[test]
+fn multistatus_reads_cdata_and_makes_hrefs_absolute() {
+    let xml = format!(
+        "<d:multistatus xmlns:d=\"DAV:\" xmlns:c=\"urn:ietf:params:xml:ns:caldav\">\
+         <d:response><d:href>/calendars/me/tasks/a.ics</d:href><d:propstat><d:prop>\
+         <d:getetag>\"1\"</d:getetag><c:calendar-data><![CDATA[{ICAL}]]></c:calendar-data>\
+         </d:prop></d:propstat></d:response>\
+         <d:response><d:href>b.ics</d:href><d:propstat><d:prop>\
+         <d:getetag>\"2\"</d:getetag><c:calendar-data>{}</c:calendar-data>\
+         </d:prop></d:propstat></d:response></d:multistatus>",
+        ICAL.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+    );
+    let todos = parse_multistatus(&xml, &base()).unwrap();
+    let hrefs = todos.iter().map(|todo| &*todo.href).collect::<Vec<_>>();
+    assert_eq!(
+        hrefs,
+        [
+            "https://dav.example.com/calendars/me/tasks/a.ics",
+            "https://dav.example.com/calendars/me/tasks/b.ics",
+        ]
+    );
+    assert!(
+        todos
+            .iter()
+            .all(|todo| todo.vtodo.summary == "Call <Bob> & Alice")
+    );
+}
+
+#[test]
+fn multistatus_fails_on_an_entry_it_cannot_read() {
+    let xml = "<d:multistatus xmlns:d=\"DAV:\"><d:response><d:href>a.ics</d:href>\
+               <d:propstat><d:prop><d:getetag/></d:prop></d:propstat></d:response>\
+               </d:multistatus>";
+    assert!(matches!(
+        parse_multistatus(xml, &base()),
+        Err(SyncError::InvalidResponse(_))
+    ));
+}
@@ -0,0 +1,70 @@
+#![cfg(debug_assertions)]
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use todos::core::{Priority, Task};
+use todos::ui::component::list::ListStorage;
+use todos::ui::component::list::chaos::{Chaos, ChaosStorage, set_chaos};
+use todos::ui::component::list::testing::{MockError, MockStorage};
+
+type Storage = ChaosStorage<MockStorage<Task>>;
+
+/// The settings are global, so the tests use them one at a time.
+static CHAOS: Mutex<()> = Mutex::new(());
+
+fn create_with(chaos: Chaos) -> Result<Task, MockError> {
+    let _guard = CHAOS.lock().unwrap_or_else(PoisonError::into_inner);
+    set_chaos(chaos);
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(Storage::create(("Task".to_string(), Priority::Low)))
+}
+
+#[test]
+fn passes_through_by_default() {
+    let task = create_with(Chaos::default()).unwrap();
+    assert_eq!(&*task.description, "Task");
+}
+
+#[test]
+fn injects_failures() {
+    let result = create_with(Chaos {
+        failures: 1.,
+        ..Default::default()
+    });
+    assert!(matches!(result, Err(MockError::Failure)));
+}
+
+#[test]
+fn injects_timeouts() {
+    let result = create_with(Chaos {
+        timeouts: 1.,
+        ..Default::default()
+    });
+    assert!(matches!(result, Err(MockError::Timeout)));
+}
+
+#[test]
+fn injects_delays() {
+    let result = create_with(Chaos {
+        max_delay: Duration::from_millis(10),
+        ..Default::default()
+    });
+    assert!(result.is_ok());
+}
+
+#[test]
+fn parses_the_settings() {
+    let chaos = Chaos::parse("failures=0.2,timeouts=0.1,delay_ms=2000,unknown=1");
+    assert_eq!(
+        chaos,
+        Chaos {
+            failures: 0.2,
+            timeouts: 0.1,
+            max_delay: Duration::from_secs(2),
+        }
+    );
+}
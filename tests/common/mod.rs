@@ -0,0 +1,61 @@
+//! Helpers of the tests, like an empty in-memory SQLite database, migrated like the one of the
+//! app, so that the tests don't need `db/Todos.db`.
+
+// Each test binary uses a part of the helpers.
+#![allow(dead_code)]
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use sqlx::sqlite::SqlitePoolOptions;
+use todos::core::{Priority, Task};
+use todos::database::{connect_options, migrate, set_pool, set_simulated_latency};
+
+/// The pool is global, so the tests of a binary use the database one at a time.
+static DATABASE: Mutex<()> = Mutex::new(());
+
+/// Task named after its id, with the fields that the picking and the focus look at.
+pub fn task(id: i64, priority: Priority, updated_at: &str) -> Task {
+    Task {
+        id,
+        description: format!("Task {id}").into(),
+        priority,
+        updated_at: updated_at.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Runs the future on a runtime of its own.
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+/// Runs the test against a new database.
+pub fn with_database<F>(test: impl FnOnce() -> F) -> F::Output
+where
+    F: Future,
+{
+    let _guard = DATABASE.lock().unwrap_or_else(PoisonError::into_inner);
+    block_on(async {
+        // Each connection to `sqlite::memory:` has its own database, which lives as long as
+        // the connection, so the pool keeps a single one.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(connect_options("sqlite::memory:").unwrap())
+            .await
+            .unwrap();
+        set_pool(pool);
+        set_simulated_latency(Duration::ZERO);
+        migrate().await.unwrap();
+        test().await
+    })
+}
@@ -0,0 +1,46 @@
+use std::env::VarError;
+use std::path::PathBuf;
+
+use todos::config::{BackupConfig, Config};
+
+#[test]
+fn missing_fields_take_their_default() {
+    let config = toml::from_str::<Config>("[backup]\ndir = \"backups\"").unwrap();
+    assert_eq!(
+        config,
+        Config {
+            backup: Some(BackupConfig {
+                dir: PathBuf::from("backups"),
+                interval_hours: 24,
+            }),
+            ..Default::default()
+        }
+    );
+    assert_eq!(toml::from_str::<Config>("").unwrap(), Config::default());
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    assert!(toml::from_str::<Config>("databse = \"Todos.db\"").is_err());
+}
+
+#[test]
+fn environment_variables_override_the_file() {
+    let file = "database = \"Todos.db\"\nfilter = \"all\"\n\n[backup]\ndir = \"backups\"";
+    let config = toml::from_str::<Config>(file)
+        .unwrap()
+        .override_with(|name| match name {
+            "TODOS_DB" => Ok("/data/Todos.db".to_string()),
+            "TODOS_BACKUP_INTERVAL_HOURS" => Ok("6".to_string()),
+            _ => Err(VarError::NotPresent),
+        });
+    assert_eq!(config.database, Some(PathBuf::from("/data/Todos.db")));
+    assert_eq!(config.filter.as_deref(), Some("all"));
+    assert_eq!(
+        config.backup,
+        Some(BackupConfig {
+            dir: PathBuf::from("backups"),
+            interval_hours: 6,
+        })
+    );
+}
@@ -0,0 +1,45 @@
+use todos::core::{CustomField, FieldKind};
+
+fn field(kind: FieldKind, options: &str) -> CustomField {
+    CustomField {
+        id: 1,
+        name: "Field".to_string(),
+        kind,
+        options: options.to_string(),
+    }
+}
+
+#[test]
+fn values_are_checked_against_the_kind() {
+    let text = field(FieldKind::Text, "");
+    assert_eq!(text.parse_value("  anything "), Ok("anything".to_string()));
+
+    let number = field(FieldKind::Number, "");
+    assert_eq!(number.parse_value("+2.5"), Ok("2.5".to_string()));
+    assert_eq!(number.parse_value("-3"), Ok("-3".to_string()));
+    assert!(number.parse_value("three").is_err());
+    assert!(number.parse_value("inf").is_err());
+
+    let date = field(FieldKind::Date, "");
+    assert_eq!(date.parse_value("2026-10-15"), Ok("2026-10-15".to_string()));
+    assert_eq!(date.parse_value("2028-02-29"), Ok("2028-02-29".to_string()));
+    for invalid in ["2026-02-29", "2026-13-01", "2026-1-5", "15/10/2026"] {
+        assert!(date.parse_value(invalid).is_err(), "{invalid} was accepted");
+    }
+}
+
+#[test]
+fn choices_are_the_non_blank_lines() {
+    let size = field(FieldKind::Choice, "S\n\n M \nL");
+    assert_eq!(size.choices().collect::<Vec<_>>(), ["S", "M", "L"]);
+    assert_eq!(size.parse_value("M"), Ok("M".to_string()));
+    assert!(size.parse_value("XL").is_err());
+}
+
+#[test]
+fn kinds_are_parsed() {
+    assert_eq!("number".parse(), Ok(FieldKind::Number));
+    assert_eq!("Date".parse(), Ok(FieldKind::Date));
+    assert_eq!("enum".parse(), Ok(FieldKind::Choice));
+    assert!("list".parse::<FieldKind>().is_err());
+}
@@ -0,0 +1,552 @@
+mod common;
+
+use common::with_database;
+use sqlx::Error as SqlxError;
+use todos::core::{FieldKind, Priority, ServerError, Status};
+use todos::database::{
+    FieldValue, TaskEventKind, TaskOrder, TaskQuery, count_by_status, create_custom_field,
+    create_task, create_task_with_priority, create_tasks, create_tasks_with_priorities,
+    delete_custom_field, delete_task, delete_tasks, get_achievements, get_custom_fields,
+    get_field_values, get_habit, get_points, get_stale_tasks, get_task, get_task_history,
+    get_tasks, get_tasks_page, pool, reorder_tasks, set_field_value, set_habit, set_habit_done,
+    set_tasks_status, unlock_achievement, update_task, update_tasks,
+};
+
+fn descriptions(descriptions: &[&str]) -> Vec<String> {
+    descriptions
+        .iter()
+        .map(|description| description.to_string())
+        .collect()
+}
+
+#[test]
+fn starts_empty() {
+    with_database(|| async {
+        assert!(get_tasks(TaskQuery::default()).await.unwrap().is_empty());
+        assert_eq!(count_by_status().await.unwrap().total(), 0);
+    });
+}
+
+#[test]
+fn creates_updates_and_deletes_a_task() {
+    with_database(|| async {
+        let task = create_task("Buy milk".to_string()).await.unwrap();
+        assert_eq!(&*task.description, "Buy milk");
+        assert_eq!(task.status, Status::ToDo);
+        assert_eq!(task.priority, Priority::Low);
+        assert_eq!(&*get_task(task.id).await.unwrap().description, "Buy milk");
+
+        let updated = update_task(
+            task.id,
+            "Buy oat milk".to_string(),
+            Status::Done,
+            Priority::High,
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.id, task.id);
+        assert_eq!(&*updated.description, "Buy oat milk");
+        assert_eq!(updated.status, Status::Done);
+        assert_eq!(updated.priority, Priority::High);
+
+        assert_eq!(delete_task(task.id).await.unwrap(), task.id);
+        assert!(matches!(
+            get_task(task.id).await,
+            Err(ServerError::Database(SqlxError::RowNotFound))
+        ));
+    });
+}
+
+#[test]
+fn does_not_reuse_the_ids_of_deleted_tasks() {
+    with_database(|| async {
+        let first = create_task("First".to_string()).await.unwrap();
+        delete_task(first.id).await.unwrap();
+        let second = create_task("Second".to_string()).await.unwrap();
+        assert!(second.id > first.id);
+    });
+}
+
+#[test]
+fn creates_tasks_in_order_at_the_end() {
+    with_database(|| async {
+        create_task("Existing".to_string()).await.unwrap();
+        let tasks = create_tasks(descriptions(&["One", "Two", "Three"]))
+            .await
+            .unwrap();
+        let created = tasks
+            .iter()
+            .map(|task| &*task.description)
+            .collect::<Vec<_>>();
+        assert_eq!(created, ["One", "Two", "Three"]);
+        assert!(
+            tasks
+                .windows(2)
+                .all(|pair| pair[0].position < pair[1].position)
+        );
+        assert!(create_tasks(Vec::new()).await.unwrap().is_empty());
+        assert_eq!(get_tasks(TaskQuery::default()).await.unwrap().len(), 4);
+    });
+}
+
+#[test]
+fn creates_more_tasks_than_a_statement_can_bind() {
+    with_database(|| async {
+        let descs = (0..12_000).map(|i| format!("Task {i}")).collect::<Vec<_>>();
+        let tasks = create_tasks(descs).await.unwrap();
+        assert_eq!(tasks.len(), 12_000);
+        assert_eq!(&*tasks[11_999].description, "Task 11999");
+        assert_eq!(get_tasks(TaskQuery::default()).await.unwrap().len(), 12_000);
+    });
+}
+
+#[test]
+fn changes_and_deletes_tasks_in_batch() {
+    with_database(|| async {
+        let tasks = create_tasks(descriptions(&["One", "Two", "Three"]))
+            .await
+            .unwrap();
+        let ids = tasks.iter().map(|task| task.id).collect::<Vec<_>>();
+
+        let done = set_tasks_status(ids[..2].to_vec(), Status::Done)
+            .await
+            .unwrap();
+        assert_eq!(done.len(), 2);
+        assert!(done.iter().all(|task| task.status == Status::Done));
+        let counts = count_by_status().await.unwrap();
+        assert_eq!(counts.done, 2);
+        assert_eq!(counts.to_do, 1);
+
+        let deleted = delete_tasks(vec![ids[0], ids[2]]).await.unwrap();
+        assert_eq!(deleted.len(), 2);
+        let remaining = get_tasks(TaskQuery::default()).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, ids[1]);
+    });
+}
+
+#[test]
+fn searches_ignoring_the_case_and_filters_by_status() {
+    with_database(|| async {
+        let tasks = create_tasks(descriptions(&["Buy milk", "Call the BANK", "Buy bread"]))
+            .await
+            .unwrap();
+        set_tasks_status(vec![tasks[2].id], Status::Done)
+            .await
+            .unwrap();
+
+        let found = get_tasks(TaskQuery {
+            search: Some("buy".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.len(), 2);
+        let found = get_tasks(TaskQuery {
+            search: Some("bank".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.len(), 1);
+
+        let found = get_tasks(TaskQuery {
+            statuses: Some(vec![Status::ToDo, Status::InProgress]),
+            search: Some("buy".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, tasks[0].id);
+    });
+}
+
+#[test]
+fn searches_ignoring_the_case_of_any_script_and_literally() {
+    with_database(|| async {
+        create_tasks(descriptions(&[
+            "Écrire à l'ÉCOLE",
+            "Ответить Ивану",
+            "Pay 1.5 $",
+        ]))
+        .await
+        .unwrap();
+        for (search, description) in [
+            ("école", "Écrire à l'ÉCOLE"),
+            ("ИВАНУ", "Ответить Ивану"),
+            ("1.5 $", "Pay 1.5 $"),
+        ] {
+            let found = get_tasks(TaskQuery {
+                search: Some(search.to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+            assert_eq!(found.len(), 1, "{search}");
+            assert_eq!(&*found[0].description, description);
+        }
+        let found = get_tasks(TaskQuery {
+            search: Some("1.5.".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert!(found.is_empty());
+    });
+}
+
+#[test]
+fn pages_through_the_tasks() {
+    with_database(|| async {
+        let descs = (1..=5).map(|n| format!("Task {n}")).collect();
+        create_tasks(descs).await.unwrap();
+        let query = TaskQuery {
+            order: Some(TaskOrder::UserOrder),
+            ..Default::default()
+        };
+
+        let (page, total) = get_tasks_page(query.clone(), 0, 2).await.unwrap();
+        assert_eq!(total, 5);
+        assert_eq!(&*page[0].description, "Task 1");
+        assert_eq!(page.len(), 2);
+        let (page, total) = get_tasks_page(query, 4, 2).await.unwrap();
+        assert_eq!(total, 5);
+        assert_eq!(page.len(), 1);
+        assert_eq!(&*page[0].description, "Task 5");
+    });
+}
+
+#[test]
+fn rolls_back_the_updates_when_one_fails() {
+    with_database(|| async {
+        let task = create_task("Unchanged".to_string()).await.unwrap();
+        let missing = task.id + 1;
+        let result = update_tasks(vec![
+            (task.id, "Changed".to_string(), Status::Done, Priority::High),
+            (missing, "Missing".to_string(), Status::Done, Priority::High),
+        ])
+        .await;
+        assert!(matches!(
+            result,
+            Err(ServerError::Database(SqlxError::RowNotFound))
+        ));
+
+        let task = get_task(task.id).await.unwrap();
+        assert_eq!(&*task.description, "Unchanged");
+        assert_eq!(task.status, Status::ToDo);
+    });
+}
+
+#[test]
+fn keeps_the_history_of_a_task() {
+    with_database(|| async {
+        let task = create_task("Draft".to_string()).await.unwrap();
+        update_task(task.id, "Final".to_string(), Status::Done, Priority::Medium)
+            .await
+            .unwrap();
+        delete_task(task.id).await.unwrap();
+
+        let history = get_task_history(task.id).await.unwrap();
+        let kinds = history.iter().map(|event| event.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                TaskEventKind::Created,
+                TaskEventKind::Updated,
+                TaskEventKind::Deleted
+            ]
+        );
+        assert_eq!(history[1].description.as_deref(), Some("Final"));
+    });
+}
+
+#[test]
+fn logs_the_changes_of_the_custom_fields() {
+    with_database(|| async {
+        let task = create_task("Estimate me".to_string()).await.unwrap();
+        let estimate = create_custom_field("Estimate", FieldKind::Number, "")
+            .await
+            .unwrap();
+        set_field_value(task.id, estimate.id, Some("3"))
+            .await
+            .unwrap();
+        // Setting the same value again isn't a change.
+        set_field_value(task.id, estimate.id, Some("3"))
+            .await
+            .unwrap();
+        set_field_value(task.id, estimate.id, None).await.unwrap();
+
+        let history = get_task_history(task.id).await.unwrap();
+        let changes = history
+            .iter()
+            .map(|event| (event.kind, event.field_id, event.field_value.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (TaskEventKind::Created, None, None),
+                (TaskEventKind::Field, Some(estimate.id), Some("3")),
+                (TaskEventKind::Field, Some(estimate.id), None),
+            ]
+        );
+        assert!(get_field_values(None).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn creates_tasks_with_their_priority_in_a_single_event() {
+    with_database(|| async {
+        let task = create_task_with_priority("Call Bob".to_string(), Priority::High)
+            .await
+            .unwrap();
+        assert_eq!(task.priority, Priority::High);
+        let history = get_task_history(task.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].priority, Some(Priority::High));
+
+        let tasks = create_tasks_with_priorities(vec![
+            ("Water the plants".to_string(), Priority::Low),
+            ("Pay the rent".to_string(), Priority::Medium),
+        ])
+        .await
+        .unwrap();
+        let priorities = tasks.iter().map(|task| task.priority).collect::<Vec<_>>();
+        assert_eq!(priorities, [Priority::Low, Priority::Medium]);
+    });
+}
+
+#[test]
+fn reorders_tasks_by_swapping_their_positions() {
+    with_database(|| async {
+        let tasks = create_tasks(descriptions(&["One", "Two", "Three"]))
+            .await
+            .unwrap();
+
+        let moved = reorder_tasks(vec![tasks[2].id, tasks[0].id]).await.unwrap();
+        assert_eq!(moved.len(), 2);
+
+        let ordered = get_tasks(TaskQuery {
+            order: Some(TaskOrder::UserOrder),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let ordered = ordered
+            .iter()
+            .map(|task| &*task.description)
+            .collect::<Vec<_>>();
+        assert_eq!(ordered, ["Three", "Two", "One"]);
+    });
+}
+
+#[test]
+fn reorders_only_the_moved_range() {
+    with_database(|| async {
+        let tasks = create_tasks(descriptions(&["One", "Two", "Three", "Four"]))
+            .await
+            .unwrap();
+
+        // "Three" moves before "Two", "One" and "Four" aren't sent and keep their place.
+        let moved = reorder_tasks(vec![tasks[2].id, tasks[1].id]).await.unwrap();
+        assert_eq!(moved.len(), 2);
+
+        let ordered = get_tasks(TaskQuery {
+            order: Some(TaskOrder::UserOrder),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let ordered = ordered
+            .iter()
+            .map(|task| &*task.description)
+            .collect::<Vec<_>>();
+        assert_eq!(ordered, ["One", "Three", "Two", "Four"]);
+    });
+}
+
+#[test]
+fn stale_tasks_are_the_active_ones_untouched_for_days() {
+    with_database(|| async {
+        let old = create_task("Call the plumber".to_string()).await.unwrap();
+        let done = create_task("File the taxes".to_string()).await.unwrap();
+        create_task("Water the plants".to_string()).await.unwrap();
+        update_task(
+            done.id,
+            done.description.to_string(),
+            Status::Done,
+            done.priority,
+        )
+        .await
+        .unwrap();
+        sqlx::query("UPDATE todos SET updated_at = datetime('now', '-30 days') WHERE id IN (?, ?)")
+            .bind(old.id)
+            .bind(done.id)
+            .execute(&pool())
+            .await
+            .unwrap();
+
+        let stale = get_stale_tasks(7).await.unwrap();
+        assert_eq!(
+            stale.iter().map(|task| task.id).collect::<Vec<_>>(),
+            [old.id]
+        );
+        assert!(get_stale_tasks(60).await.unwrap().is_empty());
+
+        update_task(
+            old.id,
+            old.description.to_string(),
+            old.status,
+            old.priority,
+        )
+        .await
+        .unwrap();
+        assert!(get_stale_tasks(7).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn awards_the_points_of_a_task_once_when_done() {
+    with_database(|| async {
+        let task = create_task("Fix the roof".to_string()).await.unwrap();
+        assert_eq!(get_points().await.unwrap().points, 0);
+
+        let description = task.description.to_string();
+        update_task(task.id, description.clone(), Status::Done, Priority::High)
+            .await
+            .unwrap();
+        update_task(task.id, description.clone(), Status::ToDo, Priority::High)
+            .await
+            .unwrap();
+        update_task(task.id, description, Status::Done, Priority::High)
+            .await
+            .unwrap();
+
+        let summary = get_points().await.unwrap();
+        assert_eq!(summary.points, 30);
+        assert_eq!(summary.completed, 1);
+        assert_eq!(summary.high_priority, 1);
+    });
+}
+
+#[test]
+fn unlocks_an_achievement_once() {
+    with_database(|| async {
+        assert!(unlock_achievement("first_task").await.unwrap());
+        assert!(!unlock_achievement("first_task").await.unwrap());
+        assert_eq!(get_achievements().await.unwrap(), ["first_task"]);
+    });
+}
+
+#[test]
+fn records_the_days_a_habit_was_done() {
+    with_database(|| async {
+        let task = create_task("Stretch".to_string()).await.unwrap();
+        assert_eq!(get_habit(task.id).await.unwrap(), None);
+        set_habit_done(task.id, "2026-10-14", true).await.unwrap();
+        assert_eq!(get_habit(task.id).await.unwrap(), None);
+
+        set_habit(task.id, true).await.unwrap();
+        set_habit_done(task.id, "2026-10-14", true).await.unwrap();
+        set_habit_done(task.id, "2026-10-15", true).await.unwrap();
+        set_habit_done(task.id, "2026-10-15", true).await.unwrap();
+        assert_eq!(
+            get_habit(task.id).await.unwrap().unwrap(),
+            ["2026-10-15", "2026-10-14"]
+        );
+        assert_eq!(get_task(task.id).await.unwrap().status, Status::ToDo);
+
+        set_habit_done(task.id, "2026-10-15", false).await.unwrap();
+        assert_eq!(get_habit(task.id).await.unwrap().unwrap(), ["2026-10-14"]);
+
+        delete_task(task.id).await.unwrap();
+        assert_eq!(get_habit(task.id).await.unwrap(), None);
+    });
+}
+
+#[test]
+fn sets_and_removes_the_values_of_custom_fields() {
+    with_database(|| async {
+        let task = create_task("Paint the fence".to_string()).await.unwrap();
+        let estimate = create_custom_field("Estimate", FieldKind::Number, "")
+            .await
+            .unwrap();
+        let size = create_custom_field("Size", FieldKind::Choice, "S\nM\nL")
+            .await
+            .unwrap();
+        assert_eq!(get_custom_fields().await.unwrap(), [estimate.clone(), size]);
+        assert!(
+            create_custom_field("Estimate", FieldKind::Text, "")
+                .await
+                .is_err()
+        );
+
+        set_field_value(task.id, estimate.id, Some("3"))
+            .await
+            .unwrap();
+        set_field_value(task.id, estimate.id, Some("5"))
+            .await
+            .unwrap();
+        let values = get_field_values(Some(task.id)).await.unwrap();
+        assert_eq!(
+            values,
+            [FieldValue {
+                task_id: task.id,
+                field_id: estimate.id,
+                value: "5".to_string(),
+            }]
+        );
+        set_field_value(task.id, estimate.id, None).await.unwrap();
+        assert!(get_field_values(None).await.unwrap().is_empty());
+
+        set_field_value(task.id, estimate.id, Some("5"))
+            .await
+            .unwrap();
+        delete_task(task.id).await.unwrap();
+        assert!(get_field_values(None).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn filters_and_orders_the_tasks_by_custom_field() {
+    with_database(|| async {
+        let tasks = create_tasks(descriptions(&["Small", "Large", "Unknown", "Tiny"]))
+            .await
+            .unwrap();
+        let estimate = create_custom_field("Estimate", FieldKind::Number, "")
+            .await
+            .unwrap();
+        for (task, value) in tasks.iter().zip(["9", "10", "", "0.5"]) {
+            if !value.is_empty() {
+                set_field_value(task.id, estimate.id, Some(value))
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let found = get_tasks(TaskQuery {
+            field: Some((estimate.id, "10".to_string())),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(&*found[0].description, "Large");
+
+        let ordered = get_tasks(TaskQuery {
+            order: Some(TaskOrder::StatusFirst),
+            field_order: Some(estimate.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let ordered = ordered
+            .iter()
+            .map(|task| &*task.description)
+            .collect::<Vec<_>>();
+        assert_eq!(ordered, ["Tiny", "Small", "Large", "Unknown"]);
+
+        delete_custom_field(estimate.id).await.unwrap();
+        assert!(get_field_values(None).await.unwrap().is_empty());
+    });
+}
@@ -0,0 +1,12 @@
+use todos::ui::direction::is_rtl_locale;
+
+#[test]
+fn arabic_and_hebrew_locales_are_right_to_left() {
+    assert!(is_rtl_locale("ar_EG.UTF-8"));
+    assert!(is_rtl_locale("he-IL"));
+    assert!(is_rtl_locale("fa"));
+    assert!(!is_rtl_locale("en_US.UTF-8"));
+    assert!(!is_rtl_locale("C"));
+    assert!(!is_rtl_locale("ara"));
+    assert!(!is_rtl_locale(""));
+}
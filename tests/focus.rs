@@ -0,0 +1,18 @@
+mod common;
+
+use common::task;
+use todos::core::Priority;
+use todos::ui::focus::most_important;
+
+#[test]
+fn the_oldest_high_priority_task_is_the_most_important() {
+    let tasks = [
+        task(1, Priority::Medium, "2026-09-01 09:00:00"),
+        task(2, Priority::High, "2026-10-14 09:00:00"),
+        task(3, Priority::High, "2026-10-01 09:00:00"),
+        task(4, Priority::Low, "2026-08-01 09:00:00"),
+    ];
+    assert_eq!(most_important(&tasks).unwrap().id, 3);
+    assert_eq!(most_important(&tasks[..1]).unwrap().id, 1);
+    assert!(most_important(&[]).is_none());
+}
@@ -0,0 +1,19 @@
+use todos::gamification::{level, level_progress, points_for_level};
+
+#[test]
+fn each_level_asks_more_points_than_the_previous_one() {
+    assert_eq!(points_for_level(1), 0);
+    assert_eq!(points_for_level(2), 50);
+    assert_eq!(points_for_level(3), 150);
+    assert_eq!(points_for_level(5), 500);
+}
+
+#[test]
+fn the_level_follows_the_points() {
+    assert_eq!(level(0), 1);
+    assert_eq!(level(49), 1);
+    assert_eq!(level(50), 2);
+    assert_eq!(level(500), 5);
+    assert_eq!(level_progress(0), 0.);
+    assert_eq!(level_progress(100), 0.5);
+}
@@ -0,0 +1,49 @@
+use todos::ui::habit::{calendar, day_number, streak};
+
+fn days(days: &[&str]) -> Vec<String> {
+    days.iter().map(|day| day.to_string()).collect()
+}
+
+#[test]
+fn counts_the_days_since_1970() {
+    assert_eq!(day_number("1970-01-01"), Some(0));
+    assert_eq!(day_number("2000-03-01"), Some(11_017));
+    assert_eq!(day_number("2026-10-15"), Some(20_741));
+    assert_eq!(day_number("day 3"), None);
+}
+
+#[test]
+fn the_streak_goes_on_while_today_is_still_to_do() {
+    let done = days(&["2026-10-14", "2026-10-13", "2026-10-11"]);
+    assert_eq!(streak(&done, "2026-10-14"), 2);
+    assert_eq!(streak(&done, "2026-10-15"), 2);
+    assert_eq!(streak(&done, "2026-10-16"), 0);
+    assert_eq!(
+        streak(&days(&["2026-09-30", "2026-10-01"]), "2026-10-01"),
+        2
+    );
+    assert_eq!(streak(&[], "2026-10-15"), 0);
+}
+
+#[test]
+fn the_calendar_ends_with_the_current_week() {
+    // 2026-10-15 is a Thursday.
+    let weeks = calendar(
+        &days(&["2026-10-12", "2026-10-15", "2026-09-21"]),
+        "2026-10-15",
+    );
+    assert_eq!(weeks.len(), 4);
+    assert_eq!(weeks[0][0], Some(true));
+    assert_eq!(
+        weeks[3],
+        [
+            Some(true),
+            Some(false),
+            Some(false),
+            Some(true),
+            None,
+            None,
+            None
+        ]
+    );
+}
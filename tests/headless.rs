@@ -0,0 +1,88 @@
+use todos::core::{CustomField, FieldKind, Status, Task};
+use todos::database::FieldValue;
+use todos::headless::{Operation, to_csv, to_csv_with_fields};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn operations_are_parsed() {
+    assert_eq!(
+        Operation::parse(&args(&["backup", "backups/todos.db"])),
+        Ok(Operation::Backup("backups/todos.db".into()))
+    );
+    assert_eq!(Operation::parse(&args(&["vacuum"])), Ok(Operation::Vacuum));
+    assert_eq!(Operation::parse(&args(&["seed"])), Ok(Operation::Seed(20)));
+    assert_eq!(
+        Operation::parse(&args(&["seed", "5"])),
+        Ok(Operation::Seed(5))
+    );
+}
+
+#[test]
+fn invalid_operations_are_rejected() {
+    for invalid in [
+        vec![],
+        vec!["export"],
+        vec!["import", "a.csv", "b.csv"],
+        vec!["seed", "many"],
+        vec!["vacuum", "now"],
+        vec!["restore", "todos.db"],
+    ] {
+        assert!(
+            Operation::parse(&args(&invalid)).is_err(),
+            "{invalid:?} was accepted"
+        );
+    }
+}
+
+#[test]
+fn exported_fields_are_quoted() {
+    let task = Task {
+        id: 1,
+        description: "Buy milk, eggs and \"good\" bread".into(),
+        status: Status::InProgress,
+        updated_at: "2026-10-15 09:30:00".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        to_csv(&[task]),
+        "id,description,status,priority,updated_at\n\
+         1,\"Buy milk, eggs and \"\"good\"\" bread\",In Progress,Low,2026-10-15 09:30:00\n"
+    );
+}
+
+#[test]
+fn custom_fields_are_exported_as_columns() {
+    let tasks = [1, 2].map(|id| Task {
+        id,
+        description: format!("Task {id}").into(),
+        updated_at: "2026-10-15 09:30:00".to_string(),
+        ..Default::default()
+    });
+    let fields = [
+        ("Estimate", FieldKind::Number),
+        ("Owner, team", FieldKind::Text),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, (name, kind))| CustomField {
+        id: index as i64 + 1,
+        name: name.to_string(),
+        kind,
+        ..Default::default()
+    })
+    .collect::<Vec<_>>();
+    let values = [FieldValue {
+        task_id: 2,
+        field_id: 1,
+        value: "3".to_string(),
+    }];
+    assert_eq!(
+        to_csv_with_fields(&tasks, &fields, &values),
+        "id,description,status,priority,updated_at,Estimate,\"Owner, team\"\n\
+         1,Task 1,To Do,Low,2026-10-15 09:30:00,,\n\
+         2,Task 2,To Do,Low,2026-10-15 09:30:00,3,\n"
+    );
+}
@@ -0,0 +1,12 @@
+use std::time::Duration;
+
+use todos::lock::retry_delay;
+
+#[test]
+fn wrong_pins_make_the_next_ones_wait_longer() {
+    assert_eq!(retry_delay(0), Duration::ZERO);
+    assert_eq!(retry_delay(2), Duration::ZERO);
+    assert_eq!(retry_delay(3), Duration::from_secs(5));
+    assert_eq!(retry_delay(4), Duration::from_secs(10));
+    assert_eq!(retry_delay(u32::MAX), Duration::from_secs(5 * 60));
+}
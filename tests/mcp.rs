@@ -0,0 +1,49 @@
+mod common;
+
+use common::block_on;
+use serde_json::{Value, json};
+use todos::mcp::{PROTOCOL_VERSION, handle};
+
+fn request(id: i64, method: &str, params: Value) -> String {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string()
+}
+
+#[test]
+fn initialize_announces_the_tools_capability() {
+    let response = block_on(handle(&request(1, "initialize", json!({})))).unwrap();
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+    assert!(response["result"]["capabilities"]["tools"].is_object());
+}
+
+#[test]
+fn tools_are_listed() {
+    let response = block_on(handle(&request(2, "tools/list", json!({})))).unwrap();
+    let names = response["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["list_tasks", "add_task", "complete_task", "update_priority"]
+    );
+}
+
+#[test]
+fn notifications_get_no_response() {
+    let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert!(block_on(handle(&notification.to_string())).is_none());
+}
+
+#[test]
+fn errors_are_reported() {
+    let response = block_on(handle("{")).unwrap();
+    assert_eq!(response["error"]["code"], -32700);
+    let response = block_on(handle(&request(3, "resources/list", json!({})))).unwrap();
+    assert_eq!(response["error"]["code"], -32601);
+    let call = json!({ "name": "add_task", "arguments": {} });
+    let response = block_on(handle(&request(4, "tools/call", call))).unwrap();
+    assert_eq!(response["result"]["isError"], true);
+}
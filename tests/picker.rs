@@ -0,0 +1,34 @@
+mod common;
+
+use common::task;
+use todos::core::Priority;
+use todos::ui::picker::pick;
+
+#[test]
+fn nothing_is_picked_from_no_tasks() {
+    assert!(pick(&[], 0.5).is_none());
+}
+
+#[test]
+fn every_task_can_be_picked() {
+    let tasks = [
+        task(1, Priority::Low, "2026-10-01 09:00:00"),
+        task(2, Priority::Low, "2026-10-14 09:00:00"),
+    ];
+    assert_eq!(pick(&tasks, 0.).unwrap().id, 2);
+    assert_eq!(pick(&tasks, 1.).unwrap().id, 1);
+}
+
+#[test]
+fn high_priority_and_old_tasks_are_picked_more_often() {
+    let tasks = [
+        task(1, Priority::Low, "2026-10-14 09:00:00"),
+        task(2, Priority::High, "2026-10-14 09:00:00"),
+        task(3, Priority::Low, "2026-09-01 09:00:00"),
+    ];
+    let mut picks = [0; 4];
+    for step in 0..1000 {
+        picks[pick(&tasks, step as f32 / 1000.).unwrap().id as usize] += 1;
+    }
+    assert!(picks[2] > picks[3] && picks[3] > picks[1], "{picks:?}");
+}
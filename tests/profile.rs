@@ -0,0 +1,18 @@
+use todos::profile::{DEFAULT_PROFILE, ProfileError, validate};
+
+#[test]
+fn names_usable_as_directories_are_accepted() {
+    for name in ["alice", "Bob-2", "kids_room", DEFAULT_PROFILE] {
+        assert!(validate(name).is_ok(), "{name}");
+    }
+}
+
+#[test]
+fn paths_and_empty_names_are_rejected() {
+    for name in ["", "../alice", "a/b", "a b", "."] {
+        assert!(
+            matches!(validate(name), Err(ProfileError::InvalidName(_))),
+            "{name}"
+        );
+    }
+}
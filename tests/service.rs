@@ -0,0 +1,253 @@
+mod common;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use common::block_on;
+use sqlx::Error as SqlxError;
+use todos::core::{CustomField, Priority, ServerError, Status, StatusCounts, Task};
+use todos::database::{FieldValue, TaskQuery};
+use todos::repository::TaskRepository;
+use todos::service::{Command, Event, execute, execute_one, subscribe};
+
+static TASKS: Mutex<Vec<Task>> = Mutex::new(Vec::new());
+static LAST_ID: AtomicI64 = AtomicI64::new(0);
+
+/// Keeps the tasks in `TASKS`.
+struct Fake;
+
+impl Fake {
+    fn create_task(description: String, priority: Priority) -> Task {
+        let mut tasks = TASKS.lock().unwrap();
+        let task = Task {
+            id: LAST_ID.fetch_add(1, Ordering::Relaxed) + 1,
+            description: description.into(),
+            priority,
+            ..Default::default()
+        };
+        tasks.push(task.clone());
+        task
+    }
+
+    fn update_task(
+        id: i64,
+        description: String,
+        status: Status,
+        priority: Priority,
+    ) -> Result<Task, ServerError> {
+        let mut tasks = TASKS.lock().unwrap();
+        let task = tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or(ServerError::Database(SqlxError::RowNotFound))?;
+        task.description = description.into();
+        task.status = status;
+        task.priority = priority;
+        Ok(task.clone())
+    }
+}
+
+impl TaskRepository for Fake {
+    async fn fetch(_query: TaskQuery) -> Result<Vec<Task>, ServerError> {
+        Ok(TASKS.lock().unwrap().clone())
+    }
+
+    async fn fetch_page(
+        _query: TaskQuery,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<Task>, i64), ServerError> {
+        let tasks = TASKS.lock().unwrap();
+        let page = tasks
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        Ok((page, tasks.len() as i64))
+    }
+
+    /// Every task, the fake keeping no history of the changes.
+    async fn fetch_since(_since: String) -> Result<(Vec<Task>, Vec<i64>), ServerError> {
+        Ok((TASKS.lock().unwrap().clone(), Vec::new()))
+    }
+
+    async fn get(id: i64) -> Result<Task, ServerError> {
+        TASKS
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|task| task.id == id)
+            .cloned()
+            .ok_or(ServerError::Database(SqlxError::RowNotFound))
+    }
+
+    async fn count_by_status() -> Result<StatusCounts, ServerError> {
+        let mut counts = StatusCounts::default();
+        for task in TASKS.lock().unwrap().iter() {
+            match task.status {
+                Status::ToDo => counts.to_do += 1,
+                Status::InProgress => counts.in_progress += 1,
+                Status::Done => counts.done += 1,
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn fetch_fields() -> Result<Vec<CustomField>, ServerError> {
+        Ok(Vec::new())
+    }
+
+    async fn fetch_field_values() -> Result<Vec<FieldValue>, ServerError> {
+        Ok(Vec::new())
+    }
+
+    async fn create(description: String, priority: Priority) -> Result<Task, ServerError> {
+        Ok(Self::create_task(description, priority))
+    }
+
+    async fn create_many(new_tasks: Vec<(String, Priority)>) -> Result<Vec<Task>, ServerError> {
+        let tasks = new_tasks
+            .into_iter()
+            .map(|(description, priority)| Self::create_task(description, priority))
+            .collect();
+        Ok(tasks)
+    }
+
+    async fn update(
+        id: i64,
+        description: String,
+        status: Status,
+        priority: Priority,
+    ) -> Result<Task, ServerError> {
+        Self::update_task(id, description, status, priority)
+    }
+
+    async fn update_many(
+        updates: Vec<(i64, String, Status, Priority)>,
+    ) -> Result<Vec<Task>, ServerError> {
+        updates
+            .into_iter()
+            .map(|(id, description, status, priority)| {
+                Self::update_task(id, description, status, priority)
+            })
+            .collect()
+    }
+
+    async fn set_status_many(ids: Vec<i64>, status: Status) -> Result<Vec<Task>, ServerError> {
+        let mut tasks = TASKS.lock().unwrap();
+        let changed = tasks
+            .iter_mut()
+            .filter(|task| ids.contains(&task.id) && task.status != status)
+            .map(|task| {
+                task.status = status;
+                task.clone()
+            })
+            .collect();
+        Ok(changed)
+    }
+
+    /// The tasks take the positions they had among themselves, like in the database.
+    async fn reorder(ids: Vec<i64>) -> Result<Vec<Task>, ServerError> {
+        let mut tasks = TASKS.lock().unwrap();
+        let mut positions = tasks
+            .iter()
+            .filter(|task| ids.contains(&task.id))
+            .map(|task| task.position)
+            .collect::<Vec<_>>();
+        positions.sort();
+        let mut moved = Vec::new();
+        for (id, position) in ids.into_iter().zip(positions) {
+            if let Some(task) = tasks.iter_mut().find(|task| task.id == id)
+                && task.position != position
+            {
+                task.position = position;
+                moved.push(task.clone());
+            }
+        }
+        Ok(moved)
+    }
+
+    /// The task, the fake keeping no values of the custom fields.
+    async fn set_field(
+        task_id: i64,
+        _field_id: i64,
+        _value: Option<String>,
+    ) -> Result<Task, ServerError> {
+        Self::get(task_id).await
+    }
+
+    async fn delete(id: i64) -> Result<i64, ServerError> {
+        TASKS.lock().unwrap().retain(|task| task.id != id);
+        Ok(id)
+    }
+
+    async fn delete_many(ids: Vec<i64>) -> Result<Vec<i64>, ServerError> {
+        TASKS.lock().unwrap().retain(|task| !ids.contains(&task.id));
+        Ok(ids)
+    }
+}
+
+/// Returns the events published for `origin`, the tests running at the same time.
+fn published(
+    events: &mut tokio::sync::broadcast::Receiver<(&'static str, Event)>,
+    origin: &str,
+) -> Vec<Event> {
+    std::iter::from_fn(|| events.try_recv().ok())
+        .filter(|(event_origin, _)| *event_origin == origin)
+        .map(|(_, event)| event)
+        .collect()
+}
+
+#[test]
+fn created_tasks_are_published_with_their_priority() {
+    let mut events = subscribe();
+    let command = Command::CreateTasks(vec![
+        ("Water the plants".to_string(), Priority::Low),
+        ("Call Bob".to_string(), Priority::High),
+    ]);
+    let returned = block_on(execute::<Fake>("create test", command)).unwrap();
+    let published = published(&mut events, "create test");
+    assert_eq!(published.len(), 2);
+    for (returned, published) in returned.iter().zip(&published) {
+        let (Event::TaskCreated(returned), Event::TaskCreated(published)) = (returned, published)
+        else {
+            panic!("expected TaskCreated events");
+        };
+        assert_eq!(returned.id, published.id);
+    }
+    let Event::TaskCreated(task) = &published[1] else {
+        unreachable!();
+    };
+    assert_eq!(&*task.description, "Call Bob");
+    assert_eq!(task.priority, Priority::High);
+}
+
+#[test]
+fn deletions_are_published_with_the_id() {
+    let mut events = subscribe();
+    let command = Command::CreateTask {
+        description: "Take out the trash".to_string(),
+        priority: Priority::Low,
+    };
+    let created = block_on(execute::<Fake>("delete test", command)).unwrap();
+    let id = created[0].id();
+    block_on(execute::<Fake>("delete test", Command::DeleteTask(id))).unwrap();
+    let published = published(&mut events, "delete test");
+    assert!(
+        matches!(published.as_slice(), [Event::TaskCreated(_), Event::TaskDeleted(deleted)] if *deleted == id)
+    );
+}
+
+#[test]
+fn execute_one_fails_when_no_task_changed() {
+    let command = Command::ChangeStatus {
+        ids: Vec::new(),
+        status: Status::Done,
+    };
+    let result = block_on(execute_one::<Fake>("execute_one test", command));
+    assert!(matches!(
+        result,
+        Err(ServerError::Database(SqlxError::RowNotFound))
+    ));
+}
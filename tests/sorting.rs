@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+
+use proptest::prelude::*;
+use todos::core::{Priority, Status, Task};
+use todos::ui::component::list::filter::{AllOf, AnyOf};
+use todos::ui::component::list::{ListFilter, ListSorter};
+use todos::ui::task_list::{StatusFilter, TaskFilter, TaskSorter};
+
+const SORTERS: [TaskSorter; 3] = [
+    TaskSorter::StatusFirst,
+    TaskSorter::PriorityFirst,
+    TaskSorter::Manual,
+];
+const FILTERS: [StatusFilter; 3] = [
+    StatusFilter::All,
+    StatusFilter::Active,
+    StatusFilter::Completed,
+];
+
+fn status() -> impl Strategy<Value = Status> {
+    prop_oneof![
+        Just(Status::ToDo),
+        Just(Status::InProgress),
+        Just(Status::Done)
+    ]
+}
+
+fn priority() -> impl Strategy<Value = Priority> {
+    prop_oneof![
+        Just(Priority::Low),
+        Just(Priority::Medium),
+        Just(Priority::High)
+    ]
+}
+
+/// Small ranges, so that the tasks often tie on some of the keys.
+fn task() -> impl Strategy<Value = (Task, f32)> {
+    (0..20i64, status(), priority(), 0..20i64, 0.0..=1.0f32).prop_map(
+        |(id, status, priority, position, score)| {
+            let task = Task {
+                id,
+                status,
+                priority,
+                position,
+                ..Default::default()
+            };
+            (task, score)
+        },
+    )
+}
+
+fn compare(sorter: TaskSorter, (a, score_a): &(Task, f32), (b, score_b): &(Task, f32)) -> Ordering {
+    sorter.sort(a, b, *score_a, *score_b)
+}
+
+proptest! {
+    #[test]
+    fn sort_is_antisymmetric(a in task(), b in task()) {
+        for sorter in SORTERS {
+            prop_assert_eq!(compare(sorter, &a, &b), compare(sorter, &b, &a).reverse());
+        }
+    }
+
+    #[test]
+    fn sort_is_transitive(a in task(), b in task(), c in task()) {
+        for sorter in SORTERS {
+            if compare(sorter, &a, &b) != Ordering::Greater
+                && compare(sorter, &b, &c) != Ordering::Greater
+            {
+                prop_assert_ne!(compare(sorter, &a, &c), Ordering::Greater);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_orders_every_pair(tasks in prop::collection::vec(task(), 0..50)) {
+        for sorter in SORTERS {
+            let mut sorted = tasks.clone();
+            sorted.sort_by(|a, b| compare(sorter, a, b));
+            for pair in sorted.windows(2) {
+                prop_assert_ne!(compare(sorter, &pair[0], &pair[1]), Ordering::Greater);
+            }
+        }
+    }
+
+    #[test]
+    fn status_filters_partition_the_tasks((task, _) in task()) {
+        let active = StatusFilter::Active.filter(&task).0;
+        let completed = StatusFilter::Completed.filter(&task).0;
+        prop_assert!(StatusFilter::All.filter(&task).0);
+        prop_assert_ne!(active, completed);
+        prop_assert_eq!(completed, task.status == Status::Done);
+    }
+
+    #[test]
+    fn task_filter_without_search_follows_the_status((task, _) in task()) {
+        for filter in FILTERS {
+            let task_filter: TaskFilter = AllOf(AllOf(Default::default(), filter), Default::default());
+            prop_assert_eq!(task_filter.filter(&task), filter.filter(&task));
+        }
+    }
+
+    #[test]
+    fn any_of_skips_the_disabled_filters((task, _) in task()) {
+        for filter in FILTERS {
+            let any_of = AnyOf(StatusFilter::All, filter);
+            prop_assert_eq!(any_of.filter(&task), filter.filter(&task));
+            prop_assert_eq!(any_of.enabled(), filter != StatusFilter::All);
+        }
+    }
+}
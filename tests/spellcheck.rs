@@ -0,0 +1,26 @@
+use todos::spellcheck::{replace_word, words};
+
+#[test]
+fn splits_the_words_keeping_their_apostrophes() {
+    assert_eq!(
+        words("Don't forgett the 'milk', 2 eggs"),
+        [
+            (0, "Don't"),
+            (6, "forgett"),
+            (14, "the"),
+            (19, "milk"),
+            (28, "eggs")
+        ]
+    );
+    assert_eq!(words("Café über"), [(0, "Café"), (6, "über")]);
+    assert!(words("42 - !").is_empty());
+}
+
+#[test]
+fn replaces_a_misspelled_word() {
+    let text = "Don't forgett the milk";
+    assert_eq!(
+        replace_word(text, 6, "forgett", "forget"),
+        "Don't forget the milk"
+    );
+}
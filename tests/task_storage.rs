@@ -0,0 +1,178 @@
+mod common;
+
+use common::with_database;
+use todos::core::{FieldKind, Priority, ServerError, Status};
+use todos::database::create_custom_field;
+use todos::repository::Database;
+use todos::service::{Command, execute, set_read_only};
+use todos::ui::component::list::storage::{ListStorage, Progress};
+use todos::ui::component::list::{ListFilter, ListQuery};
+use todos::ui::task_list::{
+    StatusFilter, TaskFilter, TaskSorter, TaskStorage, enter_submits, set_enter_submits,
+    set_start_view, start_view,
+};
+
+type Storage = TaskStorage;
+
+fn all() -> ListQuery<todos::core::Task> {
+    ListQuery {
+        filter: None,
+        sorter: None,
+    }
+}
+
+#[test]
+fn creates_updates_and_deletes_through_the_service() {
+    with_database(|| async {
+        let task = Storage::create(("Write tests".to_string(), Priority::High))
+            .await
+            .unwrap();
+        assert_eq!(&*task.description, "Write tests");
+        assert_eq!(task.priority, Priority::High);
+
+        let task = Storage::update(
+            task.id,
+            (
+                "Write more tests".to_string(),
+                Status::InProgress,
+                Priority::High,
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(task.status, Status::InProgress);
+        assert_eq!(Storage::fetch_summary().await.unwrap().in_progress, 1);
+
+        assert_eq!(Storage::delete(task.id).await.unwrap(), task.id);
+        assert!(Storage::fetch_all(all()).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn creates_and_deletes_many_tasks_in_batches() {
+    with_database(|| async {
+        let new_tasks = (1..=250)
+            .map(|n| (format!("Task {n}"), Priority::Medium))
+            .collect();
+        let tasks = Storage::create_many(new_tasks, Progress::default())
+            .await
+            .unwrap();
+        assert_eq!(tasks.len(), 250);
+        assert_eq!(Storage::fetch_all(all()).await.unwrap().len(), 250);
+        assert_eq!(Storage::fetch_summary().await.unwrap().to_do, 250);
+
+        let ids = tasks.iter().map(|task| task.id).collect();
+        let deleted = Storage::delete_many(ids, Progress::default())
+            .await
+            .unwrap();
+        assert_eq!(deleted.len(), 250);
+        assert!(Storage::fetch_all(all()).await.unwrap().is_empty());
+    });
+}
+
+#[test]
+fn refuses_writes_while_read_only() {
+    with_database(|| async {
+        let task = Storage::create(("Present".to_string(), Priority::Low))
+            .await
+            .unwrap();
+
+        set_read_only(true);
+        let created = Storage::create(("Sneak in".to_string(), Priority::Low)).await;
+        let deleted = Storage::delete(task.id).await;
+        set_read_only(false);
+
+        assert!(matches!(created, Err(ServerError::ReadOnly)));
+        assert!(matches!(deleted, Err(ServerError::ReadOnly)));
+        let tasks = Storage::fetch_all(all()).await.unwrap();
+        assert_eq!(
+            tasks.iter().map(|task| task.id).collect::<Vec<_>>(),
+            [task.id]
+        );
+    });
+}
+
+#[test]
+fn filters_and_sorts_by_custom_field() {
+    with_database(|| async {
+        let estimate = create_custom_field("Estimate", FieldKind::Number, "")
+            .await
+            .unwrap();
+        for (description, value) in [("Small", "9"), ("Large", "10"), ("Tiny", "0.5")] {
+            let task = Storage::create((description.to_string(), Priority::Low))
+                .await
+                .unwrap();
+            let command = Command::SetFieldValue {
+                task_id: task.id,
+                field_id: estimate.id,
+                value: Some(value.to_string()),
+            };
+            execute::<Database>("task storage test", command)
+                .await
+                .unwrap();
+        }
+        let descriptions = |tasks: &[todos::core::Task]| {
+            tasks
+                .iter()
+                .map(|task| task.description.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // The value is compared as stored, like "10" for "+10".
+        let mut filter = TaskFilter::default();
+        filter.load(&format!("\nall\n{} +10", estimate.id));
+        let query = ListQuery {
+            filter: Some(filter.clone()),
+            sorter: None,
+        };
+        let found = Storage::fetch_all(query).await.unwrap();
+        assert_eq!(descriptions(&found), ["Large"]);
+        assert!(filter.filter(&found[0]).0);
+
+        let query = ListQuery {
+            filter: None,
+            sorter: Some(TaskSorter::Field(estimate.id)),
+        };
+        let sorted = Storage::fetch_all(query).await.unwrap();
+        assert_eq!(descriptions(&sorted), ["Tiny", "Small", "Large"]);
+    });
+}
+
+#[test]
+fn loads_the_start_view_with_the_settings() {
+    with_database(|| async {
+        assert_eq!(Storage::load_settings().await.unwrap().default_filter, None);
+
+        set_start_view(Some(StatusFilter::Completed), Some(TaskSorter::Manual))
+            .await
+            .unwrap();
+        let settings = Storage::load_settings().await.unwrap();
+        assert_eq!(settings.default_filter.as_deref(), Some("\ncompleted\n"));
+        assert_eq!(settings.default_sorter.as_deref(), Some("manual"));
+        assert_eq!(
+            start_view().await.unwrap(),
+            (Some(StatusFilter::Completed), Some(TaskSorter::Manual))
+        );
+
+        set_start_view(None, None).await.unwrap();
+        assert_eq!(start_view().await.unwrap(), (None, None));
+        assert_eq!(Storage::load_settings().await.unwrap().default_sorter, None);
+    });
+}
+
+#[test]
+fn loads_whether_enter_submits_with_the_settings() {
+    with_database(|| async {
+        Storage::load_settings().await.unwrap();
+        assert!(enter_submits());
+
+        set_enter_submits(false).await.unwrap();
+        assert!(!enter_submits());
+        Storage::load_settings().await.unwrap();
+        assert!(!enter_submits());
+    });
+    with_database(|| async {
+        Storage::load_settings().await.unwrap();
+        assert!(enter_submits());
+    });
+}
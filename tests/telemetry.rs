@@ -0,0 +1,31 @@
+mod common;
+
+use common::with_database;
+use todos::Priority;
+use todos::repository::Database;
+use todos::service::{Command, execute};
+use todos::telemetry::{crash_signature, record, report};
+
+#[test]
+fn crash_signatures_keep_only_the_location() {
+    let report = "todos 0.1.0 on linux x86_64, crashed at 1 (Unix time)\n\n\
+        thread 'main' panicked at src/app.rs:120:5:\nno task named groceries\n";
+    assert_eq!(crash_signature(report).as_deref(), Some("src/app.rs:120:5"));
+    assert_eq!(crash_signature("no panic here"), None);
+}
+
+#[test]
+fn reports_count_the_events_without_the_tasks() {
+    with_database(|| async {
+        let command = Command::CreateTask {
+            description: "Buy groceries".to_string(),
+            priority: Priority::Medium,
+        };
+        for event in execute::<Database>("test", command).await.unwrap() {
+            record("test", &event);
+        }
+        let report = report().await.unwrap();
+        assert_eq!(report["usage"]["test: created"], 1);
+        assert!(!report.to_string().contains("groceries"));
+    });
+}
@@ -0,0 +1,39 @@
+use todos::core::{Priority, Status};
+use todos::import::ImportedTask;
+use todos::import::todoist::parse_csv;
+
+#[test]
+fn csv_export_is_parsed() {
+    let csv = "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE\r\n\
+               section,Errands,,,,,,,,\r\n\
+               task,\"Buy milk, eggs\",,1,1,Me (1),,tomorrow,en,Europe/Paris\r\n\
+               note,Organic if possible,,,,Me (1),,,,\r\n\
+               task,\"Say \"\"hi\"\"\",,4,1,Me (1),,,en,Europe/Paris\r\n";
+    let tasks = parse_csv(csv).unwrap();
+    assert_eq!(
+        tasks,
+        [
+            ImportedTask {
+                description: "Buy milk, eggs".to_string(),
+                status: Status::ToDo,
+                priority: Priority::High,
+                project: None,
+                due: Some("tomorrow".to_string()),
+                tags: Vec::new(),
+            },
+            ImportedTask {
+                description: "Say \"hi\"".to_string(),
+                status: Status::ToDo,
+                priority: Priority::Low,
+                project: None,
+                due: None,
+                tags: Vec::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn csv_without_content_is_rejected() {
+    assert!(parse_csv("TYPE,PRIORITY\ntask,1\n").is_err());
+}
@@ -0,0 +1,31 @@
+use todos::url_scheme::UrlCommand;
+
+#[test]
+fn urls_are_parsed() {
+    assert_eq!(
+        "todos://add?text=Buy%20milk+and%20eggs".parse(),
+        Ok(UrlCommand::Add("Buy milk and eggs".to_string()))
+    );
+    assert_eq!("todos://open?id=42".parse(), Ok(UrlCommand::Open(42)));
+    assert_eq!("todos:open/?id=7&from=web".parse(), Ok(UrlCommand::Open(7)));
+}
+
+#[test]
+fn commands_are_formatted_as_urls() {
+    let command = UrlCommand::Add("Reply to Zoé & Sam, 100%".to_string());
+    assert_eq!(command.to_string().parse(), Ok(command));
+    assert_eq!(UrlCommand::Open(3).to_string(), "todos://open?id=3");
+}
+
+#[test]
+fn invalid_urls_are_rejected() {
+    for url in [
+        "http://add?text=a",
+        "todos://add",
+        "todos://add?text=",
+        "todos://open?id=x",
+        "todos://delete?id=1",
+    ] {
+        assert!(url.parse::<UrlCommand>().is_err(), "{url} was accepted");
+    }
+}